@@ -0,0 +1,104 @@
+# global variables whose address in the debug info differs from the address of their symbol, like with stale debug info
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   int calib_ok          the location matches the symbol
+#   int calib_stale       the location is 0x100 bytes after the symbol
+#   int calib_local       a local symbol, with a location that doesn't match
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o address_mismatch_test.elf address_mismatch_test.s
+
+	.data
+	.p2align 3
+	.globl calib_ok
+	.type calib_ok, @object
+	.size calib_ok, 4
+calib_ok:
+	.zero 4
+	.globl calib_stale
+	.type calib_stale, @object
+	.size calib_stale, 4
+calib_stale:
+	.zero 4
+	.type calib_local, @object
+	.size calib_local, 4
+calib_local:
+	.zero 4
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 3
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "address_mismatch_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+	.uleb128 3
+	.asciz "calib_ok"
+	.long .Lint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad calib_ok
+	.uleb128 3
+	.asciz "calib_stale"
+	.long .Lint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad calib_stale + 0x100
+	.uleb128 3
+	.asciz "calib_local"
+	.long .Lint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad calib_local + 0x100
+	.byte 0
+.Lcu_end:
@@ -0,0 +1,11 @@
+// two adjacent global variables, for resolving negative offsets from the second one
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o adjacent_test.elf adjacent_test.c
+struct Header {
+    unsigned short id;
+    unsigned short length;
+};
+
+struct Header header = {1, 16};
+unsigned int payload[4] = {1, 2, 3, 4};
+
+void _start(void) {}
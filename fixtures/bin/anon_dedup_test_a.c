@@ -0,0 +1,12 @@
+// both units define a variable with the same anonymous struct, which gcc emits once per unit
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o anon_dedup_test.elf anon_dedup_test_a.c anon_dedup_test_b.c
+struct {
+    int a;
+    short b;
+} anon_a;
+
+// same members as the struct of other_b, but a different member type
+struct {
+    int a;
+    int b;
+} other_a;
@@ -0,0 +1,12 @@
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o anon_dedup_test.elf anon_dedup_test_a.c anon_dedup_test_b.c
+struct {
+    int a;
+    short b;
+} anon_b;
+
+struct {
+    int a;
+    unsigned int b;
+} other_b;
+
+void _start(void) {}
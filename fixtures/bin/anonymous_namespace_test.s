@@ -0,0 +1,94 @@
+# C++ variables in anonymous namespaces, which are named by their mangled names in the debug info, like some compilers do
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   namespace { int counter; }                     _ZN12_GLOBAL__N_17counterE
+#   namespace app { namespace { short level; } }   _ZN3app12_GLOBAL__N_15levelE
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o anonymous_namespace_test.elf anonymous_namespace_test.s
+
+	.data
+	.p2align 2
+	.type _ZN12_GLOBAL__N_17counterE, @object
+	.size _ZN12_GLOBAL__N_17counterE, 4
+_ZN12_GLOBAL__N_17counterE:
+	.long 5
+	.type _ZN3app12_GLOBAL__N_15levelE, @object
+	.size _ZN3app12_GLOBAL__N_15levelE, 2
+_ZN3app12_GLOBAL__N_15levelE:
+	.short 2
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, location
+	.uleb128 3
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x04
+	.asciz "anonymous_namespace_test.cpp"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lshort:
+	.uleb128 2
+	.asciz "short int"
+	.byte 2
+	.byte 0x05
+	.uleb128 3
+	.asciz "_ZN12_GLOBAL__N_17counterE"
+	.long .Lint - .Lcu_begin
+	.uleb128 9
+	.byte 0x03
+	.quad _ZN12_GLOBAL__N_17counterE
+	.uleb128 3
+	.asciz "_ZN3app12_GLOBAL__N_15levelE"
+	.long .Lshort - .Lcu_begin
+	.uleb128 9
+	.byte 0x03
+	.quad _ZN3app12_GLOBAL__N_15levelE
+	.byte 0
+.Lcu_end:
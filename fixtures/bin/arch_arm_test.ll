@@ -0,0 +1,22 @@
+; a global variable in an object file for a 32-bit ARM Cortex-M target
+; this is the LLVM IR equivalent of the C code:
+;   unsigned int arm_value = 0x12345678;
+; compile: llc-14 -mtriple=thumbv7em-none-eabi -filetype=obj -O0 -o arch_arm_test.o arch_arm_test.ll
+
+target datalayout = "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64"
+target triple = "thumbv7em-none-eabi"
+
+@arm_value = global i32 305419896, align 4, !dbg !0
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!20, !21}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "arm_value", scope: !2, file: !3, line: 1, type: !5, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, producer: "handwritten", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !4)
+!3 = !DIFile(filename: "arch_arm_test.c", directory: "/tmp")
+!4 = !{!0}
+!5 = !DIBasicType(name: "unsigned int", size: 32, encoding: DW_ATE_unsigned)
+
+!20 = !{i32 7, !"Dwarf Version", i32 4}
+!21 = !{i32 2, !"Debug Info Version", i32 3}
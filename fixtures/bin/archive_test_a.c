@@ -0,0 +1,10 @@
+// member of the static archives archive_test.a and archive_test_thin.a
+// compile: gcc -g -O0 -c -o archive_test_a.o archive_test_a.c
+
+struct Pair {
+    int first;
+    int second;
+};
+
+struct Pair pair_var = {1, 2};
+int counter_a;
@@ -0,0 +1,10 @@
+// member of the static archives archive_test.a and archive_test_thin.a
+// compile: gcc -g -O0 -c -o archive_test_b.o archive_test_b.c
+
+struct Triple {
+    char tag;
+    short values[3];
+};
+
+double value_b = 1.5;
+struct Triple triple_var;
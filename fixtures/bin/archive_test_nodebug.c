@@ -0,0 +1,4 @@
+// member of the static archives archive_test.a and archive_test_thin.a, without debug info
+// compile: gcc -O0 -c -o archive_test_nodebug.o archive_test_nodebug.c
+
+int nodebug_var = 3;
@@ -0,0 +1,119 @@
+# global variables that are marked as compiler-generated with DW_AT_artificial
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   int calibration                    a normal variable
+#   int __compound_literal.0           DW_AT_artificial, like gcc emits for file scope compound literals
+#   unsigned char __guard_calibration  DW_AT_artificial
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o artificial_test.elf artificial_test.s
+
+	.data
+	.p2align 3
+	.globl calibration
+calibration:
+	.zero 4
+	.globl __compound_literal.0
+__compound_literal.0:
+	.zero 4
+	.globl __guard_calibration
+__guard_calibration:
+	.zero 1
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 3
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	# artificial variable: name, type, external, artificial, location
+	.uleb128 4
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x34
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "artificial_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Luchar:
+	.uleb128 2
+	.asciz "unsigned char"
+	.byte 1
+	.byte 0x08
+	.uleb128 3
+	.asciz "calibration"
+	.long .Lint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad calibration
+	.uleb128 4
+	.asciz "__compound_literal.0"
+	.long .Lint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad __compound_literal.0
+	.uleb128 4
+	.asciz "__guard_calibration"
+	.long .Luchar - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad __guard_calibration
+	.byte 0
+.Lcu_end:
@@ -0,0 +1,13 @@
+// C11 _Atomic variables and members
+// compile: gcc -g -O0 -std=c11 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o atomic_test.elf atomic_test.c
+struct Counters {
+    _Atomic unsigned int hits;
+    _Atomic(short) values[3];
+};
+
+_Atomic int atomic_int;
+_Atomic(double) atomic_double;
+struct Counters counters;
+_Atomic struct Counters atomic_counters;
+
+void _start(void) {}
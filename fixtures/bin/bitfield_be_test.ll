@@ -0,0 +1,39 @@
+; bitfields in a struct on a big-endian target, where the first bitfield occupies the most significant bits
+; this is the LLVM IR equivalent of the C code:
+;   struct Flags {
+;       unsigned int a : 3;
+;       unsigned int b : 7;
+;       unsigned int c : 22;
+;       unsigned short d : 4;
+;       unsigned short e : 12;
+;   };
+;   struct Flags flags = { 5, 0x55, 0x2abcde, 0xa, 0x123 };
+; compile: llc-14 -mtriple=powerpc-unknown-linux-gnu -filetype=obj -O0 -debugger-tune=lldb -o bitfield_be_test.o bitfield_be_test.ll
+; compile: llc-14 -mtriple=powerpc-unknown-linux-gnu -filetype=obj -O0 --dwarf-version=3 -o bitfield_be_dwarf3_test.o bitfield_be_test.ll
+
+target datalayout = "E-m:e-p:32:32-i64:64-n32"
+target triple = "powerpc-unknown-linux-gnu"
+
+; the initializer is given as bytes: B56ABCDE contains a, b and c, A123 contains d and e
+@flags = global [8 x i8] c"\B5\6A\BC\DE\A1\23\00\00", align 4, !dbg !0
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!20, !21}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "flags", scope: !2, file: !3, line: 8, type: !5, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, producer: "handwritten", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !4)
+!3 = !DIFile(filename: "bitfield_be_test.c", directory: "/tmp")
+!4 = !{!0}
+!5 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "Flags", file: !3, line: 1, size: 64, elements: !6)
+!6 = !{!7, !9, !10, !11, !13}
+!7 = !DIDerivedType(tag: DW_TAG_member, name: "a", scope: !5, file: !3, line: 2, baseType: !8, size: 3, offset: 0, flags: DIFlagBitField, extraData: i64 0)
+!8 = !DIBasicType(name: "unsigned int", size: 32, encoding: DW_ATE_unsigned)
+!9 = !DIDerivedType(tag: DW_TAG_member, name: "b", scope: !5, file: !3, line: 3, baseType: !8, size: 7, offset: 3, flags: DIFlagBitField, extraData: i64 0)
+!10 = !DIDerivedType(tag: DW_TAG_member, name: "c", scope: !5, file: !3, line: 4, baseType: !8, size: 22, offset: 10, flags: DIFlagBitField, extraData: i64 0)
+!11 = !DIDerivedType(tag: DW_TAG_member, name: "d", scope: !5, file: !3, line: 5, baseType: !12, size: 4, offset: 32, flags: DIFlagBitField, extraData: i64 32)
+!12 = !DIBasicType(name: "unsigned short", size: 16, encoding: DW_ATE_unsigned)
+!13 = !DIDerivedType(tag: DW_TAG_member, name: "e", scope: !5, file: !3, line: 6, baseType: !12, size: 12, offset: 36, flags: DIFlagBitField, extraData: i64 32)
+
+!20 = !{i32 7, !"Dwarf Version", i32 4}
+!21 = !{i32 2, !"Debug Info Version", i32 3}
@@ -0,0 +1,140 @@
+# bit-precise integers, whose base types have a DW_AT_bit_size
+# the Dwarf 4 debug info was written by hand, since gcc 12 doesn't support _BitInt; it describes these variables:
+#   _BitInt(12) bitint12             DW_AT_byte_size 2 and DW_AT_bit_size 12, like clang generates
+#   unsigned _BitInt(7) ubitint7     only DW_AT_bit_size 7
+#   _BitInt(24) bitint24             only DW_AT_bit_size 24
+#   unsigned _BitInt(40) ubitint40   only DW_AT_bit_size 40
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o bitint_test.elf bitint_test.s
+
+	.data
+	.p2align 3
+	.globl bitint12
+bitint12:
+	.zero 2
+	.globl ubitint7
+ubitint7:
+	.zero 1
+	.p2align 2
+	.globl bitint24
+bitint24:
+	.zero 4
+	.p2align 3
+	.globl ubitint40
+ubitint40:
+	.zero 8
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, bit_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x0d
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# base type: name, bit_size, encoding
+	.uleb128 3
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0d
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 4
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "bitint_test.c"
+.Lbitint12:
+	.uleb128 2
+	.asciz "_BitInt(12)"
+	.byte 2
+	.byte 12
+	.byte 0x05
+.Lubitint7:
+	.uleb128 3
+	.asciz "unsigned _BitInt(7)"
+	.byte 7
+	.byte 0x07
+.Lbitint24:
+	.uleb128 3
+	.asciz "_BitInt(24)"
+	.byte 24
+	.byte 0x05
+.Lubitint40:
+	.uleb128 3
+	.asciz "unsigned _BitInt(40)"
+	.byte 40
+	.byte 0x07
+	.uleb128 4
+	.asciz "bitint12"
+	.long .Lbitint12 - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad bitint12
+	.uleb128 4
+	.asciz "ubitint7"
+	.long .Lubitint7 - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad ubitint7
+	.uleb128 4
+	.asciz "bitint24"
+	.long .Lbitint24 - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad bitint24
+	.uleb128 4
+	.asciz "ubitint40"
+	.long .Lubitint40 - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad ubitint40
+	.byte 0
+.Lcu_end:
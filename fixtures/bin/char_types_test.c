@@ -0,0 +1,11 @@
+// bool and character types, which have their own DWARF encodings
+// compile: gcc -g -O0 -std=c11 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o char_types_test.elf char_types_test.c
+#include <stdbool.h>
+
+bool b;
+char c;
+signed char sc;
+unsigned char uc;
+char text[16];
+
+void _start(void) {}
@@ -0,0 +1,31 @@
+// static data members of classes, which are looked up with their class scope, e.g. "app::Config::limit"
+// compile: g++ -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o class_static_test.elf class_static_test.cpp
+// compile: g++ -g -gdwarf-4 -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o class_static_dwarf4_test.elf class_static_test.cpp
+
+namespace app {
+class Config {
+public:
+    static int limit;
+    static const double scale;
+    int value;
+};
+int Config::limit = 5;
+const double Config::scale = 2.5;
+
+struct Outer {
+    struct Inner {
+        static unsigned short counter;
+    };
+};
+unsigned short Outer::Inner::counter = 7;
+}
+
+struct Global {
+    static long total;
+};
+long Global::total = 1;
+
+// a global with the same name as a static member
+int limit = 3;
+
+extern "C" void _start(void) {}
@@ -0,0 +1,23 @@
+// the const variables are optimized away, so the debug info only describes them with DW_AT_const_value
+// compile: gcc -g -O2 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o const_value_test.elf const_value_test.c
+static const int limit = 42;
+static const signed char offset_neg = -3;
+static const unsigned short mask = 0xfff0;
+static const double gain = 1.5;
+static const char label[] = "abc";
+
+struct Pair {
+    short a;
+    short b;
+};
+static const struct Pair pair = {1, 2};
+
+int counter;
+
+int get_value(void) {
+    return limit + offset_neg + mask + (int)gain + label[1] + pair.b;
+}
+
+void _start(void) {
+    counter = get_value();
+}
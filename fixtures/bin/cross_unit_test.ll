@@ -0,0 +1,66 @@
+; two compile units in one LLVM module, as after LTO. Both use struct Record, which is only
+; emitted once in the first unit, so the second unit references it with DW_FORM_ref_addr
+; this is the LLVM IR equivalent of the C code:
+;   cross_unit_a.c:
+;     struct Inner { char c; int v; };
+;     struct Record { int id; short flags[2]; struct Inner inner; };
+;     struct Record record_a;
+;     void _start(void) {}
+;   cross_unit_b.c:
+;     struct Record record_b = { 7 };
+;     struct Record *record_ptr = &record_b;
+; compile: llc-14 -mtriple=x86_64-unknown-linux-gnu -filetype=obj -O0 -o cross_unit_test.o cross_unit_test.ll
+; link: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o cross_unit_test.elf cross_unit_test.o
+
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-unknown-linux-gnu"
+
+%struct.Inner = type { i8, i32 }
+%struct.Record = type { i32, [2 x i16], %struct.Inner }
+
+@record_a = global %struct.Record zeroinitializer, align 4, !dbg !0
+@record_b = global %struct.Record { i32 7, [2 x i16] zeroinitializer, %struct.Inner zeroinitializer }, align 4, !dbg !30
+@record_ptr = global %struct.Record* @record_b, align 8, !dbg !33
+
+define void @_start() !dbg !40 {
+  ret void, !dbg !43
+}
+
+!llvm.dbg.cu = !{!2, !22}
+!llvm.module.flags = !{!50, !51}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "record_a", scope: !2, file: !3, line: 3, type: !5, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, producer: "handwritten", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !4)
+!3 = !DIFile(filename: "cross_unit_a.c", directory: "/tmp")
+!4 = !{!0}
+!5 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "Record", file: !3, line: 2, size: 128, elements: !6)
+!6 = !{!7, !9, !14}
+!7 = !DIDerivedType(tag: DW_TAG_member, name: "id", scope: !5, file: !3, line: 2, baseType: !8, size: 32)
+!8 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!9 = !DIDerivedType(tag: DW_TAG_member, name: "flags", scope: !5, file: !3, line: 2, baseType: !10, size: 32, offset: 32)
+!10 = !DICompositeType(tag: DW_TAG_array_type, baseType: !11, size: 32, elements: !12)
+!11 = !DIBasicType(name: "short", size: 16, encoding: DW_ATE_signed)
+!12 = !{!13}
+!13 = !DISubrange(count: 2)
+!14 = !DIDerivedType(tag: DW_TAG_member, name: "inner", scope: !5, file: !3, line: 2, baseType: !15, size: 64, offset: 64)
+!15 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "Inner", file: !3, line: 1, size: 64, elements: !16)
+!16 = !{!17, !19}
+!17 = !DIDerivedType(tag: DW_TAG_member, name: "c", scope: !15, file: !3, line: 1, baseType: !18, size: 8)
+!18 = !DIBasicType(name: "char", size: 8, encoding: DW_ATE_signed_char)
+!19 = !DIDerivedType(tag: DW_TAG_member, name: "v", scope: !15, file: !3, line: 1, baseType: !8, size: 32, offset: 32)
+!22 = distinct !DICompileUnit(language: DW_LANG_C99, file: !23, producer: "handwritten", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !24)
+!23 = !DIFile(filename: "cross_unit_b.c", directory: "/tmp")
+!24 = !{!30, !33}
+!30 = !DIGlobalVariableExpression(var: !31, expr: !DIExpression())
+!31 = distinct !DIGlobalVariable(name: "record_b", scope: !22, file: !23, line: 1, type: !5, isLocal: false, isDefinition: true)
+!33 = !DIGlobalVariableExpression(var: !34, expr: !DIExpression())
+!34 = distinct !DIGlobalVariable(name: "record_ptr", scope: !22, file: !23, line: 2, type: !35, isLocal: false, isDefinition: true)
+!35 = !DIDerivedType(tag: DW_TAG_pointer_type, baseType: !5, size: 64)
+!40 = distinct !DISubprogram(name: "_start", scope: !3, file: !3, line: 4, type: !41, scopeLine: 4, spFlags: DISPFlagDefinition, unit: !2, retainedNodes: !42)
+!41 = !DISubroutineType(types: !44)
+!42 = !{}
+!43 = !DILocation(line: 4, column: 20, scope: !40)
+!44 = !{null}
+!50 = !{i32 7, !"Dwarf Version", i32 4}
+!51 = !{i32 2, !"Debug Info Version", i32 3}
@@ -0,0 +1,18 @@
+// the struct types are placed in type units in .debug_types
+// compile: gcc -g -gdwarf-4 -fdebug-types-section -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o debug_types_test.elf debug_types_test.c
+struct Point {
+    int x;
+    int y;
+};
+
+struct Shape {
+    struct Point origin;
+    struct Point corners[2];
+    unsigned short color;
+};
+
+struct Shape shape;
+struct Point point;
+int counter;
+
+void _start(void) {}
@@ -0,0 +1,18 @@
+// variables declared at known source locations, for checking DW_AT_decl_file and DW_AT_decl_line
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -gdwarf-4 -o decl_test_dw4.elf decl_test.c
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -gdwarf-5 -o decl_test_dw5.elf decl_test.c
+#include "decl_test.h"
+
+int header_var = 1;
+
+struct Point {
+    short x;
+    short y;
+};
+
+struct Point point_var;
+
+void _start(void) {
+    static int static_var;
+    static_var++;
+}
@@ -0,0 +1,2 @@
+// header for decl_test.c: a variable that is declared here and defined in decl_test.c
+extern int header_var;
@@ -0,0 +1,11 @@
+// shared header for dedup_test_a.c and dedup_test_b.c
+struct Inner {
+    short x;
+    short y;
+};
+
+struct Shared {
+    int a;
+    struct Inner inner;
+    unsigned char bytes[3];
+};
@@ -0,0 +1,11 @@
+// the types in dedup_test.h are present in the debug info of both units
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o dedup_test.elf dedup_test_a.c dedup_test_b.c
+#include "dedup_test.h"
+
+// same name as the struct in dedup_test_b.c, but a different layout
+struct Local {
+    int value;
+};
+
+struct Shared shared_a;
+struct Local local_a;
@@ -0,0 +1,11 @@
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o dedup_test.elf dedup_test_a.c dedup_test_b.c
+#include "dedup_test.h"
+
+struct Local {
+    double value;
+};
+
+struct Shared shared_b;
+struct Local local_b;
+
+void _start(void) {}
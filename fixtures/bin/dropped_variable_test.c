@@ -0,0 +1,14 @@
+// a static variable that is optimized out, so that its debug info has no location
+// compile: gcc -g -O2 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o dropped_variable_test.elf dropped_variable_test.c
+static int optimized_out = 3;
+static volatile int kept;
+int used_value;
+
+int get_value(int factor) {
+    int local = factor * 2;
+    return optimized_out + kept + local;
+}
+
+void _start(void) {
+    used_value = get_value(5);
+}
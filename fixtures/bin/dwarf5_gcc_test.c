@@ -0,0 +1,19 @@
+// variables whose debug info uses the Dwarf 5 forms DW_FORM_implicit_const and DW_FORM_line_strp
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -gdwarf-5 -o dwarf5_gcc_test.elf dwarf5_gcc_test.c
+struct Flags {
+    unsigned int a : 1;
+    unsigned int b : 1;
+    unsigned int c : 1;
+    unsigned int d : 1;
+};
+
+struct Pair {
+    int first;
+    int second;
+};
+
+struct Flags flags_var;
+struct Pair pair_var;
+struct Pair pair_array[3];
+
+void _start(void) {}
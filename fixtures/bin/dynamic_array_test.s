@@ -0,0 +1,177 @@
+# arrays with dimensions whose number of elements is only known at runtime, like in variable length arrays
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   unsigned int sample_count;
+#   int samples[sample_count][4];   the DW_AT_count of the outer dimension refers to sample_count
+#   int matrix[3][n];               the DW_AT_upper_bound of the inner dimension is a DWARF expression
+#   int fixed[2][4];                an array with static dimensions for comparison
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o dynamic_array_test.elf dynamic_array_test.s
+
+	.data
+	.p2align 2
+	.globl sample_count
+	.type sample_count, @object
+	.size sample_count, 4
+sample_count:
+	.long 2
+	.globl samples
+	.type samples, @object
+	.size samples, 32
+samples:
+	.zero 32
+	.globl matrix
+	.type matrix, @object
+	.size matrix, 32
+matrix:
+	.zero 32
+	.globl fixed
+	.type fixed, @object
+	.size fixed, 32
+fixed:
+	.zero 32
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 3
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	# array type with subrange children: type
+	.uleb128 4
+	.uleb128 0x01
+	.byte 1
+	.uleb128 0x49
+	.uleb128 0x13
+	.byte 0, 0
+	# subrange: count as a reference to another DIE
+	.uleb128 5
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x37
+	.uleb128 0x13
+	.byte 0, 0
+	# subrange: count as a constant
+	.uleb128 6
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x37
+	.uleb128 0x0b
+	.byte 0, 0
+	# subrange: upper bound as an expression
+	.uleb128 7
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x2f
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "dynamic_array_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Luint:
+	.uleb128 2
+	.asciz "unsigned int"
+	.byte 4
+	.byte 0x07
+.Lsample_count:
+	.uleb128 3
+	.asciz "sample_count"
+	.long .Luint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad sample_count
+.Lsamples_type:
+	.uleb128 4
+	.long .Lint - .Lcu_begin
+	.uleb128 5
+	.long .Lsample_count - .Lcu_begin
+	.uleb128 6
+	.byte 4
+	.byte 0
+.Lmatrix_type:
+	.uleb128 4
+	.long .Lint - .Lcu_begin
+	.uleb128 6
+	.byte 3
+	# DW_OP_breg6 -24, DW_OP_deref: the upper bound is stored in the stack frame
+	.uleb128 7
+	.uleb128 3
+	.byte 0x76, 0x68, 0x06
+	.byte 0
+.Lfixed_type:
+	.uleb128 4
+	.long .Lint - .Lcu_begin
+	.uleb128 6
+	.byte 2
+	.uleb128 6
+	.byte 4
+	.byte 0
+	.uleb128 3
+	.asciz "samples"
+	.long .Lsamples_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad samples
+	.uleb128 3
+	.asciz "matrix"
+	.long .Lmatrix_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad matrix
+	.uleb128 3
+	.asciz "fixed"
+	.long .Lfixed_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad fixed
+	.byte 0
+.Lcu_end:
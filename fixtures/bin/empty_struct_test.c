@@ -0,0 +1,24 @@
+// empty structs and zero-length arrays, whose size is zero in the debug info
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o empty_struct_test.elf empty_struct_test.c
+
+// an empty struct is a gcc extension in C; unlike in C++ its size is 0
+struct Empty {};
+
+struct Holder {
+    int id;
+    struct Empty marker[3];
+    int tail[0];
+};
+
+struct Packet {
+    unsigned short length;
+    unsigned char payload[];
+};
+
+struct Empty empty_var;
+struct Holder holder_var = {7};
+struct Empty empty_array[4];
+struct Packet packet_var = {2};
+int after_var = 1;
+
+void _start(void) {}
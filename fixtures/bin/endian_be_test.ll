@@ -0,0 +1,22 @@
+; a global variable in an object file for a 64-bit big-endian target
+; this is the LLVM IR equivalent of the C code:
+;   unsigned int be_value = 0x12345678;
+; compile: llc-14 -mtriple=powerpc64-unknown-linux-gnu -filetype=obj -O0 -o endian_be_test.o endian_be_test.ll
+
+target datalayout = "E-m:e-i64:64-n32:64-S128-v256:256:256-v512:512:512"
+target triple = "powerpc64-unknown-linux-gnu"
+
+@be_value = global i32 305419896, align 4, !dbg !0
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!20, !21}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "be_value", scope: !2, file: !3, line: 1, type: !5, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, producer: "handwritten", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !4)
+!3 = !DIFile(filename: "endian_be_test.c", directory: "/tmp")
+!4 = !{!0}
+!5 = !DIBasicType(name: "unsigned int", size: 32, encoding: DW_ATE_unsigned)
+
+!20 = !{i32 7, !"Dwarf Version", i32 4}
+!21 = !{i32 2, !"Debug Info Version", i32 3}
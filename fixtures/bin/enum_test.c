@@ -0,0 +1,25 @@
+// enums with explicit, negative and implicit values, used directly, through a typedef, in an array and in a bitfield
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o enum_test.elf enum_test.c
+
+enum GearState {
+    GEAR_REVERSE = -1,
+    GEAR_NEUTRAL = 0,
+    GEAR_FIRST = 1,
+    GEAR_SECOND,
+    GEAR_PARK = 10,
+};
+
+typedef enum { MODE_OFF = 3, MODE_ON = 1, MODE_AUTO = 2 } mode_t;
+
+struct Control {
+    mode_t mode;
+    enum GearState gear : 5;
+    int level;
+};
+
+enum GearState gear_state = GEAR_FIRST;
+mode_t operating_mode = MODE_AUTO;
+enum GearState gear_history[3];
+struct Control control;
+
+void _start(void) {}
@@ -0,0 +1,18 @@
+// globals that are visible outside of their unit, and static ones that are not
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o external_test.elf external_test.c
+
+int exported_value = 1;
+// declared before its definition
+extern int declared_value;
+int declared_value = 2;
+static int internal_value = 3;
+
+int read_values(void) {
+    static int call_count;
+    call_count++;
+    return exported_value + declared_value + internal_value;
+}
+
+void _start(void) {
+    read_values();
+}
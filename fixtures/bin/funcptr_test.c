@@ -0,0 +1,17 @@
+// function pointer tables
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o funcptr_test.elf funcptr_test.c
+struct state_machine;
+typedef int handler_fn(struct state_machine *sm, unsigned char event);
+
+void isr_reset(void) {}
+void isr_timer(void) {}
+void isr_uart(void) {}
+void (*isr_table[3])(void) = {isr_reset, isr_timer, isr_uart};
+
+struct state_machine {
+    int state;
+    handler_fn *handlers[4];
+};
+struct state_machine machine;
+
+void _start(void) {}
@@ -0,0 +1,9 @@
+// this unit is identical in incomplete_incremental_test_v1.elf and incomplete_incremental_test_v2.elf. It only declares struct Opaque
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o incomplete_incremental_test_v1.elf incomplete_incremental_test_a.c incomplete_incremental_test_b.c
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -DVERSION=2 -o incomplete_incremental_test_v2.elf incomplete_incremental_test_a.c incomplete_incremental_test_b.c
+
+struct Opaque;
+
+struct Opaque *opaque_ptr;
+
+void _start(void) {}
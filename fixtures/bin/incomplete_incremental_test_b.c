@@ -0,0 +1,14 @@
+// only the second version of this unit contains the definition of struct Opaque
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o incomplete_incremental_test_v1.elf incomplete_incremental_test_a.c incomplete_incremental_test_b.c
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -DVERSION=2 -o incomplete_incremental_test_v2.elf incomplete_incremental_test_a.c incomplete_incremental_test_b.c
+
+#if VERSION == 2
+struct Opaque {
+    int value;
+    short flags;
+};
+
+struct Opaque opaque_obj;
+#endif
+
+int other_var;
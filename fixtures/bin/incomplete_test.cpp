@@ -0,0 +1,9 @@
+// global objects whose classes are only declared in this unit
+// compile: g++ -g -O0 -fno-rtti -fno-exceptions -fno-pie -c -o incomplete_test.o incomplete_test.cpp
+// compile: g++ -no-pie -nostdlib -static -Wl,--build-id=none -o incomplete_test.elf incomplete_test.o incomplete_test_widget.o incomplete_test_gadget.o
+#include "incomplete_test.h"
+
+Widget widget_obj;
+Gadget gadget_obj;
+
+extern "C" void _start(void) {}
@@ -0,0 +1,12 @@
+// header for incomplete_test: g++ only emits the definition of a class with virtual functions in the unit that defines its first virtual function
+class Widget {
+public:
+    virtual int get() const;
+    int value = 1;
+};
+
+class Gadget {
+public:
+    virtual int get() const;
+    short count = 2;
+};
@@ -0,0 +1,6 @@
+// the definition of class Gadget, compiled without debug info
+// compile: g++ -O0 -fno-rtti -fno-exceptions -fno-pie -c -o incomplete_test_gadget.o incomplete_test_gadget.cpp
+// compile: g++ -no-pie -nostdlib -static -Wl,--build-id=none -o incomplete_test.elf incomplete_test.o incomplete_test_widget.o incomplete_test_gadget.o
+#include "incomplete_test.h"
+
+int Gadget::get() const { return count; }
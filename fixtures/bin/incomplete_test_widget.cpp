@@ -0,0 +1,6 @@
+// the definition of class Widget, compiled with debug info
+// compile: g++ -g -O0 -fno-rtti -fno-exceptions -fno-pie -c -o incomplete_test_widget.o incomplete_test_widget.cpp
+// compile: g++ -no-pie -nostdlib -static -Wl,--build-id=none -o incomplete_test.elf incomplete_test.o incomplete_test_widget.o incomplete_test_gadget.o
+#include "incomplete_test.h"
+
+int Widget::get() const { return value; }
@@ -0,0 +1,15 @@
+// this unit is identical in incremental_test_v1.elf and incremental_test_v2.elf
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o incremental_test_v1.elf incremental_test_a.c incremental_test_b.c incremental_test_c.c
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -DVERSION=2 -o incremental_test_v2.elf incremental_test_a.c incremental_test_b.c incremental_test_c.c
+
+struct Settings {
+    int gain;
+    short offsets[4];
+};
+
+struct Settings settings = {1, {0, 1, 2, 3}};
+static unsigned char local_flag = 1;
+
+unsigned char *get_flag(void) { return &local_flag; }
+
+void _start(void) {}
@@ -0,0 +1,16 @@
+// this unit differs between incremental_test_v1.elf and incremental_test_v2.elf. Its variables are in .bss,
+// so that the addresses of the variables in the other units stay the same
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o incremental_test_v1.elf incremental_test_a.c incremental_test_b.c incremental_test_c.c
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -DVERSION=2 -o incremental_test_v2.elf incremental_test_a.c incremental_test_b.c incremental_test_c.c
+
+struct Measurement {
+    float value;
+#if VERSION == 2
+    float limit;
+#endif
+};
+
+struct Measurement measurement;
+#if VERSION == 2
+int added_counter;
+#endif
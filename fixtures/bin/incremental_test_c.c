@@ -0,0 +1,11 @@
+// this unit is identical in incremental_test_v1.elf and incremental_test_v2.elf, but it is located at a
+// different offset in .debug_info, because it follows the changed unit
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o incremental_test_v1.elf incremental_test_a.c incremental_test_b.c incremental_test_c.c
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -DVERSION=2 -o incremental_test_v2.elf incremental_test_a.c incremental_test_b.c incremental_test_c.c
+
+typedef struct {
+    unsigned int id;
+    double scale;
+} Channel;
+
+Channel channels[2] = {{1, 0.5}, {2, 0.25}};
@@ -0,0 +1,27 @@
+// multi-level inheritance, with a base class that has a member named "_"
+// compile: g++ -g -O0 -fno-rtti -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o inheritance_test.elf inheritance_test.cpp
+
+class BaseBase {
+  public:
+    int deep_value;
+};
+
+class Base : public BaseBase {
+  public:
+    int base_value;
+};
+
+class Underscore {
+  public:
+    int _;
+    int other;
+};
+
+class Derived : public Base, public Underscore {
+  public:
+    int derived_value;
+};
+
+Derived derived;
+
+extern "C" void _start(void) {}
@@ -0,0 +1,22 @@
+// a struct with padding, a nested array and a union, for checking the layout of a symbol
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o layout_test.elf layout_test.c
+struct Point {
+    short x;
+    short y;
+};
+
+struct Layout {
+    char tag;
+    int count;
+    struct Point points[2];
+    union {
+        unsigned int word;
+        unsigned char bytes[4];
+    } raw;
+    double value;
+};
+
+struct Layout layout_var;
+float scalar_var;
+
+void _start(void) {}
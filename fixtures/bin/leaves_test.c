@@ -0,0 +1,23 @@
+// a struct with bitfields, nested structs and an array, for expanding a symbol into its scalar elements
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o leaves_test.elf leaves_test.c
+struct Flags {
+    unsigned int ready : 1;
+    unsigned int mode : 3;
+    unsigned int count : 12;
+};
+
+struct Inner {
+    short a;
+    unsigned char b[2];
+};
+
+struct Outer {
+    int id;
+    struct Flags flags;
+    struct Inner inner[2];
+    float value;
+};
+
+struct Outer leaves_var;
+
+void _start(void) {}
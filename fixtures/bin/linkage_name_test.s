@@ -0,0 +1,164 @@
+# C++ variables whose debug info has no DW_AT_location, so only their linkage names lead to their addresses
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   namespace cfg { int scale_factor; }        a definition with a DW_AT_linkage_name
+#   namespace cfg { struct Limits { static short upper; }; }
+#   short cfg::Limits::upper;                  a definition whose declaration has the DW_AT_linkage_name
+#   int plain_only;                            no linkage name; the symbol of the same name is not used
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o linkage_name_test.elf linkage_name_test.s
+
+	.data
+	.p2align 2
+	.globl _ZN3cfg12scale_factorE
+	.type _ZN3cfg12scale_factorE, @object
+	.size _ZN3cfg12scale_factorE, 4
+_ZN3cfg12scale_factorE:
+	.long 3
+	.globl _ZN3cfg6Limits5upperE
+	.type _ZN3cfg6Limits5upperE, @object
+	.size _ZN3cfg6Limits5upperE, 2
+_ZN3cfg6Limits5upperE:
+	.short 100
+	.p2align 2
+	.globl plain_only
+	.type plain_only, @object
+	.size plain_only, 4
+plain_only:
+	.long 5
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# namespace with children: name
+	.uleb128 3
+	.uleb128 0x39
+	.byte 1
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# variable without location: name, linkage_name, type, external
+	.uleb128 4
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x6e
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.byte 0, 0
+	# structure type with children: name, byte_size
+	.uleb128 5
+	.uleb128 0x13
+	.byte 1
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.byte 0, 0
+	# static member declaration: name, linkage_name, type, external, declaration
+	.uleb128 6
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x6e
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x3c
+	.uleb128 0x19
+	.byte 0, 0
+	# variable definition without location: specification
+	.uleb128 7
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x47
+	.uleb128 0x13
+	.byte 0, 0
+	# variable without location and linkage name: name, type, external
+	.uleb128 8
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x04
+	.asciz "linkage_name_test.cpp"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lshort:
+	.uleb128 2
+	.asciz "short int"
+	.byte 2
+	.byte 0x05
+	.uleb128 3
+	.asciz "cfg"
+	.uleb128 4
+	.asciz "scale_factor"
+	.asciz "_ZN3cfg12scale_factorE"
+	.long .Lint - .Lcu_begin
+	.uleb128 5
+	.asciz "Limits"
+	.byte 1
+.Lupper_decl:
+	.uleb128 6
+	.asciz "upper"
+	.asciz "_ZN3cfg6Limits5upperE"
+	.long .Lshort - .Lcu_begin
+	.byte 0
+	.byte 0
+	.uleb128 7
+	.long .Lupper_decl - .Lcu_begin
+	.uleb128 8
+	.asciz "plain_only"
+	.long .Lint - .Lcu_begin
+	.byte 0
+.Lcu_end:
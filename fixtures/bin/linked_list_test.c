@@ -0,0 +1,29 @@
+// self-referential and mutually referential structs, which are linked through pointers
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o linked_list_test.elf linked_list_test.c
+struct Node {
+    int v;
+    struct Node *next;
+};
+
+typedef struct Tree tree_t;
+struct Tree {
+    tree_t *left;
+    tree_t *right;
+    const struct Tree *parent;
+};
+
+struct B;
+struct A {
+    struct B *b;
+};
+struct B {
+    struct A *a;
+    int value;
+};
+
+struct Node node;
+tree_t tree;
+struct A a_var;
+void *void_ptr;
+
+void _start(void) {}
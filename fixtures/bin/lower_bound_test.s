@@ -0,0 +1,186 @@
+# arrays whose indices don't start at 0, like in Ada or Fortran
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   int arr[1..4]                 DW_AT_lower_bound 1
+#   short matrix[0..1][5..7]      DW_AT_lower_bound 5 on the second dimension
+#   int fortran_array(3)          in a Fortran unit, without DW_AT_lower_bound, so the lower bound is 1
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o lower_bound_test.elf lower_bound_test.s
+
+	.data
+	.p2align 3
+	.globl arr
+arr:
+	.long 10, 20, 30, 40
+	.globl matrix
+matrix:
+	.zero 12
+	.p2align 2
+	.globl fortran_array
+fortran_array:
+	.zero 12
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# array type: type
+	.uleb128 3
+	.uleb128 0x01
+	.byte 1
+	.uleb128 0x49
+	.uleb128 0x13
+	.byte 0, 0
+	# subrange: type, lower_bound, upper_bound
+	.uleb128 4
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x22
+	.uleb128 0x0b
+	.uleb128 0x2f
+	.uleb128 0x0b
+	.byte 0, 0
+	# subrange: type, upper_bound
+	.uleb128 5
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2f
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 6
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0d
+	.asciz "lower_bound_test.adb"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lshort:
+	.uleb128 2
+	.asciz "short"
+	.byte 2
+	.byte 0x05
+.Lsizetype:
+	.uleb128 2
+	.asciz "long unsigned int"
+	.byte 8
+	.byte 0x07
+.Larr_type:
+	.uleb128 3
+	.long .Lint - .Lcu_begin
+	.uleb128 4
+	.long .Lsizetype - .Lcu_begin
+	.byte 1
+	.byte 4
+	.byte 0
+.Lmatrix_type:
+	.uleb128 3
+	.long .Lshort - .Lcu_begin
+	.uleb128 4
+	.long .Lsizetype - .Lcu_begin
+	.byte 0
+	.byte 1
+	.uleb128 4
+	.long .Lsizetype - .Lcu_begin
+	.byte 5
+	.byte 7
+	.byte 0
+	.uleb128 6
+	.asciz "arr"
+	.long .Larr_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad arr
+	.uleb128 6
+	.asciz "matrix"
+	.long .Lmatrix_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad matrix
+	.byte 0
+.Lcu_end:
+.Lcu2_begin:
+	.long .Lcu2_end - .Lcu2_version
+.Lcu2_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x08
+	.asciz "lower_bound_test.f90"
+.Lint2:
+	.uleb128 2
+	.asciz "integer(kind=4)"
+	.byte 4
+	.byte 0x05
+.Lsizetype2:
+	.uleb128 2
+	.asciz "integer(kind=8)"
+	.byte 8
+	.byte 0x05
+.Lfortran_array_type:
+	.uleb128 3
+	.long .Lint2 - .Lcu2_begin
+	.uleb128 5
+	.long .Lsizetype2 - .Lcu2_begin
+	.byte 3
+	.byte 0
+	.uleb128 6
+	.asciz "fortran_array"
+	.long .Lfortran_array_type - .Lcu2_begin
+	.byte 9
+	.byte 0x03
+	.quad fortran_array
+	.byte 0
+.Lcu2_end:
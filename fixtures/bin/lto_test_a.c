@@ -0,0 +1,6 @@
+// the struct is only declared in this unit, it is defined in lto_test_b.c
+// compile: gcc -g -O0 -flto -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o lto_test.elf lto_test_a.c lto_test_b.c
+struct Record;
+extern struct Record record;
+
+struct Record *record_ptr = &record;
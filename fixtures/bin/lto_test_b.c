@@ -0,0 +1,10 @@
+// compile: gcc -g -O0 -flto -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o lto_test.elf lto_test_a.c lto_test_b.c
+struct Record {
+    int id;
+    float values[4];
+    unsigned char flags;
+};
+
+struct Record record;
+
+void _start(void) {}
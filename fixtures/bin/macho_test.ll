@@ -0,0 +1,58 @@
+; global variables in a Mach-O object file
+; this is the LLVM IR equivalent of the C code:
+;   struct S { int a; unsigned char b[4]; };
+;   int counter = 5;
+;   struct S structvar;
+;   unsigned char var_array[33];
+;   static int staticvar = 3;
+;   int *use_staticvar(void) { return &staticvar; }
+; compile: llc-14 -mtriple=x86_64-apple-macosx10.15 -filetype=obj -O0 -o macho_test.o macho_test.ll
+
+target datalayout = "e-m:o-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+%struct.S = type { i32, [4 x i8] }
+
+@counter = global i32 5, align 4, !dbg !0
+@structvar = global %struct.S zeroinitializer, align 4, !dbg !5
+@var_array = global [33 x i8] zeroinitializer, align 16, !dbg !16
+@staticvar = internal global i32 3, align 4, !dbg !20
+
+define i32* @use_staticvar() !dbg !27 {
+  ret i32* @staticvar, !dbg !31
+}
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!23, !24}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "counter", scope: !2, file: !3, line: 2, type: !8, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, producer: "handwritten", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !4)
+!3 = !DIFile(filename: "macho_test.c", directory: "/tmp")
+!4 = !{!0, !5, !16, !20}
+!5 = !DIGlobalVariableExpression(var: !6, expr: !DIExpression())
+!6 = distinct !DIGlobalVariable(name: "structvar", scope: !2, file: !3, line: 3, type: !7, isLocal: false, isDefinition: true)
+!7 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "S", file: !3, line: 1, size: 64, elements: !9)
+!8 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!9 = !{!10, !11}
+!10 = !DIDerivedType(tag: DW_TAG_member, name: "a", scope: !7, file: !3, line: 1, baseType: !8, size: 32)
+!11 = !DIDerivedType(tag: DW_TAG_member, name: "b", scope: !7, file: !3, line: 1, baseType: !12, size: 32, offset: 32)
+!12 = !DICompositeType(tag: DW_TAG_array_type, baseType: !13, size: 32, elements: !14)
+!13 = !DIBasicType(name: "unsigned char", size: 8, encoding: DW_ATE_unsigned_char)
+!14 = !{!15}
+!15 = !DISubrange(count: 4)
+!16 = !DIGlobalVariableExpression(var: !17, expr: !DIExpression())
+!17 = distinct !DIGlobalVariable(name: "var_array", scope: !2, file: !3, line: 4, type: !18, isLocal: false, isDefinition: true)
+!18 = !DICompositeType(tag: DW_TAG_array_type, baseType: !13, size: 264, elements: !19)
+!19 = !{!22}
+!20 = !DIGlobalVariableExpression(var: !21, expr: !DIExpression())
+!21 = distinct !DIGlobalVariable(name: "staticvar", scope: !2, file: !3, line: 5, type: !8, isLocal: true, isDefinition: true)
+!22 = !DISubrange(count: 33)
+!23 = !{i32 7, !"Dwarf Version", i32 4}
+!24 = !{i32 2, !"Debug Info Version", i32 3}
+!25 = !{}
+!26 = !DISubroutineType(types: !32)
+!27 = distinct !DISubprogram(name: "use_staticvar", scope: !3, file: !3, line: 6, type: !26, scopeLine: 6, spFlags: DISPFlagDefinition, unit: !2, retainedNodes: !25)
+!31 = !DILocation(line: 6, column: 30, scope: !27)
+!32 = !{!33}
+!33 = !DIDerivedType(tag: DW_TAG_pointer_type, baseType: !8, size: 64)
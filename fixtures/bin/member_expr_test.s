@@ -0,0 +1,145 @@
+# a struct whose members are located by DWARF expressions that use DW_OP_push_object_address
+# the Dwarf 4 debug info was written by hand; it describes this variable:
+#   struct Dynamic {
+#       int len;      DW_AT_data_member_location 0
+#       int data;     DW_OP_push_object_address; DW_OP_deref; DW_OP_plus_uconst 4 - only known at runtime
+#       short tail;   DW_OP_push_object_address; DW_OP_plus_uconst 8 - the constant offset 8
+#   } dyn_var;
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o member_expr_test.elf member_expr_test.s
+
+	.data
+	.p2align 2
+	.globl dyn_var
+	.type dyn_var, @object
+	.size dyn_var, 12
+dyn_var:
+	.zero 12
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# struct type: name, byte_size
+	.uleb128 3
+	.uleb128 0x13
+	.byte 1
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.byte 0, 0
+	# member: name, type, data_member_location as a constant
+	.uleb128 4
+	.uleb128 0x0d
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x38
+	.uleb128 0x0b
+	.byte 0, 0
+	# member: name, type, data_member_location as an expression
+	.uleb128 5
+	.uleb128 0x0d
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x38
+	.uleb128 0x18
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 6
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x04
+	.asciz "member_expr_test.cpp"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lshort:
+	.uleb128 2
+	.asciz "short"
+	.byte 2
+	.byte 0x05
+.Ldynamic:
+	.uleb128 3
+	.asciz "Dynamic"
+	.byte 12
+	.uleb128 4
+	.asciz "len"
+	.long .Lint - .Lcu_begin
+	.byte 0
+	.uleb128 5
+	.asciz "data"
+	.long .Lint - .Lcu_begin
+	.uleb128 4
+	.byte 0x97
+	.byte 0x06
+	.byte 0x23
+	.uleb128 4
+	.uleb128 5
+	.asciz "tail"
+	.long .Lshort - .Lcu_begin
+	.uleb128 3
+	.byte 0x97
+	.byte 0x23
+	.uleb128 8
+	.byte 0
+	.uleb128 6
+	.asciz "dyn_var"
+	.long .Ldynamic - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad dyn_var
+	.byte 0
+.Lcu_end:
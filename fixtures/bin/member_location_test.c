@@ -0,0 +1,17 @@
+// test file for member locations that are given as location expressions instead of constants
+// compile: gcc -g -gdwarf-2 -gstrict-dwarf -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o member_location_test.elf member_location_test.c
+struct Inner {
+    char a;
+    short b;
+};
+
+struct Outer {
+    char flag;
+    int count;
+    struct Inner inner;
+    double value;
+};
+
+struct Outer outer_var;
+
+void _start(void) {}
@@ -0,0 +1,50 @@
+// multiple inheritance, where a member name is present in two base classes, and a non-virtual diamond
+// compile: g++ -g -O0 -fno-rtti -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o multiple_inheritance_test.elf multiple_inheritance_test.cpp
+
+class SensorA {
+  public:
+    int x;
+    int a_only;
+};
+
+class SensorB {
+  public:
+    int x;
+    int b_only;
+};
+
+class Fusion : public SensorA, public SensorB {
+  public:
+    int y;
+};
+
+class Override : public SensorA, public SensorB {
+  public:
+    int x;
+};
+
+class Root {
+  public:
+    int id;
+};
+
+class Left : public Root {
+  public:
+    int left_value;
+};
+
+class Right : public Root {
+  public:
+    int right_value;
+};
+
+class Bottom : public Left, public Right {
+  public:
+    int bottom_value;
+};
+
+Fusion fusion;
+Override override_var;
+Bottom bottom;
+
+extern "C" void _start(void) {}
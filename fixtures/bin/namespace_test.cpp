@@ -0,0 +1,18 @@
+// namespaced C++ globals, some of which have the same name, for lookups by their qualified names
+// compile: g++ -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o namespace_test.elf namespace_test.cpp
+
+namespace motor {
+namespace control {
+struct Params {
+    int gain;
+    short limits[2];
+};
+Params params;
+int counter;
+} // namespace control
+int counter;
+} // namespace motor
+
+int counter;
+
+extern "C" void _start(void) {}
@@ -0,0 +1,17 @@
+// a 2D array of structs which contain their own array of structs
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o nested_array_test.elf nested_array_test.c
+
+struct Point {
+    short x;
+    short y;
+};
+
+struct Cell {
+    int id;
+    struct Point corners[3];
+    char flag;
+};
+
+struct Cell grid[4][2];
+
+void _start(void) {}
@@ -0,0 +1,177 @@
+# global variables whose names and types are only found by following a chain of DW_AT_abstract_origin references
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   int level_count;      concrete entry -> origin without attributes -> origin with name and type
+#   short deep_gain;      concrete entry -> origin with the type -> origin without attributes -> origin with the name
+#   int loop_var;         concrete entry with the name -> origin with the type -> origin that refers back (a cycle)
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o origin_chain_test.elf origin_chain_test.s
+
+	.data
+	.p2align 2
+	.globl level_count
+	.type level_count, @object
+	.size level_count, 4
+level_count:
+	.long 7
+	.globl deep_gain
+	.type deep_gain, @object
+	.size deep_gain, 2
+deep_gain:
+	.short 12
+	.p2align 2
+	.globl loop_var
+	.type loop_var, @object
+	.size loop_var, 4
+loop_var:
+	.long 1
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# concrete variable: abstract_origin, location
+	.uleb128 3
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x31
+	.uleb128 0x13
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	# origin variable: abstract_origin
+	.uleb128 4
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x31
+	.uleb128 0x13
+	.byte 0, 0
+	# abstract variable: name, type, external
+	.uleb128 5
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.byte 0, 0
+	# origin variable: abstract_origin, type
+	.uleb128 6
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x31
+	.uleb128 0x13
+	.uleb128 0x49
+	.uleb128 0x13
+	.byte 0, 0
+	# abstract variable: name
+	.uleb128 7
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# concrete variable: name, abstract_origin, location
+	.uleb128 8
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x31
+	.uleb128 0x13
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "origin_chain_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lshort:
+	.uleb128 2
+	.asciz "short int"
+	.byte 2
+	.byte 0x05
+	# level_count
+	.uleb128 3
+	.long .Llevel_mid - .Lcu_begin
+	.uleb128 9
+	.byte 0x03
+	.quad level_count
+.Llevel_mid:
+	.uleb128 4
+	.long .Llevel_abstract - .Lcu_begin
+.Llevel_abstract:
+	.uleb128 5
+	.asciz "level_count"
+	.long .Lint - .Lcu_begin
+	# deep_gain
+	.uleb128 3
+	.long .Ldeep_typed - .Lcu_begin
+	.uleb128 9
+	.byte 0x03
+	.quad deep_gain
+.Ldeep_typed:
+	.uleb128 6
+	.long .Ldeep_mid - .Lcu_begin
+	.long .Lshort - .Lcu_begin
+.Ldeep_mid:
+	.uleb128 4
+	.long .Ldeep_named - .Lcu_begin
+.Ldeep_named:
+	.uleb128 7
+	.asciz "deep_gain"
+	# loop_var
+	.uleb128 8
+	.asciz "loop_var"
+	.long .Lloop_first - .Lcu_begin
+	.uleb128 9
+	.byte 0x03
+	.quad loop_var
+.Lloop_first:
+	.uleb128 6
+	.long .Lloop_second - .Lcu_begin
+	.long .Lint - .Lcu_begin
+.Lloop_second:
+	.uleb128 4
+	.long .Lloop_first - .Lcu_begin
+	.byte 0
+.Lcu_end:
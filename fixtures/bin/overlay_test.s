@@ -0,0 +1,109 @@
+# two variables at the same address, like variables in overlays that a linker script places at the same address
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   int overlay_a;      at the address of overlay_b
+#   short overlay_b;    at the address of overlay_a
+#   int after_overlay;  directly after overlay_a
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o overlay_test.elf overlay_test.s
+
+	.data
+	.p2align 2
+	.globl overlay_a
+	.type overlay_a, @object
+	.size overlay_a, 4
+	.globl overlay_b
+	.type overlay_b, @object
+	.size overlay_b, 2
+overlay_a:
+overlay_b:
+	.zero 4
+	.globl after_overlay
+	.type after_overlay, @object
+	.size after_overlay, 4
+after_overlay:
+	.zero 4
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 3
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "overlay_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lshort:
+	.uleb128 2
+	.asciz "short"
+	.byte 2
+	.byte 0x05
+	.uleb128 3
+	.asciz "overlay_a"
+	.long .Lint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad overlay_a
+	.uleb128 3
+	.asciz "overlay_b"
+	.long .Lshort - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad overlay_b
+	.uleb128 3
+	.asciz "after_overlay"
+	.long .Lint - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad after_overlay
+	.byte 0
+.Lcu_end:
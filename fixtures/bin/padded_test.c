@@ -0,0 +1,12 @@
+// a struct whose declared size is larger than the sum of its members
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o padded_test.elf padded_test.c
+struct Padded {
+    char a;
+    short b;
+} __attribute__((aligned(16)));
+typedef struct Padded padded_t;
+
+padded_t padded_var;
+padded_t padded_array[4];
+
+void _start(void) {}
@@ -0,0 +1,44 @@
+; a small Windows program with CodeView debug info, whose PDB file is created separately by pe_pdb_test_pdb.py
+; this is the LLVM IR equivalent of the C code:
+;   struct Calib { int gain; float factor; };
+;   struct Calib calib = { 42, 1.5f };
+;   short counter = 7;
+;   void mainCRTStartup(void) {}
+; compile: llc-14 -filetype=obj -O0 -o pe_pdb_test.obj pe_pdb_test.ll
+; link: ld -m i386pep --build-id --entry=mainCRTStartup -o pe_pdb_test.exe pe_pdb_test.obj
+
+target datalayout = "e-m:w-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-windows-msvc"
+
+%struct.Calib = type { i32, float }
+
+@calib = dso_local global %struct.Calib { i32 42, float 1.5 }, align 4, !dbg !0
+@counter = dso_local global i16 7, align 2, !dbg !20
+
+define dso_local void @mainCRTStartup() !dbg !30 {
+  ret void, !dbg !33
+}
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!40, !41}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "calib", scope: !2, file: !3, line: 2, type: !5, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, producer: "handwritten", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !4)
+!3 = !DIFile(filename: "pe_pdb_test.c", directory: "C:\\tmp")
+!4 = !{!0, !20}
+!5 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "Calib", file: !3, line: 1, size: 64, elements: !6)
+!6 = !{!7, !9}
+!7 = !DIDerivedType(tag: DW_TAG_member, name: "gain", scope: !5, file: !3, line: 1, baseType: !8, size: 32)
+!8 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!9 = !DIDerivedType(tag: DW_TAG_member, name: "factor", scope: !5, file: !3, line: 1, baseType: !10, size: 32, offset: 32)
+!10 = !DIBasicType(name: "float", size: 32, encoding: DW_ATE_float)
+!20 = !DIGlobalVariableExpression(var: !21, expr: !DIExpression())
+!21 = distinct !DIGlobalVariable(name: "counter", scope: !2, file: !3, line: 3, type: !22, isLocal: false, isDefinition: true)
+!22 = !DIBasicType(name: "short", size: 16, encoding: DW_ATE_signed)
+!30 = distinct !DISubprogram(name: "mainCRTStartup", scope: !3, file: !3, line: 4, type: !31, scopeLine: 4, spFlags: DISPFlagDefinition, unit: !2, retainedNodes: !32)
+!31 = !DISubroutineType(types: !32)
+!32 = !{}
+!33 = !DILocation(line: 4, column: 1, scope: !30)
+!40 = !{i32 2, !"CodeView", i32 1}
+!41 = !{i32 2, !"Debug Info Version", i32 3}
@@ -0,0 +1,140 @@
+#!/usr/bin/env python3
+# Complete a PDB file written by "llvm-pdbutil yaml2pdb" for use with an exe file:
+# yaml2pdb can't write the section headers of the exe or the global symbol records, so they are
+# added as new streams. Like a linker does, the global data symbols are moved from the symbols of
+# the modules to the global symbol records.
+# usage: pe_pdb_test_pdb.py <exe file> <pdb file>
+import struct
+import sys
+
+MSF_MAGIC = b"Microsoft C/C++ MSF 7.00\r\n\x1aDS\0\0\0"
+BLOCK_SIZE = 4096
+NIL_STREAM = 0xFFFFFFFF
+S_GDATA32 = 0x110D
+
+
+def read_msf(data):
+    assert data[:32] == MSF_MAGIC
+    block_size, _, _, dir_bytes, _, block_map_addr = struct.unpack_from("<6I", data, 32)
+    dir_block_count = (dir_bytes + block_size - 1) // block_size
+    dir_blocks = struct.unpack_from(f"<{dir_block_count}I", data, block_map_addr * block_size)
+    directory = b"".join(data[b * block_size : (b + 1) * block_size] for b in dir_blocks)
+    num_streams = struct.unpack_from("<I", directory)[0]
+    sizes = struct.unpack_from(f"<{num_streams}I", directory, 4)
+    pos = 4 + 4 * num_streams
+    streams = []
+    for size in sizes:
+        if size == NIL_STREAM:
+            streams.append(None)
+            continue
+        count = (size + block_size - 1) // block_size
+        blocks = struct.unpack_from(f"<{count}I", directory, pos)
+        pos += 4 * count
+        content = b"".join(data[b * block_size : (b + 1) * block_size] for b in blocks)
+        streams.append(content[:size])
+    return streams
+
+
+def write_msf(streams):
+    # block 0 is the super block, blocks 1 and 2 are the free block maps
+    blocks = [b"", b"", b""]
+
+    def add_blocks(content):
+        indices = []
+        for start in range(0, len(content), BLOCK_SIZE):
+            indices.append(len(blocks))
+            blocks.append(content[start : start + BLOCK_SIZE])
+        return indices
+
+    stream_blocks = [add_blocks(stream) if stream else [] for stream in streams]
+    directory = struct.pack("<I", len(streams))
+    for stream in streams:
+        directory += struct.pack("<I", NIL_STREAM if stream is None else len(stream))
+    for indices in stream_blocks:
+        directory += struct.pack(f"<{len(indices)}I", *indices)
+    dir_blocks = add_blocks(directory)
+    block_map_addr = len(blocks)
+    blocks.append(struct.pack(f"<{len(dir_blocks)}I", *dir_blocks))
+
+    num_blocks = len(blocks)
+    assert num_blocks <= BLOCK_SIZE * 8
+    # a set bit marks a free block
+    fpm = bytearray(b"\xff" * BLOCK_SIZE)
+    for idx in range(num_blocks):
+        fpm[idx // 8] &= ~(1 << (idx % 8)) & 0xFF
+    blocks[1] = bytes(fpm)
+    blocks[2] = bytes(fpm)
+    blocks[0] = MSF_MAGIC + struct.pack(
+        "<6I", BLOCK_SIZE, 1, num_blocks, len(directory), 0, block_map_addr
+    )
+    return b"".join(block.ljust(BLOCK_SIZE, b"\0") for block in blocks)
+
+
+def read_section_headers(exe):
+    pe_offset = struct.unpack_from("<I", exe, 0x3C)[0]
+    assert exe[pe_offset : pe_offset + 4] == b"PE\0\0"
+    num_sections = struct.unpack_from("<H", exe, pe_offset + 6)[0]
+    optional_header_size = struct.unpack_from("<H", exe, pe_offset + 20)[0]
+    start = pe_offset + 24 + optional_header_size
+    return exe[start : start + 40 * num_sections]
+
+
+def move_global_data_symbols(streams, dbi):
+    # the module info substream follows the 64 byte header of the DBI stream
+    mod_info_size = struct.unpack_from("<i", dbi, 24)[0]
+    records = b""
+    pos = 64
+    while pos < 64 + mod_info_size:
+        module_stream, sym_bytes = struct.unpack_from("<HI", dbi, pos + 34)
+        symbols = streams[module_stream]
+        # the symbols of a module start after a 4 byte signature
+        kept = symbols[:4]
+        sym_pos = 4
+        while sym_pos < sym_bytes:
+            length, kind = struct.unpack_from("<HH", symbols, sym_pos)
+            record = symbols[sym_pos : sym_pos + 2 + length]
+            if kind == S_GDATA32:
+                records += record
+            else:
+                kept += record
+            sym_pos += 2 + length
+        streams[module_stream] = kept + symbols[sym_bytes:]
+        struct.pack_into("<I", dbi, pos + 36, len(kept))
+        # the fixed part of the module info is followed by the module name and the object file name
+        name_end = dbi.index(b"\0", pos + 64)
+        obj_end = dbi.index(b"\0", name_end + 1)
+        pos = (obj_end + 1 + 3) & ~3
+    return records
+
+
+def main():
+    exe_name, pdb_name = sys.argv[1:3]
+    with open(exe_name, "rb") as exe_file:
+        section_headers = read_section_headers(exe_file.read())
+    with open(pdb_name, "rb") as pdb_file:
+        streams = read_msf(pdb_file.read())
+
+    dbi = bytearray(streams[3])
+    symbols_stream = len(streams)
+    streams.append(move_global_data_symbols(streams, dbi))
+    section_stream = len(streams)
+    streams.append(section_headers)
+
+    struct.pack_into("<H", dbi, 20, symbols_stream)
+    substream_sizes = struct.unpack_from("<5i", dbi, 24)
+    dbg_header_size = struct.unpack_from("<i", dbi, 48)[0]
+    ec_size = struct.unpack_from("<i", dbi, 52)[0]
+    dbg_header_pos = 64 + sum(substream_sizes) + ec_size
+    if dbg_header_size == 0:
+        dbi[dbg_header_pos:dbg_header_pos] = b"\xff\xff" * 11
+        struct.pack_into("<i", dbi, 48, 22)
+    # entry 5 of the optional debug header is the stream of the section headers
+    struct.pack_into("<H", dbi, dbg_header_pos + 10, section_stream)
+    streams[3] = bytes(dbi)
+
+    with open(pdb_name, "wb") as pdb_file:
+        pdb_file.write(write_msf(streams))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,34 @@
+// namespaces, nested classes and an anonymous namespace for the C++ qualified names of symbols
+// compile: g++ -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o qualified_name_test.elf qualified_name_test.cpp
+
+namespace ns1 {
+namespace ns2 {
+class Outer {
+  public:
+    class Inner {
+      public:
+        int value;
+        unsigned char data[4];
+    };
+    Inner inner;
+    int count;
+};
+Outer outer_var;
+} // namespace ns2
+} // namespace ns1
+
+namespace {
+struct Config {
+    int mode;
+};
+} // namespace
+
+Config config_var;
+
+struct {
+    int x;
+} anon_struct_var;
+
+extern "C" void _start(void) {
+    config_var.mode = 1;
+}
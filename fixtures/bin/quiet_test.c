@@ -0,0 +1,13 @@
+// global variables whose names used to trigger debug output while loading
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o quiet_test.elf quiet_test.c
+
+struct FsmState {
+    int state;
+    unsigned char flags[4];
+};
+
+struct FsmState g_lksStateStruct;
+struct FsmState g_FsmRunnable;
+int g_fsmRunnable = 1;
+
+void _start(void) {}
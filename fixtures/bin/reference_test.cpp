@@ -0,0 +1,16 @@
+// globals of C++ reference types, which are stored like pointers to the objects they refer to
+// compile: g++ -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o reference_test.elf reference_test.cpp
+
+struct Limits {
+    int lower;
+    int upper;
+};
+
+int gain = 3;
+Limits limits = {-10, 10};
+
+const int &gain_ref = gain;
+Limits &limits_ref = limits;
+int &&temp_ref = 42;
+
+extern "C" void _start() {}
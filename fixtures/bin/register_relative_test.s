@@ -0,0 +1,111 @@
+# a global variable whose location is relative to a register, like the data of some embedded overlay schemes
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   int overlay_table[4];   DW_AT_location: DW_OP_bregx 13 0x100
+#   int fixed_value;        DW_AT_location: DW_OP_addr fixed_value
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o register_relative_test.elf register_relative_test.s
+
+	.data
+	.p2align 2
+	.globl fixed_value
+	.type fixed_value, @object
+	.size fixed_value, 4
+fixed_value:
+	.long 9
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# array type with children: type
+	.uleb128 3
+	.uleb128 0x01
+	.byte 1
+	.uleb128 0x49
+	.uleb128 0x13
+	.byte 0, 0
+	# subrange: upper_bound
+	.uleb128 4
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x2f
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 5
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "register_relative_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lint_array:
+	.uleb128 3
+	.long .Lint - .Lcu_begin
+	.uleb128 4
+	.byte 3
+	.byte 0
+	# overlay_table: DW_OP_bregx 13 0x100
+	.uleb128 5
+	.asciz "overlay_table"
+	.long .Lint_array - .Lcu_begin
+	.uleb128 4
+	.byte 0x92
+	.uleb128 13
+	.sleb128 0x100
+	# fixed_value: DW_OP_addr
+	.uleb128 5
+	.asciz "fixed_value"
+	.long .Lint - .Lcu_begin
+	.uleb128 9
+	.byte 0x03
+	.quad fixed_value
+	.byte 0
+.Lcu_end:
@@ -0,0 +1,25 @@
+// Rust enums with data, which are described by DW_TAG_variant_part in the debug info
+// compile: rustc -g -C opt-level=0 -C panic=abort -C relocation-model=static -C link-arg=-nostdlib -C link-arg=-static -C link-arg=-Wl,--build-id=none -o rust_enum_test.elf rust_enum_test.rs
+#![no_std]
+#![no_main]
+
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(u8, u16),
+}
+
+#[unsafe(no_mangle)]
+pub static mut MESSAGE: Message = Message::Move { x: 3, y: 4 };
+
+// Option<&T> stores the discriminant in a niche: the None variant is the null pointer
+#[unsafe(no_mangle)]
+pub static mut OPTIONAL: Option<&u32> = None;
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn _start() {}
@@ -0,0 +1,8 @@
+// global variables in .data, .bss and .rodata
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o storage_test.elf storage_test.c
+
+int initialized_var = 42;
+int zero_init_var;
+const int read_only_var = 7;
+
+void _start(void) {}
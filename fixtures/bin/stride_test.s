@@ -0,0 +1,203 @@
+# global arrays with explicit strides, which C compilers don't generate
+# the Dwarf 4 debug info was written by hand; it describes these variables:
+#   int padded_ints[4]             DW_AT_byte_stride 8 on the array type
+#   int subrange_ints[3]           DW_AT_byte_stride 12 on the subrange
+#   unsigned char packed[8]        DW_AT_bit_stride 2 on the array type
+#   unsigned char packed_2d[2][3]  DW_AT_bit_stride 4 on the array type
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o stride_test.elf stride_test.s
+
+	.data
+	.p2align 3
+	.globl padded_ints
+padded_ints:
+	.zero 32
+	.globl subrange_ints
+subrange_ints:
+	.zero 36
+	.globl packed
+packed:
+	.zero 2
+	.globl packed_2d
+packed_2d:
+	.zero 3
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# array type: type, byte_stride
+	.uleb128 3
+	.uleb128 0x01
+	.byte 1
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x51
+	.uleb128 0x0b
+	.byte 0, 0
+	# subrange type: type, upper_bound
+	.uleb128 4
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2f
+	.uleb128 0x0b
+	.byte 0, 0
+	# subrange type: type, upper_bound, byte_stride
+	.uleb128 5
+	.uleb128 0x21
+	.byte 0
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2f
+	.uleb128 0x0b
+	.uleb128 0x51
+	.uleb128 0x0b
+	.byte 0, 0
+	# array type: type
+	.uleb128 6
+	.uleb128 0x01
+	.byte 1
+	.uleb128 0x49
+	.uleb128 0x13
+	.byte 0, 0
+	# array type: type, bit_stride, byte_size
+	.uleb128 7
+	.uleb128 0x01
+	.byte 1
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x2e
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 8
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "stride_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Luchar:
+	.uleb128 2
+	.asciz "unsigned char"
+	.byte 1
+	.byte 0x08
+.Lsizetype:
+	.uleb128 2
+	.asciz "long unsigned int"
+	.byte 8
+	.byte 0x07
+.Lpadded_ints_type:
+	.uleb128 3
+	.long .Lint - .Lcu_begin
+	.byte 8
+	.uleb128 4
+	.long .Lsizetype - .Lcu_begin
+	.byte 3
+	.byte 0
+.Lsubrange_ints_type:
+	.uleb128 6
+	.long .Lint - .Lcu_begin
+	.uleb128 5
+	.long .Lsizetype - .Lcu_begin
+	.byte 2
+	.byte 12
+	.byte 0
+.Lpacked_type:
+	.uleb128 7
+	.long .Luchar - .Lcu_begin
+	.byte 2
+	.byte 2
+	.uleb128 4
+	.long .Lsizetype - .Lcu_begin
+	.byte 7
+	.byte 0
+.Lpacked_2d_type:
+	.uleb128 7
+	.long .Luchar - .Lcu_begin
+	.byte 4
+	.byte 3
+	.uleb128 4
+	.long .Lsizetype - .Lcu_begin
+	.byte 1
+	.uleb128 4
+	.long .Lsizetype - .Lcu_begin
+	.byte 2
+	.byte 0
+	.uleb128 8
+	.asciz "padded_ints"
+	.long .Lpadded_ints_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad padded_ints
+	.uleb128 8
+	.asciz "subrange_ints"
+	.long .Lsubrange_ints_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad subrange_ints
+	.uleb128 8
+	.asciz "packed"
+	.long .Lpacked_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad packed
+	.uleb128 8
+	.asciz "packed_2d"
+	.long .Lpacked_2d_type - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad packed_2d
+	.byte 0
+.Lcu_end:
@@ -0,0 +1,17 @@
+// char arrays that can be measured as strings, in a global variable and as struct members
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o string_test.elf string_test.c
+#include <stdint.h>
+
+char name[16] = "engine";
+
+struct Device {
+    unsigned int id;
+    char label[8];
+    unsigned char serial[4];
+    uint8_t raw[4];
+    char matrix[2][3];
+};
+
+struct Device device = {1, "pump", {1, 2, 3, 4}, {5, 6, 7, 8}, {"ab", "cd"}};
+
+void _start(void) {}
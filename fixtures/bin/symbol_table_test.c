@@ -0,0 +1,9 @@
+// weak and common data symbols
+// compile: gcc -g -O0 -fcommon -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o symbol_table_test.elf symbol_table_test.c
+
+__attribute__((weak)) int weak_var = 3;
+__attribute__((weak)) unsigned short weak_array[4];
+int common_var;
+int strong_var = 1;
+
+void _start(void) {}
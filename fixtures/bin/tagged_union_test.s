@@ -0,0 +1,216 @@
+# a C union with a tag, described by a DW_TAG_variant_part
+# the Dwarf 4 debug info was written by hand; it describes this variable:
+#   struct KeyEvent { int type; int code; };
+#   struct MouseEvent { int type; short x; short y; };
+#   union Event {
+#       int type;                  the discriminant, DW_AT_discr of the variant part
+#       struct KeyEvent key;       active if type == 1
+#       struct MouseEvent mouse;   active if type == 2 or type == 3
+#       long raw;                  the default variant, active for all other values
+#   } event;
+# compile: gcc -nostdlib -static -no-pie -Wl,--build-id=none -o tagged_union_test.elf tagged_union_test.s
+
+	.data
+	.p2align 3
+	.globl event
+	.type event, @object
+	.size event, 8
+event:
+	.zero 8
+
+	.text
+	.globl _start
+_start:
+	ret
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	# compile unit: producer, language, name
+	.uleb128 1
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25
+	.uleb128 0x08
+	.uleb128 0x13
+	.uleb128 0x0b
+	.uleb128 0x03
+	.uleb128 0x08
+	.byte 0, 0
+	# base type: name, byte_size, encoding
+	.uleb128 2
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.uleb128 0x3e
+	.uleb128 0x0b
+	.byte 0, 0
+	# struct type: name, byte_size
+	.uleb128 3
+	.uleb128 0x13
+	.byte 1
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.byte 0, 0
+	# member: name, type, data_member_location
+	.uleb128 4
+	.uleb128 0x0d
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x38
+	.uleb128 0x0b
+	.byte 0, 0
+	# union type: name, byte_size
+	.uleb128 5
+	.uleb128 0x17
+	.byte 1
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x0b
+	.uleb128 0x0b
+	.byte 0, 0
+	# variant part: discr
+	.uleb128 6
+	.uleb128 0x33
+	.byte 1
+	.uleb128 0x15
+	.uleb128 0x13
+	.byte 0, 0
+	# variant: discr_value
+	.uleb128 7
+	.uleb128 0x19
+	.byte 1
+	.uleb128 0x16
+	.uleb128 0x0b
+	.byte 0, 0
+	# variant without discr_value
+	.uleb128 8
+	.uleb128 0x19
+	.byte 1
+	.byte 0, 0
+	# variable: name, type, external, location
+	.uleb128 9
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03
+	.uleb128 0x08
+	.uleb128 0x49
+	.uleb128 0x13
+	.uleb128 0x3f
+	.uleb128 0x19
+	.uleb128 0x02
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+.Lcu_begin:
+	.long .Lcu_end - .Lcu_version
+.Lcu_version:
+	.short 4
+	.long .Labbrev
+	.byte 8
+	.uleb128 1
+	.asciz "handwritten"
+	.byte 0x0c
+	.asciz "tagged_union_test.c"
+.Lint:
+	.uleb128 2
+	.asciz "int"
+	.byte 4
+	.byte 0x05
+.Lshort:
+	.uleb128 2
+	.asciz "short"
+	.byte 2
+	.byte 0x05
+.Llong:
+	.uleb128 2
+	.asciz "long"
+	.byte 8
+	.byte 0x05
+.Lkey_event:
+	.uleb128 3
+	.asciz "KeyEvent"
+	.byte 8
+	.uleb128 4
+	.asciz "type"
+	.long .Lint - .Lcu_begin
+	.byte 0
+	.uleb128 4
+	.asciz "code"
+	.long .Lint - .Lcu_begin
+	.byte 4
+	.byte 0
+.Lmouse_event:
+	.uleb128 3
+	.asciz "MouseEvent"
+	.byte 8
+	.uleb128 4
+	.asciz "type"
+	.long .Lint - .Lcu_begin
+	.byte 0
+	.uleb128 4
+	.asciz "x"
+	.long .Lshort - .Lcu_begin
+	.byte 4
+	.uleb128 4
+	.asciz "y"
+	.long .Lshort - .Lcu_begin
+	.byte 6
+	.byte 0
+.Levent:
+	.uleb128 5
+	.asciz "Event"
+	.byte 8
+	.uleb128 6
+	.long .Ltype_member - .Lcu_begin
+.Ltype_member:
+	.uleb128 4
+	.asciz "type"
+	.long .Lint - .Lcu_begin
+	.byte 0
+	.uleb128 7
+	.byte 1
+	.uleb128 4
+	.asciz "key"
+	.long .Lkey_event - .Lcu_begin
+	.byte 0
+	.byte 0
+	.uleb128 7
+	.byte 2
+	.uleb128 4
+	.asciz "mouse"
+	.long .Lmouse_event - .Lcu_begin
+	.byte 0
+	.byte 0
+	.uleb128 7
+	.byte 3
+	.uleb128 4
+	.asciz "mouse"
+	.long .Lmouse_event - .Lcu_begin
+	.byte 0
+	.byte 0
+	.uleb128 8
+	.uleb128 4
+	.asciz "raw"
+	.long .Llong - .Lcu_begin
+	.byte 0
+	.byte 0
+	.byte 0
+	.byte 0
+	.uleb128 9
+	.asciz "event"
+	.long .Levent - .Lcu_begin
+	.byte 9
+	.byte 0x03
+	.quad event
+	.byte 0
+.Lcu_end:
@@ -0,0 +1,8 @@
+// thread-local variables
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o tls_test.elf tls_test.c
+
+__thread int tls_counter = 5;
+_Thread_local unsigned short tls_flags;
+int plain_var = 1;
+
+void _start(void) {}
@@ -0,0 +1,19 @@
+// const qualifiers combined with typedefs and pointers
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o typedef_pointer_test.elf typedef_pointer_test.c
+
+struct S {
+    int x;
+};
+typedef struct S *SHandle;
+typedef const int CInt;
+
+struct S s_instance;
+// the pointer itself is const
+const SHandle handle = &s_instance;
+SHandle plain_handle;
+// the target of the pointer is const, but not the pointer
+const struct S *ptr_to_const;
+// the const qualifier is part of the typedef
+CInt limit = 5;
+
+void _start(void) {}
@@ -0,0 +1,19 @@
+// a struct with C++ reference members, which are stored like pointers to the objects they refer to
+// compile: g++ -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o typedef_reference_test.elf typedef_reference_test.cpp
+
+struct Limits {
+    int lower;
+    int upper;
+};
+
+struct Settings {
+    int &gain;
+    Limits &limits;
+    short scale;
+};
+
+int gain = 3;
+Limits limits = {-10, 10};
+Settings settings = {gain, limits, 2};
+
+extern "C" void _start() {}
@@ -0,0 +1,32 @@
+// classes with virtual and non-virtual base classes
+// compile: g++ -g -O0 -fno-rtti -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o virtual_base_test.elf virtual_base_test.cpp
+
+class Base {
+  public:
+    int base_value;
+};
+
+class Plain {
+  public:
+    int plain_value;
+};
+
+class Left : public virtual Base {
+  public:
+    int left_value;
+};
+
+class Right : public virtual Base {
+  public:
+    int right_value;
+};
+
+class Diamond : public Left, public Right, public Plain {
+  public:
+    int diamond_value;
+};
+
+Diamond diamond;
+Left left;
+
+extern "C" void _start(void) {}
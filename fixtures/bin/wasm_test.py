@@ -0,0 +1,141 @@
+#!/usr/bin/env python3
+# Write a WebAssembly module with Dwarf 4 debug info in its custom sections, as it is created by a
+# wasm32 build of this C code:
+#   struct Model { int gain; float offset; };
+#   struct Model model = { 7, 0.5f };     at address 1024 of the linear memory
+#   unsigned short step_count = 3;        at address 1032
+# The debug info was written by hand. The addresses of the variables are offsets into the linear memory.
+# usage: wasm_test.py <wasm file>
+import struct
+import sys
+
+MODEL_ADDRESS = 1024
+STEP_COUNT_ADDRESS = 1032
+
+
+def uleb128(value):
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def sleb128(value):
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if (value == 0 and not byte & 0x40) or (value == -1 and byte & 0x40):
+            out.append(byte)
+            return bytes(out)
+        out.append(byte | 0x80)
+
+
+def cstr(text):
+    return text.encode() + b"\0"
+
+
+def debug_abbrev():
+    def abbrev(code, tag, children, attributes):
+        out = uleb128(code) + uleb128(tag) + bytes([children])
+        for attr, form in attributes:
+            out += uleb128(attr) + uleb128(form)
+        return out + b"\0\0"
+
+    DW_FORM_string, DW_FORM_data1, DW_FORM_ref4, DW_FORM_exprloc, DW_FORM_flag_present = (
+        0x08,
+        0x0B,
+        0x13,
+        0x18,
+        0x19,
+    )
+    return (
+        # compile unit: producer, language, name
+        abbrev(1, 0x11, 1, [(0x25, DW_FORM_string), (0x13, DW_FORM_data1), (0x03, DW_FORM_string)])
+        # base type: name, byte_size, encoding
+        + abbrev(2, 0x24, 0, [(0x03, DW_FORM_string), (0x0B, DW_FORM_data1), (0x3E, DW_FORM_data1)])
+        # structure type: name, byte_size
+        + abbrev(3, 0x13, 1, [(0x03, DW_FORM_string), (0x0B, DW_FORM_data1)])
+        # member: name, type, data_member_location
+        + abbrev(4, 0x0D, 0, [(0x03, DW_FORM_string), (0x49, DW_FORM_ref4), (0x38, DW_FORM_data1)])
+        # variable: name, type, external, location
+        + abbrev(
+            5,
+            0x34,
+            0,
+            [(0x03, DW_FORM_string), (0x49, DW_FORM_ref4), (0x3F, DW_FORM_flag_present), (0x02, DW_FORM_exprloc)],
+        )
+        + b"\0"
+    )
+
+
+def debug_info():
+    # the offsets of the DIEs are relative to the start of the unit; the unit header is 11 bytes long
+    dies = bytearray()
+    offsets = {}
+
+    def die(label, data):
+        offsets[label] = 11 + len(dies)
+        dies.extend(data)
+
+    def ref(label):
+        return struct.pack("<I", offsets[label])
+
+    def addr_location(address):
+        # DW_OP_addr with the 4 byte addresses of wasm32
+        expr = b"\x03" + struct.pack("<I", address)
+        return uleb128(len(expr)) + expr
+
+    die("cu", uleb128(1) + cstr("handwritten") + bytes([0x0C]) + cstr("wasm_test.c"))
+    die("int", uleb128(2) + cstr("int") + bytes([4, 0x05]))
+    die("float", uleb128(2) + cstr("float") + bytes([4, 0x04]))
+    die("ushort", uleb128(2) + cstr("unsigned short") + bytes([2, 0x07]))
+    die("model_type", uleb128(3) + cstr("Model") + bytes([8]))
+    die("gain", uleb128(4) + cstr("gain") + ref("int") + bytes([0]))
+    die("offset", uleb128(4) + cstr("offset") + ref("float") + bytes([4]))
+    die("model_end", b"\0")
+    die("model", uleb128(5) + cstr("model") + ref("model_type") + addr_location(MODEL_ADDRESS))
+    die("step_count", uleb128(5) + cstr("step_count") + ref("ushort") + addr_location(STEP_COUNT_ADDRESS))
+    die("cu_end", b"\0")
+
+    # unit header: version 4, abbrev offset 0, address size 4
+    header = struct.pack("<HIB", 4, 0, 4)
+    return struct.pack("<I", len(header) + len(dies)) + header + bytes(dies)
+
+
+def section(section_id, content):
+    return bytes([section_id]) + uleb128(len(content)) + content
+
+
+def custom_section(name, content):
+    encoded_name = name.encode()
+    return section(0, uleb128(len(encoded_name)) + encoded_name + content)
+
+
+def main():
+    wasm_name = sys.argv[1]
+    # one memory of one page without maximum
+    memory = section(5, uleb128(1) + b"\x00" + uleb128(1))
+    # an active data segment of memory 0, which starts at the address of model
+    init = struct.pack("<if", 7, 0.5) + struct.pack("<H", 3)
+    offset_expr = b"\x41" + sleb128(MODEL_ADDRESS) + b"\x0b"
+    data = section(11, uleb128(1) + b"\x00" + offset_expr + uleb128(len(init)) + init)
+    module = (
+        b"\0asm"
+        + struct.pack("<I", 1)
+        + memory
+        + data
+        + custom_section(".debug_abbrev", debug_abbrev())
+        + custom_section(".debug_info", debug_info())
+    )
+    with open(wasm_name, "wb") as wasm_file:
+        wasm_file.write(module)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,17 @@
+// 128 bit integers and extended precision floating point types
+// compile: gcc -g -O0 -fno-pie -no-pie -nostdlib -static -Wl,--build-id=none -o wide_types_test.elf wide_types_test.c
+__int128 sint128_var;
+unsigned __int128 uint128_var;
+long double long_double_var;
+_Float128 float128_var;
+long long sint64_var;
+unsigned long long uint64_var;
+
+struct Wide {
+    char tag;
+    unsigned __int128 value;
+    long double scale;
+};
+struct Wide wide_var;
+
+void _start(void) {}
@@ -6,18 +6,18 @@ use a2lfile::DataType;
 // all other types cannot be sensibly measured / calibrated anyway
 pub(crate) fn get_a2l_datatype(typeinfo: &TypeInfo) -> DataType {
     match &typeinfo.datatype {
-        DbgDataType::Uint8 => DataType::Ubyte,
+        DbgDataType::Bool | DbgDataType::UChar | DbgDataType::Uint8 => DataType::Ubyte,
         DbgDataType::Uint16 => DataType::Uword,
         DbgDataType::Uint32 => DataType::Ulong,
         DbgDataType::Uint64 => DataType::AUint64,
-        DbgDataType::Sint8 => DataType::Sbyte,
+        DbgDataType::SChar | DbgDataType::Sint8 => DataType::Sbyte,
         DbgDataType::Sint16 => DataType::Sword,
         DbgDataType::Sint32 => DataType::Slong,
         DbgDataType::Sint64 => DataType::AInt64,
         DbgDataType::Float => DataType::Float32Ieee,
         DbgDataType::Double => DataType::Float64Ieee,
         DbgDataType::Bitfield { basetype, .. } => get_a2l_datatype(basetype),
-        DbgDataType::Pointer(size, _) => {
+        DbgDataType::Pointer(size, _) | DbgDataType::Reference { size, .. } => {
             if *size == 8 {
                 DataType::AUint64
             } else {
@@ -66,7 +66,8 @@ pub(crate) fn get_type_limits(
         } => {
             let raw_range: u64 = 1 << bit_size;
             match &basetype.datatype {
-                DbgDataType::Sint8
+                DbgDataType::SChar
+                | DbgDataType::Sint8
                 | DbgDataType::Sint16
                 | DbgDataType::Sint32
                 | DbgDataType::Sint64 => {
@@ -79,11 +80,12 @@ pub(crate) fn get_type_limits(
         }
         DbgDataType::Double => (f64::MIN, f64::MAX),
         DbgDataType::Float => (f64::from(f32::MIN), f64::from(f32::MAX)),
-        DbgDataType::Uint8 => (f64::from(u8::MIN), f64::from(u8::MAX)),
+        DbgDataType::Bool => (0f64, 1f64),
+        DbgDataType::UChar | DbgDataType::Uint8 => (f64::from(u8::MIN), f64::from(u8::MAX)),
         DbgDataType::Uint16 => (f64::from(u16::MIN), f64::from(u16::MAX)),
         DbgDataType::Uint32 => (f64::from(u32::MIN), f64::from(u32::MAX)),
         DbgDataType::Uint64 => (u64::MIN as f64, u64::MAX as f64),
-        DbgDataType::Sint8 => (f64::from(i8::MIN), f64::from(i8::MAX)),
+        DbgDataType::SChar | DbgDataType::Sint8 => (f64::from(i8::MIN), f64::from(i8::MAX)),
         DbgDataType::Sint16 => (f64::from(i16::MIN), f64::from(i16::MAX)),
         DbgDataType::Sint32 => (f64::from(i32::MIN), f64::from(i32::MAX)),
         DbgDataType::Sint64 => (i64::MIN as f64, i64::MAX as f64),
@@ -1,7 +1,9 @@
 use crate::symbol;
 
 use super::{DebugDataReader, UnitList};
-use gimli::{DebugAddrBase, DebuggingInformationEntry, EndianSlice, RunTimeEndian, UnitHeader};
+use crate::debuginfo::ConstValue;
+use gimli::{DebugAddrBase, DebuggingInformationEntry, EndianSlice, RunTimeEndian};
+use std::collections::HashMap;
 
 type SliceType<'a> = EndianSlice<'a, RunTimeEndian>;
 type OptionalAttribute<'data> = Option<gimli::AttributeValue<SliceType<'data>>>;
@@ -22,41 +24,67 @@ pub(crate) fn get_name_attribute(
 ) -> Result<String, String> {
     let name_attr = get_attr_value(entry, gimli::constants::DW_AT_name)
         .ok_or_else(|| "failed to get name attribute".to_string())?;
-    match name_attr {
-        gimli::AttributeValue::String(slice) => {
-            if let Ok(utf8string) = slice.to_string() {
-                return Ok(utf8string.to_owned());
-            }
-            Err(format!("could not decode {slice:#?} as a utf-8 string"))
-        }
-        gimli::AttributeValue::DebugStrRef(str_offset) => {
-            match dwarf.debug_str.get_str(str_offset) {
-                Ok(slice) => {
-                    if let Ok(utf8string) = slice.to_string() {
-                        return Ok(utf8string.to_owned());
-                    }
-                    Err(format!("could not decode {slice:#?} as a utf-8 string"))
-                }
-                Err(err) => Err(err.to_string()),
-            }
-        }
-        gimli::AttributeValue::DebugStrOffsetsIndex(index) => {
-            let unit = dwarf.unit(*unit_header).unwrap();
-            let offset = dwarf
-                .debug_str_offsets
-                .get_str_offset(unit.encoding().format, unit.str_offsets_base, index)
-                .unwrap();
-            match dwarf.debug_str.get_str(offset) {
-                Ok(slice) => {
-                    if let Ok(utf8string) = slice.to_string() {
-                        return Ok(utf8string.to_owned());
-                    }
-                    Err(format!("could not decode {slice:#?} as a utf-8 string"))
-                }
-                Err(err) => Err(err.to_string()),
-            }
+    get_string_value(name_attr, dwarf, unit_header)
+}
+
+// get the DW_AT_producer attribute of a compile unit, which names the compiler that created it
+pub(crate) fn get_producer_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    dwarf: &gimli::Dwarf<EndianSlice<RunTimeEndian>>,
+    unit_header: &gimli::UnitHeader<EndianSlice<RunTimeEndian>>,
+) -> Option<String> {
+    let producer_attr = get_attr_value(entry, gimli::constants::DW_AT_producer)?;
+    get_string_value(producer_attr, dwarf, unit_header).ok()
+}
+
+// convert the value of a string attribute to a String.
+// Dwarf 5 strings may be stored in .debug_line_str (DW_FORM_line_strp), or they may be referenced
+// through an index into .debug_str_offsets (DW_FORM_strx*). Only the index requires the str_offsets_base
+// of the unit, so the comparatively expensive parsing of the unit is skipped for all other forms
+fn get_string_value(
+    value: gimli::AttributeValue<SliceType>,
+    dwarf: &gimli::Dwarf<EndianSlice<RunTimeEndian>>,
+    unit_header: &gimli::UnitHeader<EndianSlice<RunTimeEndian>>,
+) -> Result<String, String> {
+    let slice = match value {
+        gimli::AttributeValue::String(slice) => slice,
+        gimli::AttributeValue::DebugStrRef(str_offset) => dwarf
+            .debug_str
+            .get_str(str_offset)
+            .map_err(|err| err.to_string())?,
+        gimli::AttributeValue::DebugLineStrRef(str_offset) => dwarf
+            .debug_line_str
+            .get_str(str_offset)
+            .map_err(|err| err.to_string())?,
+        gimli::AttributeValue::DebugStrRefSup(str_offset) => dwarf
+            .sup()
+            .ok_or_else(|| "no supplementary object file is loaded".to_string())?
+            .debug_str
+            .get_str(str_offset)
+            .map_err(|err| err.to_string())?,
+        gimli::AttributeValue::DebugStrOffsetsIndex(_) => {
+            let unit = dwarf.unit(*unit_header).map_err(|err| err.to_string())?;
+            dwarf
+                .attr_string(&unit, value)
+                .map_err(|err| err.to_string())?
         }
-        _ => Err(format!("invalid name attribute type {name_attr:#?}")),
+        _ => return Err(format!("invalid string attribute type {value:#?}")),
+    };
+    slice
+        .to_string()
+        .map(str::to_owned)
+        .map_err(|_| format!("could not decode {slice:#?} as a utf-8 string"))
+}
+
+// get the DW_AT_language attribute of a compile unit
+pub(crate) fn get_language_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<gimli::DwLang> {
+    let language_attr = get_attr_value(entry, gimli::constants::DW_AT_language)?;
+    if let gimli::AttributeValue::Language(language) = language_attr {
+        Some(language)
+    } else {
+        None
     }
 }
 
@@ -68,66 +96,28 @@ pub(crate) fn get_linkage_name_attribute(
 ) -> Result<String, String> {
     let name_attr = get_attr_value(entry, gimli::constants::DW_AT_linkage_name)
         .ok_or_else(|| "failed to get name attribute".to_string())?;
-    match name_attr {
-        gimli::AttributeValue::String(slice) => {
-            if let Ok(utf8string) = slice.to_string() {
-                // could not demangle, but successfully converted the slice to utf8
-                return Ok(utf8string.to_owned());
-            }
-            Err(format!("could not decode {slice:#?} as a utf-8 string"))
-        }
-        gimli::AttributeValue::DebugStrRef(str_offset) => {
-            match dwarf.debug_str.get_str(str_offset) {
-                Ok(slice) => {
-                    if let Ok(utf8string) = slice.to_string() {
-                        // could not demangle, but successfully converted the slice to utf8
-                        return Ok(utf8string.to_owned());
-                    }
-                    Err(format!("could not decode {slice:#?} as a utf-8 string"))
-                }
-                Err(err) => Err(err.to_string()),
-            }
-        }
-        gimli::AttributeValue::DebugStrOffsetsIndex(index) => {
-            let unit = dwarf.unit(*unit_header).unwrap();
-            let offset = dwarf
-                .debug_str_offsets
-                .get_str_offset(unit.encoding().format, unit.str_offsets_base, index)
-                .unwrap();
-            match dwarf.debug_str.get_str(offset) {
-                Ok(slice) => {
-                    if let Ok(utf8string) = slice.to_string() {
-                        // could not demangle, but successfully converted the slice to utf8
-                        return Ok(utf8string.to_owned());
-                    }
-                    Err(format!("could not decode {slice:#?} as a utf-8 string"))
-                }
-                Err(err) => Err(err.to_string()),
-            }
-        }
-        _ => Err(format!("invalid name attribute type {name_attr:#?}")),
-    }
+    get_string_value(name_attr, dwarf, unit_header)
 }
 
 // get a type reference as an offset relative to the start of .debug_info from a DW_AT_type attribute
 // it the type reference is a UnitRef (relative to the unit header) it will be converted first
 pub(crate) fn get_typeref_attribute(
     entry: &DebuggingInformationEntry<SliceType, usize>,
-    unit: &UnitHeader<SliceType>,
+    unit_list: &UnitList<'_>,
+    current_unit: usize,
 ) -> Result<usize, String> {
     let type_attr = get_attr_value(entry, gimli::constants::DW_AT_type)
         .ok_or_else(|| "failed to get type reference attribute".to_string())?;
     match type_attr {
         gimli::AttributeValue::UnitRef(unitoffset) => {
-            Ok(unitoffset.to_debug_info_offset(unit).unwrap().0)
+            Ok(unit_list.get_offset(current_unit, unitoffset))
         }
         gimli::AttributeValue::DebugInfoRef(infooffset) => Ok(infooffset.0),
-        gimli::AttributeValue::DebugTypesRef(_typesig) => {
-            // .debug_types was added in DWARF v4 and removed again in v5.
-            // silently ignore references to the .debug_types section
-            // this is unlikely to matter as few compilers ever bothered with .debug_types
-            // (for example gcc supports this, but support is only enabled if the user requests this explicitly)
-            Err("unsupported reference to a .debug_types entry (Dwarf 4)".to_string())
+        gimli::AttributeValue::DebugTypesRef(typesig) => {
+            // a reference to a type unit by its signature. This is used by gcc with -fdebug-types-section
+            unit_list
+                .get_signature_offset(typesig)
+                .ok_or_else(|| format!("no type unit with the signature {:#x}", typesig.0))
         }
         _ => Err(format!("unsupported type reference: {type_attr:#?}")),
     }
@@ -136,57 +126,104 @@ pub(crate) fn get_typeref_attribute(
 // get the address of a variable from a DW_AT_location attribute
 // The DW_AT_location contains an Exprloc expression that allows the address to be calculated
 // in complex ways, so the expression must be evaluated in order to get the address
+// The returned flag is true if the address is an offset into the thread-local storage block
 pub(crate) fn get_location_attribute(
     debug_data_reader: &DebugDataReader,
     entry: &DebuggingInformationEntry<SliceType, usize>,
     encoding: gimli::Encoding,
     current_unit: usize,
-    symbols: &Vec<(String, u64)>,
-) -> Option<u64> {
-    let loc_attr = get_attr_value(entry, gimli::constants::DW_AT_location)?;
+    symbols: &HashMap<String, u64>,
+) -> Option<(u64, bool)> {
+    let Some(loc_attr) = get_attr_value(entry, gimli::constants::DW_AT_location) else {
+        // the compiler may omit the location, e.g. of a C++ static member, even though the variable exists
+        return get_linkage_name_address(debug_data_reader, entry, current_unit, symbols)
+            .map(|address| (address, false));
+    };
     if let gimli::AttributeValue::Exprloc(expression) = loc_attr {
-        evaluate_exprloc(debug_data_reader, expression, encoding, current_unit)
+        evaluate_exprloc(debug_data_reader, expression, encoding, current_unit).or_else(|| {
+            get_linkage_name_address(debug_data_reader, entry, current_unit, symbols)
+                .map(|address| (address, false))
+        })
     } else {
-        // Try to find the variable's address in the symbol table by its name
-        // Try to resolve by DW_AT_name or DW_AT_linkage_name
-        for get_name in [
-            |e, d, u| get_name_attribute(e, d, u),
-            |e, d, u| get_linkage_name_attribute(e, d, u),
-        ] {
-            if let Ok(name) = get_name(
-                entry,
-                &debug_data_reader.dwarf,
-                &debug_data_reader.units[current_unit].0,
-            ) {
-                if let Some((_, sym_addr)) = symbols.iter().find(|(sym_name, _)| sym_name == &name)
-                {
-                    return Some(*sym_addr);
-                }
-            }
+        get_symbol_table_address(
+            entry,
+            &debug_data_reader.dwarf,
+            &debug_data_reader.units[current_unit].0,
+            symbols,
+        )
+        .map(|address| (address, false))
+    }
+}
+
+// Try to find the variable's address in the symbol table by its name
+// Try to resolve by DW_AT_name or DW_AT_linkage_name
+pub(crate) fn get_symbol_table_address(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    dwarf: &gimli::Dwarf<EndianSlice<RunTimeEndian>>,
+    unit_header: &gimli::UnitHeader<EndianSlice<RunTimeEndian>>,
+    symbols: &HashMap<String, u64>,
+) -> Option<u64> {
+    for get_name in [
+        |e, d, u| get_name_attribute(e, d, u),
+        |e, d, u| get_linkage_name_attribute(e, d, u),
+    ] {
+        if let Ok(name) = get_name(entry, dwarf, unit_header)
+            && let Some(sym_addr) = symbols.get(&name)
+        {
+            return Some(*sym_addr);
         }
-        None
     }
+    None
+}
+
+// Try to find the address of a variable without a usable location in the symbol table by its DW_AT_linkage_name.
+// The plain name is not used here, because a local variable of the same name as a global would get the global's
+// address. The linkage name of a definition with a DW_AT_specification is given by the declaration
+fn get_linkage_name_address(
+    debug_data_reader: &DebugDataReader,
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    current_unit: usize,
+    symbols: &HashMap<String, u64>,
+) -> Option<u64> {
+    // a declaration refers to a variable that is defined elsewhere
+    if get_declaration_attribute(entry).unwrap_or(false) {
+        return None;
+    }
+    let units = &debug_data_reader.units;
+    let dwarf = &debug_data_reader.dwarf;
+    let linkage_name = get_linkage_name_attribute(entry, dwarf, &units[current_unit].0)
+        .ok()
+        .or_else(|| {
+            let (spec_unit_idx, specification_entry) =
+                get_specification_attribute(entry, units, current_unit)?;
+            get_linkage_name_attribute(&specification_entry, dwarf, &units[spec_unit_idx].0).ok()
+        })?;
+    symbols.get(&linkage_name).copied()
 }
 
 // get the address offset of a struct member from a DW_AT_data_member_location attribute
+// The result is Some(None) if the attribute is present, but its location expression can't be evaluated
+// statically, e.g. "DW_OP_push_object_address; DW_OP_deref", which reads the offset from the object at runtime
 pub(crate) fn get_data_member_location_attribute(
     debug_data_reader: &DebugDataReader,
     entry: &DebuggingInformationEntry<SliceType, usize>,
     encoding: gimli::Encoding,
     current_unit: usize,
-) -> Option<u64> {
+) -> Option<Option<u64>> {
     let loc_attr = get_attr_value(entry, gimli::constants::DW_AT_data_member_location)?;
     match loc_attr {
-        gimli::AttributeValue::Exprloc(expression) => {
+        gimli::AttributeValue::Exprloc(expression) => Some(
             evaluate_exprloc(debug_data_reader, expression, encoding, current_unit)
-        }
-        gimli::AttributeValue::Udata(val) => Some(val),
-        gimli::AttributeValue::Data1(val) => Some(u64::from(val)),
-        gimli::AttributeValue::Data2(val) => Some(u64::from(val)),
-        gimli::AttributeValue::Data4(val) => Some(u64::from(val)),
-        gimli::AttributeValue::Data8(val) => Some(val),
+                .map(|(offset, _)| offset),
+        ),
+        gimli::AttributeValue::Udata(val) => Some(Some(val)),
+        gimli::AttributeValue::Sdata(val) if val >= 0 => Some(Some(val as u64)),
+        gimli::AttributeValue::Data1(val) => Some(Some(u64::from(val))),
+        gimli::AttributeValue::Data2(val) => Some(Some(u64::from(val))),
+        gimli::AttributeValue::Data4(val) => Some(Some(u64::from(val))),
+        gimli::AttributeValue::Data8(val) => Some(Some(val)),
         other => {
-            println!("unexpected data_member_location attribute: {other:?}");
+            log::warn!("unexpected data_member_location attribute: {other:?}");
             None
         }
     }
@@ -268,6 +305,26 @@ pub(crate) fn get_count_attribute(
     }
 }
 
+// check if the number of elements of an array dimension is only known at runtime. This is the case if
+// DW_AT_count or DW_AT_upper_bound refer to another DIE, e.g. the variable that holds the length, or are an expression
+pub(crate) fn has_dynamic_bound(entry: &DebuggingInformationEntry<SliceType, usize>) -> bool {
+    [
+        gimli::constants::DW_AT_count,
+        gimli::constants::DW_AT_upper_bound,
+    ]
+    .into_iter()
+    .any(|attrtype| {
+        matches!(
+            get_attr_value(entry, attrtype),
+            Some(
+                gimli::AttributeValue::UnitRef(_)
+                    | gimli::AttributeValue::DebugInfoRef(_)
+                    | gimli::AttributeValue::Exprloc(_)
+            )
+        )
+    })
+}
+
 // get the byte stride of an array from the DW_AT_upper_bound attribute
 // this attribute is only present if the stride is different from the element size
 pub(crate) fn get_byte_stride_attribute(
@@ -285,6 +342,14 @@ pub(crate) fn get_byte_stride_attribute(
     }
 }
 
+// get the bit stride of an array from the DW_AT_bit_stride attribute
+// this attribute is used instead of DW_AT_byte_stride if the elements of the array are bit-packed
+pub(crate) fn get_bit_stride_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<u64> {
+    get_attr_value(entry, gimli::constants::DW_AT_bit_stride)?.udata_value()
+}
+
 // get the const value of an enumerator from the DW_AT_const_value attribute
 pub(crate) fn get_const_value_attribute(
     entry: &DebuggingInformationEntry<SliceType, usize>,
@@ -301,6 +366,29 @@ pub(crate) fn get_const_value_attribute(
     }
 }
 
+// get the value of a constant variable from the DW_AT_const_value attribute.
+// The sign of values in DW_FORM_data<n> depends on the type of the variable, so they are returned as ConstValue::Uint
+pub(crate) fn get_variable_const_value_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    dwarf: &gimli::Dwarf<EndianSlice<RunTimeEndian>>,
+    unit_header: &gimli::UnitHeader<EndianSlice<RunTimeEndian>>,
+) -> Option<ConstValue> {
+    let constval_attr = get_attr_value(entry, gimli::constants::DW_AT_const_value)?;
+    match constval_attr {
+        gimli::AttributeValue::Sdata(value) => Some(ConstValue::Int(value)),
+        gimli::AttributeValue::Udata(value) => Some(ConstValue::Uint(value)),
+        gimli::AttributeValue::Data1(value) => Some(ConstValue::Uint(u64::from(value))),
+        gimli::AttributeValue::Data2(value) => Some(ConstValue::Uint(u64::from(value))),
+        gimli::AttributeValue::Data4(value) => Some(ConstValue::Uint(u64::from(value))),
+        gimli::AttributeValue::Data8(value) => Some(ConstValue::Uint(value)),
+        gimli::AttributeValue::Block(block) => Some(ConstValue::Bytes(block.to_vec())),
+        // C strings, e.g. of a const char array
+        other => get_string_value(other, dwarf, unit_header)
+            .ok()
+            .map(|string| ConstValue::Bytes(string.into_bytes())),
+    }
+}
+
 // get the bit size of a variable from the DW_AT_bit_size attribute
 // this attribute is only present if the variable is in a bitfield
 pub(crate) fn get_bit_size_attribute(
@@ -352,34 +440,82 @@ pub(crate) fn get_data_bit_offset_attribute(
     }
 }
 
-pub(crate) fn get_specification_attribute<'data, 'abbrev, 'unit>(
-    entry: &'data DebuggingInformationEntry<SliceType, usize>,
-    unit: &'unit UnitHeader<EndianSlice<'data, RunTimeEndian>>,
-    abbrev: &'abbrev gimli::Abbreviations,
-) -> Option<DebuggingInformationEntry<'abbrev, 'unit, EndianSlice<'data, RunTimeEndian>, usize>> {
+// get the DIE referenced by a DW_AT_specification attribute, together with the index of the unit that contains it
+pub(crate) fn get_specification_attribute<'units, 'data>(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    unit_list: &'units UnitList<'data>,
+    current_unit: usize,
+) -> Option<(
+    usize,
+    DebuggingInformationEntry<'units, 'units, SliceType<'data>, usize>,
+)> {
     let specification_attr = get_attr_value(entry, gimli::constants::DW_AT_specification)?;
-    match specification_attr {
-        gimli::AttributeValue::UnitRef(unitoffset) => unit.entry(abbrev, unitoffset).ok(),
-        gimli::AttributeValue::DebugInfoRef(_) => {
-            // presumably, a debugger could also generate a DebugInfo ref instead on a UnitRef
-            // parsing this would take info that we don't have here, e.g. the unit headers and abbreviations of all units
-            // fortunately I have not seen a compiler generate this variation yet
-            None
-        }
-        _ => None,
-    }
+    get_referenced_entry(specification_attr, unit_list, current_unit)
 }
 
-pub(crate) fn get_abstract_origin_attribute<'data, 'abbrev, 'unit>(
-    entry: &'data DebuggingInformationEntry<SliceType, usize>,
-    unit: &'unit UnitHeader<EndianSlice<'data, RunTimeEndian>>,
-    abbrev: &'abbrev gimli::Abbreviations,
-) -> Option<DebuggingInformationEntry<'abbrev, 'unit, EndianSlice<'data, RunTimeEndian>, usize>> {
+// get the DIE referenced by a DW_AT_abstract_origin attribute, together with the index of the unit that contains it
+pub(crate) fn get_abstract_origin_attribute<'units, 'data>(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    unit_list: &'units UnitList<'data>,
+    current_unit: usize,
+) -> Option<(
+    usize,
+    DebuggingInformationEntry<'units, 'units, SliceType<'data>, usize>,
+)> {
     let origin_attr = get_attr_value(entry, gimli::constants::DW_AT_abstract_origin)?;
-    match origin_attr {
-        gimli::AttributeValue::UnitRef(unitoffset) => unit.entry(abbrev, unitoffset).ok(),
-        _ => None,
+    get_referenced_entry(origin_attr, unit_list, current_unit)
+}
+
+// inlined or cloned entities can refer to an abstract origin which has an abstract origin of its own.
+// Follow the chain and return all origins, nearest first. The chain ends at a cycle or after MAX_ORIGIN_CHAIN steps
+pub(crate) fn get_abstract_origin_chain<'units, 'data>(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    unit_list: &'units UnitList<'data>,
+    current_unit: usize,
+) -> Vec<(
+    usize,
+    DebuggingInformationEntry<'units, 'units, SliceType<'data>, usize>,
+)> {
+    const MAX_ORIGIN_CHAIN: usize = 16;
+    let mut visited = vec![unit_list.get_offset(current_unit, entry.offset())];
+    let mut chain = Vec::new();
+    let mut next = get_abstract_origin_attribute(entry, unit_list, current_unit);
+    while let Some((origin_unit, origin_entry)) = next {
+        let offset = unit_list.get_offset(origin_unit, origin_entry.offset());
+        if visited.contains(&offset) || chain.len() >= MAX_ORIGIN_CHAIN {
+            break;
+        }
+        visited.push(offset);
+        next = get_abstract_origin_attribute(&origin_entry, unit_list, origin_unit);
+        chain.push((origin_unit, origin_entry));
     }
+    chain
+}
+
+// a reference is usually a UnitRef inside the current unit, but it can also be a DebugInfoRef (DW_FORM_ref_addr)
+// into any other unit. gcc uses these with -flto, where the final units refer back to the DIEs of the compile-time units
+fn get_referenced_entry<'units, 'data>(
+    attr: gimli::AttributeValue<SliceType>,
+    unit_list: &'units UnitList<'data>,
+    current_unit: usize,
+) -> Option<(
+    usize,
+    DebuggingInformationEntry<'units, 'units, SliceType<'data>, usize>,
+)> {
+    let (unit_idx, unit_offset) = match attr {
+        gimli::AttributeValue::UnitRef(unit_offset) => (current_unit, unit_offset),
+        gimli::AttributeValue::DebugInfoRef(dbginfo_offset) => {
+            let unit_idx = unit_list.get_unit(dbginfo_offset.0)?;
+            (
+                unit_idx,
+                unit_list.get_unit_offset(unit_idx, dbginfo_offset.0)?,
+            )
+        }
+        _ => return None,
+    };
+    let (unit, abbrev) = &unit_list[unit_idx];
+    let entry = unit.entry(abbrev, unit_offset).ok()?;
+    Some((unit_idx, entry))
 }
 
 pub(crate) fn get_addr_base_attribute(
@@ -392,24 +528,85 @@ pub(crate) fn get_addr_base_attribute(
     }
 }
 
+// get the register and the offset of a variable whose DW_AT_location is relative to a register, e.g.
+// "DW_OP_bregx 13 0x100". Some embedded linker overlay schemes place global data at such locations
+pub(crate) fn get_register_relative_location(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    encoding: gimli::Encoding,
+) -> Option<(u16, i64)> {
+    let gimli::AttributeValue::Exprloc(expression) =
+        get_attr_value(entry, gimli::constants::DW_AT_location)?
+    else {
+        return None;
+    };
+    evaluate_register_relative(expression, encoding)
+}
+
+// evaluate an expression that is based on the value of exactly one register
+pub(crate) fn evaluate_register_relative(
+    expression: gimli::Expression<EndianSlice<RunTimeEndian>>,
+    encoding: gimli::Encoding,
+) -> Option<(u16, i64)> {
+    let mut evaluation = expression.evaluation(encoding);
+    evaluation.set_max_iterations(100);
+    let mut register = None;
+    let mut eval_result = evaluation.evaluate().ok()?;
+    while eval_result != gimli::EvaluationResult::Complete {
+        match eval_result {
+            gimli::EvaluationResult::RequiresRegister { register: reg, .. }
+                if register.is_none() =>
+            {
+                // with a register value of 0 the expression evaluates to the offset from the register
+                register = Some(reg.0);
+                eval_result = evaluation
+                    .resume_with_register(gimli::Value::Generic(0))
+                    .ok()?;
+            }
+            _ => return None,
+        }
+    }
+    let register = register?;
+    if let Some(gimli::Piece {
+        location: gimli::Location::Address { address },
+        ..
+    }) = evaluation.result().first()
+    {
+        // the evaluation wraps at the address size, so the offset needs to be sign-extended
+        let shift = 64 - u32::from(encoding.address_size) * 8;
+        Some((register, ((*address << shift) as i64) >> shift))
+    } else {
+        None
+    }
+}
+
 // evaluate an exprloc expression to get a variable address or struct member offset
+// the returned flag is set if the expression produced an offset into the thread-local storage block
 fn evaluate_exprloc(
     debug_data_reader: &DebugDataReader,
     expression: gimli::Expression<EndianSlice<RunTimeEndian>>,
     encoding: gimli::Encoding,
     current_unit: usize,
-) -> Option<u64> {
+) -> Option<(u64, bool)> {
     let mut evaluation = expression.evaluation(encoding);
     evaluation.set_object_address(0);
+    // member locations like DW_OP_plus_uconst expect the address of the struct on the stack.
+    // Starting from 0 makes them evaluate to the offset of the member
     evaluation.set_initial_value(0);
     evaluation.set_max_iterations(100);
-    let mut eval_result = evaluation.evaluate().unwrap();
+    let mut is_tls = false;
+    let mut eval_result = evaluation.evaluate().ok()?;
     while eval_result != gimli::EvaluationResult::Complete {
         match eval_result {
             gimli::EvaluationResult::RequiresRelocatedAddress(address) => {
                 // assume that there is no relocation
                 // this would be a bad bet on PC, but on embedded controllers where A2l files are used this is the standard
-                eval_result = evaluation.resume_with_relocated_address(address).unwrap();
+                eval_result = evaluation.resume_with_relocated_address(address).ok()?;
+            }
+            gimli::EvaluationResult::RequiresTls(offset) => {
+                // DW_OP_form_tls_address / DW_OP_GNU_push_tls_address: the variable is thread-local
+                // Its address is only known at runtime, but the offset inside the TLS block is fixed
+                is_tls = true;
+                eval_result = evaluation.resume_with_tls(offset).ok()?;
             }
             gimli::EvaluationResult::RequiresFrameBase => {
                 // a variable in the stack frame of a function. Not useful in the conext of A2l files, where we only care about global values
@@ -431,7 +628,7 @@ fn evaluate_exprloc(
                     .debug_addr
                     .get_address(address_size, base, index)
                     .ok()?;
-                eval_result = evaluation.resume_with_indexed_address(addr).unwrap();
+                eval_result = evaluation.resume_with_indexed_address(addr).ok()?;
             }
             _other => {
                 // there are a lot of other types of address expressions that can only be evaluated by a debugger while a program is running
@@ -441,12 +638,12 @@ fn evaluate_exprloc(
         };
     }
     let result = evaluation.result();
-    if let gimli::Piece {
+    if let Some(gimli::Piece {
         location: gimli::Location::Address { address },
         ..
-    } = result[0]
+    }) = result.first()
     {
-        Some(address)
+        Some((*address, is_tls))
     } else {
         None
     }
@@ -468,14 +665,64 @@ pub(crate) fn get_type_attribute(
             }
         }
         Some(gimli::AttributeValue::UnitRef(unit_offset)) => {
-            let (unit, _) = &unit_list[current_unit];
-            let dbginfo_offset = unit_offset.to_debug_info_offset(unit).unwrap();
-            Ok((current_unit, dbginfo_offset))
+            let dbginfo_offset = unit_list.get_offset(current_unit, unit_offset);
+            Ok((current_unit, gimli::DebugInfoOffset(dbginfo_offset)))
+        }
+        Some(gimli::AttributeValue::DebugTypesRef(typesig)) => {
+            if let Some(dbginfo_offset) = unit_list.get_signature_offset(typesig)
+                && let Some(unit_idx) = unit_list.get_unit(dbginfo_offset)
+            {
+                Ok((unit_idx, gimli::DebugInfoOffset(dbginfo_offset)))
+            } else {
+                Err(format!("no type unit with the signature {:#x}", typesig.0))
+            }
         }
         _ => Err("failed to get DIE tree".to_string()),
     }
 }
 
+// get the DW_AT_signature attribute of a type DIE that only refers to the full type in a type unit
+// returns the offset of the referenced type
+pub(crate) fn get_signature_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    unit_list: &UnitList<'_>,
+) -> Option<usize> {
+    let signature_attr = get_attr_value(entry, gimli::constants::DW_AT_signature)?;
+    if let gimli::AttributeValue::DebugTypesRef(typesig) = signature_attr {
+        unit_list.get_signature_offset(typesig)
+    } else {
+        None
+    }
+}
+
+// get the value of the discriminant that selects a variant from the DW_AT_discr_value attribute
+pub(crate) fn get_discr_value_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<u64> {
+    let discr_value_attr = get_attr_value(entry, gimli::constants::DW_AT_discr_value)?;
+    match discr_value_attr {
+        gimli::AttributeValue::Sdata(discr_value) => Some(discr_value as u64),
+        gimli::AttributeValue::Udata(discr_value) => Some(discr_value),
+        gimli::AttributeValue::Data1(discr_value) => Some(u64::from(discr_value)),
+        gimli::AttributeValue::Data2(discr_value) => Some(u64::from(discr_value)),
+        gimli::AttributeValue::Data4(discr_value) => Some(u64::from(discr_value)),
+        gimli::AttributeValue::Data8(discr_value) => Some(discr_value),
+        _ => None,
+    }
+}
+
+// get the DW_AT_virtuality attribute
+pub(crate) fn get_virtuality_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<gimli::DwVirtuality> {
+    let virtuality_attr = get_attr_value(entry, gimli::constants::DW_AT_virtuality)?;
+    if let gimli::AttributeValue::Virtuality(virtuality) = virtuality_attr {
+        Some(virtuality)
+    } else {
+        None
+    }
+}
+
 // get the DW_AT_declaration attribute
 pub(crate) fn get_declaration_attribute(
     entry: &DebuggingInformationEntry<SliceType, usize>,
@@ -487,3 +734,46 @@ pub(crate) fn get_declaration_attribute(
         None
     }
 }
+
+// get the DW_AT_artificial attribute, which marks entries that were generated by the compiler
+pub(crate) fn get_artificial_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<bool> {
+    let artificial_attr = get_attr_value(entry, gimli::constants::DW_AT_artificial)?;
+    if let gimli::AttributeValue::Flag(flag) = artificial_attr {
+        Some(flag)
+    } else {
+        None
+    }
+}
+
+// get the DW_AT_external attribute, which marks variables and functions that are visible outside of their unit
+pub(crate) fn get_external_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<bool> {
+    let external_attr = get_attr_value(entry, gimli::constants::DW_AT_external)?;
+    if let gimli::AttributeValue::Flag(flag) = external_attr {
+        Some(flag)
+    } else {
+        None
+    }
+}
+
+// get the DW_AT_decl_file attribute, which is an index into the file table of the line program
+pub(crate) fn get_decl_file_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<u64> {
+    let decl_file_attr = get_attr_value(entry, gimli::constants::DW_AT_decl_file)?;
+    if let gimli::AttributeValue::FileIndex(file_index) = decl_file_attr {
+        Some(file_index)
+    } else {
+        decl_file_attr.udata_value()
+    }
+}
+
+// get the DW_AT_decl_line attribute
+pub(crate) fn get_decl_line_attribute(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+) -> Option<u64> {
+    get_attr_value(entry, gimli::constants::DW_AT_decl_line)?.udata_value()
+}
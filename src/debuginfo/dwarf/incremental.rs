@@ -0,0 +1,324 @@
+use super::attributes::{get_declaration_attribute, get_location_attribute};
+use super::{ClassInfo, DebugDataReader};
+use crate::debuginfo::{DebugData, DroppedVariable, TypeInfo, UnitInfo, VarInfo, get_storage};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// The results of loading a file, split up by compile unit. A later load of a new version of the
+/// same file uses them for all compile units whose content has not changed
+pub(crate) struct UnitCache {
+    units: Vec<CachedUnit>,
+    /// the indices of the compile units that were parsed by the load that created this cache.
+    /// The results for all other compile units were taken from the previous cache
+    pub(crate) parsed_units: Vec<usize>,
+}
+
+struct CachedUnit {
+    fingerprint: u64,
+    // offset of the unit in .debug_info and index of the unit, in the file that was loaded
+    offset: usize,
+    unit_idx: usize,
+    // None if the results depend on other units, e.g. because the unit refers to DIEs in other units.
+    // Such a unit is parsed again in every load
+    content: Option<UnitContent>,
+}
+
+// everything that loading one compile unit contributes to the DebugData, before the types are deduplicated
+#[derive(Clone)]
+struct UnitContent {
+    unit_name: Option<String>,
+    unit_info: UnitInfo,
+    variables: IndexMap<String, Vec<VarInfo>>,
+    types: HashMap<usize, TypeInfo>,
+    typenames: HashMap<String, Vec<usize>>,
+    type_scopes: HashMap<usize, String>,
+    class_names: HashMap<usize, ClassInfo>,
+    dropped_variables: Vec<DroppedVariable>,
+}
+
+impl DebugDataReader<'_> {
+    // read the debug info like read_debug_info_entries, but take the results for each compile unit
+    // from the cache if the content of the unit is unchanged. The result is the same as with a full parse
+    pub(crate) fn read_debug_info_entries_incremental(
+        mut self,
+        cache: Option<&UnitCache>,
+    ) -> (DebugData, UnitCache) {
+        self.load_unit_headers();
+        let cached_units: HashMap<u64, &CachedUnit> = cache
+            .iter()
+            .flat_map(|cache| cache.units.iter())
+            .filter(|cached_unit| cached_unit.content.is_some())
+            .map(|cached_unit| (cached_unit.fingerprint, cached_unit))
+            .collect();
+
+        let mut variables = IndexMap::<String, Vec<VarInfo>>::new();
+        let mut types = HashMap::<usize, TypeInfo>::new();
+        let mut typenames = HashMap::<String, Vec<usize>>::new();
+        let mut new_cache = UnitCache {
+            units: Vec::new(),
+            parsed_units: Vec::new(),
+        };
+        // compile units that are parsed, with their variables and the information needed to cache them
+        let mut parsed_units = Vec::new();
+
+        for unit_idx in 0..self.units.list.len() {
+            let (unit, _) = &self.units[unit_idx];
+            let offset = self.units.get_unit_base_offset(unit);
+            let is_compile_unit =
+                matches!(unit.offset(), gimli::UnitSectionOffset::DebugInfoOffset(_));
+            // type units don't contain variables; they are only read to get their class names and type scopes
+            let (fingerprint, self_contained) = if is_compile_unit {
+                self.unit_fingerprint(unit_idx)
+            } else {
+                (0, false)
+            };
+
+            if is_compile_unit
+                && let Some(cached_unit) = cached_units.get(&fingerprint)
+                && let Some(content) = &cached_unit.content
+            {
+                let mut content = content.clone();
+                content.rebase(
+                    offset.wrapping_sub(cached_unit.offset),
+                    unit_idx.wrapping_sub(cached_unit.unit_idx),
+                );
+                self.unit_names.push(content.unit_name.clone());
+                self.unit_info.push(content.unit_info.clone());
+                self.class_names.extend(content.class_names.clone());
+                self.type_scopes.extend(content.type_scopes.clone());
+                self.dropped_variables
+                    .extend(content.dropped_variables.iter().cloned());
+                for (name, var_list) in &content.variables {
+                    let entry = variables.entry(name.clone()).or_default();
+                    for varinfo in var_list {
+                        // the sections may have moved, even if the content of the unit is unchanged
+                        let mut varinfo = varinfo.clone();
+                        varinfo.storage = match varinfo.address {
+                            Some(address) if !varinfo.is_tls() => {
+                                get_storage(&self.sections, address)
+                            }
+                            _ => None,
+                        };
+                        entry.push(varinfo);
+                    }
+                }
+                types.extend(content.types.clone());
+                merge_typenames(&mut typenames, &content.typenames);
+                new_cache.units.push(CachedUnit {
+                    fingerprint,
+                    offset,
+                    unit_idx,
+                    content: Some(content),
+                });
+            } else {
+                // the class names and type scopes of the unit are collected separately, so that they can be cached
+                let class_names = std::mem::take(&mut self.class_names);
+                let type_scopes = std::mem::take(&mut self.type_scopes);
+                let dropped_variables = std::mem::take(&mut self.dropped_variables);
+                let mut unit_variables = IndexMap::<String, Vec<VarInfo>>::new();
+                self.load_unit_variables(unit_idx, &mut unit_variables);
+                let unit_class_names = std::mem::replace(&mut self.class_names, class_names);
+                let unit_type_scopes = std::mem::replace(&mut self.type_scopes, type_scopes);
+                let unit_dropped_variables =
+                    std::mem::replace(&mut self.dropped_variables, dropped_variables);
+                self.class_names.extend(unit_class_names.clone());
+                self.type_scopes.extend(unit_type_scopes.clone());
+                self.dropped_variables
+                    .extend(unit_dropped_variables.iter().cloned());
+                for (name, var_list) in &unit_variables {
+                    variables
+                        .entry(name.clone())
+                        .or_default()
+                        .extend(var_list.iter().cloned());
+                }
+                if is_compile_unit {
+                    new_cache.parsed_units.push(unit_idx);
+                    parsed_units.push((
+                        unit_idx,
+                        fingerprint,
+                        self_contained,
+                        unit_variables,
+                        unit_class_names,
+                        unit_type_scopes,
+                        unit_dropped_variables,
+                    ));
+                }
+            }
+        }
+
+        // the types can only be loaded once the class names of all units are known, since
+        // declarations are replaced by definitions from other units
+        for (
+            unit_idx,
+            fingerprint,
+            self_contained,
+            unit_variables,
+            class_names,
+            type_scopes,
+            dropped_variables,
+        ) in parsed_units
+        {
+            let (unit_types, unit_typenames) = self.load_types(&unit_variables, &mut |_| {});
+            types.extend(unit_types.iter().map(|(k, v)| (*k, v.clone())));
+            merge_typenames(&mut typenames, &unit_typenames);
+
+            // a unit can only be reused if all of its types are located inside the unit
+            let (unit, _) = &self.units[unit_idx];
+            let offset = self.units.get_unit_base_offset(unit);
+            let unit_range = offset..offset + unit.length_including_self();
+            let content = (self_contained
+                && unit_types
+                    .keys()
+                    .all(|type_offset| unit_range.contains(type_offset)))
+            .then(|| UnitContent {
+                unit_name: self.unit_names[unit_idx].clone(),
+                unit_info: self.unit_info[unit_idx].clone(),
+                variables: unit_variables,
+                types: unit_types,
+                typenames: unit_typenames,
+                type_scopes,
+                class_names,
+                dropped_variables,
+            });
+            new_cache.units.push(CachedUnit {
+                fingerprint,
+                offset,
+                unit_idx,
+                content,
+            });
+        }
+        for offsets in typenames.values_mut() {
+            offsets.sort_unstable();
+            offsets.dedup();
+        }
+        log::debug!(
+            "Parsed {} compile units, the results for {} compile units were reused",
+            new_cache.parsed_units.len(),
+            new_cache.units.len() - new_cache.parsed_units.len()
+        );
+
+        (self.into_debug_data(variables, types, typenames), new_cache)
+    }
+
+    // hash the content of a compile unit: all DIEs with their attributes, where strings are resolved,
+    // and the addresses of the variables, which may have been taken from the symbol table.
+    // The returned flag is false if the unit refers to DIEs in other units, or if it contains declarations
+    // of types. These are replaced by the definitions from other units, so the result depends on them
+    fn unit_fingerprint(&self, unit_idx: usize) -> (u64, bool) {
+        let (unit_header, abbreviations) = &self.units[unit_idx];
+        let Ok(unit) = self.dwarf.unit(*unit_header) else {
+            return (0, false);
+        };
+        let mut hasher = DefaultHasher::new();
+        let mut self_contained = true;
+        let mut value_text = String::new();
+        unit_header.encoding().hash(&mut hasher);
+        // the declaration locations of the variables are resolved using the file table of the line program
+        self.load_file_table(unit_idx).hash(&mut hasher);
+
+        let mut entries = unit_header.entries(abbreviations);
+        loop {
+            let (depth_delta, entry) = match entries.next_dfs() {
+                Ok(Some(item)) => item,
+                Ok(None) => break,
+                Err(_) => return (0, false),
+            };
+            depth_delta.hash(&mut hasher);
+            entry.tag().0.hash(&mut hasher);
+            if matches!(
+                entry.tag(),
+                gimli::constants::DW_TAG_structure_type
+                    | gimli::constants::DW_TAG_class_type
+                    | gimli::constants::DW_TAG_union_type
+                    | gimli::constants::DW_TAG_enumeration_type
+            ) && get_declaration_attribute(entry).unwrap_or(false)
+            {
+                self_contained = false;
+            }
+            let mut attrs = entry.attrs();
+            while let Ok(Some(attr)) = attrs.next() {
+                attr.name().0.hash(&mut hasher);
+                let value = attr.value();
+                if matches!(
+                    value,
+                    gimli::AttributeValue::DebugInfoRef(_)
+                        | gimli::AttributeValue::DebugInfoRefSup(_)
+                        | gimli::AttributeValue::DebugTypesRef(_)
+                ) {
+                    self_contained = false;
+                }
+                if let Ok(string) = self.dwarf.attr_string(&unit, value) {
+                    string.slice().hash(&mut hasher);
+                } else {
+                    value_text.clear();
+                    let _ = write!(value_text, "{value:?}");
+                    value_text.hash(&mut hasher);
+                }
+            }
+            if entry.tag() == gimli::constants::DW_TAG_variable {
+                get_location_attribute(
+                    self,
+                    entry,
+                    unit_header.encoding(),
+                    unit_idx,
+                    &self.symbol_table,
+                )
+                .hash(&mut hasher);
+            }
+        }
+
+        (hasher.finish(), self_contained)
+    }
+}
+
+impl UnitContent {
+    // adjust the content of a cached unit that is located at a different offset or has a different index in the new file
+    fn rebase(&mut self, offset_delta: usize, unit_delta: usize) {
+        if offset_delta == 0 && unit_delta == 0 {
+            return;
+        }
+        let rebase_offset = |offset: usize| offset.wrapping_add(offset_delta);
+        for varinfo in self.variables.values_mut().flatten() {
+            varinfo.typeref = rebase_offset(varinfo.typeref);
+            varinfo.dbginfo_offset = rebase_offset(varinfo.dbginfo_offset);
+            varinfo.unit_idx = varinfo.unit_idx.wrapping_add(unit_delta);
+        }
+        self.types = std::mem::take(&mut self.types)
+            .into_iter()
+            .map(|(offset, mut typeinfo)| {
+                typeinfo.rebase_type_offsets(offset_delta, unit_delta);
+                (rebase_offset(offset), typeinfo)
+            })
+            .collect();
+        for offsets in self.typenames.values_mut() {
+            for offset in offsets {
+                *offset = rebase_offset(*offset);
+            }
+        }
+        self.type_scopes = std::mem::take(&mut self.type_scopes)
+            .into_iter()
+            .map(|(offset, scope)| (rebase_offset(offset), scope))
+            .collect();
+        self.class_names = std::mem::take(&mut self.class_names)
+            .into_iter()
+            .map(|(offset, class_info)| (rebase_offset(offset), class_info))
+            .collect();
+        for dropped in &mut self.dropped_variables {
+            dropped.offset = rebase_offset(dropped.offset);
+        }
+    }
+}
+
+fn merge_typenames(
+    typenames: &mut HashMap<String, Vec<usize>>,
+    other: &HashMap<String, Vec<usize>>,
+) {
+    for (name, offsets) in other {
+        typenames
+            .entry(name.clone())
+            .or_default()
+            .extend(offsets.iter().copied());
+    }
+}
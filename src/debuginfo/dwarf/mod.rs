@@ -1,26 +1,46 @@
-use crate::debuginfo::{DbgDataType, DebugData, TypeInfo, VarInfo};
+use crate::debuginfo::{
+    AddressDiscrepancy, ConstValue, DbgDataType, DebugData, DebugDataError, DropReason,
+    DroppedVariable, LoadOptions, LoadPhase, LocationKind, ProgressEvent, SourceLanguage, TypeInfo,
+    UnitInfo, VarInfo, get_storage, simplify_unit_name,
+};
 use gimli::{Abbreviations, DebuggingInformationEntry, Dwarf, UnitHeader};
-use gimli::{EndianSlice, RunTimeEndian};
+use gimli::{EndianSlice, Endianity, RunTimeEndian};
 use indexmap::IndexMap;
 use object::ObjectSymbol;
 use object::read::ObjectSection;
-use object::{Endianness, Object};
+use object::{Architecture, Endianness, Object};
 use std::ffi::OsStr;
-use std::ops::Index;
+use std::io::Read;
+use std::ops::{Deref, Index};
+use std::path::{Path, PathBuf};
 use std::{collections::HashMap, fs::File};
 type SliceType<'a> = EndianSlice<'a, RunTimeEndian>;
 
 mod attributes;
 use attributes::{
-    get_abstract_origin_attribute, get_declaration_attribute, get_location_attribute,
-    get_name_attribute, get_specification_attribute, get_typeref_attribute,
+    get_abstract_origin_chain, get_artificial_attribute, get_attr_value, get_decl_file_attribute,
+    get_decl_line_attribute, get_declaration_attribute, get_external_attribute,
+    get_language_attribute, get_location_attribute, get_name_attribute, get_producer_attribute,
+    get_register_relative_location, get_specification_attribute, get_type_attribute,
+    get_typeref_attribute, get_variable_const_value_attribute,
 };
+#[cfg(test)]
+mod incremental;
 mod typereader;
+#[cfg(test)]
+pub(crate) use incremental::UnitCache;
 
+// Offsets of DIEs are stored as a single usize, which is the offset relative to the start of .debug_info.
+// The type units in .debug_types (Dwarf 4) have their own offset space, so their offsets are
+// placed after the end of .debug_info
 pub(crate) struct UnitList<'a> {
     list: Vec<(UnitHeader<SliceType<'a>>, gimli::Abbreviations)>,
+    debug_types_base: usize,
+    // type units can be referenced by their signature (DW_FORM_ref_sig8). Maps the signature to the offset of the type
+    type_signatures: HashMap<u64, usize>,
 }
 
+#[derive(Clone)]
 pub struct ClassInfo {
     name: String,
     linkage_name: String,
@@ -81,86 +101,452 @@ impl ClassInfo {
 
 struct DebugDataReader<'elffile> {
     dwarf: Dwarf<EndianSlice<'elffile, RunTimeEndian>>,
-    verbose: bool,
     units: UnitList<'elffile>,
     unit_names: Vec<Option<String>>,
+    unit_info: Vec<UnitInfo>,
     endian: Endianness,
+    architecture: Architecture,
     sections: HashMap<String, (u64, u64)>,
     class_names: HashMap<usize, ClassInfo>,
-    symbol_table: Vec<(String, u64)>,
+    type_scopes: HashMap<usize, String>,
+    symbol_table: HashMap<String, u64>,
+    dropped_variables: Vec<DroppedVariable>,
+    options: LoadOptions,
+    // only the variables of the compile units with these names are loaded, see load_dwarf_filtered()
+    unit_filter: Option<Vec<String>>,
 }
 
 // load the debug info from an elf file
-pub(crate) fn load_dwarf(filename: &OsStr, verbose: bool) -> Result<DebugData, String> {
+// diagnostic messages are emitted through the log crate. Nothing is printed unless a logger is installed
+// and the log level filter is raised, either by the caller or by setting verbose
+// progress is called at the unit boundaries while the variables are read, and after each variable while the types are read
+pub(crate) fn load_dwarf(
+    filename: &OsStr,
+    verbose: bool,
+    options: LoadOptions,
+    progress: &mut dyn FnMut(ProgressEvent),
+) -> Result<DebugData, DebugDataError> {
+    super::raise_log_level(verbose);
+    // on macOS the debug info is usually stored in a separate .dSYM bundle
+    let path = Path::new(filename);
+    if path.is_dir() {
+        let dwarf_path = get_dsym_dwarf_path(path)?;
+        let filedata = load_filedata(dwarf_path.as_os_str())?;
+        let elffile = load_elf_file(&dwarf_path.to_string_lossy(), &filedata)?;
+        return load_dwarf_from_object(
+            &elffile,
+            &elffile,
+            &filename.to_string_lossy(),
+            options,
+            progress,
+        );
+    }
+
     let filedata = load_filedata(filename)?;
-    let elffile = load_elf_file(&filename.to_string_lossy(), &filedata)?;
-    // check if the elf file is including the required debug info section
-    if !elffile
-        .sections()
-        .any(|section| section.name() == Ok(".debug_info"))
+    if filedata.starts_with(&object::archive::MAGIC)
+        || filedata.starts_with(&object::archive::THIN_MAGIC)
     {
-        return Err(format!(
-            "Error: {} does not contain DWARF2+ debug info. The section .debug_info is missing.",
-            filename.to_string_lossy()
+        return load_archive(&filedata, filename, None, options, progress);
+    }
+    let elffile = load_elf_file(&filename.to_string_lossy(), &filedata)?;
+    if !has_debug_info(&elffile, options.section_names) {
+        // a thin executable, with its debug info in the bundle <executable>.dSYM next to it
+        let mut dsym_path = filename.to_owned();
+        dsym_path.push(".dSYM");
+        let dsym_path = Path::new(&dsym_path);
+        if elffile.format() == object::BinaryFormat::MachO && dsym_path.is_dir() {
+            let dwarf_path = get_dsym_dwarf_path(dsym_path)?;
+            let dsym_filedata = load_filedata(dwarf_path.as_os_str())?;
+            let dsym_file = load_elf_file(&dwarf_path.to_string_lossy(), &dsym_filedata)?;
+            check_dsym_uuid(&dsym_file, &elffile, dsym_path)?;
+            // the addresses in the dSYM are the same as in the executable, so the symbol table of the executable is used
+            return load_dwarf_from_object(
+                &dsym_file,
+                &elffile,
+                &dwarf_path.to_string_lossy(),
+                options,
+                progress,
+            );
+        }
+    }
+
+    load_dwarf_from_object(
+        &elffile,
+        &elffile,
+        &filename.to_string_lossy(),
+        options,
+        progress,
+    )
+}
+
+// load the debug info of selected compile units from an elf file.
+// The units are given by their DW_AT_name, either in full or as a simple name, as in {CompileUnit:...}.
+// The DIEs of the other units are not traversed, but the types that are defined in them can still be referenced
+#[cfg(test)]
+pub(crate) fn load_dwarf_filtered(
+    filename: &OsStr,
+    units: &[&str],
+    verbose: bool,
+) -> Result<DebugData, DebugDataError> {
+    super::raise_log_level(verbose);
+    let filedata = load_filedata(filename)?;
+    let elffile = load_elf_file(&filename.to_string_lossy(), &filedata)?;
+    let unit_filter = units.iter().map(|unit| unit.to_string()).collect();
+    read_dwarf_from_object(
+        &elffile,
+        &elffile,
+        &filename.to_string_lossy(),
+        LoadOptions::default(),
+        |mut reader| {
+            reader.unit_filter = Some(unit_filter);
+            reader.read_debug_info_entries(&mut |_| {})
+        },
+    )
+}
+
+// load the debug info from the content of an elf file that is already in memory
+// the returned DebugData does not borrow from data
+#[cfg(test)]
+pub(crate) fn load_dwarf_from_bytes(
+    data: &[u8],
+    verbose: bool,
+) -> Result<DebugData, DebugDataError> {
+    super::raise_log_level(verbose);
+    let elffile = load_elf_file("<memory>", data)?;
+    load_dwarf_from_object(
+        &elffile,
+        &elffile,
+        "<memory>",
+        LoadOptions::default(),
+        &mut |_| {},
+    )
+}
+
+// load the debug info from an elf file that is read from a stream, e.g. stdin or a pipe
+// The file can't be memory-mapped, so all of it is read into a buffer before it is parsed
+#[cfg(test)]
+pub(crate) fn load_dwarf_from_reader(
+    mut reader: impl std::io::Read,
+    verbose: bool,
+) -> Result<DebugData, DebugDataError> {
+    let mut data = Vec::new();
+    reader
+        .read_to_end(&mut data)
+        .map_err(|error| DebugDataError::Io {
+            context: "Error: Failed to read the input stream".to_string(),
+            error,
+        })?;
+    load_dwarf_from_bytes(&data, verbose)
+}
+
+// load the debug info from a static archive (lib*.a)
+// If a member name is given, only that member is loaded. Otherwise the debug info of all members is merged
+#[cfg(test)]
+pub(crate) fn load_dwarf_archive(
+    filename: &OsStr,
+    member: Option<&str>,
+    verbose: bool,
+) -> Result<DebugData, DebugDataError> {
+    super::raise_log_level(verbose);
+    let filedata = load_filedata(filename)?;
+    load_archive(
+        &filedata,
+        filename,
+        member,
+        LoadOptions::default(),
+        &mut |_| {},
+    )
+}
+
+fn load_archive(
+    filedata: &[u8],
+    filename: &OsStr,
+    member: Option<&str>,
+    options: LoadOptions,
+    progress: &mut dyn FnMut(ProgressEvent),
+) -> Result<DebugData, DebugDataError> {
+    let archive_name = filename.to_string_lossy();
+    let parse_error = |err: object::read::Error| {
+        DebugDataError::Parse(format!(
+            "Error: Failed to parse archive '{archive_name}': {err}"
+        ))
+    };
+    let archive = object::read::archive::ArchiveFile::parse(filedata).map_err(parse_error)?;
+
+    let mut merged_data: Option<DebugData> = None;
+    let mut offset_base = 0;
+    for archive_member in archive.members() {
+        let archive_member = archive_member.map_err(parse_error)?;
+        let member_name = String::from_utf8_lossy(archive_member.name());
+        if member.is_some_and(|name| name != member_name) {
+            continue;
+        }
+
+        // the members of a thin archive are separate files, whose path is relative to the archive
+        let thin_member_data;
+        let member_data = if archive_member.is_thin() {
+            let archive_dir = Path::new(filename).parent().unwrap_or(Path::new(""));
+            thin_member_data = load_filedata(archive_dir.join(&*member_name).as_os_str())?;
+            &thin_member_data[..]
+        } else {
+            archive_member.data(filedata).map_err(parse_error)?
+        };
+        let member_filename = format!("{archive_name}({member_name})");
+        let member_file = load_elf_file(&member_filename, member_data)?;
+        // when all members are loaded, members without debug info are skipped
+        if member.is_none() && !has_debug_info(&member_file, options.section_names) {
+            log::debug!("{member_filename} does not contain any debug info");
+            continue;
+        }
+
+        let debug_data = load_dwarf_from_object(
+            &member_file,
+            &member_file,
+            &member_filename,
+            options,
+            progress,
+        )?;
+        match &mut merged_data {
+            Some(merged) => merged.merge_rebased(debug_data, offset_base),
+            None => merged_data = Some(debug_data),
+        }
+        // the offsets of the types of the next member must not overlap with the offsets of this member
+        let debug_info_name = get_debug_section_name(
+            &member_file,
+            gimli::SectionId::DebugInfo,
+            options.section_names,
+        );
+        if let Some(section) = member_file.section_by_name(&debug_info_name) {
+            offset_base += section.size() as usize;
+        }
+    }
+
+    match (merged_data, member) {
+        (Some(debug_data), _) => Ok(debug_data),
+        (None, Some(member)) => Err(DebugDataError::ArchiveMemberNotFound {
+            archive: archive_name.to_string(),
+            member: member.to_string(),
+        }),
+        (None, None) => Err(DebugDataError::NoDebugInfo(archive_name.to_string())),
+    }
+}
+
+// load the debug info from an elf file, and return a cache of the results for each compile unit.
+// If a cache from a previous load is given, the compile units whose content is unchanged are not parsed again
+#[cfg(test)]
+pub(crate) fn load_dwarf_incremental(
+    filename: &OsStr,
+    cache: Option<&UnitCache>,
+    verbose: bool,
+) -> Result<(DebugData, UnitCache), DebugDataError> {
+    super::raise_log_level(verbose);
+    let filedata = load_filedata(filename)?;
+    let elffile = load_elf_file(&filename.to_string_lossy(), &filedata)?;
+    read_dwarf_from_object(
+        &elffile,
+        &elffile,
+        &filename.to_string_lossy(),
+        LoadOptions::default(),
+        |reader| reader.read_debug_info_entries_incremental(cache),
+    )
+}
+
+// load the debug info from dwarf_file; symbol_file provides the symbol table and the section addresses
+// Usually both are the same file, but with a dSYM bundle they are separate
+fn load_dwarf_from_object(
+    dwarf_file: &object::read::File,
+    symbol_file: &object::read::File,
+    filename: &str,
+    options: LoadOptions,
+    progress: &mut dyn FnMut(ProgressEvent),
+) -> Result<DebugData, DebugDataError> {
+    if options.address_unit_bits == 0 || !options.address_unit_bits.is_multiple_of(8) {
+        return Err(DebugDataError::InvalidAddressUnit(
+            options.address_unit_bits,
         ));
     }
+    let debug_data =
+        read_dwarf_from_object(dwarf_file, symbol_file, filename, options, |reader| {
+            reader.read_debug_info_entries(progress)
+        })?;
+    Ok(debug_data.with_address_unit_bits(options.address_unit_bits))
+}
+
+// set up a DebugDataReader for dwarf_file and symbol_file, and use it to read the debug info
+fn read_dwarf_from_object<T>(
+    dwarf_file: &object::read::File,
+    symbol_file: &object::read::File,
+    filename: &str,
+    options: LoadOptions,
+    read: impl FnOnce(DebugDataReader<'_>) -> T,
+) -> Result<T, DebugDataError> {
+    // check if the elf file is including the required debug info section
+    if !has_debug_info(dwarf_file, options.section_names) {
+        return Err(DebugDataError::NoDebugInfo(filename.to_string()));
+    }
 
-    let symbol_table = get_symbol_table(&elffile);
+    let symbol_table = get_symbol_table(symbol_file);
 
-    let dwarf = load_dwarf_sections(&elffile)?;
+    let relocated_sections = relocate_debug_sections(dwarf_file, options.section_names)
+        .map_err(DebugDataError::Parse)?;
+    let dwarf = load_dwarf_sections(dwarf_file, &relocated_sections, options.section_names)
+        .map_err(DebugDataError::Parse)?;
 
     if !verify_dwarf_compile_units(&dwarf) {
-        return Err(format!(
-            "Error: {} does not contain DWARF2+ debug info - zero compile units contain debug info.",
-            filename.to_string_lossy()
-        ));
+        return Err(DebugDataError::NoCompileUnits(filename.to_string()));
     }
 
-    let sections = get_elf_sections(&elffile);
+    let sections = get_elf_sections(symbol_file);
 
     let dbg_reader = DebugDataReader {
         dwarf,
-        verbose,
         units: UnitList::new(),
         unit_names: Vec::new(),
-        endian: elffile.endianness(),
+        unit_info: Vec::new(),
+        endian: dwarf_file.endianness(),
+        architecture: dwarf_file.architecture(),
         sections,
         class_names: HashMap::new(),
+        type_scopes: HashMap::new(),
         symbol_table,
+        dropped_variables: Vec::new(),
+        options,
+        unit_filter: None,
+    };
+
+    Ok(read(dbg_reader))
+}
+
+// check if the file contains a .debug_info section
+fn has_debug_info(elffile: &object::read::File, section_names: Option<SectionNames>) -> bool {
+    let debug_info_name =
+        get_debug_section_name(elffile, gimli::SectionId::DebugInfo, section_names);
+    elffile
+        .sections()
+        .any(|section| section.name() == Ok(&debug_info_name))
+}
+
+// get the path of the file containing the DWARF data inside a dSYM bundle:
+// <name>.dSYM/Contents/Resources/DWARF/<name>
+fn get_dsym_dwarf_path(bundle_path: &Path) -> Result<PathBuf, DebugDataError> {
+    let invalid_bundle = |reason: &str| DebugDataError::InvalidDsymBundle {
+        path: bundle_path.to_string_lossy().to_string(),
+        reason: reason.to_string(),
     };
 
-    Ok(dbg_reader.read_debug_info_entries())
+    let dwarf_dir = bundle_path.join("Contents").join("Resources").join("DWARF");
+    let Ok(dir_entries) = std::fs::read_dir(&dwarf_dir) else {
+        return Err(invalid_bundle(
+            "the directory Contents/Resources/DWARF is missing",
+        ));
+    };
+    let files: Vec<PathBuf> = dir_entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+
+    // the DWARF file should have the same name as the bundle, minus the .dSYM extension.
+    // Otherwise, a single file in the directory is accepted.
+    let expected_name = bundle_path.file_stem();
+    if let Some(file) = files.iter().find(|file| file.file_name() == expected_name) {
+        Ok(file.clone())
+    } else if files.len() == 1 {
+        Ok(files[0].clone())
+    } else if files.is_empty() {
+        Err(invalid_bundle(
+            "the directory Contents/Resources/DWARF does not contain any files",
+        ))
+    } else {
+        Err(invalid_bundle(
+            "the directory Contents/Resources/DWARF contains several files, none of which matches the bundle name",
+        ))
+    }
+}
+
+// make sure that the dSYM bundle was created for the executable
+fn check_dsym_uuid(
+    dsym_file: &object::read::File,
+    executable: &object::read::File,
+    bundle_path: &Path,
+) -> Result<(), DebugDataError> {
+    if let (Ok(Some(dsym_uuid)), Ok(Some(exe_uuid))) =
+        (dsym_file.mach_uuid(), executable.mach_uuid())
+        && dsym_uuid != exe_uuid
+    {
+        return Err(DebugDataError::InvalidDsymBundle {
+            path: bundle_path.to_string_lossy().to_string(),
+            reason: "the UUID of the debug info does not match the executable".to_string(),
+        });
+    }
+    Ok(())
+}
+
+// the content of a file, either mapped into memory or read into a buffer
+enum FileData {
+    Mapped(memmap2::Mmap),
+    Owned(Vec<u8>),
+}
+
+impl Deref for FileData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            FileData::Mapped(mmap) => mmap,
+            FileData::Owned(data) => data,
+        }
+    }
 }
 
 // open a file and mmap its content
-fn load_filedata(filename: &OsStr) -> Result<memmap2::Mmap, String> {
-    let file = match File::open(filename) {
-        Ok(file) => file,
+// Some file systems and platforms don't allow files to be mapped; in that case the whole file is read instead
+fn load_filedata(filename: &OsStr) -> Result<FileData, DebugDataError> {
+    let file = open_file(filename)?;
+    match unsafe { memmap2::Mmap::map(&file) } {
+        Ok(mmap) => Ok(FileData::Mapped(mmap)),
         Err(error) => {
-            return Err(format!(
-                "Error: could not open file {}: {error}",
+            log::debug!(
+                "Failed to map file '{}': {error}. Reading it instead",
                 filename.to_string_lossy()
-            ));
+            );
+            read_filedata(file, filename)
         }
-    };
+    }
+}
 
-    match unsafe { memmap2::Mmap::map(&file) } {
-        Ok(mmap) => Ok(mmap),
-        Err(err) => Err(format!(
-            "Error: Failed to map file '{}': {err}",
-            filename.to_string_lossy()
-        )),
+// read the whole content of an opened file into a buffer
+fn read_filedata(mut file: File, filename: &OsStr) -> Result<FileData, DebugDataError> {
+    let mut data = Vec::new();
+    match file.read_to_end(&mut data) {
+        Ok(_) => Ok(FileData::Owned(data)),
+        Err(error) => Err(DebugDataError::Io {
+            context: format!(
+                "Error: Failed to read file '{}'",
+                filename.to_string_lossy()
+            ),
+            error,
+        }),
     }
 }
 
+fn open_file(filename: &OsStr) -> Result<File, DebugDataError> {
+    File::open(filename).map_err(|error| DebugDataError::Io {
+        context: format!("Error: could not open file {}", filename.to_string_lossy()),
+        error,
+    })
+}
+
 // read the headers and sections of an elf/object file
 fn load_elf_file<'data>(
     filename: &str,
     filedata: &'data [u8],
-) -> Result<object::read::File<'data>, String> {
+) -> Result<object::read::File<'data>, DebugDataError> {
     match object::File::parse(filedata) {
         Ok(file) => Ok(file),
-        Err(err) => Err(format!("Error: Failed to parse file '{filename}': {err}")),
+        Err(err) => Err(DebugDataError::Parse(format!(
+            "Error: Failed to parse file '{filename}': {err}"
+        ))),
     }
 }
 
@@ -181,31 +567,141 @@ fn get_elf_sections(elffile: &object::read::File) -> HashMap<String, (u64, u64)>
 }
 
 // load the DWARF debug info from the .debug_<xyz> sections
+// sections that are present in relocated_sections are read from there instead of the file
 fn load_dwarf_sections<'data>(
     elffile: &object::read::File<'data>,
+    relocated_sections: &'data HashMap<String, Vec<u8>>,
+    section_names: Option<SectionNames>,
 ) -> Result<gimli::Dwarf<SliceType<'data>>, String> {
     // Dwarf::load takes two closures / functions and uses them to load all the required debug sections
-    let loader = |section: gimli::SectionId| get_file_section_reader(elffile, section.name());
+    let loader = |section: gimli::SectionId| {
+        let section_name = get_debug_section_name(elffile, section, section_names);
+        if let Some(data) = relocated_sections.get(&section_name) {
+            Ok(EndianSlice::new(data, get_endian(elffile)))
+        } else {
+            get_file_section_reader(elffile, &section_name)
+        }
+    };
     gimli::Dwarf::load(loader)
 }
+
+// In relocatable ELF object files, e.g. the members of a static archive, the references between
+// the debug sections and the addresses of variables are only filled in by the linker.
+// Here the relocations are applied to copies of the debug sections.
+fn relocate_debug_sections(
+    elffile: &object::read::File,
+    section_names: Option<SectionNames>,
+) -> Result<HashMap<String, Vec<u8>>, String> {
+    let mut relocated_sections = HashMap::new();
+    if elffile.format() != object::BinaryFormat::Elf
+        || elffile.kind() != object::ObjectKind::Relocatable
+    {
+        return Ok(relocated_sections);
+    }
+
+    let endian = get_endian(elffile);
+    for section in elffile.sections() {
+        let Ok(name) = section.name() else {
+            continue;
+        };
+        let is_debug_section = name.starts_with(".debug_")
+            || section_names.is_some_and(|section_names| {
+                DWARF_SECTIONS.iter().any(|id| section_names(*id) == name)
+            });
+        if !is_debug_section || section.relocations().next().is_none() {
+            continue;
+        }
+        let relocation_map = section.relocation_map().map_err(|err| err.to_string())?;
+        let mut data = section.data().map_err(|err| err.to_string())?.to_vec();
+        for (offset, relocation) in section.relocations() {
+            let start = offset as usize;
+            match relocation.size() {
+                32 => {
+                    if let Some(bytes) = data.get_mut(start..start + 4) {
+                        let value = relocation_map.relocate(offset, endian.read_u32(bytes).into());
+                        endian.write_u32(bytes, value as u32);
+                    }
+                }
+                64 => {
+                    if let Some(bytes) = data.get_mut(start..start + 8) {
+                        let value = relocation_map.relocate(offset, endian.read_u64(bytes));
+                        endian.write_u64(bytes, value);
+                    }
+                }
+                _ => {}
+            }
+        }
+        relocated_sections.insert(name.to_string(), data);
+    }
+
+    Ok(relocated_sections)
+}
+
+// mapping from the DWARF sections to the names of the sections in the object file
+type SectionNames = fn(gimli::SectionId) -> &'static str;
+
+// the sections that are loaded by gimli::Dwarf::load
+const DWARF_SECTIONS: [gimli::SectionId; 13] = [
+    gimli::SectionId::DebugAbbrev,
+    gimli::SectionId::DebugAddr,
+    gimli::SectionId::DebugAranges,
+    gimli::SectionId::DebugInfo,
+    gimli::SectionId::DebugLine,
+    gimli::SectionId::DebugLineStr,
+    gimli::SectionId::DebugStr,
+    gimli::SectionId::DebugStrOffsets,
+    gimli::SectionId::DebugTypes,
+    gimli::SectionId::DebugLoc,
+    gimli::SectionId::DebugLocLists,
+    gimli::SectionId::DebugRanges,
+    gimli::SectionId::DebugRngLists,
+];
+
+// get the name of a debug section in the object file
+// If a mapping of the section names is given, it replaces the standard names.
+// Mach-O files store the debug info in sections named __debug_<xyz> in the __DWARF segment.
+// Mach-O section names are limited to 16 characters, e.g. .debug_str_offsets becomes __debug_str_offs
+fn get_debug_section_name(
+    elffile: &object::read::File,
+    section: gimli::SectionId,
+    section_names: Option<SectionNames>,
+) -> String {
+    if let Some(section_names) = section_names {
+        return section_names(section).to_string();
+    }
+    let name = section.name();
+    if elffile.format() == object::BinaryFormat::MachO {
+        let mut macho_name = format!("__{}", &name[1..]);
+        macho_name.truncate(16);
+        macho_name
+    } else {
+        name.to_string()
+    }
+}
 /// 获取 ELF 文件的符号表信息（全局符号名和地址）
-/// 返回 Vec<(String, u64)>，每个元素为(符号名, 地址)
-fn get_symbol_table(elffile: &object::read::File) -> Vec<(String, u64)> {
-    let mut symbols = Vec::new();
+/// 返回 HashMap<String, u64>，符号名 -> 地址
+fn get_symbol_table(elffile: &object::read::File) -> HashMap<String, u64> {
+    // in Mach-O files the symbol names of C variables have a leading underscore, which is not present in the debug info
+    let is_macho = elffile.format() == object::BinaryFormat::MachO;
+    let mut symbols = HashMap::new();
     for sym in elffile.symbols() {
         // 只保留全局、已定义、数据符号且有名字和地址
         if sym.is_global()
             && sym.is_definition()
             && sym.kind() == object::SymbolKind::Data
             && sym.address() != 0
+            && let Ok(name) = sym.name()
         {
-            if let Ok(name) = sym.name() {
-                symbols.push((name.to_string(), sym.address()));
-                // println!("Symbol: {}, Address: 0x{:x}", name, sym.address());
-                if name.starts_with("g_") {
-                    println!("Symbol: {}, Address: 0x{:x}", name, sym.address());
-                }
-            }
+            let name = if is_macho {
+                name.strip_prefix('_').unwrap_or(name)
+            } else {
+                name
+            };
+            // if the same name occurs more than once, the first definition is used
+            symbols
+                .entry(name.to_string())
+                .or_insert_with(|| sym.address());
+            log::trace!("Symbol: {}, Address: 0x{:x}", name, sym.address());
         }
     }
     symbols
@@ -249,9 +745,19 @@ fn get_endian(elffile: &object::read::File) -> RunTimeEndian {
 
 impl DebugDataReader<'_> {
     // read the debug information entries in the DWAF data to get all the global variables and their types
-    fn read_debug_info_entries(mut self) -> DebugData {
-        let mut variables = self.load_variables();
-        let (types, typenames) = self.load_types(&variables);
+    fn read_debug_info_entries(mut self, progress: &mut dyn FnMut(ProgressEvent)) -> DebugData {
+        let variables = self.load_variables(progress);
+        let (types, typenames) = self.load_types(&variables, progress);
+        self.into_debug_data(variables, types, typenames)
+    }
+
+    // combine the variables and types with the information about the units that was collected while reading
+    fn into_debug_data(
+        mut self,
+        mut variables: IndexMap<String, Vec<VarInfo>>,
+        types: HashMap<usize, TypeInfo>,
+        typenames: HashMap<String, Vec<usize>>,
+    ) -> DebugData {
         let varname_list: Vec<&String> = variables.keys().collect();
         let demangled_names = demangle_cpp_varnames(&varname_list);
 
@@ -259,187 +765,488 @@ impl DebugDataReader<'_> {
         std::mem::swap(&mut unit_names, &mut self.unit_names);
 
         self.update_variable_type_offset(&mut variables);
+        self.update_const_values(&mut variables, &types);
+        let address_discrepancies = find_address_discrepancies(&variables, &self.symbol_table);
 
-        DebugData {
+        let mut debug_data = DebugData {
             variables,
             types,
             typenames,
             demangled_names,
             unit_names,
             sections: self.sections,
+            type_scopes: self.type_scopes,
+            unit_info: self.unit_info,
+            dropped_variables: self.dropped_variables,
+            address_discrepancies,
+            endian: self.endian,
+            architecture: self.architecture,
+        };
+        let type_count = debug_data.types.len();
+        let removed = debug_data.deduplicate_types();
+        log::debug!("Merged {removed} of {type_count} types that are duplicated across units");
+
+        debug_data
+    }
+
+    // the meaning of a DW_AT_const_value depends on the type of the constant, which is only known once the types are loaded
+    fn update_const_values(
+        &self,
+        variables: &mut IndexMap<String, Vec<VarInfo>>,
+        types: &HashMap<usize, TypeInfo>,
+    ) {
+        for varinfo in variables.values_mut().flatten() {
+            if let Some(typeinfo) = types.get(&varinfo.typeref) {
+                varinfo.const_value = varinfo
+                    .const_value
+                    .take()
+                    .map(|const_value| convert_const_value(const_value, typeinfo, self.endian));
+            }
         }
     }
 
     // load all global variables from the dwarf data
-    fn load_variables(&mut self) -> IndexMap<String, Vec<VarInfo>> {
+    fn load_variables(
+        &mut self,
+        progress: &mut dyn FnMut(ProgressEvent),
+    ) -> IndexMap<String, Vec<VarInfo>> {
         let mut variables = IndexMap::<String, Vec<VarInfo>>::new();
 
+        self.load_unit_headers();
+        let units_total = self.units.list.len();
+        for unit_idx in 0..units_total {
+            self.load_unit_variables(unit_idx, &mut variables);
+            progress(ProgressEvent {
+                phase: LoadPhase::Variables,
+                done: unit_idx + 1,
+                total: units_total,
+            });
+        }
+
+        variables
+    }
+
+    // all units must be known before the DIEs are read, because references can point forward into later units
+    fn load_unit_headers(&mut self) {
         let mut iter = self.dwarf.debug_info.units();
-        while let Ok(Some(unit)) = iter.next() {
+        loop {
+            let unit = match iter.next() {
+                Ok(Some(unit)) => unit,
+                Ok(None) => break,
+                Err(err) => {
+                    // the following units can't be located if the header of this one is broken
+                    log::warn!("Failed to parse a compile unit header: {err}");
+                    break;
+                }
+            };
             let abbreviations = unit.abbreviations(&self.dwarf.debug_abbrev).unwrap();
             self.units.add(unit, abbreviations);
-            let unit_idx = self.units.list.len() - 1;
-            let (unit, abbreviations) = &self.units[unit_idx];
-
-            // The root of the tree inside of a unit is always a DW_TAG_compile_unit or DW_TAG_partial_unit.
-            // The global variables are among the immediate children of the unit; static variables
-            // in functions are declared inside of DW_TAG_subprogram[/DW_TAG_lexical_block]*.
-            // We can easily find all of them by using depth-first traversal of the tree
-            let mut entries_cursor = unit.entries(abbreviations);
-            if let Ok(Some((_, entry))) = entries_cursor.next_dfs() {
-                if entry.tag() == gimli::constants::DW_TAG_compile_unit
-                    || entry.tag() == gimli::constants::DW_TAG_partial_unit
-                {
-                    let cu_name = get_name_attribute(entry, &self.dwarf, unit).ok();
-                    self.unit_names.push(cu_name);
+        }
+        // Dwarf 4 type units, created by gcc with -fdebug-types-section
+        self.units.debug_types_base = gimli::Section::reader(&self.dwarf.debug_info).len();
+        let mut iter = self.dwarf.debug_types.units();
+        loop {
+            let unit = match iter.next() {
+                Ok(Some(unit)) => unit,
+                Ok(None) => break,
+                Err(err) => {
+                    log::warn!("Failed to parse a type unit header: {err}");
+                    break;
                 }
-            }
+            };
+            let abbreviations = unit.abbreviations(&self.dwarf.debug_abbrev).unwrap();
+            self.units.add(unit, abbreviations);
+        }
+    }
 
-            let mut depth = 0;
-            let mut context: Vec<(gimli::DwTag, Option<String>)> = Vec::new();
-            while let Ok(Some((depth_delta, entry))) = entries_cursor.next_dfs() {
-                depth += depth_delta;
-                debug_assert!(depth >= 1);
-                context.truncate((depth - 1) as usize);
-                let tag = entry.tag();
-                // It's essential to only get those names that might actually be needed.
-                // Getting all names unconditionally doubled the runtime of the program
-                // as a result of countless useless string allocations and deallocations.
-                if tag == gimli::constants::DW_TAG_namespace
-                    || tag == gimli::constants::DW_TAG_subprogram
-                {
-                    context.push((tag, get_name_attribute(entry, &self.dwarf, unit).ok()));
-                    // 打印最后一个元素的值
-                    if let Some((tag, opt_string)) = context.last() {
-                        match opt_string {
-                            Some(s) => {} //println!("Last DwTag: {:?}, String: {}", tag, s),
-                            None => {}
-                        }
-                    } else {
-                        println!("The context is empty.");
-                    }
-                } else {
-                    context.push((tag, None));
+    // check if the variables of the compile unit with the given name are loaded, see load_dwarf_filtered()
+    fn is_unit_selected(&self, unit_name: Option<&str>) -> bool {
+        let Some(unit_filter) = &self.unit_filter else {
+            return true;
+        };
+        unit_name.is_some_and(|unit_name| {
+            let simple_name = simplify_unit_name(unit_name);
+            unit_filter.iter().any(|spec| {
+                spec == unit_name || simple_name.eq_ignore_ascii_case(&simplify_unit_name(spec))
+            })
+        })
+    }
+
+    // load the global variables of one unit, and store the name, class names and type scopes of the unit
+    fn load_unit_variables(
+        &mut self,
+        unit_idx: usize,
+        variables: &mut IndexMap<String, Vec<VarInfo>>,
+    ) {
+        let (unit, abbreviations) = &self.units[unit_idx];
+
+        // The root of the tree inside of a unit is always a DW_TAG_compile_unit or DW_TAG_partial_unit.
+        // The global variables are among the immediate children of the unit; static variables
+        // in functions are declared inside of DW_TAG_subprogram[/DW_TAG_lexical_block]*.
+        // We can easily find all of them by using depth-first traversal of the tree
+        let mut entries_cursor = unit.entries(abbreviations);
+        if let Ok(Some((_, entry))) = entries_cursor.next_dfs() {
+            if entry.tag() == gimli::constants::DW_TAG_compile_unit
+                || entry.tag() == gimli::constants::DW_TAG_partial_unit
+            {
+                let cu_name = get_name_attribute(entry, &self.dwarf, unit).ok();
+                let is_selected = self.is_unit_selected(cu_name.as_deref());
+                self.unit_names.push(cu_name);
+                self.unit_info.push(UnitInfo {
+                    producer: get_producer_attribute(entry, &self.dwarf, unit),
+                    language: get_language_attribute(entry).map(get_source_language),
+                    dwarf_version: unit.version(),
+                });
+                if !is_selected {
+                    log::debug!("Skipping the variables of unit {unit_idx}");
+                    return;
                 }
-                debug_assert_eq!(depth as usize, context.len());
+            } else {
+                // type units have no name, but unit_names must still be indexed by unit_idx
+                self.unit_names.push(None);
+                self.unit_info.push(UnitInfo {
+                    dwarf_version: unit.version(),
+                    ..UnitInfo::default()
+                });
+            }
+        }
+
+        let file_table = self.load_file_table(unit_idx);
+        let mut depth = 0;
+        let mut context: Vec<(gimli::DwTag, Option<String>)> = Vec::new();
+        // C++ variables in namespaces are often declared inside the namespace, but defined at the top level
+        // of the unit with a DW_AT_specification that refers to the declaration
+        let mut declaration_namespaces = HashMap::<gimli::UnitOffset, Vec<String>>::new();
+        while let Ok(Some((depth_delta, entry))) = entries_cursor.next_dfs() {
+            depth += depth_delta;
+            debug_assert!(depth >= 1);
+            context.truncate((depth - 1) as usize);
+            let tag = entry.tag();
+            // It's essential to only get those names that might actually be needed.
+            // Getting all names unconditionally doubled the runtime of the program
+            // as a result of countless useless string allocations and deallocations.
+            if tag == gimli::constants::DW_TAG_namespace
+                || tag == gimli::constants::DW_TAG_subprogram
+                || tag == gimli::constants::DW_TAG_class_type
+                || tag == gimli::constants::DW_TAG_structure_type
+                || tag == gimli::constants::DW_TAG_union_type
+            {
+                context.push((tag, get_name_attribute(entry, &self.dwarf, unit).ok()));
+            } else {
+                context.push((tag, None));
+            }
+            debug_assert_eq!(depth as usize, context.len());
 
-                if entry.tag() == gimli::constants::DW_TAG_variable {
-                    let variable_name = get_name_attribute(entry, &self.dwarf, unit)
-                        .unwrap_or_else(|_| "unknown_variable".to_string());
-                    if variable_name == "g_fsmRunnable" {
-                        println!("Found variable: {}", variable_name);
+            if entry.tag() == gimli::constants::DW_TAG_variable {
+                match self.get_global_variable(entry, unit_idx, &file_table) {
+                    Ok(Some((name, .., true))) if !self.options.include_artificial => {
+                        // compiler-generated variables are only loaded on request
+                        log::debug!("Skipping compiler-generated variable {name}");
+                    }
+                    Ok(Some((
+                        name,
+                        typeref,
+                        address,
+                        is_tls,
+                        decl_location,
+                        is_external,
+                        artificial,
+                    ))) => {
+                        let (function, mut namespaces) = get_varinfo_from_context(&context);
+                        if namespaces.is_empty()
+                            && let Some(gimli::AttributeValue::UnitRef(spec_offset)) =
+                                get_attr_value(entry, gimli::constants::DW_AT_specification)
+                            && let Some(decl_namespaces) = declaration_namespaces.get(&spec_offset)
+                        {
+                            namespaces = decl_namespaces.clone();
+                        }
+                        variables.entry(name).or_default().push(VarInfo {
+                            address: Some(address),
+                            typeref,
+                            dbginfo_offset: self.units.get_offset(unit_idx, entry.offset()),
+                            unit_idx,
+                            function,
+                            namespaces,
+                            location_kind: if is_tls {
+                                LocationKind::Tls
+                            } else {
+                                LocationKind::Static
+                            },
+                            storage: if is_tls {
+                                None
+                            } else {
+                                get_storage(&self.sections, address)
+                            },
+                            decl_location,
+                            artificial,
+                            const_value: None,
+                            is_const: self.is_variable_const(entry, unit_idx),
+                            is_external,
+                        });
                     }
-                    match self.get_global_variable(entry, unit, abbreviations) {
-                        Ok(Some((name, typeref, address))) => {
-                            let (function, namespaces) = get_varinfo_from_context(&context);
+                    Ok(None) => {
+                        // unremarkable, the variable is not a global variable
+                        if get_declaration_attribute(entry).unwrap_or(false) {
+                            let namespaces = get_declaration_scope(&context);
+                            if !namespaces.is_empty() {
+                                declaration_namespaces.insert(entry.offset(), namespaces);
+                            }
+                        } else if !context
+                            .iter()
+                            .any(|(tag, _)| *tag == gimli::constants::DW_TAG_subprogram)
+                            && let Some((register, offset)) =
+                                get_register_relative_location(entry, unit.encoding())
+                            && let Ok(name) = get_name_attribute(entry, &self.dwarf, unit)
+                            && let Ok(typeref) = get_typeref_attribute(entry, &self.units, unit_idx)
+                        {
+                            // a global whose address is held in a register at runtime, e.g. in an overlay.
+                            // It has no address, but it is kept so that users can locate it themselves
+                            let (_, namespaces) = get_varinfo_from_context(&context);
                             variables.entry(name).or_default().push(VarInfo {
-                                address,
+                                address: None,
                                 typeref,
+                                dbginfo_offset: self.units.get_offset(unit_idx, entry.offset()),
                                 unit_idx,
-                                function,
+                                function: None,
                                 namespaces,
+                                location_kind: LocationKind::RegisterRelative { register, offset },
+                                storage: None,
+                                decl_location: get_decl_location(entry, &file_table),
+                                artificial: get_artificial_attribute(entry).unwrap_or(false),
+                                const_value: None,
+                                is_const: self.is_variable_const(entry, unit_idx),
+                                is_external: get_external_attribute(entry).unwrap_or(false),
+                            });
+                        } else if !context
+                            .iter()
+                            .any(|(tag, _)| *tag == gimli::constants::DW_TAG_subprogram)
+                            && let Some((name, typeref, const_value)) =
+                                self.get_global_constant(entry, unit_idx)
+                        {
+                            // a const global whose storage was optimized away still has its value
+                            let (_, namespaces) = get_varinfo_from_context(&context);
+                            variables.entry(name).or_default().push(VarInfo {
+                                address: None,
+                                typeref,
+                                dbginfo_offset: self.units.get_offset(unit_idx, entry.offset()),
+                                unit_idx,
+                                function: None,
+                                namespaces,
+                                location_kind: LocationKind::Static,
+                                storage: None,
+                                decl_location: get_decl_location(entry, &file_table),
+                                artificial: get_artificial_attribute(entry).unwrap_or(false),
+                                const_value: Some(const_value),
+                                is_const: self.is_variable_const(entry, unit_idx),
+                                is_external: get_external_attribute(entry).unwrap_or(false),
+                            });
+                        } else if !context
+                            .iter()
+                            .any(|(tag, _)| *tag == gimli::constants::DW_TAG_subprogram)
+                            && let Ok(name) = get_name_attribute(entry, &self.dwarf, unit)
+                        {
+                            // a definition outside of any function should have had a static address.
+                            // Variables inside of functions are usually locals, so they are not reported
+                            self.dropped_variables.push(DroppedVariable {
+                                name,
+                                offset: self.units.get_offset(unit_idx, entry.offset()),
+                                reason: DropReason::NoLocation,
                             });
                         }
-                        Ok(None) => {
-                            // unremarkable, the variable is not a global variable
-                        }
-                        Err(errmsg) => {
-                            if self.verbose {
-                                let offset = entry
-                                    .offset()
-                                    .to_debug_info_offset(unit)
-                                    .unwrap_or(gimli::DebugInfoOffset(0))
-                                    .0;
-                                println!("Error loading variable @{offset:x}: {errmsg}");
-                            }
-                        }
+                    }
+                    Err(errmsg) => {
+                        let offset = self.units.get_offset(unit_idx, entry.offset());
+                        log::debug!("Error loading variable @{offset:x}: {errmsg}");
+                        self.dropped_variables.push(DroppedVariable {
+                            name: get_name_attribute(entry, &self.dwarf, unit).unwrap_or_default(),
+                            offset,
+                            reason: DropReason::Invalid(errmsg),
+                        });
                     }
                 }
+            } else if entry.tag() == gimli::constants::DW_TAG_member
+                && get_declaration_attribute(entry).unwrap_or(false)
+            {
+                // up to Dwarf 4, a static data member is declared as a member of its class, and defined by a
+                // variable with a DW_AT_specification that refers to the member
+                let namespaces = get_declaration_scope(&context);
+                declaration_namespaces.insert(entry.offset(), namespaces);
+            }
 
-                // if the entry is a class, store its name and namespace
-                if entry.tag() == gimli::constants::DW_TAG_class_type {
-                    // if the class has a linkage name, use it, otherwise use the class name
-                    let is_declaration = get_declaration_attribute(entry).unwrap_or(false);
-                    let class_name = get_name_attribute(entry, &self.dwarf, unit)
-                        .unwrap_or_else(|_| "unknown_class".to_string());
-                    let linkage_name = String::new();
-                    // 拼接所有 namespace 名称，使用 "::" 作为分隔符
-                    let namespace = context
-                        .iter()
-                        .filter_map(|(tag, name)| {
-                            if *tag == gimli::constants::DW_TAG_namespace {
-                                name.as_ref()
-                            } else {
-                                None
-                            }
-                        })
-                        .cloned()
-                        .collect::<Vec<_>>()
-                        .join("::");
-                    // insert the class info into the class_names map
-                    let offset = entry
-                        .offset()
-                        .to_debug_info_offset(unit)
-                        .unwrap_or(gimli::DebugInfoOffset(0))
-                        .0;
-                    self.class_names.insert(
-                        offset,
-                        ClassInfo::new(
-                            class_name,
-                            linkage_name.to_string(),
-                            namespace,
-                            is_declaration,
-                        ),
-                    );
+            // if the entry is a class, struct or union, store its name and namespace
+            // this allows a declaration to be replaced by the definition, which may be located in another unit
+            if entry.tag() == gimli::constants::DW_TAG_class_type
+                || entry.tag() == gimli::constants::DW_TAG_structure_type
+                || entry.tag() == gimli::constants::DW_TAG_union_type
+            {
+                // if the class has a linkage name, use it, otherwise use the class name
+                let is_declaration = get_declaration_attribute(entry).unwrap_or(false);
+                let class_name = get_name_attribute(entry, &self.dwarf, unit)
+                    .unwrap_or_else(|_| "unknown_class".to_string());
+                let linkage_name = String::new();
+                // 拼接所有 namespace 名称，使用 "::" 作为分隔符
+                let namespace = context
+                    .iter()
+                    .filter_map(|(tag, name)| {
+                        if *tag == gimli::constants::DW_TAG_namespace {
+                            name.as_ref()
+                        } else {
+                            None
+                        }
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("::");
+                // insert the class info into the class_names map
+                let offset = self.units.get_offset(unit_idx, entry.offset());
+                self.class_names.insert(
+                    offset,
+                    ClassInfo::new(
+                        class_name,
+                        linkage_name.to_string(),
+                        namespace,
+                        is_declaration,
+                    ),
+                );
+            }
+
+            // store the scope of named types (namespaces and enclosing classes) for qualified names
+            if entry.tag() == gimli::constants::DW_TAG_class_type
+                || entry.tag() == gimli::constants::DW_TAG_structure_type
+                || entry.tag() == gimli::constants::DW_TAG_union_type
+            {
+                let scope = get_scope_from_context(&context[..context.len() - 1]);
+                if !scope.is_empty() {
+                    let offset = self.units.get_offset(unit_idx, entry.offset());
+                    self.type_scopes.insert(offset, scope);
                 }
             }
         }
-
-        variables
     }
 
     // an entry of the type DW_TAG_variable only describes a global variable if there is a name, a type and an address
-    // this function tries to get all three and returns them
+    // this function tries to get all three and returns them, together with flags that mark thread-local and artificial variables
     fn get_global_variable(
         &self,
         entry: &DebuggingInformationEntry<SliceType, usize>,
-        unit: &UnitHeader<SliceType>,
-        abbrev: &gimli::Abbreviations,
-    ) -> Result<Option<(String, usize, u64)>, String> {
-        match get_location_attribute(
-            self,
-            entry,
-            unit.encoding(),
-            &self.units.list.len() - 1,
-            &self.symbol_table,
-        ) {
-            Some(address) => {
+        unit_idx: usize,
+        file_table: &[Option<String>],
+    ) -> Result<Option<GlobalVariable>, String> {
+        let (unit, _) = &self.units[unit_idx];
+        match get_location_attribute(self, entry, unit.encoding(), unit_idx, &self.symbol_table) {
+            Some((address, is_tls)) => {
                 // if debugging information entry A has a DW_AT_specification or DW_AT_abstract_origin attribute
                 // pointing to another debugging information entry B, any attributes of B are considered to be part of A.
-                if let Some(specification_entry) = get_specification_attribute(entry, unit, abbrev)
+                // B is not necessarily located in the same unit as A
+                if let Some((spec_unit_idx, specification_entry)) =
+                    get_specification_attribute(entry, &self.units, unit_idx)
                 {
                     // the entry refers to a specification, which contains the name and type reference
-                    let name = get_name_attribute(&specification_entry, &self.dwarf, unit)?;
-                    let typeref = get_typeref_attribute(&specification_entry, unit)?;
-
-                    Ok(Some((name, typeref, address)))
-                } else if let Some(abstract_origin_entry) =
-                    get_abstract_origin_attribute(entry, unit, abbrev)
-                {
-                    // the entry refers to an abstract origin, which should also be considered when getting the name and type ref
-                    let name = get_name_attribute(entry, &self.dwarf, unit).or_else(|_| {
-                        get_name_attribute(&abstract_origin_entry, &self.dwarf, unit)
+                    let (spec_unit, _) = &self.units[spec_unit_idx];
+                    let name = get_name_attribute(&specification_entry, &self.dwarf, spec_unit)?;
+                    let typeref =
+                        get_typeref_attribute(&specification_entry, &self.units, spec_unit_idx)?;
+                    // the file index of the specification can only be resolved if it is in the same unit
+                    let decl_location = get_decl_location(entry, file_table).or_else(|| {
+                        (spec_unit_idx == unit_idx)
+                            .then(|| get_decl_location(&specification_entry, file_table))
+                            .flatten()
+                    });
+
+                    let is_external = get_external_attribute(entry)
+                        .or_else(|| get_external_attribute(&specification_entry))
+                        .unwrap_or(false);
+                    let artificial = get_artificial_attribute(entry)
+                        .or_else(|| get_artificial_attribute(&specification_entry))
+                        .unwrap_or(false);
+
+                    Ok(Some((
+                        name,
+                        typeref,
+                        address,
+                        is_tls,
+                        decl_location,
+                        is_external,
+                        artificial,
+                    )))
+                } else if get_attr_value(entry, gimli::constants::DW_AT_abstract_origin).is_some() {
+                    // the entry refers to an abstract origin, which can in turn refer to another origin.
+                    // Attributes that are missing in the entry are taken from the nearest origin that has them
+                    let origins = get_abstract_origin_chain(entry, &self.units, unit_idx);
+                    let name = get_name_attribute(entry, &self.dwarf, unit).or_else(|err| {
+                        origins
+                            .iter()
+                            .find_map(|(origin_unit_idx, origin_entry)| {
+                                let (origin_unit, _) = &self.units[*origin_unit_idx];
+                                get_name_attribute(origin_entry, &self.dwarf, origin_unit).ok()
+                            })
+                            .ok_or(err)
                     })?;
-                    let typeref = get_typeref_attribute(entry, unit)
-                        .or_else(|_| get_typeref_attribute(&abstract_origin_entry, unit))?;
-
-                    Ok(Some((name, typeref, address)))
+                    let typeref =
+                        get_typeref_attribute(entry, &self.units, unit_idx).or_else(|err| {
+                            origins
+                                .iter()
+                                .find_map(|(origin_unit_idx, origin_entry)| {
+                                    get_typeref_attribute(
+                                        origin_entry,
+                                        &self.units,
+                                        *origin_unit_idx,
+                                    )
+                                    .ok()
+                                })
+                                .ok_or(err)
+                        })?;
+                    // file indices can only be resolved for the origins in the same unit
+                    let decl_location = get_decl_location(entry, file_table).or_else(|| {
+                        origins
+                            .iter()
+                            .filter(|(origin_unit_idx, _)| *origin_unit_idx == unit_idx)
+                            .find_map(|(_, origin_entry)| {
+                                get_decl_location(origin_entry, file_table)
+                            })
+                    });
+
+                    let is_external = get_external_attribute(entry)
+                        .or_else(|| {
+                            origins
+                                .iter()
+                                .find_map(|(_, origin_entry)| get_external_attribute(origin_entry))
+                        })
+                        .unwrap_or(false);
+                    let artificial = get_artificial_attribute(entry)
+                        .or_else(|| {
+                            origins.iter().find_map(|(_, origin_entry)| {
+                                get_artificial_attribute(origin_entry)
+                            })
+                        })
+                        .unwrap_or(false);
+
+                    Ok(Some((
+                        name,
+                        typeref,
+                        address,
+                        is_tls,
+                        decl_location,
+                        is_external,
+                        artificial,
+                    )))
                 } else {
                     // usual case: there is no specification or abstract origin and all info is part of this entry
                     let name = get_name_attribute(entry, &self.dwarf, unit)?;
-                    let typeref = get_typeref_attribute(entry, unit)?;
-
-                    Ok(Some((name, typeref, address)))
+                    let typeref = get_typeref_attribute(entry, &self.units, unit_idx)?;
+                    let decl_location = get_decl_location(entry, file_table);
+                    let is_external = get_external_attribute(entry).unwrap_or(false);
+                    let artificial = get_artificial_attribute(entry).unwrap_or(false);
+
+                    Ok(Some((
+                        name,
+                        typeref,
+                        address,
+                        is_tls,
+                        decl_location,
+                        is_external,
+                        artificial,
+                    )))
                 }
             }
             None => {
@@ -448,6 +1255,189 @@ impl DebugDataReader<'_> {
             }
         }
     }
+
+    // check if the type of a variable is const. If the variable refers to its declaration with a
+    // DW_AT_specification or to an abstract origin, then the type can be given there instead
+    fn is_variable_const(
+        &self,
+        entry: &gimli::DebuggingInformationEntry<SliceType, usize>,
+        unit_idx: usize,
+    ) -> bool {
+        let type_ref = get_type_attribute(entry, &self.units, unit_idx)
+            .ok()
+            .or_else(|| {
+                let (spec_unit_idx, spec_entry) =
+                    get_specification_attribute(entry, &self.units, unit_idx)?;
+                get_type_attribute(&spec_entry, &self.units, spec_unit_idx).ok()
+            })
+            .or_else(|| {
+                get_abstract_origin_chain(entry, &self.units, unit_idx)
+                    .iter()
+                    .find_map(|(origin_unit_idx, origin_entry)| {
+                        get_type_attribute(origin_entry, &self.units, *origin_unit_idx).ok()
+                    })
+            });
+        type_ref
+            .and_then(|(type_unit_idx, dbginfo_offset)| {
+                self.is_const_type(type_unit_idx, dbginfo_offset)
+            })
+            .unwrap_or(false)
+    }
+
+    // a DW_TAG_variable without a location can still describe a constant, if it has a DW_AT_const_value.
+    // Returns the name, the type reference and the value of the constant
+    fn get_global_constant(
+        &self,
+        entry: &DebuggingInformationEntry<SliceType, usize>,
+        unit_idx: usize,
+    ) -> Option<(String, usize, ConstValue)> {
+        let (unit, _) = &self.units[unit_idx];
+        let const_value = get_variable_const_value_attribute(entry, &self.dwarf, unit)?;
+        // the name and type can also be given by the declaration of the constant, e.g. of a static class member
+        let specification = get_specification_attribute(entry, &self.units, unit_idx);
+        let name = get_name_attribute(entry, &self.dwarf, unit)
+            .or_else(|err| match &specification {
+                Some((spec_unit_idx, spec_entry)) => {
+                    get_name_attribute(spec_entry, &self.dwarf, &self.units[*spec_unit_idx].0)
+                }
+                None => Err(err),
+            })
+            .ok()?;
+        let typeref = get_typeref_attribute(entry, &self.units, unit_idx)
+            .or_else(|err| match &specification {
+                Some((spec_unit_idx, spec_entry)) => {
+                    get_typeref_attribute(spec_entry, &self.units, *spec_unit_idx)
+                }
+                None => Err(err),
+            })
+            .ok()?;
+        Some((name, typeref, const_value))
+    }
+
+    // get the paths of the source files in the line program of a unit, indexed by the value of DW_AT_decl_file.
+    // Up to Dwarf 4 the file table is indexed starting from 1, and the index 0 means that no file is given.
+    // In Dwarf 5 the file table is indexed starting from 0
+    fn load_file_table(&self, unit_idx: usize) -> Vec<Option<String>> {
+        let (unit_header, _) = &self.units[unit_idx];
+        let Ok(unit) = self.dwarf.unit(*unit_header) else {
+            return Vec::new();
+        };
+        let Some(line_program) = &unit.line_program else {
+            return Vec::new();
+        };
+        let header = line_program.header();
+        let attr_path = |value| {
+            self.dwarf
+                .attr_string(&unit, value)
+                .ok()
+                .map(|path| PathBuf::from(path.to_string_lossy().as_ref()))
+        };
+
+        let mut file_table = if header.version() <= 4 {
+            vec![None]
+        } else {
+            Vec::new()
+        };
+        for file_entry in header.file_names() {
+            // relative paths are relative to the directory of the file entry, which in turn may be relative to the compilation directory
+            let path = attr_path(file_entry.path_name()).map(|file_path| {
+                let mut full_path = unit
+                    .comp_dir
+                    .map(|comp_dir| PathBuf::from(comp_dir.to_string_lossy().as_ref()))
+                    .unwrap_or_default();
+                if let Some(dir_path) = file_entry.directory(header).and_then(attr_path) {
+                    full_path.push(dir_path);
+                }
+                full_path.push(file_path);
+                full_path.to_string_lossy().into_owned()
+            });
+            file_table.push(path);
+        }
+        file_table
+    }
+}
+
+// find the global variables whose address in the debug info differs from the address of the symbol of the same name.
+// A name can be defined more than once, e.g. as a static variable in several units, and only one of the definitions
+// has the global symbol. Therefore a name is only reported if none of its definitions matches the symbol.
+// Thread-local variables and static variables inside of functions are not checked
+fn find_address_discrepancies(
+    variables: &IndexMap<String, Vec<VarInfo>>,
+    symbol_table: &HashMap<String, u64>,
+) -> Vec<AddressDiscrepancy> {
+    let mut discrepancies = Vec::new();
+    for (name, varinfo_list) in variables {
+        let Some(&symbol_address) = symbol_table.get(name) else {
+            continue;
+        };
+        let candidates: Vec<(&VarInfo, u64)> = varinfo_list
+            .iter()
+            .filter(|varinfo| !varinfo.is_tls() && varinfo.function.is_none())
+            .filter_map(|varinfo| Some((varinfo, varinfo.address?)))
+            .collect();
+        if candidates
+            .iter()
+            .any(|(_, address)| *address == symbol_address)
+        {
+            continue;
+        }
+        for (varinfo, address) in candidates {
+            log::warn!(
+                "The address 0x{address:x} of {name} in the debug info differs from the address 0x{symbol_address:x} in the symbol table"
+            );
+            discrepancies.push(AddressDiscrepancy {
+                name: name.clone(),
+                unit_idx: varinfo.unit_idx,
+                dwarf_address: address,
+                symbol_address,
+            });
+        }
+    }
+    discrepancies
+}
+
+// values of signed types in DW_FORM_data<n> are sign-extended, and the bytes of a float or double are
+// converted to its value. All other values are returned unchanged
+fn convert_const_value(
+    const_value: ConstValue,
+    typeinfo: &TypeInfo,
+    endian: Endianness,
+) -> ConstValue {
+    match (&typeinfo.datatype, const_value) {
+        (DbgDataType::Sint8, ConstValue::Uint(value)) => ConstValue::Int(i64::from(value as i8)),
+        (DbgDataType::Sint16, ConstValue::Uint(value)) => ConstValue::Int(i64::from(value as i16)),
+        (DbgDataType::Sint32, ConstValue::Uint(value)) => ConstValue::Int(i64::from(value as i32)),
+        (DbgDataType::Sint64, ConstValue::Uint(value)) => ConstValue::Int(value as i64),
+        (DbgDataType::Float, ConstValue::Bytes(bytes)) => match <[u8; 4]>::try_from(&bytes[..]) {
+            Ok(bytes) if endian == Endianness::Little => {
+                ConstValue::Float(f64::from(f32::from_le_bytes(bytes)))
+            }
+            Ok(bytes) => ConstValue::Float(f64::from(f32::from_be_bytes(bytes))),
+            Err(_) => ConstValue::Bytes(bytes),
+        },
+        (DbgDataType::Double, ConstValue::Bytes(bytes)) => match <[u8; 8]>::try_from(&bytes[..]) {
+            Ok(bytes) if endian == Endianness::Little => {
+                ConstValue::Float(f64::from_le_bytes(bytes))
+            }
+            Ok(bytes) => ConstValue::Float(f64::from_be_bytes(bytes)),
+            Err(_) => ConstValue::Bytes(bytes),
+        },
+        (_, const_value) => const_value,
+    }
+}
+
+// name, typeref, address, is_tls, the declaration location, the external flag and the artificial flag of a global variable
+type GlobalVariable = (String, usize, u64, bool, Option<(String, u64)>, bool, bool);
+
+// get the source file and line of a declaration, using the file table of the unit that contains the entry
+fn get_decl_location(
+    entry: &DebuggingInformationEntry<SliceType, usize>,
+    file_table: &[Option<String>],
+) -> Option<(String, u64)> {
+    let file_index = get_decl_file_attribute(entry)?;
+    let file = file_table.get(file_index as usize)?.clone()?;
+    let line = get_decl_line_attribute(entry)?;
+    Some((file, line))
 }
 
 fn get_varinfo_from_context(
@@ -470,8 +1460,76 @@ fn get_varinfo_from_context(
     (function, namespaces)
 }
 
-fn demangle_cpp_varnames(input: &[&String]) -> HashMap<String, String> {
-    let mut demangled_symbols = HashMap::<String, String>::new();
+// get the scope of a variable declaration, innermost first. Static data members are declared inside
+// their class, so the names of the enclosing classes are part of the scope, just like namespaces
+fn get_declaration_scope(context: &[(gimli::DwTag, Option<String>)]) -> Vec<String> {
+    context
+        .iter()
+        .rev()
+        .filter_map(|(tag, name)| {
+            matches!(
+                *tag,
+                gimli::constants::DW_TAG_namespace
+                    | gimli::constants::DW_TAG_class_type
+                    | gimli::constants::DW_TAG_structure_type
+                    | gimli::constants::DW_TAG_union_type
+            )
+            .then(|| name.clone())
+            .flatten()
+        })
+        .collect()
+}
+
+fn get_source_language(language: gimli::DwLang) -> SourceLanguage {
+    match language {
+        gimli::constants::DW_LANG_C89
+        | gimli::constants::DW_LANG_C
+        | gimli::constants::DW_LANG_C99
+        | gimli::constants::DW_LANG_C11
+        | gimli::constants::DW_LANG_C17 => SourceLanguage::C,
+        gimli::constants::DW_LANG_C_plus_plus
+        | gimli::constants::DW_LANG_C_plus_plus_03
+        | gimli::constants::DW_LANG_C_plus_plus_11
+        | gimli::constants::DW_LANG_C_plus_plus_14
+        | gimli::constants::DW_LANG_C_plus_plus_17
+        | gimli::constants::DW_LANG_C_plus_plus_20 => SourceLanguage::Cpp,
+        gimli::constants::DW_LANG_Rust => SourceLanguage::Rust,
+        other => SourceLanguage::Other(other.0),
+    }
+}
+
+// get the C++ scope of an item from the context, e.g. "ns1::ns2::Outer"
+// anonymous namespaces are named the same way as by the demangler
+fn get_scope_from_context(context: &[(gimli::DwTag, Option<String>)]) -> String {
+    context
+        .iter()
+        .filter_map(|(tag, name)| match *tag {
+            gimli::constants::DW_TAG_namespace => Some(
+                name.clone()
+                    .unwrap_or_else(|| "(anonymous namespace)".to_string()),
+            ),
+            gimli::constants::DW_TAG_class_type
+            | gimli::constants::DW_TAG_structure_type
+            | gimli::constants::DW_TAG_union_type => name.clone(),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+// the demangled name of an anonymous namespace
+const ANONYMOUS_NAMESPACE: &str = "(anonymous namespace)";
+
+// the number of names that were passed to the demangler on the current thread
+#[cfg(test)]
+thread_local! {
+    static DEMANGLE_COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+// all names are demangled once while loading, and the results are kept in DebugData::demangled_names.
+// Lookups by a mangled or demangled name use this map, so they never need to run the demangler again
+fn demangle_cpp_varnames(input: &[&String]) -> HashMap<String, Vec<String>> {
+    let mut demangled_symbols = HashMap::<String, Vec<String>>::new();
     let demangle_opts = cpp_demangle::DemangleOptions::new()
         .no_params()
         .no_return_type();
@@ -479,13 +1537,29 @@ fn demangle_cpp_varnames(input: &[&String]) -> HashMap<String, String> {
         // some really simple strings can be processed by the demangler, e.g "c" -> "const", which is wrong here.
         // by only processing symbols that start with _Z (variables in classes/namespaces) this problem is avoided
         if varname.starts_with("_Z") {
-            if let Ok(sym) = cpp_demangle::Symbol::new(*varname) {
-                // exclude useless demangled names like "typeinfo for std::type_info" or "{vtable(std::type_info)}"
-                if let Ok(demangled) = sym.demangle(&demangle_opts) {
-                    if !demangled.contains(' ') && !demangled.starts_with("{vtable") {
-                        demangled_symbols.insert(demangled, (*varname).clone());
+            #[cfg(test)]
+            DEMANGLE_COUNT.with(|count| count.set(count.get() + 1));
+            match cpp_demangle::Symbol::new(*varname) {
+                Ok(sym) => {
+                    // exclude useless demangled names like "typeinfo for std::type_info" or "{vtable(std::type_info)}".
+                    // The only space that is allowed is the one in "(anonymous namespace)", which the mangled
+                    // namespace _GLOBAL__N_1 demangles to, e.g. "ns::(anonymous namespace)::var"
+                    match sym.demangle(&demangle_opts) {
+                        Ok(demangled) => {
+                            if !demangled.replace(ANONYMOUS_NAMESPACE, "").contains(' ')
+                                && !demangled.starts_with("{vtable")
+                            {
+                                insert_demangled_name(
+                                    &mut demangled_symbols,
+                                    demangled,
+                                    (*varname).clone(),
+                                );
+                            }
+                        }
+                        Err(err) => log::trace!("Failed to demangle {varname}: {err}"),
                     }
                 }
+                Err(err) => log::trace!("Failed to demangle {varname}: {err}"),
             }
         }
     }
@@ -493,19 +1567,54 @@ fn demangle_cpp_varnames(input: &[&String]) -> HashMap<String, String> {
     demangled_symbols
 }
 
+// several mangled names can demangle to the same name, e.g. overloads, which differ only in the
+// omitted parameters. All mangled names are kept in sorted order, so that the result does not
+// depend on the order in which the names are found
+pub(crate) fn insert_demangled_name(
+    demangled_symbols: &mut HashMap<String, Vec<String>>,
+    demangled: String,
+    mangled: String,
+) {
+    let mangled_list = demangled_symbols.entry(demangled).or_default();
+    if let Err(pos) = mangled_list.binary_search(&mangled) {
+        if !mangled_list.is_empty() {
+            log::debug!(
+                "Mangled names {} and {mangled} both demangle to the same name",
+                mangled_list[0]
+            );
+        }
+        mangled_list.insert(pos, mangled);
+    }
+}
+
 // UnitList holds a list of all UnitHeaders in the Dwarf data for convenient access
 impl<'a> UnitList<'a> {
     fn new() -> Self {
-        Self { list: Vec::new() }
+        Self {
+            list: Vec::new(),
+            debug_types_base: 0,
+            type_signatures: HashMap::new(),
+        }
     }
 
     fn add(&mut self, unit: UnitHeader<SliceType<'a>>, abbrev: Abbreviations) {
+        if let gimli::UnitType::Type {
+            type_signature,
+            type_offset,
+        } = unit.type_()
+        {
+            let type_dbginfo_offset = self.get_unit_base_offset(&unit) + type_offset.0;
+            self.type_signatures
+                .insert(type_signature.0, type_dbginfo_offset);
+        }
         self.list.push((unit, abbrev));
     }
 
+    // length_including_self() accounts for the size of the unit length field, which is 4 bytes in the 32-bit
+    // DWARF format and 12 bytes in the 64-bit DWARF format. Both formats can be mixed in one file
     fn get_unit(&self, itemoffset: usize) -> Option<usize> {
         for (idx, (unit, _)) in self.list.iter().enumerate() {
-            let unitoffset = unit.offset().as_debug_info_offset().unwrap().0;
+            let unitoffset = self.get_unit_base_offset(unit);
             if unitoffset < itemoffset && unitoffset + unit.length_including_self() > itemoffset {
                 return Some(idx);
             }
@@ -513,6 +1622,37 @@ impl<'a> UnitList<'a> {
 
         None
     }
+
+    fn get_unit_base_offset(&self, unit: &UnitHeader<SliceType<'a>>) -> usize {
+        match unit.offset() {
+            gimli::UnitSectionOffset::DebugInfoOffset(offset) => offset.0,
+            gimli::UnitSectionOffset::DebugTypesOffset(offset) => self.debug_types_base + offset.0,
+        }
+    }
+
+    // convert an offset inside a unit to an offset relative to the start of .debug_info
+    pub(crate) fn get_offset(&self, unit_idx: usize, unit_offset: gimli::UnitOffset) -> usize {
+        self.get_unit_base_offset(&self.list[unit_idx].0) + unit_offset.0
+    }
+
+    // convert an offset relative to the start of .debug_info to an offset inside the given unit
+    pub(crate) fn get_unit_offset(
+        &self,
+        unit_idx: usize,
+        offset: usize,
+    ) -> Option<gimli::UnitOffset> {
+        let (unit, _) = &self.list[unit_idx];
+        let unit_offset = offset.checked_sub(self.get_unit_base_offset(unit))?;
+        (unit_offset < unit.length_including_self()).then_some(gimli::UnitOffset(unit_offset))
+    }
+
+    // get the the offset of the type in the type unit with the given signature
+    pub(crate) fn get_signature_offset(
+        &self,
+        signature: gimli::DebugTypeSignature,
+    ) -> Option<usize> {
+        self.type_signatures.get(&signature.0).copied()
+    }
 }
 
 impl<'a> Index<usize> for UnitList<'a> {
@@ -526,12 +1666,14 @@ impl<'a> Index<usize> for UnitList<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::debuginfo::Storage;
 
-    static ELF_FILE_NAMES: [&str; 4] = [
+    static ELF_FILE_NAMES: [&str; 5] = [
         "fixtures/bin/debugdata_clang.elf",
         "fixtures/bin/debugdata_clang_dw4.elf",
         "fixtures/bin/debugdata_gcc.elf",
         "fixtures/bin/debugdata_gcc_dw3.elf",
+        "fixtures/bin/debugdata_gcc_dw64.elf",
     ];
 
     #[test]
@@ -679,7 +1821,7 @@ mod test {
                             },
                             ..
                         },
-                        0
+                        Some(0)
                     ))
                 ));
                 assert!(matches!(
@@ -693,7 +1835,7 @@ mod test {
                             },
                             ..
                         },
-                        0
+                        Some(0)
                     ))
                 ));
                 assert!(matches!(
@@ -707,7 +1849,7 @@ mod test {
                             },
                             ..
                         },
-                        4
+                        Some(4)
                     ))
                 ));
                 assert!(matches!(
@@ -721,7 +1863,7 @@ mod test {
                             },
                             ..
                         },
-                        4
+                        Some(4)
                     ))
                 ));
             }
@@ -767,7 +1909,10 @@ mod test {
             assert_eq!(*size, 33);
             assert_eq!(dim.len(), 1);
             assert_eq!(dim[0], 33);
-            assert!(matches!(arraytype.datatype, DbgDataType::Uint8));
+            assert!(matches!(
+                arraytype.datatype,
+                DbgDataType::Uint8 | DbgDataType::UChar
+            ));
 
             let varinfo = debugdata.variables.get("var_multidim").unwrap();
             let typeinfo = debugdata.types.get(&varinfo[0].typeref).unwrap();
@@ -780,6 +1925,602 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_load_tls_variables() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/tls_test.elf"), true).unwrap();
+
+        // thread-local variables are found, and their address is the offset in the TLS block
+        let varinfo = &debugdata.variables.get("tls_counter").unwrap()[0];
+        assert!(varinfo.is_tls());
+        assert_eq!(varinfo.address, Some(0));
+        let varinfo = &debugdata.variables.get("tls_flags").unwrap()[0];
+        assert!(varinfo.is_tls());
+        assert_eq!(varinfo.address, Some(4));
+
+        let varinfo = &debugdata.variables.get("plain_var").unwrap()[0];
+        assert!(!varinfo.is_tls());
+        assert!(varinfo.address.is_some_and(|address| address != 0));
+        assert_eq!(varinfo.storage, Some(Storage::Initialized));
+        // the storage of thread-local variables is not derived from their offset
+        let varinfo = &debugdata.variables.get("tls_counter").unwrap()[0];
+        assert_eq!(varinfo.storage, None);
+    }
+
+    #[test]
+    fn test_variable_storage() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/storage_test.elf"), true).unwrap();
+
+        let storage = |name| debugdata.variables.get(name).unwrap()[0].storage;
+        assert_eq!(storage("initialized_var"), Some(Storage::Initialized));
+        assert_eq!(storage("zero_init_var"), Some(Storage::ZeroInit));
+        assert_eq!(storage("read_only_var"), Some(Storage::ReadOnly));
+
+        let symbol = crate::symbol::find_symbol("zero_init_var", &debugdata).unwrap();
+        assert_eq!(symbol.storage, Some(Storage::ZeroInit));
+    }
+
+    #[test]
+    fn test_load_dwarf64() {
+        // the first unit uses the 32-bit DWARF format, all others use the 64-bit format
+        let filename = "fixtures/bin/debugdata_gcc_dw64.elf";
+        let debugdata = DebugData::load_dwarf(OsStr::new(filename), false).unwrap();
+        assert_eq!(debugdata.variables.len(), 28);
+        for name in [
+            "class1",
+            "class2",
+            "class3",
+            "class4",
+            "staticvar",
+            "structvar",
+            "bitfield",
+        ] {
+            assert!(debugdata.variables.get(name).is_some(), "{name}");
+        }
+        for (name, varinfo) in &debugdata.variables {
+            assert!(debugdata.types.contains_key(&varinfo[0].typeref), "{name}");
+        }
+
+        // the type of class1 is located in one of the 64-bit units
+        let filedata = std::fs::read(filename).unwrap();
+        let elffile = object::read::File::parse(&*filedata).unwrap();
+        let relocated_sections = HashMap::new();
+        let dwarf = load_dwarf_sections(&elffile, &relocated_sections, None).unwrap();
+        let mut units = dwarf.units();
+        let mut formats = Vec::new();
+        let typeref = debugdata.variables.get("class1").unwrap()[0].typeref;
+        let mut typeref_format = None;
+        while let Ok(Some(unit)) = units.next() {
+            let offset = unit.offset().as_debug_info_offset().unwrap().0;
+            if (offset..offset + unit.length_including_self()).contains(&typeref) {
+                typeref_format = Some(unit.format());
+            }
+            formats.push(unit.format());
+        }
+        assert_eq!(formats[0], gimli::Format::Dwarf32);
+        assert!(
+            formats[1..]
+                .iter()
+                .all(|format| *format == gimli::Format::Dwarf64)
+        );
+        assert_eq!(typeref_format, Some(gimli::Format::Dwarf64));
+        assert!(matches!(
+            debugdata.types.get(&typeref),
+            Some(TypeInfo {
+                datatype: DbgDataType::Class { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_c_declaration() {
+        for (filename, name, expected) in [
+            (
+                "fixtures/bin/leaves_test.elf",
+                "leaves_var",
+                "struct Outer { int32_t id; struct Flags { uint32_t ready : 1; uint32_t mode : 3; uint32_t count : 12; } flags; struct Inner { int16_t a; unsigned char b[2]; } inner[2]; float value; } leaves_var",
+            ),
+            (
+                "fixtures/bin/nested_array_test.elf",
+                "grid",
+                "struct Cell { int32_t id; struct Point { int16_t x; int16_t y; } corners[3]; char flag; } grid[4][2]",
+            ),
+            (
+                "fixtures/bin/linked_list_test.elf",
+                "node",
+                "struct Node { int32_t v; struct Node *next; } node",
+            ),
+            (
+                "fixtures/bin/linked_list_test.elf",
+                "void_ptr",
+                "void *void_ptr",
+            ),
+            (
+                "fixtures/bin/funcptr_test.elf",
+                "isr_table",
+                "void (*isr_table[3])(void)",
+            ),
+            (
+                "fixtures/bin/funcptr_test.elf",
+                "machine",
+                "struct state_machine { int32_t state; int32_t (*handlers[4])(struct state_machine *, unsigned char); } machine",
+            ),
+            ("fixtures/bin/string_test.elf", "name", "char name[16]"),
+        ] {
+            let debugdata = DebugData::load_dwarf(OsStr::new(filename), false).unwrap();
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+            assert_eq!(typeinfo.to_c_declaration(name, &debugdata.types), expected);
+        }
+    }
+
+    #[test]
+    fn test_read_filedata() {
+        // the file is read into a buffer, as if it could not be mapped into memory
+        let filename = OsStr::new("fixtures/bin/storage_test.elf");
+        let filedata = read_filedata(open_file(filename).unwrap(), filename).unwrap();
+        assert!(matches!(filedata, FileData::Owned(_)));
+        let elffile = load_elf_file("storage_test.elf", &filedata).unwrap();
+        let debugdata = load_dwarf_from_object(
+            &elffile,
+            &elffile,
+            "storage_test.elf",
+            LoadOptions::default(),
+            &mut |_| {},
+        )
+        .unwrap();
+
+        let mapped_debugdata = DebugData::load_dwarf(filename, false).unwrap();
+        assert_eq!(debugdata.variables.len(), mapped_debugdata.variables.len());
+        for (name, varinfo_list) in &mapped_debugdata.variables {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            assert_eq!(varinfo.address, varinfo_list[0].address);
+        }
+        assert!(matches!(load_filedata(filename), Ok(FileData::Mapped(_))));
+    }
+
+    #[test]
+    fn test_artificial_variables() {
+        let filename = OsStr::new("fixtures/bin/artificial_test.elf");
+
+        // by default, compiler-generated variables are not loaded
+        let debugdata = DebugData::load_dwarf(filename, false).unwrap();
+        assert!(debugdata.variables.contains_key("calibration"));
+        assert!(!debugdata.variables.contains_key("__compound_literal.0"));
+        assert!(!debugdata.variables.contains_key("__guard_calibration"));
+        assert!(!debugdata.variables["calibration"][0].artificial);
+        // skipped artificial variables are not reported as dropped
+        assert!(debugdata.dropped_variables.is_empty());
+
+        let options = LoadOptions {
+            include_artificial: true,
+            ..LoadOptions::default()
+        };
+        let debugdata = DebugData::load_dwarf_with_options(filename, false, options).unwrap();
+        assert_eq!(debugdata.variables.len(), 3);
+        assert!(!debugdata.variables["calibration"][0].artificial);
+        let compound_literal = &debugdata.variables["__compound_literal.0"][0];
+        assert!(compound_literal.artificial);
+        assert_eq!(
+            compound_literal.address,
+            debugdata.variables["calibration"][0]
+                .address
+                .map(|address| address + 4)
+        );
+        assert!(debugdata.variables["__guard_calibration"][0].artificial);
+    }
+
+    #[test]
+    fn test_address_unit_bits() {
+        let filename = OsStr::new("fixtures/bin/storage_test.elf");
+        let debugdata = DebugData::load_dwarf(filename, false).unwrap();
+
+        // a word-addressed target with 16 bit addressing units: the byte addresses are twice the DWARF addresses
+        let options = LoadOptions {
+            address_unit_bits: 16,
+            ..LoadOptions::default()
+        };
+        let word_debugdata = DebugData::load_dwarf_with_options(filename, false, options).unwrap();
+        assert_eq!(debugdata.variables.len(), word_debugdata.variables.len());
+        for (name, varinfo_list) in &debugdata.variables {
+            let word_list = word_debugdata.variables.get(name).unwrap();
+            for (varinfo, word_varinfo) in varinfo_list.iter().zip(word_list) {
+                assert_eq!(
+                    word_varinfo.address,
+                    varinfo.address.map(|address| address * 2),
+                    "{name}"
+                );
+                // the scaled address is still inside the scaled section
+                assert_eq!(
+                    get_storage(&word_debugdata.sections, word_varinfo.address.unwrap()),
+                    varinfo.storage,
+                    "{name}"
+                );
+            }
+        }
+        for (name, (start, end)) in &debugdata.sections {
+            assert_eq!(
+                word_debugdata.sections.get(name),
+                Some(&(start * 2, end * 2))
+            );
+        }
+
+        // units that are not a multiple of 8 bits can't be converted to byte addresses
+        for address_unit_bits in [0, 12] {
+            let options = LoadOptions {
+                address_unit_bits,
+                ..LoadOptions::default()
+            };
+            let result = DebugData::load_dwarf_with_options(filename, false, options);
+            assert!(matches!(
+                result,
+                Err(DebugDataError::InvalidAddressUnit(bits)) if bits == address_unit_bits
+            ));
+        }
+    }
+
+    #[test]
+    fn test_section_names() {
+        let filename = OsStr::new("fixtures/bin/section_names_test.elf");
+        // .debug_info was renamed to .dbg_info_mcu, so the standard names don't find the debug info
+        let result = DebugData::load_dwarf(filename, false);
+        assert!(matches!(result, Err(DebugDataError::NoDebugInfo(_))));
+
+        let options = LoadOptions {
+            section_names: Some(|section| match section {
+                gimli::SectionId::DebugInfo => ".dbg_info_mcu",
+                _ => section.name(),
+            }),
+            ..LoadOptions::default()
+        };
+        let debugdata = DebugData::load_dwarf_with_options(filename, false, options).unwrap();
+        let expected =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/storage_test.elf"), false).unwrap();
+        assert_eq!(debugdata.variables.len(), expected.variables.len());
+        for (name, varinfo_list) in &expected.variables {
+            assert_eq!(
+                debugdata.variables.get(name).unwrap()[0].address,
+                varinfo_list[0].address
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_discrepancies() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/address_mismatch_test.elf"), false)
+                .unwrap();
+        // calib_local has no global symbol, so its address can't be checked
+        assert_eq!(
+            debugdata.address_discrepancies(),
+            [AddressDiscrepancy {
+                name: "calib_stale".to_string(),
+                unit_idx: 0,
+                dwarf_address: 0x402104,
+                symbol_address: 0x402004,
+            }]
+        );
+        // the variable is still loaded with the address from the debug info
+        assert_eq!(
+            debugdata.variables["calib_stale"][0].address,
+            Some(0x402104)
+        );
+
+        for filename in [
+            "fixtures/bin/storage_test.elf",
+            "fixtures/bin/tls_test.elf",
+            "fixtures/bin/qualified_name_test.elf",
+            "fixtures/bin/debugdata_gcc_dw64.elf",
+        ] {
+            let debugdata = DebugData::load_dwarf(OsStr::new(filename), false).unwrap();
+            assert!(
+                debugdata.address_discrepancies().is_empty(),
+                "{filename}: {:?}",
+                debugdata.address_discrepancies()
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_files() {
+        let load = |name: &str| {
+            DebugData::load_dwarf(OsStr::new(&format!("fixtures/bin/{name}")), false).unwrap()
+        };
+        let storage_data = load("storage_test.elf");
+        let list_data = load("linked_list_test.elf");
+        let storage_units = storage_data.unit_names.len();
+
+        let mut merged = load("storage_test.elf");
+        // both files are linked to the same addresses
+        assert!(
+            merged
+                .overlapping_sections(&list_data)
+                .contains(&(".data".to_string(), ".bss".to_string()))
+        );
+        merged.merge(load("linked_list_test.elf"));
+        assert_eq!(
+            merged.unit_names.len(),
+            storage_units + list_data.unit_names.len()
+        );
+        assert_eq!(
+            merged.variables.len(),
+            storage_data.variables.len() + list_data.variables.len()
+        );
+
+        // the symbols of both files resolve to the same address and type as in the separate files
+        for (name, data) in [("zero_init_var", &storage_data), ("node.next", &list_data)] {
+            let expected = crate::symbol::find_symbol(name, data).unwrap();
+            let symbol = crate::symbol::find_symbol(name, &merged).unwrap();
+            assert_eq!(symbol.address, expected.address);
+            assert_eq!(
+                symbol.typeinfo.get_size(),
+                expected.typeinfo.get_size(),
+                "{name}"
+            );
+            assert_eq!(symbol.typeinfo.name, expected.typeinfo.name, "{name}");
+        }
+        let node = crate::symbol::find_symbol("node", &merged).unwrap();
+        assert!(node.unit_idx >= storage_units);
+        assert_eq!(node.typeinfo.name.as_deref(), Some("Node"));
+
+        // a variable that is present in both files can be selected by its unit
+        merged.merge(load("storage_test.elf"));
+        assert_eq!(merged.variables["initialized_var"].len(), 2);
+        let second_unit = storage_units + list_data.unit_names.len();
+        let symbol =
+            crate::symbol::find_symbol(&format!("initialized_var{{Unit:{second_unit}}}"), &merged)
+                .unwrap();
+        assert_eq!(symbol.unit_idx, second_unit);
+    }
+
+    #[test]
+    fn test_variable_dbginfo_offset() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/storage_test.elf"), false).unwrap();
+        // the offsets of the DIEs, as shown by llvm-dwarfdump --debug-info
+        for (name, offset) in [
+            ("initialized_var", 0x2e),
+            ("zero_init_var", 0x4f),
+            ("read_only_var", 0x64),
+        ] {
+            assert_eq!(debugdata.variables[name][0].dbginfo_offset, offset);
+            let symbol = crate::symbol::find_symbol(name, &debugdata).unwrap();
+            assert_eq!(symbol.dbginfo_offset, offset);
+        }
+    }
+
+    #[test]
+    fn test_load_bias() {
+        const LOAD_BIAS: u64 = 0x5555_0000_0000;
+        for filename in ["fixtures/bin/storage_test.elf", "fixtures/bin/tls_test.elf"] {
+            let debugdata = DebugData::load_dwarf(OsStr::new(filename), false).unwrap();
+            let biased = DebugData::load_dwarf(OsStr::new(filename), false)
+                .unwrap()
+                .with_load_bias(LOAD_BIAS);
+
+            for (name, varinfo_list) in &debugdata.variables {
+                let biased_list = biased.variables.get(name).unwrap();
+                for (varinfo, biased_varinfo) in varinfo_list.iter().zip(biased_list) {
+                    if varinfo.is_tls() {
+                        assert_eq!(
+                            biased_varinfo.address, varinfo.address,
+                            "{filename}: {name}"
+                        );
+                    } else {
+                        assert_eq!(
+                            biased_varinfo.address,
+                            varinfo.address.map(|address| address + LOAD_BIAS),
+                            "{filename}: {name}"
+                        );
+                        // the shifted address is still inside its section
+                        assert_eq!(
+                            get_storage(&biased.sections, biased_varinfo.address.unwrap()),
+                            varinfo.storage,
+                            "{filename}: {name}"
+                        );
+                    }
+                }
+
+                let symbol = crate::symbol::find_symbol(name, &debugdata).unwrap();
+                let biased_symbol = crate::symbol::find_symbol(name, &biased).unwrap();
+                let expected_bias = if symbol.is_tls { 0 } else { LOAD_BIAS };
+                assert_eq!(biased_symbol.address, symbol.address + expected_bias);
+            }
+            for (name, (start, end)) in &debugdata.sections {
+                assert_eq!(
+                    biased.sections.get(name),
+                    Some(&(start + LOAD_BIAS, end + LOAD_BIAS))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decl_location() {
+        // Dwarf 4 indexes the file table starting from 1, Dwarf 5 starting from 0
+        for filename in [
+            "fixtures/bin/decl_test_dw4.elf",
+            "fixtures/bin/decl_test_dw5.elf",
+        ] {
+            let debugdata = DebugData::load_dwarf(OsStr::new(filename), true).unwrap();
+            let decl_location = |name| {
+                debugdata.variables.get(name).unwrap()[0]
+                    .decl_location
+                    .clone()
+                    .unwrap()
+            };
+            let (file, line) = decl_location("point_var");
+            assert!(file.ends_with("decl_test.c"), "{filename}: {file}");
+            assert_eq!(line, 13);
+            let (file, line) = decl_location("static_var");
+            assert!(file.ends_with("decl_test.c"), "{filename}: {file}");
+            assert_eq!(line, 16);
+            // the definition has its own location, separate from the declaration in the header
+            let (file, line) = decl_location("header_var");
+            assert!(file.ends_with("decl_test.c"), "{filename}: {file}");
+            assert_eq!(line, 6);
+
+            let symbol = crate::symbol::find_symbol("point_var.y", &debugdata).unwrap();
+            let (file, line) = symbol.decl_location().unwrap();
+            assert!(file.ends_with("decl_test.c"));
+            assert_eq!(line, 13);
+        }
+    }
+
+    #[test]
+    fn test_dwarf5_forms() {
+        // gcc stores the unit name as DW_FORM_line_strp and uses DW_FORM_implicit_const for many attributes
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/dwarf5_gcc_test.elf"), true).unwrap();
+        assert!(
+            debugdata.unit_names[0]
+                .as_ref()
+                .unwrap()
+                .ends_with("dwarf5_gcc_test.c")
+        );
+        assert!(debugdata.unit_producer(0).unwrap().starts_with("GNU C"));
+        for (idx, name) in ["a", "b", "c", "d"].iter().enumerate() {
+            let symbol =
+                crate::symbol::find_symbol(&format!("flags_var.{name}"), &debugdata).unwrap();
+            let DbgDataType::Bitfield {
+                basetype,
+                bit_offset,
+                bit_size,
+            } = &symbol.typeinfo.datatype
+            else {
+                panic!("flags_var.{name} is not a bitfield");
+            };
+            assert_eq!(*bit_offset as usize, idx);
+            assert_eq!(*bit_size, 1);
+            assert!(matches!(basetype.datatype, DbgDataType::Uint32));
+        }
+        let symbol = crate::symbol::find_symbol("pair_array[2].second", &debugdata).unwrap();
+        let pair_array = crate::symbol::find_symbol("pair_array", &debugdata).unwrap();
+        assert_eq!(symbol.address, pair_array.address + 2 * 8 + 4);
+        assert_eq!(symbol.decl_location().unwrap().1, 17);
+
+        // llvm references all strings through .debug_str_offsets and all addresses through .debug_addr
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/dwarf5_llvm_test.elf"), true).unwrap();
+        assert_eq!(
+            debugdata.unit_names[0].as_deref(),
+            Some("dwarf5_llvm_test.c")
+        );
+        assert_eq!(debugdata.unit_producer(0), Some("handwritten"));
+        let address = |name| debugdata.variables.get(name).unwrap()[0].address;
+        assert_eq!(address("counter"), Some(0x403000));
+        assert_eq!(address("staticvar"), Some(0x403004));
+        assert_eq!(address("structvar"), Some(0x403008));
+        let symbol = crate::symbol::find_symbol("structvar.b._3_", &debugdata).unwrap();
+        assert_eq!(symbol.address, 0x403008 + 4 + 3);
+        assert_eq!(symbol.typeinfo.name.as_deref(), Some("unsigned char"));
+    }
+
+    #[test]
+    fn test_self_referential_types() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/linked_list_test.elf"), true).unwrap();
+        let pointer_target = |name| {
+            let symbol = crate::symbol::find_symbol(name, &debugdata).unwrap();
+            let DbgDataType::Pointer(8, target) = symbol.typeinfo.datatype else {
+                panic!("{name} is not a pointer");
+            };
+            (symbol.typeinfo.name.clone(), target)
+        };
+
+        // the pointer refers back to the type of the variable, instead of expanding it again
+        let node = crate::symbol::find_symbol("node", &debugdata).unwrap();
+        let (name, target) = pointer_target("node.next");
+        assert_eq!(name.as_deref(), Some("Node"));
+        assert_eq!(target, node.typeinfo.dbginfo_offset);
+
+        let tree = crate::symbol::find_symbol("tree", &debugdata).unwrap();
+        let tree_type = tree.typeinfo.get_reference(&debugdata.types);
+        assert_eq!(tree_type.get_members().unwrap().len(), 3);
+        let (name, target) = pointer_target("tree.left");
+        assert_eq!(name.as_deref(), Some("tree_t"));
+        let target_type = debugdata.types.get(&target).unwrap();
+        assert!(matches!(
+            target_type.get_reference(&debugdata.types).datatype,
+            DbgDataType::Struct { .. }
+        ));
+        // the name of a const qualified target comes from the qualified type
+        let (name, _) = pointer_target("tree.parent");
+        assert_eq!(name.as_deref(), Some("Tree"));
+
+        // struct A and struct B point to each other; both are fully loaded
+        let (_, target) = pointer_target("a_var.b");
+        let b_type = debugdata.types.get(&target).unwrap();
+        assert_eq!(b_type.get_members().unwrap().len(), 2);
+        let a_var = crate::symbol::find_symbol("a_var", &debugdata).unwrap();
+        let (a_pointer, _) = b_type.get_members().unwrap().get("a").unwrap();
+        assert!(matches!(
+            a_pointer.datatype,
+            DbgDataType::Pointer(8, target) if target == a_var.typeinfo.dbginfo_offset
+        ));
+
+        let (name, target) = pointer_target("void_ptr");
+        assert_eq!(name.as_deref(), Some("void"));
+        assert_eq!(target, 0);
+    }
+
+    #[test]
+    fn test_symbol_table_address_fallback() {
+        let filedata = load_filedata(OsStr::new("fixtures/bin/symbol_table_test.elf")).unwrap();
+        let elffile = load_elf_file("symbol_table_test.elf", &filedata).unwrap();
+        let symbol_table = get_symbol_table(&elffile);
+        let relocated_sections = HashMap::new();
+        let dwarf = load_dwarf_sections(&elffile, &relocated_sections, None).unwrap();
+
+        // find the DIEs of the variables and resolve their addresses by name using only the symbol table
+        let mut found = Vec::new();
+        let mut units = dwarf.debug_info.units();
+        while let Ok(Some(unit)) = units.next() {
+            let abbreviations = unit.abbreviations(&dwarf.debug_abbrev).unwrap();
+            let mut entries = unit.entries(&abbreviations);
+            while let Ok(Some((_, entry))) = entries.next_dfs() {
+                if entry.tag() == gimli::constants::DW_TAG_variable
+                    && let Ok(name) = get_name_attribute(entry, &dwarf, &unit)
+                {
+                    let address =
+                        attributes::get_symbol_table_address(entry, &dwarf, &unit, &symbol_table);
+                    assert_eq!(address, symbol_table.get(&name).copied());
+                    assert!(address.is_some());
+                    found.push(name);
+                }
+            }
+        }
+        found.sort();
+        assert_eq!(
+            found,
+            ["common_var", "strong_var", "weak_array", "weak_var"]
+        );
+    }
+
+    #[test]
+    fn test_symbol_table_weak_common() {
+        let filedata = load_filedata(OsStr::new("fixtures/bin/symbol_table_test.elf")).unwrap();
+        let elffile = load_elf_file("symbol_table_test.elf", &filedata).unwrap();
+        let symbol_table = get_symbol_table(&elffile);
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/symbol_table_test.elf"), false).unwrap();
+
+        // weak symbols and symbols that were COMMON before linking are global definitions in the symbol table
+        for name in ["weak_var", "weak_array", "common_var"] {
+            let sym_address = *symbol_table.get(name).unwrap();
+            assert_ne!(sym_address, 0);
+            assert_eq!(
+                debugdata.variables.get(name).unwrap()[0].address,
+                Some(sym_address)
+            );
+        }
+
+        let sym_info = crate::symbol::find_symbol("weak_var", &debugdata).unwrap();
+        assert_eq!(sym_info.address, symbol_table["weak_var"]);
+    }
+
     #[test]
     fn test_load_mingw_exe() {
         // The file fixtures/bin/update_test.c was compiled with mingw64 gcc
@@ -811,4 +2552,1374 @@ mod test {
             assert!(debugdata_exe.variables.contains_key(var));
         }
     }
+
+    // collects the log records of the loader. The tests run in parallel, so each record is stored
+    // with the thread that emitted it, and a test only looks at the records of its own thread
+    struct TestLogger(std::sync::Mutex<Vec<(std::thread::ThreadId, log::Level, String)>>);
+
+    impl log::Log for TestLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata
+                .target()
+                .starts_with(module_path!().trim_end_matches("::test"))
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                let mut records = self.0.lock().unwrap();
+                records.push((
+                    std::thread::current().id(),
+                    record.level(),
+                    record.args().to_string(),
+                ));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static TEST_LOGGER: TestLogger = TestLogger(std::sync::Mutex::new(Vec::new()));
+    static TEST_LOGGER_INIT: std::sync::Once = std::sync::Once::new();
+
+    // install the test logger once, and get the messages that the current thread logs while running f
+    fn capture_log_messages(f: impl FnOnce()) -> Vec<(log::Level, String)> {
+        TEST_LOGGER_INIT.call_once(|| {
+            log::set_logger(&TEST_LOGGER).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        let thread_id = std::thread::current().id();
+        TEST_LOGGER
+            .0
+            .lock()
+            .unwrap()
+            .retain(|(id, _, _)| *id != thread_id);
+        f();
+        TEST_LOGGER
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(id, _, _)| *id == thread_id)
+            .map(|(_, level, message)| (*level, message.clone()))
+            .collect()
+    }
+
+    // check that two DebugData are identical, including the offsets of all types
+    fn assert_same_debug_data(left: &DebugData, right: &DebugData) {
+        assert_eq!(
+            format!("{:?}", left.variables),
+            format!("{:?}", right.variables)
+        );
+        assert_eq!(left.types.len(), right.types.len());
+        for (offset, typeinfo) in &left.types {
+            let other = right.types.get(offset).unwrap();
+            assert_eq!(format!("{typeinfo:?}"), format!("{other:?}"));
+        }
+        assert_eq!(left.typenames, right.typenames);
+        assert_eq!(left.demangled_names, right.demangled_names);
+        assert_eq!(left.unit_names, right.unit_names);
+        assert_eq!(left.sections, right.sections);
+        assert_eq!(left.type_scopes, right.type_scopes);
+        assert_eq!(
+            format!("{:?}", left.unit_info),
+            format!("{:?}", right.unit_info)
+        );
+    }
+
+    #[test]
+    fn test_load_incremental() {
+        let v1 = OsStr::new("fixtures/bin/incremental_test_v1.elf");
+        let v2 = OsStr::new("fixtures/bin/incremental_test_v2.elf");
+
+        // without a cache, all compile units are parsed
+        let (debugdata_v1, cache_v1) = DebugData::load_dwarf_incremental(v1, None, false).unwrap();
+        assert_eq!(cache_v1.parsed_units, vec![0, 1, 2]);
+        assert_same_debug_data(&debugdata_v1, &DebugData::load_dwarf(v1, false).unwrap());
+
+        // reloading the same file reuses everything
+        let (debugdata, cache) =
+            DebugData::load_dwarf_incremental(v1, Some(&cache_v1), false).unwrap();
+        assert!(cache.parsed_units.is_empty());
+        assert_same_debug_data(&debugdata, &debugdata_v1);
+
+        // only the changed unit is parsed; the unit after it is reused at its new offset
+        let full_v2 = DebugData::load_dwarf(v2, false).unwrap();
+        let (debugdata_v2, cache_v2) =
+            DebugData::load_dwarf_incremental(v2, Some(&cache_v1), false).unwrap();
+        assert_eq!(cache_v2.parsed_units, vec![1]);
+        assert_same_debug_data(&debugdata_v2, &full_v2);
+        assert!(debugdata_v2.variables.contains_key("added_counter"));
+        let symbol = crate::symbol::find_symbol("channels[1].scale", &debugdata_v2).unwrap();
+        assert!(matches!(symbol.typeinfo.datatype, DbgDataType::Double));
+
+        // switching back to the first version again only parses the changed unit
+        let (debugdata, cache) =
+            DebugData::load_dwarf_incremental(v1, Some(&cache_v2), false).unwrap();
+        assert_eq!(cache.parsed_units, vec![1]);
+        assert_same_debug_data(&debugdata, &debugdata_v1);
+        assert!(!debugdata.variables.contains_key("added_counter"));
+    }
+
+    #[test]
+    fn test_load_incremental_declaration() {
+        // struct Opaque is only declared in the unchanged unit. The second version of the other unit adds
+        // its definition, so the cached incomplete type must not be reused
+        let v1 = OsStr::new("fixtures/bin/incomplete_incremental_test_v1.elf");
+        let v2 = OsStr::new("fixtures/bin/incomplete_incremental_test_v2.elf");
+        let (debugdata_v1, cache_v1) = DebugData::load_dwarf_incremental(v1, None, false).unwrap();
+        assert_same_debug_data(&debugdata_v1, &DebugData::load_dwarf(v1, false).unwrap());
+
+        let full_v2 = DebugData::load_dwarf(v2, false).unwrap();
+        let (debugdata_v2, cache_v2) =
+            DebugData::load_dwarf_incremental(v2, Some(&cache_v1), false).unwrap();
+        assert_eq!(cache_v2.parsed_units, vec![0, 1]);
+        assert_same_debug_data(&debugdata_v2, &full_v2);
+        let opaque_ptr = crate::symbol::find_symbol("opaque_ptr", &debugdata_v2).unwrap();
+        let DbgDataType::Pointer(_, target) = opaque_ptr.typeinfo.datatype else {
+            panic!("the type of opaque_ptr is not a pointer");
+        };
+        assert!(matches!(
+            debugdata_v2.types.get(&target).unwrap().datatype,
+            DbgDataType::Struct { .. }
+        ));
+    }
+
+    #[test]
+    fn test_load_incremental_cross_unit() {
+        // LTO units and units that use type units refer to DIEs in other units, so they are always parsed.
+        // The same applies to units that only contain a declaration of a type, like the early unit of lto_test.elf.
+        // Either way, the result must be the same as with a full parse
+        for (filename, reparsed_units) in [
+            ("fixtures/bin/dedup_test.elf", vec![]),
+            ("fixtures/bin/lto_test.elf", vec![0, 1]),
+            ("fixtures/bin/debug_types_test.elf", vec![0]),
+            ("fixtures/bin/qualified_name_test.elf", vec![]),
+        ] {
+            let filename = OsStr::new(filename);
+            let full = DebugData::load_dwarf(filename, false).unwrap();
+            let (debugdata, cache) =
+                DebugData::load_dwarf_incremental(filename, None, false).unwrap();
+            assert_same_debug_data(&debugdata, &full);
+            let (debugdata, cache) =
+                DebugData::load_dwarf_incremental(filename, Some(&cache), false).unwrap();
+            assert_eq!(cache.parsed_units, reparsed_units, "{filename:?}");
+            assert_same_debug_data(&debugdata, &full);
+        }
+    }
+
+    #[test]
+    fn test_load_archive() {
+        for archive in [
+            "fixtures/bin/archive_test.a",
+            "fixtures/bin/archive_test_thin.a",
+        ] {
+            // the debug info of all members is merged; the member without debug info is skipped
+            let debugdata = DebugData::load_dwarf(OsStr::new(archive), false).unwrap();
+            assert_eq!(debugdata.variables.len(), 4);
+            assert_eq!(debugdata.unit_names.len(), 2);
+            assert!(debugdata.variables.get("nodebug_var").is_none());
+
+            // the types of both members are still found after merging
+            let varinfo = &debugdata.variables.get("pair_var").unwrap()[0];
+            assert_eq!(varinfo.unit_idx, 0);
+            let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+            assert_eq!(typeinfo.name.as_deref(), Some("Pair"));
+            let varinfo = &debugdata.variables.get("value_b").unwrap()[0];
+            assert_eq!(varinfo.unit_idx, 1);
+            let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+            assert!(matches!(typeinfo.datatype, DbgDataType::Double));
+            let symbol = crate::symbol::find_symbol("triple_var.values[2]", &debugdata).unwrap();
+            assert!(matches!(symbol.typeinfo.datatype, DbgDataType::Sint16));
+            assert_eq!(
+                symbol.address,
+                debugdata.variables.get("triple_var").unwrap()[0]
+                    .address
+                    .unwrap()
+                    + 6
+            );
+            assert_eq!(symbol.qualified_name(&debugdata), "Triple::values");
+        }
+
+        // load a single member
+        let debugdata = DebugData::load_dwarf_archive(
+            OsStr::new("fixtures/bin/archive_test.a"),
+            Some("archive_test_b.o"),
+            false,
+        )
+        .unwrap();
+        let mut names: Vec<_> = debugdata.variables.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["triple_var", "value_b"]);
+
+        let result = DebugData::load_dwarf_archive(
+            OsStr::new("fixtures/bin/archive_test.a"),
+            Some("missing.o"),
+            false,
+        );
+        assert!(matches!(
+            result,
+            Err(DebugDataError::ArchiveMemberNotFound { .. })
+        ));
+        let result = DebugData::load_dwarf_archive(
+            OsStr::new("fixtures/bin/archive_test.a"),
+            Some("archive_test_nodebug.o"),
+            false,
+        );
+        assert!(matches!(result, Err(DebugDataError::NoDebugInfo(_))));
+    }
+
+    #[test]
+    fn test_load_macho() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/macho_test.o"), false).unwrap();
+        assert_eq!(debugdata.variables.len(), 4);
+        assert!(debugdata.variables.get("counter").is_some());
+        assert!(debugdata.variables.get("structvar").is_some());
+        assert!(debugdata.variables.get("var_array").is_some());
+        assert!(debugdata.variables.get("staticvar").is_some());
+
+        for (_, varinfo) in &debugdata.variables {
+            assert!(debugdata.types.contains_key(&varinfo[0].typeref));
+        }
+
+        let varinfo = debugdata.variables.get("structvar").unwrap();
+        let typeinfo = debugdata.types.get(&varinfo[0].typeref).unwrap();
+        let DbgDataType::Struct { members, .. } = &typeinfo.datatype else {
+            panic!("Expected struct type, got {:?}", typeinfo.datatype);
+        };
+        assert!(matches!(
+            members.get("a"),
+            Some((
+                TypeInfo {
+                    datatype: DbgDataType::Sint32,
+                    ..
+                },
+                Some(0)
+            ))
+        ));
+
+        let varinfo = debugdata.variables.get("var_array").unwrap();
+        let typeinfo = debugdata.types.get(&varinfo[0].typeref).unwrap();
+        let DbgDataType::Array { dim, arraytype, .. } = &typeinfo.datatype else {
+            panic!("Expected array type, got {:?}", typeinfo.datatype);
+        };
+        assert_eq!(dim, &[33]);
+        assert!(matches!(
+            arraytype.datatype,
+            DbgDataType::Uint8 | DbgDataType::UChar
+        ));
+
+        // the Mach-O symbol names are matched with the variable names despite their leading underscore
+        let filedata = load_filedata(OsStr::new("fixtures/bin/macho_test.o")).unwrap();
+        let machofile = load_elf_file("macho_test.o", &filedata).unwrap();
+        let symbol_table = get_symbol_table(&machofile);
+        for name in ["counter", "structvar", "var_array"] {
+            let sym_address = symbol_table.get(name).unwrap();
+            assert_eq!(
+                Some(*sym_address),
+                debugdata.variables.get(name).unwrap()[0].address
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_dsym() {
+        // the thin executable and the bundle can both be used to load the debug info
+        for filename in ["fixtures/bin/dsym_test", "fixtures/bin/dsym_test.dSYM"] {
+            let debugdata = DebugData::load_dwarf(OsStr::new(filename), false).unwrap();
+            assert_eq!(debugdata.variables.len(), 4);
+            assert!(debugdata.variables.get("counter").is_some());
+            assert!(debugdata.variables.get("structvar").is_some());
+            assert!(debugdata.variables.get("var_array").is_some());
+            assert!(debugdata.variables.get("staticvar").is_some());
+            for (_, varinfo) in &debugdata.variables {
+                assert!(debugdata.types.contains_key(&varinfo[0].typeref));
+            }
+        }
+
+        let filedata = load_filedata(OsStr::new("fixtures/bin/dsym_test")).unwrap();
+        let executable = load_elf_file("dsym_test", &filedata).unwrap();
+        assert!(!has_debug_info(&executable, None));
+
+        // directories which are not a dSYM bundle are rejected
+        let result = DebugData::load_dwarf(OsStr::new("fixtures/a2l"), false);
+        let Err(err) = result else { panic!() };
+        assert!(matches!(err, DebugDataError::InvalidDsymBundle { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Error: fixtures/a2l is not a valid dSYM bundle: the directory Contents/Resources/DWARF is missing"
+        );
+    }
+
+    #[test]
+    fn test_load_lto() {
+        // the variables in the final unit only have a DW_AT_abstract_origin that refers to the compile-time units.
+        // lto_test_a.c only declares struct Record, its members are defined in lto_test_b.c
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/lto_test.elf"), false).unwrap();
+        assert!(debugdata.variables.get("record_ptr").is_some());
+
+        let varinfo = &debugdata.variables.get("record").unwrap()[0];
+        assert!(varinfo.address.is_some_and(|address| address != 0));
+        let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+        assert_eq!(typeinfo.name.as_deref(), Some("Record"));
+        let DbgDataType::Struct { size, members } = &typeinfo.datatype else {
+            panic!("record is not a struct: {typeinfo:?}");
+        };
+        assert_eq!(*size, 24);
+        let offsets: Vec<_> = members
+            .iter()
+            .map(|(name, (_, offset))| (name.as_str(), *offset))
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![("id", Some(0)), ("values", Some(4)), ("flags", Some(20))]
+        );
+    }
+
+    #[test]
+    fn test_deduplicate_types() {
+        // both units contain the types from dedup_test.h, as well as a different struct Local each
+        let mut debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/dedup_test.elf"), false).unwrap();
+        assert_eq!(debugdata.typenames.get("Shared").unwrap().len(), 1);
+        assert_eq!(debugdata.typenames.get("Inner").unwrap().len(), 1);
+        assert_eq!(debugdata.typenames.get("int").unwrap().len(), 1);
+        assert_eq!(debugdata.typenames.get("Local").unwrap().len(), 2);
+
+        let shared_a = &debugdata.variables.get("shared_a").unwrap()[0];
+        let shared_b = &debugdata.variables.get("shared_b").unwrap()[0];
+        assert_eq!(shared_a.typeref, shared_b.typeref);
+        assert_ne!(shared_a.unit_idx, shared_b.unit_idx);
+        let local_a = &debugdata.variables.get("local_a").unwrap()[0];
+        let local_b = &debugdata.variables.get("local_b").unwrap()[0];
+        assert_ne!(local_a.typeref, local_b.typeref);
+
+        // all references point to types that still exist
+        let typeinfo = debugdata.types.get(&shared_b.typeref).unwrap();
+        let DbgDataType::Struct { members, .. } = &typeinfo.datatype else {
+            panic!("shared_b is not a struct: {typeinfo:?}");
+        };
+        let (inner_type, _) = members.get("inner").unwrap();
+        let DbgDataType::TypeRef(inner_offset, _) = inner_type.datatype else {
+            panic!("member inner is not a TypeRef: {inner_type:?}");
+        };
+        assert_eq!(
+            Some(&inner_offset),
+            debugdata.typenames.get("Inner").unwrap().first()
+        );
+        assert!(debugdata.types.contains_key(&inner_offset));
+
+        // there is nothing left to merge
+        assert_eq!(debugdata.deduplicate_types(), 0);
+    }
+
+    #[test]
+    fn test_deduplicate_anonymous_types() {
+        // both units contain an anonymous struct { int a; short b; }, as well as a different anonymous struct each
+        let mut debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/anon_dedup_test.elf"), false).unwrap();
+        let typeref = |name: &str| debugdata.variables.get(name).unwrap()[0].typeref;
+        assert_eq!(typeref("anon_a"), typeref("anon_b"));
+        assert_ne!(typeref("other_a"), typeref("other_b"));
+        assert_ne!(typeref("anon_a"), typeref("other_a"));
+        assert!(debugdata.types.contains_key(&typeref("anon_b")));
+        let anonymous_structs = debugdata
+            .types
+            .values()
+            .filter(|typeinfo| {
+                typeinfo.name.is_none() && matches!(typeinfo.datatype, DbgDataType::Struct { .. })
+            })
+            .count();
+        assert_eq!(anonymous_structs, 3);
+
+        // there is nothing left to merge
+        assert_eq!(debugdata.deduplicate_types(), 0);
+    }
+
+    #[test]
+    fn test_load_debug_types() {
+        // the struct types are located in type units, and the variables refer to them by signature
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/debug_types_test.elf"), false).unwrap();
+        assert_eq!(debugdata.unit_names.len(), 3);
+        assert!(
+            debugdata.unit_names[0]
+                .as_deref()
+                .unwrap()
+                .ends_with("debug_types_test.c")
+        );
+
+        let varinfo = &debugdata.variables.get("shape").unwrap()[0];
+        let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+        assert_eq!(typeinfo.name.as_deref(), Some("Shape"));
+        let DbgDataType::Struct { size, members } = &typeinfo.datatype else {
+            panic!("shape is not a struct: {typeinfo:?}");
+        };
+        assert_eq!(*size, 28);
+        let offsets: Vec<_> = members
+            .iter()
+            .map(|(name, (_, offset))| (name.as_str(), *offset))
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![
+                ("origin", Some(0)),
+                ("corners", Some(8)),
+                ("color", Some(24))
+            ]
+        );
+
+        // the member origin refers to struct Point in another type unit through a DW_AT_signature
+        let (origin_type, _) = members.get("origin").unwrap();
+        let origin_type = origin_type.get_reference(&debugdata.types);
+        assert_eq!(origin_type.name.as_deref(), Some("Point"));
+        assert!(matches!(
+            &origin_type.datatype,
+            DbgDataType::Struct { members, .. } if members.len() == 2
+        ));
+
+        let varinfo = &debugdata.variables.get("point").unwrap()[0];
+        let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+        assert!(matches!(
+            &typeinfo.datatype,
+            DbgDataType::Struct { size: 8, .. }
+        ));
+        let varinfo = &debugdata.variables.get("counter").unwrap()[0];
+        let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+        assert!(matches!(&typeinfo.datatype, DbgDataType::Sint32));
+    }
+
+    #[test]
+    fn test_unit_producer_and_language() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/tls_test.elf"), false).unwrap();
+        assert!(debugdata.unit_producer(0).unwrap().starts_with("GNU C"));
+        assert_eq!(debugdata.unit_language(0), Some(SourceLanguage::C));
+        assert_eq!(debugdata.unit_producer(1), None);
+        assert_eq!(debugdata.unit_language(1), None);
+
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/qualified_name_test.elf"), false)
+                .unwrap();
+        assert!(debugdata.unit_producer(0).unwrap().starts_with("GNU C++"));
+        assert_eq!(debugdata.unit_language(0), Some(SourceLanguage::Cpp));
+
+        // type units don't have a producer or language of their own
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/debug_types_test.elf"), false).unwrap();
+        assert_eq!(debugdata.unit_info.len(), debugdata.unit_names.len());
+        assert_eq!(debugdata.unit_language(0), Some(SourceLanguage::C));
+        assert_eq!(debugdata.unit_language(1), None);
+    }
+
+    #[test]
+    fn test_unit_dwarf_version() {
+        let load = |name: &str| {
+            DebugData::load_dwarf(OsStr::new(&format!("fixtures/bin/{name}")), false).unwrap()
+        };
+        let debugdata = load("bitfield_be_dwarf3_test.o");
+        assert_eq!(debugdata.unit_dwarf_version(0), 3);
+        let debugdata = load("decl_test_dw4.elf");
+        assert_eq!(debugdata.unit_dwarf_version(0), 4);
+        let debugdata = load("decl_test_dw5.elf");
+        assert_eq!(debugdata.unit_dwarf_version(0), 5);
+        assert_eq!(debugdata.unit_dwarf_version(debugdata.unit_info.len()), 0);
+
+        // type units report the version of their own header
+        let debugdata = load("debug_types_test.elf");
+        assert_eq!(debugdata.unit_dwarf_version(1), 4);
+    }
+
+    #[test]
+    fn test_bit_int_types() {
+        // base types with a DW_AT_bit_size are stored in the smallest integer type that can hold them
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/bitint_test.elf"), false).unwrap();
+        let get_type = |name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            debugdata.types.get(&varinfo.typeref).unwrap()
+        };
+        let bitint12 = get_type("bitint12");
+        assert!(matches!(bitint12.datatype, DbgDataType::Sint16));
+        assert_eq!(bitint12.get_size(), 2);
+        let ubitint7 = get_type("ubitint7");
+        assert!(matches!(ubitint7.datatype, DbgDataType::Uint8));
+        assert_eq!(ubitint7.get_size(), 1);
+        let bitint24 = get_type("bitint24");
+        assert!(matches!(bitint24.datatype, DbgDataType::Sint32));
+        assert_eq!(bitint24.get_size(), 4);
+        let ubitint40 = get_type("ubitint40");
+        assert!(matches!(ubitint40.datatype, DbgDataType::Uint64));
+        assert_eq!(ubitint40.get_size(), 8);
+    }
+
+    #[test]
+    fn test_atomic_types() {
+        // DW_TAG_atomic_type is transparent, like const and volatile
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/atomic_test.elf"), false).unwrap();
+        let get_type = |name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            debugdata.types.get(&varinfo.typeref).unwrap()
+        };
+        assert!(matches!(
+            get_type("atomic_int").datatype,
+            DbgDataType::Sint32
+        ));
+        assert!(matches!(
+            get_type("atomic_double").datatype,
+            DbgDataType::Double
+        ));
+
+        for varname in ["counters", "atomic_counters"] {
+            let typeinfo = get_type(varname);
+            assert_eq!(typeinfo.name.as_deref(), Some("Counters"));
+            let DbgDataType::Struct { members, .. } = &typeinfo.datatype else {
+                panic!("{varname} is not a struct: {typeinfo:?}");
+            };
+            let (hits_type, hits_offset) = members.get("hits").unwrap();
+            assert!(matches!(hits_type.datatype, DbgDataType::Uint32));
+            assert_eq!(*hits_offset, Some(0));
+            let (values_type, values_offset) = members.get("values").unwrap();
+            let DbgDataType::Array { dim, arraytype, .. } = &values_type.datatype else {
+                panic!("values is not an array: {values_type:?}");
+            };
+            assert_eq!(dim, &vec![3]);
+            assert!(matches!(arraytype.datatype, DbgDataType::Sint16));
+            assert_eq!(*values_offset, Some(4));
+        }
+    }
+
+    #[test]
+    fn test_wide_base_types() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/wide_types_test.elf"), false).unwrap();
+        let get_type = |name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            debugdata.types.get(&varinfo.typeref).unwrap()
+        };
+        assert!(matches!(
+            get_type("sint128_var").datatype,
+            DbgDataType::Sint128
+        ));
+        assert!(matches!(
+            get_type("uint128_var").datatype,
+            DbgDataType::Uint128
+        ));
+        assert!(matches!(
+            get_type("long_double_var").datatype,
+            DbgDataType::LongDouble(16)
+        ));
+        assert!(matches!(
+            get_type("float128_var").datatype,
+            DbgDataType::Float128
+        ));
+        assert!(matches!(
+            get_type("sint64_var").datatype,
+            DbgDataType::Sint64
+        ));
+        assert!(matches!(
+            get_type("uint64_var").datatype,
+            DbgDataType::Uint64
+        ));
+        for name in [
+            "sint128_var",
+            "uint128_var",
+            "long_double_var",
+            "float128_var",
+        ] {
+            assert_eq!(get_type(name).get_size(), 16);
+        }
+        assert_eq!(get_type("sint64_var").get_size(), 8);
+
+        let wide_type = get_type("wide_var");
+        assert_eq!(wide_type.get_size(), 48);
+        let DbgDataType::Struct { members, .. } = &wide_type.datatype else {
+            panic!("wide_var is not a struct: {wide_type:?}");
+        };
+        let (value_type, value_offset) = members.get("value").unwrap();
+        assert!(matches!(value_type.datatype, DbgDataType::Uint128));
+        assert_eq!(*value_offset, Some(16));
+        let (scale_type, scale_offset) = members.get("scale").unwrap();
+        assert!(matches!(scale_type.datatype, DbgDataType::LongDouble(16)));
+        assert_eq!(*scale_offset, Some(32));
+    }
+
+    #[test]
+    fn test_bool_and_char_types() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/char_types_test.elf"), false).unwrap();
+        let get_type = |name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            debugdata.types.get(&varinfo.typeref).unwrap()
+        };
+        assert!(matches!(get_type("b").datatype, DbgDataType::Bool));
+        // plain char is signed on x86_64
+        assert!(matches!(get_type("c").datatype, DbgDataType::SChar));
+        assert!(matches!(get_type("sc").datatype, DbgDataType::SChar));
+        assert!(matches!(get_type("uc").datatype, DbgDataType::UChar));
+        for name in ["b", "c", "sc", "uc"] {
+            assert_eq!(get_type(name).get_size(), 1);
+        }
+        assert_eq!(get_type("b").name.as_deref(), Some("_Bool"));
+
+        let text_type = get_type("text");
+        let DbgDataType::Array { arraytype, .. } = &text_type.datatype else {
+            panic!("text is not an array: {text_type:?}");
+        };
+        assert!(matches!(arraytype.datatype, DbgDataType::SChar));
+    }
+
+    #[test]
+    fn test_declared_byte_size() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/padded_test.elf"), false).unwrap();
+        let get_type = |name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            debugdata.types.get(&varinfo.typeref).unwrap()
+        };
+
+        // the byte size of the struct is propagated through the typedef
+        let padded_type = get_type("padded_var");
+        assert_eq!(padded_type.byte_size, Some(16));
+        assert_eq!(padded_type.get_size(), 16);
+        let members = padded_type.get_members().unwrap();
+        let member_sizes: u64 = members.values().map(|(m, _)| m.get_size()).sum();
+        assert!(member_sizes < padded_type.get_size());
+
+        let array_type = get_type("padded_array");
+        assert_eq!(array_type.get_size(), 64);
+        assert_eq!(array_type.element_stride(), Some((16, 16)));
+    }
+
+    #[test]
+    fn test_struct_holes() {
+        let get_type = |debugdata: &DebugData, name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            debugdata
+                .types
+                .get(&varinfo.typeref)
+                .unwrap()
+                .get_reference(&debugdata.types)
+                .clone()
+        };
+
+        // tag is followed by 3 bytes of padding, and the union raw by 4 bytes before the double value
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/layout_test.elf"), false).unwrap();
+        let layout_type = get_type(&debugdata, "layout_var");
+        assert_eq!(layout_type.holes(), Some(vec![(1, 3), (20, 4)]));
+        assert_eq!(layout_type.trailing_padding(), None);
+        assert_eq!(get_type(&debugdata, "scalar_var").holes(), None);
+
+        // the struct is aligned to 16 bytes, so most of it is trailing padding
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/padded_test.elf"), false).unwrap();
+        let padded_type = get_type(&debugdata, "padded_var");
+        assert_eq!(padded_type.holes(), Some(vec![(1, 1)]));
+        assert_eq!(padded_type.trailing_padding(), Some((4, 12)));
+
+        // the bitfields a, b and c fill their storage, while d and e only use 2 of the last 4 bytes
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/bitfield_be_dwarf3_test.o"), false)
+                .unwrap();
+        let flags_type = get_type(&debugdata, "flags");
+        assert_eq!(flags_type.holes(), Some(vec![]));
+        assert_eq!(flags_type.trailing_padding(), Some((6, 2)));
+    }
+
+    #[test]
+    fn test_load_from_bytes() {
+        let data = std::fs::read("fixtures/bin/symbol_table_test.elf").unwrap();
+        let debugdata = DebugData::load_dwarf_from_bytes(&data, false).unwrap();
+        drop(data);
+
+        let debugdata_file =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/symbol_table_test.elf"), false).unwrap();
+        assert_eq!(debugdata.variables.len(), debugdata_file.variables.len());
+        for (name, varinfo) in &debugdata_file.variables {
+            assert_eq!(
+                debugdata.variables.get(name).unwrap()[0].address,
+                varinfo[0].address
+            );
+        }
+
+        let result = load_dwarf_from_bytes(b"not an elf file", false);
+        let Err(err) = result else { panic!() };
+        assert!(matches!(err, DebugDataError::Parse(_)));
+        assert!(
+            err.to_string()
+                .starts_with("Error: Failed to parse file '<memory>'")
+        );
+    }
+
+    #[test]
+    fn test_load_from_reader() {
+        let data = std::fs::read("fixtures/bin/storage_test.elf").unwrap();
+        let debugdata =
+            DebugData::load_dwarf_from_reader(std::io::Cursor::new(data), false).unwrap();
+        let debugdata_file =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/storage_test.elf"), false).unwrap();
+        assert_eq!(debugdata.variables.len(), debugdata_file.variables.len());
+        for (name, varinfo) in &debugdata_file.variables {
+            assert_eq!(debugdata.variables[name][0].address, varinfo[0].address);
+        }
+
+        struct BrokenPipe;
+        impl std::io::Read for BrokenPipe {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+            }
+        }
+        let result = load_dwarf_from_reader(BrokenPipe, false);
+        let Err(err) = result else { panic!() };
+        assert!(matches!(err, DebugDataError::Io { .. }));
+        assert!(
+            err.to_string()
+                .starts_with("Error: Failed to read the input stream")
+        );
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let result = DebugData::load_dwarf(OsStr::new("fixtures/bin/does_not_exist.elf"), false);
+        let Err(err) = result else { panic!() };
+        assert!(matches!(err, DebugDataError::Io { .. }));
+        assert!(std::error::Error::source(&err).is_some());
+        assert!(
+            err.to_string()
+                .starts_with("Error: could not open file fixtures/bin/does_not_exist.elf: ")
+        );
+
+        // a file that is not an object file
+        let result = DebugData::load_dwarf(OsStr::new("fixtures/bin/quiet_test.c"), false);
+        assert!(matches!(result, Err(DebugDataError::Parse(_))));
+    }
+
+    #[test]
+    fn test_log_messages() {
+        let records = capture_log_messages(|| {
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/quiet_test.elf"), false).unwrap();
+        });
+        assert!(
+            records
+                .iter()
+                .any(|(level, message)| *level == log::Level::Trace
+                    && message.starts_with("Symbol: g_fsmRunnable, Address: 0x"))
+        );
+    }
+
+    #[test]
+    fn test_verbose_log_level() {
+        // verbose raises the log level filter, but never lowers it
+        let data = std::fs::read("fixtures/bin/quiet_test.elf").unwrap();
+        load_dwarf_from_bytes(&data, true).unwrap();
+        assert!(log::max_level() >= log::LevelFilter::Debug);
+        load_dwarf_from_bytes(&data, false).unwrap();
+        assert!(log::max_level() >= log::LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_member_location_expressions() {
+        // strict Dwarf 2 has no constant form for DW_AT_data_member_location, gcc emits DW_OP_plus_uconst instead
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/member_location_test.elf"), true)
+                .unwrap();
+        let outer_var = crate::symbol::find_symbol("outer_var", &debugdata).unwrap();
+        for (name, offset) in [
+            ("flag", 0),
+            ("count", 4),
+            ("inner", 8),
+            ("inner.a", 8),
+            ("inner.b", 10),
+            ("value", 16),
+        ] {
+            let symbol =
+                crate::symbol::find_symbol(&format!("outer_var.{name}"), &debugdata).unwrap();
+            assert_eq!(
+                symbol.address,
+                outer_var.address + offset,
+                "outer_var.{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let mut events = Vec::new();
+        let debugdata = DebugData::load_dwarf_with_progress(
+            OsStr::new("fixtures/bin/incremental_test_v1.elf"),
+            false,
+            |event| events.push(event),
+        )
+        .unwrap();
+        let unit_events: Vec<_> = events
+            .iter()
+            .filter(|event| event.phase == LoadPhase::Variables)
+            .collect();
+        assert_eq!(unit_events.len(), 3);
+        assert_eq!(unit_events.len(), debugdata.unit_names.len());
+        for (idx, event) in unit_events.iter().enumerate() {
+            assert_eq!(event.done, idx + 1);
+            assert_eq!(event.total, 3);
+        }
+        // the types are loaded after all units were read, and the progress is reported for each variable
+        let type_events = &events[unit_events.len()..];
+        assert_eq!(type_events.len(), debugdata.variables.len());
+        assert!(type_events.iter().all(
+            |event| event.phase == LoadPhase::Types && event.total == debugdata.variables.len()
+        ));
+        assert_eq!(type_events.last().unwrap().done, debugdata.variables.len());
+    }
+
+    #[test]
+    fn test_incomplete_types() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/incomplete_test.elf"), false).unwrap();
+
+        // class Widget is only declared in the unit of widget_obj, but it is defined in another unit
+        let widget_obj = crate::symbol::find_symbol("widget_obj.value", &debugdata).unwrap();
+        assert_eq!(widget_obj.address, 0x403008);
+        assert!(matches!(widget_obj.typeinfo.datatype, DbgDataType::Sint32));
+
+        // there is no definition of class Gadget, but gadget_obj is still available
+        let gadget_obj = crate::symbol::find_symbol("gadget_obj", &debugdata).unwrap();
+        assert_eq!(gadget_obj.address, 0x403010);
+        let DbgDataType::Incomplete { name } = &gadget_obj.typeinfo.datatype else {
+            panic!("the type of gadget_obj is not incomplete");
+        };
+        assert_eq!(name, "Gadget");
+        assert_eq!(gadget_obj.typeinfo.get_size(), 0);
+        assert!(crate::symbol::find_symbol("gadget_obj.count", &debugdata).is_err());
+    }
+
+    #[test]
+    fn test_cross_unit_type_references() {
+        // record_b and record_ptr refer to struct Record in the other unit with DW_FORM_ref_addr
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/cross_unit_test.elf"), false).unwrap();
+        let record_a = &debugdata.variables.get("record_a").unwrap()[0];
+        let record_b = &debugdata.variables.get("record_b").unwrap()[0];
+        assert_ne!(record_a.unit_idx, record_b.unit_idx);
+        assert_eq!(record_a.typeref, record_b.typeref);
+
+        for (name, offset) in [("id", 0), ("flags[1]", 6), ("inner.c", 8), ("inner.v", 12)] {
+            let symbol =
+                crate::symbol::find_symbol(&format!("record_b.{name}"), &debugdata).unwrap();
+            assert_eq!(symbol.address, 0x403000 + offset, "record_b.{name}");
+        }
+        let inner_v = crate::symbol::find_symbol("record_b.inner.v", &debugdata).unwrap();
+        assert!(matches!(inner_v.typeinfo.datatype, DbgDataType::Sint32));
+
+        let record_ptr = crate::symbol::find_symbol("record_ptr", &debugdata).unwrap();
+        let DbgDataType::Pointer(8, target) = record_ptr.typeinfo.datatype else {
+            panic!("record_ptr is not a pointer");
+        };
+        assert_eq!(target, record_a.typeref);
+        let target_type = debugdata.types.get(&target).unwrap();
+        assert_eq!(target_type.name.as_deref(), Some("Record"));
+        assert_eq!(target_type.get_members().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_load_filtered() {
+        // struct Record is defined in cross_unit_a.c, which is not loaded
+        let debugdata = DebugData::load_dwarf_filtered(
+            OsStr::new("fixtures/bin/cross_unit_test.elf"),
+            &["cross_unit_b.c"],
+            false,
+        )
+        .unwrap();
+        assert!(!debugdata.variables.contains_key("record_a"));
+        assert!(debugdata.variables.contains_key("record_b"));
+        assert!(debugdata.variables.contains_key("record_ptr"));
+        let inner_v = crate::symbol::find_symbol("record_b.inner.v", &debugdata).unwrap();
+        assert_eq!(inner_v.address, 0x403000 + 12);
+        assert_eq!(debugdata.unit_names.len(), 2);
+
+        // a simple unit name also selects the unit
+        let debugdata = DebugData::load_dwarf_filtered(
+            OsStr::new("fixtures/bin/cross_unit_test.elf"),
+            &["CROSS_UNIT_A_C"],
+            false,
+        )
+        .unwrap();
+        let names: Vec<&String> = debugdata.variables.keys().collect();
+        assert_eq!(names, vec!["record_a"]);
+    }
+
+    #[test]
+    fn test_union_discriminant() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/tagged_union_test.elf"), false).unwrap();
+        let event = crate::symbol::find_symbol("event", &debugdata).unwrap();
+        let DbgDataType::Union {
+            members,
+            discriminant: Some((discr_name, variants)),
+            ..
+        } = &event.typeinfo.datatype
+        else {
+            panic!("event is not a union with a tag: {}", event.typeinfo);
+        };
+        assert_eq!(discr_name, "type");
+        let names: Vec<&str> = members.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["type", "key", "mouse", "raw"]);
+        let expected = vec![
+            (Some(1), "key".to_string()),
+            (Some(2), "mouse".to_string()),
+            (Some(3), "mouse".to_string()),
+            (None, "raw".to_string()),
+        ];
+        assert_eq!(variants, &expected);
+
+        let mouse_y = crate::symbol::find_symbol("event.mouse.y", &debugdata).unwrap();
+        assert_eq!(mouse_y.address, event.address + 6);
+    }
+
+    #[test]
+    fn test_overlapping_variables() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/overlay_test.elf"), false).unwrap();
+        let overlay_a = crate::symbol::find_symbol("overlay_a", &debugdata).unwrap();
+        let overlay_b = crate::symbol::find_symbol("overlay_b", &debugdata).unwrap();
+        let after_overlay = crate::symbol::find_symbol("after_overlay", &debugdata).unwrap();
+        assert_eq!(overlay_a.address_range(), (0x402000, 0x402004));
+        assert_eq!(overlay_b.address_range(), (0x402000, 0x402002));
+        assert_eq!(after_overlay.address_range(), (0x402004, 0x402008));
+
+        // after_overlay starts at the end of overlay_a, so it doesn't overlap
+        assert_eq!(
+            debugdata.overlapping_variables(),
+            vec![("overlay_a".to_string(), "overlay_b".to_string())]
+        );
+
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/storage_test.elf"), false).unwrap();
+        assert!(debugdata.overlapping_variables().is_empty());
+    }
+
+    #[test]
+    fn test_const_value() {
+        // the const variables are optimized away, so they only have a DW_AT_const_value
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/const_value_test.elf"), false).unwrap();
+        let const_value = |name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            assert_eq!(varinfo.address, None, "{name}");
+            varinfo.const_value.clone()
+        };
+        assert_eq!(const_value("limit"), Some(ConstValue::Int(42)));
+        assert_eq!(const_value("offset_neg"), Some(ConstValue::Int(-3)));
+        assert_eq!(const_value("mask"), Some(ConstValue::Uint(0xfff0)));
+        assert_eq!(const_value("gain"), Some(ConstValue::Float(1.5)));
+        assert_eq!(
+            const_value("label"),
+            Some(ConstValue::Bytes(b"abc".to_vec()))
+        );
+        assert_eq!(
+            const_value("pair"),
+            Some(ConstValue::Bytes(vec![1, 0, 2, 0]))
+        );
+        let counter = &debugdata.variables.get("counter").unwrap()[0];
+        assert!(counter.address.is_some());
+        assert_eq!(counter.const_value, None);
+        assert!(debugdata.dropped_variables.is_empty());
+
+        let limit = crate::symbol::find_symbol("limit", &debugdata).unwrap();
+        assert_eq!(limit.const_value, Some(&ConstValue::Int(42)));
+        assert!(matches!(
+            crate::symbol::find_symbol("pair.b", &debugdata),
+            Err(DebugDataError::ConstantWithoutAddress(_))
+        ));
+        assert!(matches!(
+            crate::symbol::find_symbol_with_address("limit", &debugdata),
+            Err(DebugDataError::ConstantWithoutAddress(_))
+        ));
+        // constants have no address, so they don't contain any symbols
+        let names: Vec<String> = debugdata.iter(false).map(|symbol| symbol.name).collect();
+        assert_eq!(names, vec!["counter"]);
+    }
+
+    #[test]
+    fn test_endianness() {
+        for filename in ["storage_test.elf", "const_value_test.elf"] {
+            let path = format!("fixtures/bin/{filename}");
+            let debugdata = DebugData::load_dwarf(OsStr::new(&path), false).unwrap();
+            assert_eq!(debugdata.endianness(), object::Endianness::Little);
+        }
+        for filename in ["endian_be_test.o", "bitfield_be_test.o"] {
+            let path = format!("fixtures/bin/{filename}");
+            let debugdata = DebugData::load_dwarf(OsStr::new(&path), false).unwrap();
+            assert_eq!(debugdata.endianness(), object::Endianness::Big);
+        }
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/endian_be_test.o"), false).unwrap();
+        assert!(debugdata.variables.contains_key("be_value"));
+    }
+
+    #[test]
+    fn test_architecture() {
+        for (filename, architecture, pointer_size) in [
+            ("arch_arm_test.o", Architecture::Arm, 4),
+            ("storage_test.elf", Architecture::X86_64, 8),
+            ("debugdata_gcc_dw64.elf", Architecture::I386, 4),
+            ("endian_be_test.o", Architecture::PowerPc64, 8),
+        ] {
+            let path = format!("fixtures/bin/{filename}");
+            let debugdata = DebugData::load_dwarf(OsStr::new(&path), false).unwrap();
+            assert_eq!(debugdata.architecture(), architecture, "{filename}");
+            assert_eq!(debugdata.pointer_size(), pointer_size, "{filename}");
+        }
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/arch_arm_test.o"), false).unwrap();
+        assert!(debugdata.variables.contains_key("arm_value"));
+    }
+
+    #[test]
+    fn test_dynamic_array_dimensions() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/dynamic_array_test.elf"), false)
+                .unwrap();
+        let array_info = |name: &str| {
+            let varinfo = &debugdata.variables.get(name).unwrap()[0];
+            let typeinfo = debugdata.types.get(&varinfo.typeref).unwrap();
+            let DbgDataType::Array {
+                size,
+                dim,
+                dynamic_dims,
+                ..
+            } = &typeinfo.datatype
+            else {
+                panic!("{name} is not an array");
+            };
+            (*size, dim.clone(), dynamic_dims.clone())
+        };
+        assert_eq!(array_info("samples"), (0, vec![0, 4], vec![0]));
+        assert_eq!(array_info("matrix"), (0, vec![3, 0], vec![1]));
+        assert_eq!(array_info("fixed"), (32, vec![2, 4], vec![]));
+
+        let samples = &debugdata.variables.get("samples").unwrap()[0];
+        let typeinfo = debugdata.types.get(&samples.typeref).unwrap();
+        assert_eq!(
+            typeinfo.to_c_declaration("samples", &debugdata.types),
+            "int32_t samples[][4]"
+        );
+
+        // the array itself can be used, but not its elements
+        let symbol = crate::symbol::find_symbol("samples", &debugdata).unwrap();
+        assert_eq!(symbol.address, 0x402004);
+        assert!(matches!(
+            crate::symbol::find_symbol("samples[1][2]", &debugdata),
+            Err(DebugDataError::DynamicArrayDimension { dimension: 0, .. })
+        ));
+        assert!(matches!(
+            crate::symbol::find_symbol("matrix[1][2]", &debugdata),
+            Err(DebugDataError::DynamicArrayDimension { dimension: 1, .. })
+        ));
+        let symbol = crate::symbol::find_symbol("fixed[1][2]", &debugdata).unwrap();
+        assert_eq!(symbol.address, 0x402044 + 6 * 4);
+    }
+
+    #[test]
+    fn test_const_typedef_pointer() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/typedef_pointer_test.elf"), false)
+                .unwrap();
+        let symbol_info = |name: &str| {
+            let symbol = crate::symbol::find_symbol(name, &debugdata).unwrap();
+            (
+                symbol.typeinfo.name.clone(),
+                symbol.is_const,
+                symbol.is_pointer(&debugdata),
+            )
+        };
+        assert_eq!(
+            symbol_info("handle"),
+            (Some("SHandle".to_string()), true, true)
+        );
+        assert_eq!(
+            symbol_info("plain_handle"),
+            (Some("SHandle".to_string()), false, true)
+        );
+        // pointers without a typedef are named after their target
+        assert_eq!(
+            symbol_info("ptr_to_const"),
+            (Some("S".to_string()), false, true)
+        );
+        assert_eq!(
+            symbol_info("limit"),
+            (Some("CInt".to_string()), true, false)
+        );
+        assert_eq!(
+            symbol_info("s_instance.x"),
+            (Some("int".to_string()), false, false)
+        );
+
+        let handle = crate::symbol::find_symbol("handle", &debugdata).unwrap();
+        assert_eq!(handle.storage, Some(Storage::ReadOnly));
+        assert_eq!(
+            handle.a2l_kind(&debugdata),
+            crate::symbol::A2lKind::Characteristic
+        );
+    }
+
+    #[test]
+    fn test_external_variables() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/external_test.elf"), false).unwrap();
+        let is_external = |name: &str| debugdata.variables.get(name).unwrap()[0].is_external;
+        assert!(is_external("exported_value"));
+        assert!(is_external("declared_value"));
+        assert!(!is_external("internal_value"));
+        assert!(!is_external("call_count"));
+
+        let symbol = crate::symbol::find_symbol("exported_value", &debugdata).unwrap();
+        assert!(symbol.is_external);
+        let symbol = crate::symbol::find_symbol("internal_value", &debugdata).unwrap();
+        assert!(!symbol.is_external);
+    }
+
+    #[test]
+    fn test_linkage_name_address() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/linkage_name_test.elf"), false).unwrap();
+        let scale_factor = &debugdata.variables.get("scale_factor").unwrap()[0];
+        assert_eq!(scale_factor.address, Some(0x402000));
+        assert_eq!(scale_factor.namespaces, ["cfg"]);
+        // the linkage name is taken from the declaration of the static member
+        let upper = &debugdata.variables.get("upper").unwrap()[0];
+        assert_eq!(upper.address, Some(0x402004));
+        assert_eq!(upper.namespaces, ["Limits", "cfg"]);
+        let symbol = crate::symbol::find_symbol("cfg::Limits::upper", &debugdata).unwrap();
+        assert_eq!(symbol.address, 0x402004);
+
+        // the plain name is not looked up in the symbol table
+        assert!(!debugdata.variables.contains_key("plain_only"));
+        let dropped: Vec<&str> = debugdata
+            .dropped_variables
+            .iter()
+            .map(|dropped| dropped.name.as_str())
+            .collect();
+        assert_eq!(dropped, vec!["plain_only"]);
+    }
+
+    #[test]
+    fn test_register_relative_location() {
+        let evaluate = |bytes: &[u8], address_size: u8| {
+            let encoding = gimli::Encoding {
+                format: gimli::Format::Dwarf32,
+                version: 4,
+                address_size,
+            };
+            let expression = gimli::Expression(EndianSlice::new(bytes, RunTimeEndian::Little));
+            attributes::evaluate_register_relative(expression, encoding)
+        };
+        // DW_OP_bregx 13 0x100
+        assert_eq!(evaluate(&[0x92, 13, 0x80, 0x02], 8), Some((13, 0x100)));
+        // DW_OP_breg5 -16; the offset is sign-extended from the address size
+        assert_eq!(evaluate(&[0x75, 0x70], 4), Some((5, -16)));
+        assert_eq!(evaluate(&[0x75, 0x70], 8), Some((5, -16)));
+        // DW_OP_breg1 8; DW_OP_plus_uconst 4
+        assert_eq!(evaluate(&[0x71, 0x08, 0x23, 0x04], 8), Some((1, 12)));
+        // DW_OP_addr 0x1000 is not register-relative
+        assert_eq!(evaluate(&[0x03, 0x00, 0x10, 0, 0, 0, 0, 0, 0], 8), None);
+        // DW_OP_breg0 0; DW_OP_breg1 0; DW_OP_plus depends on two registers
+        assert_eq!(evaluate(&[0x70, 0x00, 0x71, 0x00, 0x22], 8), None);
+        // DW_OP_reg3: the value is in the register itself, not in memory
+        assert_eq!(evaluate(&[0x53], 8), None);
+    }
+
+    #[test]
+    fn test_register_relative_variable() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/register_relative_test.elf"), false)
+                .unwrap();
+        let overlay_table = &debugdata.variables.get("overlay_table").unwrap()[0];
+        assert_eq!(overlay_table.address, None);
+        assert_eq!(
+            overlay_table.location_kind,
+            LocationKind::RegisterRelative {
+                register: 13,
+                offset: 0x100
+            }
+        );
+        assert!(matches!(
+            crate::symbol::find_symbol("overlay_table[1]", &debugdata),
+            Err(DebugDataError::RegisterRelativeLocation {
+                register: 13,
+                offset: 0x100,
+                ..
+            })
+        ));
+        let fixed_value = &debugdata.variables.get("fixed_value").unwrap()[0];
+        assert_eq!(fixed_value.location_kind, LocationKind::Static);
+        assert!(fixed_value.address.is_some());
+    }
+
+    #[test]
+    fn test_abstract_origin_chain() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/origin_chain_test.elf"), false).unwrap();
+        let level_count = &debugdata.variables.get("level_count").unwrap()[0];
+        assert_eq!(level_count.address, Some(0x402000));
+        assert!(level_count.is_external);
+        let typeinfo = &debugdata.types[&level_count.typeref];
+        assert_eq!(typeinfo.name.as_deref(), Some("int"));
+
+        // the name and the type are found in different origins of the chain
+        let deep_gain = &debugdata.variables.get("deep_gain").unwrap()[0];
+        assert_eq!(deep_gain.address, Some(0x402004));
+        let typeinfo = &debugdata.types[&deep_gain.typeref];
+        assert_eq!(typeinfo.name.as_deref(), Some("short int"));
+
+        // the cycle in the origins of loop_var ends the chain
+        let loop_var = &debugdata.variables.get("loop_var").unwrap()[0];
+        assert_eq!(loop_var.address, Some(0x402008));
+        let typeinfo = &debugdata.types[&loop_var.typeref];
+        assert_eq!(typeinfo.name.as_deref(), Some("int"));
+        assert_eq!(debugdata.variables.len(), 3);
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_load_wasm() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/wasm_test.wasm"), false).unwrap();
+        assert_eq!(debugdata.endianness(), object::Endianness::Little);
+        // the addresses of wasm variables are offsets into the linear memory
+        let model = &debugdata.variables.get("model").unwrap()[0];
+        assert_eq!(model.address, Some(1024));
+        let step_count = &debugdata.variables.get("step_count").unwrap()[0];
+        assert_eq!(step_count.address, Some(1032));
+        let symbol = crate::symbol::find_symbol("model.offset", &debugdata).unwrap();
+        assert_eq!(symbol.address, 1028);
+        assert!(matches!(symbol.typeinfo.datatype, DbgDataType::Float));
+    }
+
+    #[test]
+    fn test_dropped_variables() {
+        let debug_data =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/dropped_variable_test.elf"), false)
+                .unwrap();
+        assert!(debug_data.variables.contains_key("kept"));
+        assert!(debug_data.variables.contains_key("used_value"));
+        assert!(!debug_data.variables.contains_key("optimized_out"));
+
+        // the local variable in get_value has no static address either, but it is not reported
+        assert_eq!(debug_data.dropped_variables.len(), 1);
+        let dropped = &debug_data.dropped_variables[0];
+        assert_eq!(dropped.name, "optimized_out");
+        assert_eq!(dropped.offset, 0x2e);
+        assert_eq!(dropped.reason, DropReason::NoLocation);
+    }
+
+    #[test]
+    fn test_reference_types() {
+        let debugdata =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/reference_test.elf"), false).unwrap();
+        let types = &debugdata.types;
+        for (name, target_name) in [
+            ("gain_ref", "int"),
+            ("limits_ref", "Limits"),
+            ("temp_ref", "int"),
+        ] {
+            let symbol = crate::symbol::find_symbol(name, &debugdata).unwrap();
+            let DbgDataType::Reference { size: 8, target } = symbol.typeinfo.datatype else {
+                panic!("{name} should be a reference");
+            };
+            assert_eq!(symbol.typeinfo.get_size(), 8);
+            let target_type = types[&target].get_reference(types);
+            assert_eq!(target_type.name.as_deref(), Some(target_name));
+        }
+        let gain_ref = &debugdata.variables["gain_ref"][0];
+        assert_eq!(gain_ref.address, Some(0x402000));
+        let limits_ref = crate::symbol::find_symbol("limits_ref", &debugdata).unwrap();
+        assert_eq!(
+            limits_ref.typeinfo.to_c_declaration("limits_ref", types),
+            "struct Limits &limits_ref"
+        );
+
+        // the members of the referenced struct can't be located statically
+        assert!(matches!(
+            crate::symbol::find_symbol("limits_ref.upper", &debugdata),
+            Err(DebugDataError::ReferenceDescent { reference, .. }) if reference == "limits_ref"
+        ));
+    }
+
+    #[test]
+    fn test_demangle_once() {
+        let mut debug_data =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/qualified_name_test.elf"), false)
+                .unwrap();
+        // some compilers name C++ variables by their mangled name instead of the plain name
+        let mangled = "_ZN3ns13ns29outer_varE".to_string();
+        let outer_var = debug_data.variables.shift_remove("outer_var").unwrap();
+        debug_data
+            .variables
+            .insert(mangled.clone(), outer_var.clone());
+        // a name that looks mangled, but can't be demangled
+        let invalid = "_Zinvalid".to_string();
+        debug_data.variables.insert(invalid.clone(), outer_var);
+
+        DEMANGLE_COUNT.with(|count| count.set(0));
+        debug_data.demangled_names = demangle_cpp_varnames(&[&mangled, &invalid]);
+        assert_eq!(DEMANGLE_COUNT.with(|count| count.get()), 2);
+
+        // repeated lookups use the stored results, including the failure
+        for _ in 0..2 {
+            let symbol = crate::symbol::find_symbol("ns1::ns2::outer_var.count", &debug_data);
+            assert_eq!(symbol.unwrap().name, "_ZN3ns13ns29outer_varE.count");
+            assert!(crate::symbol::find_symbol("_ZN3ns13ns29outer_varE", &debug_data).is_ok());
+            assert!(crate::symbol::find_symbol("_Zinvalid.count", &debug_data).is_ok());
+            assert!(crate::symbol::find_symbol("_ZN3ns13ns29missingE", &debug_data).is_err());
+        }
+        assert_eq!(DEMANGLE_COUNT.with(|count| count.get()), 2);
+    }
+
+    #[test]
+    fn test_anonymous_namespace() {
+        // "typeinfo for Foo" is still rejected, only the anonymous namespace may contain a space
+        let typeinfo = "_ZTI3Foo".to_string();
+        let counter = "_ZN12_GLOBAL__N_17counterE".to_string();
+        let demangled = demangle_cpp_varnames(&[&typeinfo, &counter]);
+        assert_eq!(demangled.len(), 1);
+        assert_eq!(
+            demangled.get("(anonymous namespace)::counter"),
+            Some(&vec![counter])
+        );
+
+        let debugdata = DebugData::load_dwarf(
+            OsStr::new("fixtures/bin/anonymous_namespace_test.elf"),
+            false,
+        )
+        .unwrap();
+        let symbol =
+            crate::symbol::find_symbol("(anonymous namespace)::counter", &debugdata).unwrap();
+        assert_eq!(symbol.address, 0x402000);
+        assert_eq!(symbol.match_kind, crate::symbol::MatchKind::Demangled);
+        let symbol =
+            crate::symbol::find_symbol("app::(anonymous namespace)::level", &debugdata).unwrap();
+        assert_eq!(symbol.address, 0x402004);
+        assert_eq!(symbol.name, "_ZN3app12_GLOBAL__N_15levelE");
+        assert_eq!(
+            symbol.qualified_name(&debugdata),
+            "app::(anonymous namespace)::level"
+        );
+    }
+
+    #[test]
+    fn test_demangle_collisions() {
+        // both overloads demangle to "ns::func", since the parameters are omitted
+        let first = "_ZN2ns4funcEv".to_string();
+        let second = "_ZN2ns4funcEi".to_string();
+        let other = "_ZN2ns5valueE".to_string();
+
+        let forward = demangle_cpp_varnames(&[&first, &second, &other]);
+        let backward = demangle_cpp_varnames(&[&other, &second, &first]);
+        assert_eq!(forward, backward);
+        // all mangled names are kept, the lexicographically smallest one first
+        assert_eq!(forward.get("ns::func"), Some(&vec![second, first]));
+        assert_eq!(forward.get("ns::value"), Some(&vec![other]));
+    }
 }
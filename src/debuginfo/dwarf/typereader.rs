@@ -1,5 +1,9 @@
 use super::{DbgDataType, TypeInfo, VarInfo};
 use super::{DebugDataReader, attributes::*};
+use crate::debuginfo::BaseClass;
+use crate::debuginfo::EnumVariant;
+use crate::debuginfo::UnionDiscriminant;
+use crate::debuginfo::{LoadPhase, ProgressEvent, SourceLanguage};
 use gimli::{DebugInfoOffset, DwTag, EndianSlice, EntriesTreeNode, RunTimeEndian, UnitOffset};
 use indexmap::IndexMap;
 use object::Endianness;
@@ -16,6 +20,8 @@ struct TypeReaderData {
     types: HashMap<usize, TypeInfo>,
     typenames: HashMap<String, Vec<usize>>,
     wip_items: Vec<WipItemInfo>,
+    // types that are pointed to by the types that were loaded, but which have not been loaded themselves
+    pointer_targets: Vec<(usize, DebugInfoOffset)>,
 }
 
 impl DebugDataReader<'_> {
@@ -23,23 +29,16 @@ impl DebugDataReader<'_> {
     pub(crate) fn load_types(
         &mut self,
         variables: &IndexMap<String, Vec<VarInfo>>,
+        progress: &mut dyn FnMut(ProgressEvent),
     ) -> (HashMap<usize, TypeInfo>, HashMap<String, Vec<usize>>) {
         let mut typereader_data = TypeReaderData {
             types: HashMap::<usize, TypeInfo>::new(),
             typenames: HashMap::<String, Vec<usize>>::new(),
             wip_items: Vec::new(),
+            pointer_targets: Vec::new(),
         };
         // for each variable
-        for (name, var_list) in variables {
-            if name.to_string() == "g_lksStateStruct" {
-                //fsm hello g_fsmRunnable  g_FsmRunnable g_lksStateStruct
-                // this is a special variable that contains a list of runnable FSMs
-                // it is not a type, so skip it
-                println!("Found g_lksStateStruct!");
-            } else {
-                // println!("Loading type info for variable {}", name);
-                // continue;
-            }
+        for (var_idx, (name, var_list)) in variables.iter().enumerate() {
             for VarInfo { typeref, .. } in var_list {
                 // check if the type was already loaded
                 if !typereader_data.types.contains_key(typeref) {
@@ -50,38 +49,52 @@ impl DebugDataReader<'_> {
                         // load one type and add it to the collection (always succeeds for correctly structured DWARF debug info)
                         let result = self.get_type(unit_idx, dbginfo_offset, &mut typereader_data);
                         if let Err(errmsg) = result {
-                            if self.verbose {
-                                println!("Error loading type info for variable {name}: {errmsg}");
-                            }
+                            log::debug!("Error loading type info for variable {name}: {errmsg}");
                         }
                         typereader_data.wip_items.clear();
+                        self.load_pointer_targets(&mut typereader_data);
                     }
                 }
             }
+            progress(ProgressEvent {
+                phase: LoadPhase::Types,
+                done: var_idx + 1,
+                total: variables.len(),
+            });
+        }
+        // each offset is listed once and in ascending order, so that the result does not depend
+        // on the order in which the types were loaded
+        for offsets in typereader_data.typenames.values_mut() {
+            offsets.sort_unstable();
+            offsets.dedup();
         }
 
         (typereader_data.types, typereader_data.typenames)
     }
 
+    // load the types that are pointed to by the loaded types. Each one is loaded as a new root, so
+    // a pointer back to a type that is still being loaded never causes the loading to recurse
+    fn load_pointer_targets(&self, typereader_data: &mut TypeReaderData) {
+        while let Some((unit_idx, dbginfo_offset)) = typereader_data.pointer_targets.pop() {
+            if !typereader_data.types.contains_key(&dbginfo_offset.0) {
+                if let Err(errmsg) = self.get_type(unit_idx, dbginfo_offset, typereader_data) {
+                    log::debug!(
+                        "Error loading the target type @{:x} of a pointer: {errmsg}",
+                        dbginfo_offset.0
+                    );
+                }
+                typereader_data.wip_items.clear();
+            }
+        }
+    }
+
     pub(crate) fn update_variable_type_offset(
         &mut self,
         variables: &mut IndexMap<String, Vec<VarInfo>>,
     ) -> Result<(), String> {
         // for each variable
-        for (name, var_list) in variables.iter_mut() {
-            if name.to_string() == "g_FsmRunnable" {
-                //fsm hello g_fsmRunnable  g_FsmRunnable
-                // this is a special variable that contains a list of runnable FSMs
-                // it is not a type, so skip it
-                println!("Found g_FsmRunnable!");
-            } else {
-                // println!("Loading type info for variable {}", name);
-                // continue;
-            }
-            for VarInfo {
-                typeref, unit_idx, ..
-            } in var_list.iter_mut()
-            {
+        for var_list in variables.values_mut() {
+            for VarInfo { typeref, .. } in var_list.iter_mut() {
                 // check if the type was already loaded
 
                 if let Some(unit_idx_calc) = self.units.get_unit(*typeref) {
@@ -93,8 +106,8 @@ impl DebugDataReader<'_> {
                     let mut dbginfo_offset = dbginfo_offset;
                     self.check_type_offset_address(&mut current_unit, &mut dbginfo_offset)?;
                     // 更新变量的 typeref 为新的 dbginfo_offset.0
+                    // the unit of the variable is kept, even if its type is defined in another unit
                     *typeref = dbginfo_offset.0;
-                    *unit_idx = current_unit;
                 }
             }
         }
@@ -117,15 +130,23 @@ impl DebugDataReader<'_> {
         match self.get_type_wrapped(current_unit, dbginfo_offset, typereader_data) {
             Ok(typeinfo) => Ok(typeinfo),
             Err(errmsg) => {
-                // try to print a readable error message
-                println!("Failed to read type: {errmsg}");
-                // for (idx, wip) in typereader_data.wip_items.iter().enumerate() {
-                //     print!("  {:indent$}{}", "", wip.tag, indent = idx * 2);
-                //     if let Some(name) = &wip.name {
-                //         print!(" {name}");
-                //     }
-                //     println!(" @0x{:X}", wip.offset);
-                // }
+                if log::log_enabled!(log::Level::Debug) {
+                    // try to print a readable error message
+                    let mut message = format!("Failed to read type: {errmsg}");
+                    for (idx, wip) in typereader_data.wip_items.iter().enumerate() {
+                        message.push_str(&format!(
+                            "\n  {:indent$}{}",
+                            "",
+                            wip.tag,
+                            indent = idx * 2
+                        ));
+                        if let Some(name) = &wip.name {
+                            message.push_str(&format!(" {name}"));
+                        }
+                        message.push_str(&format!(" @0x{:X}", wip.offset));
+                    }
+                    log::debug!("{message}");
+                }
 
                 // create a dummy typeinfo using DwarfDataType::Other, rather than propagate the error
                 // this allows the caller to continue, which is more useful
@@ -139,6 +160,7 @@ impl DebugDataReader<'_> {
                         .and_then(|wip| wip.name.clone()),
                     unit_idx: current_unit,
                     dbginfo_offset: dbginfo_offset.0,
+                    byte_size: None,
                 };
                 typereader_data
                     .types
@@ -153,59 +175,50 @@ impl DebugDataReader<'_> {
         current_unit: &mut usize,
         dbginfo_offset: &mut DebugInfoOffset,
     ) -> Result<(), String> {
-        // Debug: Print special type offsets for investigation
-        match dbginfo_offset.0 {
-            0xa316a1 | 0x9f888b | 0x936de5 | 0x3f7a79 | 0xab967a | 0x4FF174 => {
-                // println!("Found special type: {:?}", dbginfo_offset);
-            }
-            _ => {}
-        }
-
         let (unit, abbrev) = &self.units[*current_unit];
-        let offset = dbginfo_offset.to_unit_offset(unit).unwrap();
+        let offset = self
+            .units
+            .get_unit_offset(*current_unit, dbginfo_offset.0)
+            .ok_or_else(|| format!("invalid type offset 0x{:X}", dbginfo_offset.0))?;
         let mut entries_tree = unit
             .entries_tree(abbrev, Some(offset))
             .map_err(|err| err.to_string())?;
         let entries_tree_node = entries_tree.root().map_err(|err| err.to_string())?;
         let entry = entries_tree_node.entry();
-        let _typename = get_name_attribute(entry, &self.dwarf, unit).ok();
         let is_declaration = get_declaration_attribute(entry).unwrap_or(false);
 
-        if let Some(ref name) = _typename {
-            if name == "CCalcFuncStaMach" {
-                // println!("Found type: {:?}", name);
-            }
+        // a type with a DW_AT_signature is only a placeholder for the full type in a type unit
+        if let Some(signature_offset) = get_signature_attribute(entry, &self.units) {
+            *current_unit = self
+                .units
+                .get_unit(signature_offset)
+                .ok_or_else(|| format!("invalid type signature offset 0x{signature_offset:X}"))?;
+            *dbginfo_offset = gimli::DebugInfoOffset(signature_offset);
+            return Ok(());
         }
 
-        if is_declaration {
-            if let Some(class_info) = self.class_names.get(&dbginfo_offset.0) {
-                for (addr, class_info_tmp) in self.class_names.iter() {
-                    if class_info_tmp.name == class_info.name
-                        && !class_info_tmp.is_declaration
-                        && class_info_tmp.namespace == class_info.namespace
-                    {
-                        // dbginfo_offset is an immutable reference parameter, cannot assign to it
-                        // If you want to use the new addr, you need to return it or handle it differently
-                        *dbginfo_offset = gimli::DebugInfoOffset(*addr); // <-- Fixed type
-                        if let Some(unit_idx) = self.units.get_unit(*addr) {
-                            // Handle the case where we found a unit index
-                            *current_unit = unit_idx;
-                        } else {
-                            return Err(format!(
-                                "No matching address found for class name: {}",
-                                class_info.name
-                            ));
-                        }
-                        return Ok(());
+        // a declaration is replaced by the definition of the class with the same name and namespace.
+        // Without a definition the declaration is kept, and it is loaded as an incomplete type
+        if is_declaration && let Some(class_info) = self.class_names.get(&dbginfo_offset.0) {
+            for (addr, class_info_tmp) in self.class_names.iter() {
+                if class_info_tmp.name == class_info.name
+                    && !class_info_tmp.is_declaration
+                    && class_info_tmp.namespace == class_info.namespace
+                {
+                    // dbginfo_offset is an immutable reference parameter, cannot assign to it
+                    // If you want to use the new addr, you need to return it or handle it differently
+                    *dbginfo_offset = gimli::DebugInfoOffset(*addr); // <-- Fixed type
+                    if let Some(unit_idx) = self.units.get_unit(*addr) {
+                        // Handle the case where we found a unit index
+                        *current_unit = unit_idx;
+                    } else {
+                        return Err(format!(
+                            "No matching address found for class name: {}",
+                            class_info.name
+                        ));
                     }
+                    return Ok(());
                 }
-            } else {
-                // If the type is not a declaration, it should have a valid address
-                // If the address is 0, it means the type is not defined
-                return Err(format!(
-                    "Type at offset 0x{:X} in unit {} is not defined",
-                    dbginfo_offset.0, current_unit
-                ));
             }
         }
         Ok(())
@@ -225,14 +238,11 @@ impl DebugDataReader<'_> {
             return Ok(t.clone());
         }
 
-        if dbginfo_offset.0 == 153913 {
-            // this is a special case where the type is a pointer to a struct that is defined later
-            // this is used in the C code for the FSMs
-            // println!("Found type: {:?}", dbginfo_offset);
-        }
-
         let (unit, abbrev) = &self.units[current_unit];
-        let offset = dbginfo_offset.to_unit_offset(unit).unwrap();
+        let offset = self
+            .units
+            .get_unit_offset(current_unit, dbginfo_offset.0)
+            .ok_or_else(|| format!("invalid type offset 0x{:X}", dbginfo_offset.0))?;
         let mut entries_tree = unit
             .entries_tree(abbrev, Some(offset))
             .map_err(|err| err.to_string())?;
@@ -244,13 +254,21 @@ impl DebugDataReader<'_> {
         if is_declaration {
             // This is a declaration, not a definition. This happens when a type is declared but not defined
             // e.g. "struct foo;" in a header file.
-            // We can't do anything with this - return a dummy type, and don't store it in the types map.
-            return Ok(TypeInfo {
-                datatype: DbgDataType::Other(0),
+            // check_type_offset_address already replaced it by the definition if any unit contains one,
+            // so the type is incomplete. It is stored, so that variables of this type remain available
+            let typeinfo = TypeInfo {
+                datatype: DbgDataType::Incomplete {
+                    name: typename.clone().unwrap_or_default(),
+                },
                 name: typename,
                 unit_idx: current_unit,
                 dbginfo_offset: dbginfo_offset.0,
-            });
+                byte_size: None,
+            };
+            typereader_data
+                .types
+                .insert(dbginfo_offset.0, typeinfo.clone());
+            return Ok(typeinfo);
         }
 
         // 检查是否已经在处理中，防止递归无限调用
@@ -269,6 +287,7 @@ impl DebugDataReader<'_> {
                 name: typename,
                 unit_idx: current_unit,
                 dbginfo_offset: dbginfo_offset.0,
+                byte_size: None,
             });
         }
 
@@ -280,58 +299,44 @@ impl DebugDataReader<'_> {
         ));
 
         let mut dbginfo_offset_new = dbginfo_offset.0;
+        let mut byte_size = get_byte_size_attribute(entry);
         let tag_info = entry.tag();
         let (datatype, inner_name) = match entry.tag() {
             gimli::constants::DW_TAG_base_type => {
-                let (datatype, name) = get_base_type(entry, &self.units[current_unit].0);
+                let (datatype, name) =
+                    get_base_type(entry, &self.units[current_unit].0, typename.as_deref());
                 (datatype, Some(name))
             }
-            // gimli::constants::DW_TAG_pointer_type
             gimli::constants::DW_TAG_reference_type
             | gimli::constants::DW_TAG_rvalue_reference_type => {
+                // a C++ reference is stored like a pointer. The referenced type is queued like a pointer target,
+                // since a reference member of a struct can refer back to the struct
                 let (unit, _) = &self.units[current_unit];
-                if let Ok((new_cur_unit, ptype_offset)) =
+                let size = get_byte_size_attribute(entry)
+                    .unwrap_or_else(|| u64::from(unit.encoding().address_size));
+                if let Ok((mut target_unit, mut target_offset)) =
                     get_type_attribute(entry, &self.units, current_unit)
                 {
-                    if let Some(idx) = typereader_data
-                        .wip_items
-                        .iter()
-                        .position(|item| item.offset == ptype_offset.0)
+                    if self
+                        .check_type_offset_address(&mut target_unit, &mut target_offset)
+                        .is_err()
                     {
-                        // this is a linked list or similar self-referential data structure, and one of the callers
-                        // of this function is already working to get this type
-                        // Trying to recursively decode this type would result in an infinite loop
-                        //
-                        // Unfortunately the name in wip_items could be None: pointer names propagate backward from items
-                        // e.g pointer -> const -> volatile -> typedef (name comes from here!) -> any
-                        let name = typereader_data.get_pointer_name(idx);
-                        (
-                            DbgDataType::Pointer(
-                                u64::from(unit.encoding().address_size),
-                                ptype_offset.0,
-                            ),
-                            name.clone(),
-                        )
-                    } else {
-                        let pt_type = self.get_type(new_cur_unit, ptype_offset, typereader_data)?;
-                        dbginfo_offset_new = pt_type.dbginfo_offset;
-                        (
-                            DbgDataType::Pointer(
-                                u64::from(unit.encoding().address_size),
-                                // ptype_offset.0,
-                                pt_type.dbginfo_offset,
-                            ),
-                            pt_type.name,
-                        )
+                        (target_unit, target_offset) =
+                            get_type_attribute(entry, &self.units, current_unit)?;
                     }
-                } else {
-                    // void*
+                    typereader_data
+                        .pointer_targets
+                        .push((target_unit, target_offset));
                     (
-                        DbgDataType::Pointer(u64::from(unit.encoding().address_size), 0),
-                        Some("void".to_string()),
+                        DbgDataType::Reference {
+                            size,
+                            target: target_offset.0,
+                        },
+                        self.get_pointer_target_name(target_unit, target_offset),
                     )
+                } else {
+                    (DbgDataType::Reference { size, target: 0 }, None)
                 }
-                //DwarfDataType::Pointer(u64::from(unit.encoding().address_size), dest_type)
             }
             gimli::constants::DW_TAG_array_type => {
                 self.get_array_type(entry, current_unit, offset, typereader_data)?
@@ -343,12 +348,18 @@ impl DebugDataReader<'_> {
             gimli::constants::DW_TAG_structure_type => {
                 let size = get_byte_size_attribute(entry)
                     .ok_or_else(|| "missing struct byte size attribute".to_string())?;
-                let members = self.get_struct_or_union_members(
-                    entries_tree_node,
-                    current_unit,
-                    typereader_data,
-                )?;
-                (DbgDataType::Struct { size, members }, None)
+                if let Some(datatype) =
+                    self.get_variant_enum(size, current_unit, offset, typereader_data)?
+                {
+                    (datatype, None)
+                } else {
+                    let members = self.get_struct_or_union_members(
+                        entries_tree_node,
+                        current_unit,
+                        typereader_data,
+                    )?;
+                    (DbgDataType::Struct { size, members }, None)
+                }
             }
             gimli::constants::DW_TAG_class_type => (
                 self.get_class_type(current_unit, offset, typereader_data)?,
@@ -357,18 +368,32 @@ impl DebugDataReader<'_> {
             gimli::constants::DW_TAG_union_type => {
                 let size = get_byte_size_attribute(entry)
                     .ok_or_else(|| "missing union byte size attribute".to_string())?;
-                let members = self.get_struct_or_union_members(
+                let mut members = self.get_struct_or_union_members(
                     entries_tree_node,
                     current_unit,
                     typereader_data,
                 )?;
-                (DbgDataType::Union { size, members }, None)
+                let discriminant = self.get_union_discriminant(
+                    current_unit,
+                    offset,
+                    &mut members,
+                    typereader_data,
+                )?;
+                (
+                    DbgDataType::Union {
+                        size,
+                        members,
+                        discriminant,
+                    },
+                    None,
+                )
             }
             gimli::constants::DW_TAG_typedef => {
                 let (new_cur_unit, dbginfo_offset) =
                     get_type_attribute(entry, &self.units, current_unit)?;
                 let reftype = self.get_type(new_cur_unit, dbginfo_offset, typereader_data)?;
                 dbginfo_offset_new = reftype.dbginfo_offset;
+                byte_size = byte_size.or(reftype.byte_size);
                 (reftype.datatype, None)
             }
             gimli::constants::DW_TAG_const_type
@@ -384,7 +409,13 @@ impl DebugDataReader<'_> {
                 {
                     let typeinfo = self.get_type(new_cur_unit, dbginfo_offset, typereader_data)?;
                     dbginfo_offset_new = typeinfo.dbginfo_offset;
-                    (typeinfo.datatype, typeinfo.name)
+                    byte_size = byte_size.or(typeinfo.byte_size);
+                    // the loaded type is the target of any typedefs, so the name of a qualified typedef,
+                    // e.g. "const SHandle", is taken from the typedef itself
+                    let name = self
+                        .get_pointer_target_name(new_cur_unit, dbginfo_offset)
+                        .or(typeinfo.name);
+                    (typeinfo.datatype, name)
                 } else {
                     // const void* / volatile void* / packed void*???
                     (
@@ -396,16 +427,60 @@ impl DebugDataReader<'_> {
             gimli::constants::DW_TAG_subroutine_type => {
                 // function pointer
                 (
-                    DbgDataType::FuncPtr(u64::from(unit.encoding().address_size)),
+                    self.get_function_pointer_type(current_unit, offset, typereader_data)?,
                     Some("p_function".to_string()),
                 )
             }
-            gimli::constants::DW_TAG_pointer_type | gimli::constants::DW_TAG_unspecified_type => {
-                // ?
-                (
-                    DbgDataType::Other(get_byte_size_attribute(entry).unwrap_or(0)),
-                    None,
-                )
+            gimli::constants::DW_TAG_pointer_type
+                if let Some((subroutine_unit, subroutine_offset)) =
+                    self.get_pointer_subroutine(entry, current_unit) =>
+            {
+                // pointer to a function, possibly through a typedef of the function type
+                let subroutine =
+                    self.get_type(subroutine_unit, subroutine_offset, typereader_data)?;
+                (subroutine.datatype, Some("p_function".to_string()))
+            }
+            gimli::constants::DW_TAG_pointer_type => {
+                let pointer_size = get_byte_size_attribute(entry)
+                    .unwrap_or_else(|| u64::from(unit.encoding().address_size));
+                if let Ok((mut pt_unit, mut pt_offset)) =
+                    get_type_attribute(entry, &self.units, current_unit)
+                {
+                    // The target is not loaded here, but only queued: a linked list node or any other
+                    // self-referential type would otherwise expand itself without end.
+                    // A pointer to a declared struct refers to its definition, if there is one
+                    if self
+                        .check_type_offset_address(&mut pt_unit, &mut pt_offset)
+                        .is_err()
+                    {
+                        (pt_unit, pt_offset) =
+                            get_type_attribute(entry, &self.units, current_unit)?;
+                    }
+                    typereader_data.pointer_targets.push((pt_unit, pt_offset));
+                    (
+                        DbgDataType::Pointer(pointer_size, pt_offset.0),
+                        self.get_pointer_target_name(pt_unit, pt_offset),
+                    )
+                } else {
+                    // void*
+                    (
+                        DbgDataType::Pointer(pointer_size, 0),
+                        Some("void".to_string()),
+                    )
+                }
+            }
+            gimli::constants::DW_TAG_unspecified_type => {
+                // e.g. decltype(nullptr) in C++, which has a size. Without a size nothing is known about the type
+                if let Some(size) = byte_size {
+                    (DbgDataType::Other(size), None)
+                } else {
+                    (
+                        DbgDataType::Incomplete {
+                            name: typename.clone().unwrap_or_default(),
+                        },
+                        None,
+                    )
+                }
             }
             other_tag => {
                 return Err(format!(
@@ -419,11 +494,6 @@ impl DebugDataReader<'_> {
         if dbginfo_offset.0 != dbginfo_offset_new {
             // this is a special case where the type is a pointer to a struct that is defined later
             // this is used in the C code for the FSMs
-            // println!(
-            //     "Found type: {:?}, offset: {:?}, new offset: {:?}",
-            //     inner_name, dbginfo_offset, dbginfo_offset_new
-            // );
-            // println!("type tag: {:?}", tag_info.to_string());
             dbginfo_offset = DebugInfoOffset(dbginfo_offset_new);
             diff_flag = true;
         }
@@ -442,6 +512,7 @@ impl DebugDataReader<'_> {
             name: display_name,
             unit_idx: current_unit,
             dbginfo_offset: dbginfo_offset.0,
+            byte_size,
         };
 
         if let Some(name) = typename {
@@ -495,21 +566,37 @@ impl DebugDataReader<'_> {
             get_type_attribute(entry, &self.units, current_unit)?;
         let arraytype = self.get_type(new_cur_unit, arraytype_offset, typereader_data)?;
         let arraytype_name = arraytype.name.clone();
-        let stride = if let Some(stride) = get_byte_stride_attribute(entry) {
-            stride
-        } else {
-            // this is the usual case
-            arraytype.get_size()
+        // an explicit stride overrides the size of the elements. It is usually given on the array type,
+        // but Fortran compilers may set it on the subrange of a dimension instead
+        let array_bit_stride = get_stride_bits(entry);
+        let mut subrange_bit_stride = None;
+
+        // without a DW_AT_lower_bound, the index of the first element depends on the language, e.g. 1 in Fortran
+        let default_lbound = match self
+            .unit_info
+            .get(current_unit)
+            .and_then(|info| info.language)
+        {
+            Some(SourceLanguage::Other(language)) => {
+                gimli::DwLang(language).default_lower_bound().unwrap_or(0) as u64
+            }
+            _ => 0,
         };
 
         // get the array dimensions
         let mut dim = Vec::<u64>::new();
+        let mut lower_bounds = Vec::<u64>::new();
+        let mut dynamic_dims = Vec::<usize>::new();
         let mut iter = entries_tree_node.children();
         while let Ok(Some(child_node)) = iter.next() {
             let child_entry = child_node.entry();
             if child_entry.tag() == gimli::constants::DW_TAG_subrange_type {
-                let count = if let Some(ubound) = get_upper_bound_attribute(child_entry) {
-                    let lbound = get_lower_bound_attribute(child_entry).unwrap_or(0);
+                let lbound = get_lower_bound_attribute(child_entry).unwrap_or(default_lbound);
+                lower_bounds.push(lbound);
+                let count = if has_dynamic_bound(child_entry) {
+                    dynamic_dims.push(dim.len());
+                    0
+                } else if let Some(ubound) = get_upper_bound_attribute(child_entry) {
                     // compilers may use the bit pattern FFF.. to mean that the array size is unknown
                     // this can happen when a pointer to an array is declared
                     if ubound != u64::from(u32::MAX) && ubound != u64::MAX {
@@ -522,6 +609,8 @@ impl DebugDataReader<'_> {
                     get_count_attribute(child_entry).unwrap_or_default()
                 };
                 dim.push(count);
+                // the stride of the innermost dimension is the distance between the elements
+                subrange_bit_stride = get_stride_bits(child_entry);
             } else if child_entry.tag() == gimli::constants::DW_TAG_enumeration_type {
                 // the DWARF spec allows an array dimension to be given using an enumeration type
                 // presumably this could be created by languages other than C / C++
@@ -533,15 +622,40 @@ impl DebugDataReader<'_> {
                     }
                 }
                 dim.push(enum_count);
+                lower_bounds.push(0);
             }
         }
+        // the lower bounds are only stored if any dimension doesn't start at 0
+        if lower_bounds.iter().all(|lbound| *lbound == 0) {
+            lower_bounds.clear();
+        }
+
+        // this is the usual case: the stride is the size of the elements
+        let bit_stride = array_bit_stride
+            .or(subrange_bit_stride)
+            .unwrap_or_else(|| arraytype.get_size() * 8);
 
         // try to fix the dimension of the array, if the DW_TAG_subrange_type didn't contain enough info
-        if dim.len() == 1 && dim[0] == 0 && stride != 0 {
-            if let Some(count) = maybe_size.map(|s: u64| s / stride) {
-                dim[0] = count;
+        if dim.len() == 1
+            && dim[0] == 0
+            && dynamic_dims.is_empty()
+            && bit_stride != 0
+            && let Some(count) = maybe_size.map(|s: u64| s * 8 / bit_stride)
+        {
+            dim[0] = count;
+        }
+
+        if !bit_stride.is_multiple_of(8) {
+            let dbginfo_offset = self.units.get_offset(current_unit, offset);
+            let mut datatype =
+                self.get_packed_array_type(&arraytype, &dim, bit_stride, 0, dbginfo_offset);
+            if let (Some(byte_size), DbgDataType::Struct { size, .. }) = (maybe_size, &mut datatype)
+            {
+                *size = byte_size;
             }
+            return Ok((datatype, arraytype_name));
         }
+        let stride = bit_stride / 8;
         let size = maybe_size.unwrap_or_else(|| dim.iter().fold(stride, |acc, num| acc * num));
         Ok((
             DbgDataType::Array {
@@ -549,11 +663,135 @@ impl DebugDataReader<'_> {
                 arraytype: Box::new(arraytype),
                 size,
                 stride,
+                lower_bounds,
+                dynamic_dims,
             },
             arraytype_name,
         ))
     }
 
+    // A2L can't describe an array whose elements are not byte aligned, so a bit-packed array is
+    // loaded as a struct with one bitfield member for each element instead. The members are named like
+    // array elements, e.g. "_3_", and a multi-dimensional array becomes a nest of structs.
+    // first_bit is the bit position of the first element relative to the start of the struct
+    fn get_packed_array_type(
+        &self,
+        arraytype: &TypeInfo,
+        dim: &[u64],
+        bit_stride: u64,
+        first_bit: u64,
+        dbginfo_offset: usize,
+    ) -> DbgDataType {
+        let inner_count: u64 = dim[1..].iter().product();
+        let type_size = arraytype.get_size();
+        let type_size_bits = type_size * 8;
+        let bit_size = bit_stride.min(type_size_bits);
+        let mut members = IndexMap::new();
+        if type_size_bits == 0 {
+            return DbgDataType::Struct { size: 0, members };
+        }
+
+        for idx in 0..dim[0] {
+            let start_bit = first_bit + idx * inner_count * bit_stride;
+            if dim.len() > 1 {
+                let datatype = self.get_packed_array_type(
+                    arraytype,
+                    &dim[1..],
+                    bit_stride,
+                    start_bit % 8,
+                    dbginfo_offset,
+                );
+                let inner_typeinfo = TypeInfo {
+                    name: None,
+                    unit_idx: arraytype.unit_idx,
+                    dbginfo_offset,
+                    datatype,
+                    byte_size: None,
+                };
+                members.insert(format!("_{idx}_"), (inner_typeinfo, Some(start_bit / 8)));
+            } else {
+                let offset = (start_bit / type_size_bits) * type_size;
+                let mut bit_offset = start_bit % type_size_bits;
+                if bit_offset + bit_size > type_size_bits {
+                    // the element can't be described by a bit mask of the element type
+                    log::debug!(
+                        "Element {idx} of the bit-packed array @{dbginfo_offset:x} crosses a storage unit boundary"
+                    );
+                    continue;
+                }
+                if self.endian == Endianness::Big {
+                    bit_offset = type_size_bits - bit_offset - bit_size;
+                }
+                let element_typeinfo = TypeInfo {
+                    name: arraytype.name.clone(),
+                    unit_idx: arraytype.unit_idx,
+                    dbginfo_offset,
+                    datatype: DbgDataType::Bitfield {
+                        basetype: Box::new(arraytype.clone()),
+                        bit_offset: bit_offset as u16,
+                        bit_size: bit_size as u16,
+                    },
+                    byte_size: None,
+                };
+                members.insert(format!("_{idx}_"), (element_typeinfo, Some(offset)));
+            }
+        }
+        let size = (first_bit + dim[0] * inner_count * bit_stride).div_ceil(8);
+        DbgDataType::Struct { size, members }
+    }
+
+    // check if a type is const-qualified. Typedefs and the other qualifiers are looked through, but pointers
+    // are not, since the constness of the target of a pointer doesn't make the variable itself const
+    pub(crate) fn is_const_type(
+        &self,
+        unit_idx: usize,
+        dbginfo_offset: DebugInfoOffset,
+    ) -> Option<bool> {
+        let mut unit_idx = unit_idx;
+        let mut dbginfo_offset = dbginfo_offset;
+        loop {
+            let (unit, abbrev) = &self.units[unit_idx];
+            let offset = self.units.get_unit_offset(unit_idx, dbginfo_offset.0)?;
+            let mut entries = unit.entries_at_offset(abbrev, offset).ok()?;
+            let (_, entry) = entries.next_dfs().ok()??;
+            match entry.tag() {
+                gimli::constants::DW_TAG_const_type => return Some(true),
+                gimli::constants::DW_TAG_typedef
+                | gimli::constants::DW_TAG_volatile_type
+                | gimli::constants::DW_TAG_restrict_type
+                | gimli::constants::DW_TAG_atomic_type => {}
+                _ => return Some(false),
+            }
+            (unit_idx, dbginfo_offset) = get_type_attribute(entry, &self.units, unit_idx).ok()?;
+        }
+    }
+
+    // get the name of the type that a pointer points to, without loading the type.
+    // Qualifiers like const and volatile have no name of their own, so the name is taken from the qualified type
+    fn get_pointer_target_name(
+        &self,
+        unit_idx: usize,
+        dbginfo_offset: DebugInfoOffset,
+    ) -> Option<String> {
+        let mut unit_idx = unit_idx;
+        let mut dbginfo_offset = dbginfo_offset;
+        loop {
+            let (unit, abbrev) = &self.units[unit_idx];
+            let offset = self.units.get_unit_offset(unit_idx, dbginfo_offset.0)?;
+            let mut entries = unit.entries_at_offset(abbrev, offset).ok()?;
+            let (_, entry) = entries.next_dfs().ok()??;
+            if let Ok(name) = get_name_attribute(entry, &self.dwarf, unit) {
+                return Some(name);
+            }
+            if entry.tag() != gimli::constants::DW_TAG_const_type
+                && entry.tag() != gimli::constants::DW_TAG_volatile_type
+            {
+                return None;
+            }
+            (unit_idx, dbginfo_offset) = get_type_attribute(entry, &self.units, unit_idx).ok()?;
+        }
+    }
+
     fn get_enumeration_type(
         &self,
         current_unit: usize,
@@ -582,7 +820,8 @@ impl DebugDataReader<'_> {
             // get size and signedness of the underlying type
             let signed = matches!(
                 utype.datatype,
-                DbgDataType::Sint8
+                DbgDataType::SChar
+                    | DbgDataType::Sint8
                     | DbgDataType::Sint16
                     | DbgDataType::Sint32
                     | DbgDataType::Sint64
@@ -637,24 +876,8 @@ impl DebugDataReader<'_> {
         let inheritance = self
             .get_class_inheritance(entries_tree_node2, current_unit, typereader_data)
             .unwrap_or_default();
-        let mut members =
+        let members =
             self.get_struct_or_union_members(entries_tree_node, current_unit, typereader_data)?;
-        // copy all inherited members from the base classes
-        // this allows the inherited members ot be accessed without naming the base class
-        for (baseclass_type, baseclass_offset) in inheritance.values() {
-            if let DbgDataType::Class {
-                members: baseclass_members,
-                ..
-            } = &baseclass_type.datatype
-            {
-                for (name, (m_type, m_offset)) in baseclass_members {
-                    // members.insert(
-                    //     name.to_owned(),
-                    //     (m_type.clone(), m_offset + baseclass_offset),
-                    // );
-                }
-            }
-        }
         Ok(DbgDataType::Class {
             size,
             inheritance,
@@ -663,41 +886,307 @@ impl DebugDataReader<'_> {
     }
 
     // get all the members of a struct or union or class
+    // Rust enums with data are represented as a DW_TAG_structure_type containing a DW_TAG_variant_part.
+    // The variant part contains the discriminant and one DW_TAG_variant for each variant of the enum.
+    // Returns None if the struct has no variant part
+    fn get_variant_enum(
+        &self,
+        size: u64,
+        current_unit: usize,
+        offset: UnitOffset,
+        typereader_data: &mut TypeReaderData,
+    ) -> Result<Option<DbgDataType>, String> {
+        let (unit, abbrev) = &self.units[current_unit];
+        let mut entries_tree = unit
+            .entries_tree(abbrev, Some(offset))
+            .map_err(|err| err.to_string())?;
+        let entries_tree_node = entries_tree.root().map_err(|err| err.to_string())?;
+        let mut iter = entries_tree_node.children();
+        while let Ok(Some(child_node)) = iter.next() {
+            if child_node.entry().tag() != gimli::constants::DW_TAG_variant_part {
+                continue;
+            }
+
+            let mut discriminant = None;
+            let mut variants = IndexMap::new();
+            let mut part_iter = child_node.children();
+            while let Ok(Some(part_node)) = part_iter.next() {
+                let part_entry = part_node.entry();
+                match part_entry.tag() {
+                    gimli::constants::DW_TAG_member => {
+                        // the discriminant, which is referenced by the DW_AT_discr of the variant part
+                        let (discr_unit, discr_offset) =
+                            get_type_attribute(part_entry, &self.units, current_unit)?;
+                        let discr_type =
+                            self.get_type(discr_unit, discr_offset, typereader_data)?;
+                        let discr_location = get_data_member_location_attribute(
+                            self,
+                            part_entry,
+                            unit.encoding(),
+                            current_unit,
+                        )
+                        .flatten()
+                        .unwrap_or(0);
+                        discriminant = Some((Box::new(discr_type), discr_location));
+                    }
+                    gimli::constants::DW_TAG_variant => {
+                        // a variant without DW_AT_discr_value is the default variant
+                        let discr_value = get_discr_value_attribute(part_entry);
+                        // each variant contains a single member, whose name is the name of the variant
+                        let members = self.get_struct_or_union_members(
+                            part_node,
+                            current_unit,
+                            typereader_data,
+                        )?;
+                        for (name, (typeinfo, offset)) in members {
+                            let Some(offset) = offset else {
+                                continue;
+                            };
+                            variants.insert(
+                                discr_value,
+                                EnumVariant {
+                                    name,
+                                    typeinfo,
+                                    offset,
+                                },
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            return Ok(Some(DbgDataType::VariantEnum {
+                size,
+                discriminant,
+                variants,
+            }));
+        }
+        Ok(None)
+    }
+
+    // Some compilers describe a C union with a tag as a DW_TAG_union_type containing a DW_TAG_variant_part.
+    // The DW_AT_discr of the variant part refers to the discriminant member, and each DW_TAG_variant contains
+    // the members that are active for its DW_AT_discr_value.
+    // The members of the variant part are added to members. Returns None if the union has no variant part
+    fn get_union_discriminant(
+        &self,
+        current_unit: usize,
+        offset: UnitOffset,
+        members: &mut IndexMap<String, (TypeInfo, Option<u64>)>,
+        typereader_data: &mut TypeReaderData,
+    ) -> Result<Option<UnionDiscriminant>, String> {
+        let (unit, abbrev) = &self.units[current_unit];
+        let mut entries_tree = unit
+            .entries_tree(abbrev, Some(offset))
+            .map_err(|err| err.to_string())?;
+        let entries_tree_node = entries_tree.root().map_err(|err| err.to_string())?;
+        let mut iter = entries_tree_node.children();
+        while let Ok(Some(child_node)) = iter.next() {
+            let part_entry = child_node.entry();
+            if part_entry.tag() != gimli::constants::DW_TAG_variant_part {
+                continue;
+            }
+
+            // the discriminant is usually a member of the variant part, but it can also be a member of the union
+            let mut part_tree = unit
+                .entries_tree(abbrev, Some(part_entry.offset()))
+                .map_err(|err| err.to_string())?;
+            let part_members = self.get_struct_or_union_members(
+                part_tree.root().map_err(|err| err.to_string())?,
+                current_unit,
+                typereader_data,
+            )?;
+            let discr_name = match get_attr_value(part_entry, gimli::constants::DW_AT_discr) {
+                Some(gimli::AttributeValue::UnitRef(discr_offset)) => unit
+                    .entry(abbrev, discr_offset)
+                    .ok()
+                    .and_then(|discr_entry| {
+                        get_name_attribute(&discr_entry, &self.dwarf, unit).ok()
+                    }),
+                _ => part_members.keys().next().cloned(),
+            }
+            .ok_or_else(|| "missing discriminant of the variant part".to_string())?;
+            for (name, member) in part_members {
+                members.entry(name).or_insert(member);
+            }
+
+            let mut variants = Vec::new();
+            let mut part_iter = child_node.children();
+            while let Ok(Some(variant_node)) = part_iter.next() {
+                if variant_node.entry().tag() != gimli::constants::DW_TAG_variant {
+                    continue;
+                }
+                // a variant without DW_AT_discr_value is the default variant
+                let discr_value = get_discr_value_attribute(variant_node.entry());
+                let variant_members =
+                    self.get_struct_or_union_members(variant_node, current_unit, typereader_data)?;
+                for (name, member) in variant_members {
+                    variants.push((discr_value, name.clone()));
+                    // a member can be active for several values
+                    members.entry(name).or_insert(member);
+                }
+            }
+            return Ok(Some((discr_name, variants)));
+        }
+        Ok(None)
+    }
+
+    // get the return type and the parameter types of a DW_TAG_subroutine_type
+    fn get_function_pointer_type(
+        &self,
+        current_unit: usize,
+        offset: UnitOffset,
+        typereader_data: &mut TypeReaderData,
+    ) -> Result<DbgDataType, String> {
+        let (unit, abbrev) = &self.units[current_unit];
+        let mut entries_tree = unit
+            .entries_tree(abbrev, Some(offset))
+            .map_err(|err| err.to_string())?;
+        let entries_tree_node = entries_tree.root().map_err(|err| err.to_string())?;
+        let entry = entries_tree_node.entry();
+
+        // the DW_AT_type attribute is missing if the function returns void
+        let return_type = if let Ok((return_unit, return_offset)) =
+            get_type_attribute(entry, &self.units, current_unit)
+        {
+            self.get_type(return_unit, return_offset, typereader_data)?
+        } else {
+            TypeInfo {
+                name: Some("void".to_string()),
+                unit_idx: current_unit,
+                datatype: DbgDataType::Other(0),
+                dbginfo_offset: 0,
+                byte_size: None,
+            }
+        };
+
+        let mut params = Vec::new();
+        let mut iter = entries_tree_node.children();
+        while let Ok(Some(child_node)) = iter.next() {
+            let child_entry = child_node.entry();
+            // DW_TAG_unspecified_parameters (the "..." of a variadic function) has no type and is skipped
+            if child_entry.tag() == gimli::constants::DW_TAG_formal_parameter {
+                let (param_unit, param_offset) =
+                    get_type_attribute(child_entry, &self.units, current_unit)?;
+                params.push(self.get_type(param_unit, param_offset, typereader_data)?);
+            }
+        }
+
+        Ok(DbgDataType::FunctionPointer {
+            return_type: Box::new(return_type),
+            params,
+            size: u64::from(unit.encoding().address_size),
+        })
+    }
+
+    // check if a DW_TAG_pointer_type points to a DW_TAG_subroutine_type, either directly or through
+    // typedefs and qualifiers. Returns the unit and offset of the DW_TAG_subroutine_type
+    fn get_pointer_subroutine(
+        &self,
+        entry: &gimli::DebuggingInformationEntry<EndianSlice<RunTimeEndian>, usize>,
+        current_unit: usize,
+    ) -> Option<(usize, DebugInfoOffset)> {
+        let (mut target_unit, mut target_offset) =
+            get_type_attribute(entry, &self.units, current_unit).ok()?;
+        // the number of steps is limited, in case the debug info contains a reference loop
+        for _ in 0..16 {
+            let (unit, abbrev) = &self.units[target_unit];
+            let unit_offset = self.units.get_unit_offset(target_unit, target_offset.0)?;
+            let target_entry = unit.entry(abbrev, unit_offset).ok()?;
+            match target_entry.tag() {
+                gimli::constants::DW_TAG_subroutine_type => {
+                    return Some((target_unit, target_offset));
+                }
+                gimli::constants::DW_TAG_typedef
+                | gimli::constants::DW_TAG_const_type
+                | gimli::constants::DW_TAG_volatile_type
+                | gimli::constants::DW_TAG_atomic_type => {
+                    (target_unit, target_offset) =
+                        get_type_attribute(&target_entry, &self.units, target_unit).ok()?;
+                }
+                _ => return None,
+            }
+        }
+        None
+    }
+
     fn get_struct_or_union_members(
         &self,
         entries_tree: EntriesTreeNode<EndianSlice<RunTimeEndian>>,
         current_unit: usize,
         typereader_data: &mut TypeReaderData,
-    ) -> Result<IndexMap<String, (TypeInfo, u64)>, String> {
+    ) -> Result<IndexMap<String, (TypeInfo, Option<u64>)>, String> {
         let (unit, _) = &self.units[current_unit];
-        let mut members = IndexMap::<String, (TypeInfo, u64)>::new();
+        let mut members = IndexMap::<String, (TypeInfo, Option<u64>)>::new();
         let mut iter = entries_tree.children();
         while let Ok(Some(child_node)) = iter.next() {
             let child_entry = child_node.entry();
             if child_entry.tag() == gimli::constants::DW_TAG_member {
+                // a member can refer to another DIE through DW_AT_specification or DW_AT_abstract_origin, e.g. with -flto.
+                // Any attributes that are missing in the member are taken from the referenced DIE, which may be in another unit
+                let origin = get_specification_attribute(child_entry, &self.units, current_unit)
+                    .or_else(|| {
+                        get_abstract_origin_attribute(child_entry, &self.units, current_unit)
+                    });
+
                 // the name can be missing if this struct/union contains an anonymous struct/union
                 let opt_name = get_name_attribute(child_entry, &self.dwarf, unit)
+                    .or_else(|err| match &origin {
+                        Some((origin_unit, origin_entry)) => get_name_attribute(
+                            origin_entry,
+                            &self.dwarf,
+                            &self.units[*origin_unit].0,
+                        ),
+                        None => Err(err),
+                    })
                     .map_err(|_| "missing struct/union member name".to_string());
 
-                let mut offset = get_data_member_location_attribute(
+                let location = get_data_member_location_attribute(
                     self,
                     child_entry,
                     unit.encoding(),
                     current_unit,
                 )
-                .unwrap_or(0);
+                .or_else(|| {
+                    let (origin_unit, origin_entry) = origin.as_ref()?;
+                    get_data_member_location_attribute(
+                        self,
+                        origin_entry,
+                        self.units[*origin_unit].0.encoding(),
+                        *origin_unit,
+                    )
+                });
+                let mut offset = match location {
+                    Some(Some(offset)) => Some(offset),
+                    Some(None) => {
+                        // e.g. members of variable-layout objects, whose location is read through DW_OP_push_object_address
+                        log::warn!(
+                            "The location of the member {} @{:x} can't be evaluated statically",
+                            opt_name.as_deref().unwrap_or("<anonymous>"),
+                            self.units.get_offset(current_unit, child_entry.offset())
+                        );
+                        None
+                    }
+                    // union members usually have no location
+                    None => Some(0),
+                };
                 let (new_cur_unit, new_dbginfo_offset) =
-                    get_type_attribute(child_entry, &self.units, current_unit)?;
-                if new_dbginfo_offset.0 == 14133146 {
-                    // println!("Found type: {:?}", new_dbginfo_offset);
-                }
+                    get_type_attribute(child_entry, &self.units, current_unit).or_else(|err| {
+                        match &origin {
+                            Some((origin_unit, origin_entry)) => {
+                                get_type_attribute(origin_entry, &self.units, *origin_unit)
+                            }
+                            None => Err(err),
+                        }
+                    })?;
                 if let Ok(mut membertype) =
                     self.get_type(new_cur_unit, new_dbginfo_offset, typereader_data)
                 {
                     // wrap bitfield members in a TypeInfo::Bitfield to store bit_size and bit_offset
                     if let Some(bit_size) = get_bit_size_attribute(child_entry) {
                         let dbginfo_offset =
-                            child_entry.offset().to_debug_info_offset(unit).unwrap().0;
+                            self.units.get_offset(current_unit, child_entry.offset());
                         if let Some(bit_offset) = get_bit_offset_attribute(child_entry) {
                             // Dwarf 2 / 3
                             let type_size = membertype.get_size();
@@ -713,6 +1202,7 @@ impl DebugDataReader<'_> {
                                     bit_size: bit_size as u16,
                                     bit_offset: bit_offset_le as u16,
                                 },
+                                byte_size: None,
                             };
                         } else if let Some(mut data_bit_offset) =
                             get_data_bit_offset_attribute(child_entry)
@@ -724,7 +1214,9 @@ impl DebugDataReader<'_> {
                             let type_size_bits = type_size * 8;
                             if data_bit_offset >= type_size_bits {
                                 // Dwarf 4 / 5: re-calculate offset
-                                offset += (data_bit_offset / type_size_bits) * type_size;
+                                offset = offset.map(|offset| {
+                                    offset + (data_bit_offset / type_size_bits) * type_size
+                                });
                                 data_bit_offset %= type_size_bits;
                             }
                             if self.endian == Endianness::Big {
@@ -743,6 +1235,7 @@ impl DebugDataReader<'_> {
                                     bit_size: bit_size as u16,
                                     bit_offset: data_bit_offset as u16,
                                 },
+                                byte_size: None,
                             };
                         }
                     }
@@ -754,6 +1247,7 @@ impl DebugDataReader<'_> {
                             if matches!(membertype.datatype, DbgDataType::Struct { .. })
                                 || matches!(membertype.datatype, DbgDataType::Union { .. })
                                 || matches!(membertype.datatype, DbgDataType::Class { .. })
+                                || matches!(membertype.datatype, DbgDataType::VariantEnum { .. })
                             {
                                 membertype.datatype = DbgDataType::TypeRef(
                                     // new_dbginfo_offset.0,
@@ -780,7 +1274,8 @@ impl DebugDataReader<'_> {
                                 ..
                             } => {
                                 for (am_name, (am_type, am_offset)) in anon_members {
-                                    members.insert(am_name, (am_type, offset + am_offset));
+                                    let am_offset = offset.zip(am_offset).map(|(a, b)| a + b);
+                                    members.insert(am_name, (am_type, am_offset));
                                 }
                             }
                             _ => {}
@@ -798,25 +1293,36 @@ impl DebugDataReader<'_> {
         entries_tree: EntriesTreeNode<EndianSlice<RunTimeEndian>>,
         current_unit: usize,
         typereader_data: &mut TypeReaderData,
-    ) -> Result<IndexMap<String, (TypeInfo, u64)>, String> {
+    ) -> Result<IndexMap<String, BaseClass>, String> {
         let (unit, _) = &self.units[current_unit];
-        let mut inheritance = IndexMap::<String, (TypeInfo, u64)>::new();
+        let mut inheritance = IndexMap::<String, BaseClass>::new();
         let mut iter = entries_tree.children();
         while let Ok(Some(child_node)) = iter.next() {
             let child_entry = child_node.entry();
             if child_entry.tag() == gimli::constants::DW_TAG_inheritance {
-                let data_location = get_data_member_location_attribute(
-                    self,
-                    child_entry,
-                    unit.encoding(),
-                    current_unit,
-                )
-                .ok_or_else(|| "missing byte offset for inherited class".to_string())?;
+                // the location of a virtual base class is given by an expression that reads the offset from the vtable
+                let is_virtual = get_virtuality_attribute(child_entry)
+                    .is_some_and(|virtuality| virtuality != gimli::constants::DW_VIRTUALITY_none);
+                // a location expression that can't be evaluated statically is treated like the location of a virtual base
+                let data_location = if is_virtual {
+                    None
+                } else {
+                    get_data_member_location_attribute(
+                        self,
+                        child_entry,
+                        unit.encoding(),
+                        current_unit,
+                    )
+                    .ok_or_else(|| "missing byte offset for inherited class".to_string())?
+                };
                 let (new_cur_unit, new_dbginfo_offset) =
                     get_type_attribute(child_entry, &self.units, current_unit)?;
 
                 let (unit, abbrev) = &self.units[new_cur_unit];
-                let new_unit_offset = new_dbginfo_offset.to_unit_offset(unit).unwrap();
+                let new_unit_offset = self
+                    .units
+                    .get_unit_offset(new_cur_unit, new_dbginfo_offset.0)
+                    .ok_or_else(|| "invalid base class offset".to_string())?;
                 let mut baseclass_tree = unit
                     .entries_tree(abbrev, Some(new_unit_offset))
                     .map_err(|err| err.to_string())?;
@@ -827,18 +1333,39 @@ impl DebugDataReader<'_> {
                 let baseclass_type =
                     self.get_type(new_cur_unit, new_dbginfo_offset, typereader_data)?;
 
-                inheritance.insert(baseclass_name, (baseclass_type, data_location));
+                inheritance.insert(
+                    baseclass_name,
+                    BaseClass {
+                        typeinfo: baseclass_type,
+                        offset: data_location,
+                        is_virtual,
+                    },
+                );
             }
         }
         Ok(inheritance)
     }
 }
 
+// get the stride of an array or of one of its dimensions in bits, from DW_AT_byte_stride or DW_AT_bit_stride
+fn get_stride_bits(
+    entry: &gimli::DebuggingInformationEntry<EndianSlice<RunTimeEndian>, usize>,
+) -> Option<u64> {
+    get_byte_stride_attribute(entry)
+        .map(|byte_stride| byte_stride * 8)
+        .or_else(|| get_bit_stride_attribute(entry))
+}
+
 fn get_base_type(
     entry: &gimli::DebuggingInformationEntry<EndianSlice<RunTimeEndian>, usize>,
     unit: &gimli::UnitHeader<EndianSlice<RunTimeEndian>>,
+    typename: Option<&str>,
 ) -> (DbgDataType, String) {
-    let byte_size = get_byte_size_attribute(entry).unwrap_or(1u64);
+    // base types whose values don't fill whole bytes, e.g. _BitInt(12), may have a DW_AT_bit_size instead of,
+    // or in addition to DW_AT_byte_size. Without a byte size, the storage size is derived from the bit size
+    let byte_size = get_byte_size_attribute(entry)
+        .or_else(|| get_bit_size_attribute(entry).map(bit_int_storage_size))
+        .unwrap_or(1u64);
     let encoding = get_encoding_attribute(entry).unwrap_or(gimli::constants::DW_ATE_unsigned);
     match encoding {
         gimli::constants::DW_ATE_address => {
@@ -849,62 +1376,67 @@ fn get_base_type(
                 "unknown".to_string(),
             )
         }
-        gimli::constants::DW_ATE_float => {
-            if byte_size == 8 {
-                (DbgDataType::Double, "double".to_string())
-            } else {
-                (DbgDataType::Float, "float".to_string())
+        gimli::constants::DW_ATE_float => match byte_size {
+            4 => (DbgDataType::Float, "float".to_string()),
+            8 => (DbgDataType::Double, "double".to_string()),
+            // 16 byte floating point types can either be the padded x87 long double, or IEEE 754 binary128.
+            // The encoding is the same for both, so they can only be distinguished by the type name
+            16 if matches!(typename, Some("_Float128" | "__float128" | "__ieee128")) => {
+                (DbgDataType::Float128, "float128".to_string())
             }
+            // the x87 extended precision type has 80 bits, but it is padded to 12 or 16 bytes
+            10 | 12 | 16 => (
+                DbgDataType::LongDouble(byte_size),
+                "long double".to_string(),
+            ),
+            _ => (DbgDataType::Other(byte_size), "other".to_string()),
+        },
+        gimli::constants::DW_ATE_boolean if byte_size == 1 => {
+            (DbgDataType::Bool, "bool".to_string())
+        }
+        gimli::constants::DW_ATE_signed_char if byte_size == 1 => {
+            (DbgDataType::SChar, "schar".to_string())
+        }
+        gimli::constants::DW_ATE_unsigned_char | gimli::constants::DW_ATE_UTF if byte_size == 1 => {
+            (DbgDataType::UChar, "uchar".to_string())
         }
         gimli::constants::DW_ATE_signed | gimli::constants::DW_ATE_signed_char => match byte_size {
             1 => (DbgDataType::Sint8, "sint8".to_string()),
             2 => (DbgDataType::Sint16, "sint16".to_string()),
             4 => (DbgDataType::Sint32, "sint32".to_string()),
             8 => (DbgDataType::Sint64, "sint64".to_string()),
+            16 => (DbgDataType::Sint128, "sint128".to_string()),
             _ => (DbgDataType::Other(byte_size), "double".to_string()),
         },
+        // multi-byte booleans and characters (e.g. char16_t, char32_t) are treated as plain integers
         gimli::constants::DW_ATE_boolean
         | gimli::constants::DW_ATE_unsigned
-        | gimli::constants::DW_ATE_unsigned_char => match byte_size {
+        | gimli::constants::DW_ATE_unsigned_char
+        | gimli::constants::DW_ATE_UTF => match byte_size {
             1 => (DbgDataType::Uint8, "uint8".to_string()),
             2 => (DbgDataType::Uint16, "uint16".to_string()),
             4 => (DbgDataType::Uint32, "uint32".to_string()),
             8 => (DbgDataType::Uint64, "uint64".to_string()),
+            16 => (DbgDataType::Uint128, "uint128".to_string()),
             _ => (DbgDataType::Other(byte_size), "other".to_string()),
         },
         _other => (DbgDataType::Other(byte_size), "other".to_string()),
     }
 }
 
-impl WipItemInfo {
-    fn new(offset: usize, name: Option<String>, tag: DwTag) -> Self {
-        Self { offset, name, tag }
+// get the number of bytes that hold an integer of bit_size bits, like a _BitInt(N): the next power of two
+// of the number of bytes up to 8 bytes, and a multiple of 8 bytes above that
+fn bit_int_storage_size(bit_size: u64) -> u64 {
+    let bytes = bit_size.div_ceil(8).max(1);
+    if bytes <= 8 {
+        bytes.next_power_of_two()
+    } else {
+        bytes.next_multiple_of(8)
     }
 }
 
-impl TypeReaderData {
-    // get_pointer_name() is a solution for a really ugly edge case:
-    // Data structures can reference themselves using pointers.
-    // Since types are normally read recursively, this would case would result in an infinite loop.
-    // The fix is to keep track of in-progress types in self.wip_items, and break the recursion if needed.
-    // Now pointers have a new problem: they normally get their names from the pointed-to child type, whose info is not available yet
-    // Here we try to recover a name from the wip_items stack
-    fn get_pointer_name(&self, idx: usize) -> Option<String> {
-        let mut nameidx = idx;
-        while nameidx < self.wip_items.len() {
-            if self.wip_items[nameidx].name.is_some() {
-                return self.wip_items[nameidx].name.clone();
-            }
-            // if the type would propagate its name backward, we're allowed to look further up the stack
-            if !(self.wip_items[nameidx].tag == gimli::constants::DW_TAG_const_type
-                || self.wip_items[nameidx].tag == gimli::constants::DW_TAG_volatile_type
-                || self.wip_items[nameidx].tag == gimli::constants::DW_TAG_pointer_type
-                || self.wip_items[nameidx].tag == gimli::constants::DW_TAG_array_type)
-            {
-                return None;
-            }
-            nameidx += 1;
-        }
-        None
+impl WipItemInfo {
+    fn new(offset: usize, name: Option<String>, tag: DwTag) -> Self {
+        Self { offset, name, tag }
     }
 }
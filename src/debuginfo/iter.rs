@@ -1,5 +1,5 @@
 use crate::debuginfo::{DbgDataType, DebugData, TypeInfo, VarInfo};
-use crate::symbol::SymbolInfo;
+use crate::symbol::{MatchKind, SymbolInfo};
 use std::collections::HashMap;
 use std::fmt::Write;
 
@@ -9,7 +9,39 @@ pub(crate) struct TypeInfoIter<'dbg> {
     position_stack: Vec<usize>,
     offset_stack: Vec<u64>,
     name_stack: Vec<String>,
+    // the array indices on the path to the current item, and the number of them that belong to each level of the stack
+    indices: Vec<u64>,
+    indices_len_stack: Vec<usize>,
     use_new_arrays: bool,
+    // C strings are returned as one item, without their characters
+    strings_as_values: bool,
+}
+
+/// a scalar element of a symbol, with the information that is needed to write it as an A2L MEASUREMENT
+#[derive(Debug, Clone)]
+#[cfg(test)]
+pub(crate) struct FlatRecord<'dbg> {
+    /// the name of the element, e.g. "grid._1_._0_.corners._2_.x"
+    pub(crate) full_name: String,
+    pub(crate) address: u64,
+    /// the type of the element; for bitfields this is the type of the storage that contains the bits
+    pub(crate) base_type: &'dbg TypeInfo,
+    pub(crate) is_bitfield: bool,
+    /// position and width of a bitfield in its storage; both are 0 if the element is not a bitfield
+    pub(crate) bit_offset: u16,
+    pub(crate) bit_size: u16,
+    /// the indices of all arrays that contain the element, from the outermost to the innermost array
+    pub(crate) dim_indices: Vec<u64>,
+}
+
+/// iterator over the scalar elements of a symbol, see DebugData::flatten_symbol()
+#[cfg(test)]
+pub(crate) struct FlattenedSymbol<'dbg> {
+    name: String,
+    address: u64,
+    // the symbol itself, if it is a scalar
+    own_record: Option<FlatRecord<'dbg>>,
+    type_iter: TypeInfoIter<'dbg>,
 }
 
 pub(crate) struct VariablesIterator<'dbg> {
@@ -46,12 +78,33 @@ impl<'dbg> TypeInfoIter<'dbg> {
             position_stack: vec![0],
             offset_stack: vec![0],
             name_stack: vec!["".to_string()],
+            indices: Vec::new(),
+            indices_len_stack: vec![0],
             use_new_arrays,
+            strings_as_values: false,
         }
     }
 
+    /// get the array indices on the path to the item that was returned last, from the outermost to the innermost array
+    #[cfg(test)]
+    pub(crate) fn current_indices(&self) -> &[u64] {
+        let len = self.indices_len_stack.last().copied().unwrap_or(0);
+        &self.indices[..len]
+    }
+
+    /// don't return the characters of C strings, see TypeInfo::is_c_string()
+    #[cfg(test)]
+    pub(crate) fn strings_as_values(mut self) -> Self {
+        self.strings_as_values = true;
+        self
+    }
+
     fn next_core(&mut self) -> Option<(String, &'dbg TypeInfo, u64)> {
-        match &self.type_stack.last()?.datatype {
+        let current_type = self.type_stack.last()?;
+        if self.strings_as_values && current_type.is_c_string(self.types) {
+            return None;
+        }
+        match &current_type.datatype {
             DbgDataType::Class { members, .. }
             | DbgDataType::Struct { members, .. }
             | DbgDataType::Union { members, .. } => {
@@ -61,18 +114,26 @@ impl<'dbg> TypeInfoIter<'dbg> {
                 let prev_name = &self.name_stack[depth];
                 let (member_name, (member_typeinfo, member_offset)) =
                     members.get_index(position)?;
-                let member_typeinfo = member_typeinfo.get_reference(self.types);
-                let complete_offset = base + member_offset;
-                let fullname = format!("{prev_name}.{member_name}");
 
                 // advance to next member
                 self.position_stack[depth] += 1;
 
+                let Some(member_offset) = member_offset else {
+                    // the location of the member is only known at runtime, so it can't be listed with an offset
+                    return self.next_core();
+                };
+                let member_typeinfo = member_typeinfo.get_reference(self.types);
+                let complete_offset = base + member_offset;
+                let fullname = format!("{prev_name}.{member_name}");
+
                 // prepare to return the children of the current member
                 self.type_stack.push(member_typeinfo);
                 self.position_stack.push(0);
                 self.offset_stack.push(complete_offset);
                 self.name_stack.push(fullname.clone());
+                let indices_len = self.indices_len_stack[depth];
+                self.indices.truncate(indices_len);
+                self.indices_len_stack.push(indices_len);
 
                 Some((fullname, member_typeinfo, complete_offset))
             }
@@ -81,8 +142,13 @@ impl<'dbg> TypeInfoIter<'dbg> {
                 dim,
                 stride,
                 arraytype,
+                lower_bounds,
+                ..
             } => {
-                let total_elemcount = size / stride;
+                // the elements of an array of empty structs have a stride of 0
+                let total_elemcount = size
+                    .checked_div(*stride)
+                    .unwrap_or_else(|| dim.iter().product());
                 let depth = self.type_stack.len() - 1;
                 let position = self.position_stack[depth] as u64;
                 let prev_name = &self.name_stack[depth];
@@ -94,9 +160,10 @@ impl<'dbg> TypeInfoIter<'dbg> {
                     let mut current_indices = vec![0; dim.len()];
                     let mut rem = position;
 
-                    // going backward over the list of array dimensions, divide and keep the remainder
+                    // going backward over the list of array dimensions, divide and keep the remainder.
+                    // The indices are shifted by the lower bounds, so that they match the indices in the source code
                     for idx in (0..dim.len()).rev() {
-                        current_indices[idx] = rem % dim[idx];
+                        current_indices[idx] = rem % dim[idx] + lower_bounds.get(idx).unwrap_or(&0);
                         rem /= dim[idx];
                     }
                     let idxstr =
@@ -122,6 +189,9 @@ impl<'dbg> TypeInfoIter<'dbg> {
                     self.position_stack.push(0);
                     self.offset_stack.push(complete_offset);
                     self.name_stack.push(idxstr.clone());
+                    self.indices.truncate(self.indices_len_stack[depth]);
+                    self.indices.extend(&current_indices);
+                    self.indices_len_stack.push(self.indices.len());
 
                     Some((idxstr, arraytype, complete_offset))
                 } else {
@@ -137,6 +207,7 @@ impl<'dbg> TypeInfoIter<'dbg> {
         self.position_stack.pop();
         self.name_stack.pop();
         self.offset_stack.pop();
+        self.indices_len_stack.pop();
     }
 
     // pub(crate) fn next_sibling(&mut self) -> Option<(String, &'dbg TypeInfo, u64)> {
@@ -145,6 +216,70 @@ impl<'dbg> TypeInfoIter<'dbg> {
     // }
 }
 
+#[cfg(test)]
+impl<'dbg> FlattenedSymbol<'dbg> {
+    pub(crate) fn new(types: &'dbg HashMap<usize, TypeInfo>, symbol: &SymbolInfo<'dbg>) -> Self {
+        let typeinfo = symbol.typeinfo.get_reference(types);
+        let own_record = typeinfo
+            .is_scalar()
+            .then(|| make_flat_record(symbol.name.clone(), symbol.address, typeinfo, Vec::new()));
+        Self {
+            name: symbol.name.clone(),
+            address: symbol.address,
+            own_record,
+            type_iter: TypeInfoIter::new(types, typeinfo, false),
+        }
+    }
+}
+
+#[cfg(test)]
+impl<'dbg> Iterator for FlattenedSymbol<'dbg> {
+    type Item = FlatRecord<'dbg>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(own_record) = self.own_record.take() {
+            return Some(own_record);
+        }
+        for (component_name, typeinfo, offset) in self.type_iter.by_ref() {
+            if typeinfo.is_scalar() {
+                return Some(make_flat_record(
+                    format!("{}{component_name}", self.name),
+                    self.address + offset,
+                    typeinfo,
+                    self.type_iter.current_indices().to_vec(),
+                ));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+fn make_flat_record<'dbg>(
+    full_name: String,
+    address: u64,
+    typeinfo: &'dbg TypeInfo,
+    dim_indices: Vec<u64>,
+) -> FlatRecord<'dbg> {
+    let (base_type, is_bitfield, bit_offset, bit_size) = match &typeinfo.datatype {
+        DbgDataType::Bitfield {
+            basetype,
+            bit_offset,
+            bit_size,
+        } => (&**basetype, true, *bit_offset, *bit_size),
+        _ => (typeinfo, false, 0, 0),
+    };
+    FlatRecord {
+        full_name,
+        address,
+        base_type,
+        is_bitfield,
+        bit_offset,
+        bit_size,
+        dim_indices,
+    }
+}
+
 impl<'dbg> Iterator for VariablesIterator<'dbg> {
     type Item = SymbolInfo<'dbg>;
 
@@ -153,6 +288,12 @@ impl<'dbg> Iterator for VariablesIterator<'dbg> {
             if self.position < list.len() {
                 let varinfo = &list[self.position];
                 let is_unique = list.len() == 1;
+                let Some(address) = varinfo.address.filter(|_| !varinfo.is_tls()) else {
+                    // compile-time constants have no address, so they don't contain any symbols.
+                    // The address of thread-local variables is only an offset into the TLS block
+                    self.position += 1;
+                    return self.next();
+                };
 
                 if self.type_iter.is_none() {
                     // newly set current_var, should be returned before using type_iter to return its sub-elements
@@ -168,24 +309,40 @@ impl<'dbg> Iterator for VariablesIterator<'dbg> {
                     ));
                     Some(SymbolInfo {
                         name: varname.to_string(),
-                        address: varinfo.address,
+                        address,
                         typeinfo,
                         unit_idx: varinfo.unit_idx,
                         function_name: &varinfo.function,
                         namespaces: &varinfo.namespaces,
                         is_unique,
+                        is_tls: varinfo.is_tls(),
+                        storage: varinfo.storage,
+                        source_location: &varinfo.decl_location,
+                        dbginfo_offset: varinfo.dbginfo_offset,
+                        match_kind: MatchKind::Exact,
+                        const_value: None,
+                        is_const: varinfo.is_const,
+                        is_external: varinfo.is_external,
                     })
                 } else if let Some((var_component_name, typeinfo, offset)) =
                     self.type_iter.as_mut().unwrap().next()
                 {
                     Some(SymbolInfo {
                         name: format!("{varname}{var_component_name}"),
-                        address: varinfo.address + offset,
+                        address: address + offset,
                         typeinfo,
                         unit_idx: varinfo.unit_idx,
                         function_name: &varinfo.function,
                         namespaces: &varinfo.namespaces,
                         is_unique,
+                        is_tls: varinfo.is_tls(),
+                        storage: varinfo.storage,
+                        source_location: &varinfo.decl_location,
+                        dbginfo_offset: varinfo.dbginfo_offset,
+                        match_kind: MatchKind::Exact,
+                        const_value: None,
+                        is_const: varinfo.is_const,
+                        is_external: varinfo.is_external,
                     })
                 } else {
                     // reached the end of this type_iter, try to advance to the next position within the list
@@ -213,6 +370,7 @@ impl<'dbg> VariablesIterator<'dbg> {
         unit_idx: usize::MAX,
         datatype: DbgDataType::Sint16,
         dbginfo_offset: 0,
+        byte_size: None,
     };
 
     pub(crate) fn new(debugdata: &'dbg DebugData, use_new_arrays: bool) -> Self {
@@ -243,6 +401,7 @@ impl<'dbg> VariablesIterator<'dbg> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::debuginfo::LocationKind;
     use indexmap::IndexMap;
 
     const DEFAULT_TYPEINFO: TypeInfo = TypeInfo {
@@ -250,6 +409,7 @@ mod test {
         unit_idx: usize::MAX,
         datatype: DbgDataType::Sint16,
         dbginfo_offset: 0,
+        byte_size: None,
     };
 
     #[test]
@@ -275,12 +435,12 @@ mod test {
             datatype: DbgDataType::Uint64,
             ..DEFAULT_TYPEINFO.clone()
         };
-        let mut structmembers_a: IndexMap<String, (TypeInfo, u64)> = IndexMap::new();
-        structmembers_a.insert("structmember_1".to_string(), (t_uint64.clone(), 0));
-        structmembers_a.insert("structmember_2".to_string(), (t_uint64.clone(), 0));
-        structmembers_a.insert("structmember_3".to_string(), (t_uint64.clone(), 0));
-        structmembers_a.insert("structmember_4".to_string(), (t_uint64.clone(), 0));
-        structmembers_a.insert("structmember_5".to_string(), (t_uint64.clone(), 0));
+        let mut structmembers_a: IndexMap<String, (TypeInfo, Option<u64>)> = IndexMap::new();
+        structmembers_a.insert("structmember_1".to_string(), (t_uint64.clone(), Some(0)));
+        structmembers_a.insert("structmember_2".to_string(), (t_uint64.clone(), Some(0)));
+        structmembers_a.insert("structmember_3".to_string(), (t_uint64.clone(), Some(0)));
+        structmembers_a.insert("structmember_4".to_string(), (t_uint64.clone(), Some(0)));
+        structmembers_a.insert("structmember_5".to_string(), (t_uint64.clone(), Some(0)));
         let typeinfo_inner_1 = TypeInfo {
             datatype: DbgDataType::Struct {
                 size: 64,
@@ -288,10 +448,10 @@ mod test {
             },
             ..DEFAULT_TYPEINFO.clone()
         };
-        let mut structmembers_b: IndexMap<String, (TypeInfo, u64)> = IndexMap::new();
-        structmembers_b.insert("foobar_1".to_string(), (t_sint8.clone(), 0));
-        structmembers_b.insert("foobar_2".to_string(), (t_sint8.clone(), 0));
-        structmembers_b.insert("foobar_3".to_string(), (t_sint8.clone(), 0));
+        let mut structmembers_b: IndexMap<String, (TypeInfo, Option<u64>)> = IndexMap::new();
+        structmembers_b.insert("foobar_1".to_string(), (t_sint8.clone(), Some(0)));
+        structmembers_b.insert("foobar_2".to_string(), (t_sint8.clone(), Some(0)));
+        structmembers_b.insert("foobar_3".to_string(), (t_sint8.clone(), Some(0)));
         let typeinfo_inner_2 = TypeInfo {
             datatype: DbgDataType::Struct {
                 size: 64,
@@ -309,9 +469,9 @@ mod test {
             datatype: DbgDataType::TypeRef(101, 0),
             ..DEFAULT_TYPEINFO.clone()
         };
-        let mut structmembers: IndexMap<String, (TypeInfo, u64)> = IndexMap::new();
-        structmembers.insert("inner_a".to_string(), (typeref_inner_1, 0));
-        structmembers.insert("inner_b".to_string(), (typeref_inner_2, 0));
+        let mut structmembers: IndexMap<String, (TypeInfo, Option<u64>)> = IndexMap::new();
+        structmembers.insert("inner_a".to_string(), (typeref_inner_1, Some(0)));
+        structmembers.insert("inner_b".to_string(), (typeref_inner_2, Some(0)));
         let typeinfo = TypeInfo {
             datatype: DbgDataType::Struct {
                 size: 64,
@@ -329,50 +489,90 @@ mod test {
         variables.insert(
             "var_a".to_string(),
             vec![VarInfo {
-                address: 1,
+                address: Some(1),
                 typeref: 0,
+                dbginfo_offset: 0,
                 unit_idx: 0,
                 function: None,
                 namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
             }],
         );
         variables.insert(
             "var_b".to_string(),
             vec![VarInfo {
-                address: 2,
+                address: Some(2),
                 typeref: 0,
+                dbginfo_offset: 0,
                 unit_idx: 0,
                 function: None,
                 namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
             }],
         );
         variables.insert(
             "var_c".to_string(),
             vec![
                 VarInfo {
-                    address: 3,
+                    address: Some(3),
                     typeref: 1,
+                    dbginfo_offset: 0,
                     unit_idx: 0,
                     function: None,
                     namespaces: vec![],
+                    location_kind: LocationKind::Static,
+                    storage: None,
+                    decl_location: None,
+                    artificial: false,
+                    const_value: None,
+                    is_const: false,
+                    is_external: false,
                 },
                 VarInfo {
-                    address: 33,
+                    address: Some(33),
                     typeref: 1,
+                    dbginfo_offset: 0,
                     unit_idx: 1,
                     function: None,
                     namespaces: vec![],
+                    location_kind: LocationKind::Static,
+                    storage: None,
+                    decl_location: None,
+                    artificial: false,
+                    const_value: None,
+                    is_const: false,
+                    is_external: false,
                 },
             ],
         );
         variables.insert(
             "var_d_wo_type_info".to_string(),
             vec![VarInfo {
-                address: 4,
+                address: Some(4),
                 typeref: 404, // some number with no correspondence in the types hash map
+                dbginfo_offset: 0,
                 unit_idx: 0,
                 function: None,
                 namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
             }],
         );
 
@@ -381,9 +581,9 @@ mod test {
             datatype: DbgDataType::Uint8,
             ..DEFAULT_TYPEINFO.clone()
         };
-        let mut structmembers: IndexMap<String, (TypeInfo, u64)> = IndexMap::new();
-        structmembers.insert("member_1".to_string(), (t_uint8.clone(), 0));
-        structmembers.insert("member_2".to_string(), (t_uint8.clone(), 1));
+        let mut structmembers: IndexMap<String, (TypeInfo, Option<u64>)> = IndexMap::new();
+        structmembers.insert("member_1".to_string(), (t_uint8.clone(), Some(0)));
+        structmembers.insert("member_2".to_string(), (t_uint8.clone(), Some(1)));
         let structtype = TypeInfo {
             datatype: DbgDataType::Struct {
                 size: 64,
@@ -400,6 +600,12 @@ mod test {
             demangled_names,
             unit_names: vec![Some("file_a.c".to_string()), Some("file_b.c".to_string())],
             sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: object::Endianness::Little,
+            architecture: object::Architecture::Unknown,
         };
 
         // test iter.next_sibling()
@@ -418,4 +624,63 @@ mod test {
         }
         assert_eq!(count, 5);
     }
+
+    #[test]
+    fn test_flatten_symbol() {
+        let debugdata = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/nested_array_test.elf"),
+            false,
+        )
+        .unwrap();
+        let grid = crate::symbol::find_symbol("grid", &debugdata).unwrap();
+        let records: Vec<FlatRecord> = debugdata.flatten_symbol(&grid).collect();
+        // each of the 4 * 2 cells has an id, 3 corners with x and y, and a flag
+        assert_eq!(records.len(), 4 * 2 * 8);
+        assert_eq!(records[0].full_name, "grid._0_._0_.id");
+        assert_eq!(records[0].dim_indices, [0, 0]);
+        assert_eq!(records[1].full_name, "grid._0_._0_.corners._0_.x");
+        assert_eq!(records[1].dim_indices, [0, 0, 0]);
+
+        let record = records
+            .iter()
+            .find(|record| record.full_name == "grid._3_._1_.corners._2_.y")
+            .unwrap();
+        assert_eq!(record.dim_indices, [3, 1, 2]);
+        assert!(!record.is_bitfield);
+        assert_eq!((record.bit_offset, record.bit_size), (0, 0));
+        assert!(matches!(record.base_type.datatype, DbgDataType::Sint16));
+        let symbol = crate::symbol::find_symbol(&record.full_name, &debugdata).unwrap();
+        assert_eq!(record.address, symbol.address);
+        // the flag after the nested array only has the indices of the outer array
+        let record = records
+            .iter()
+            .find(|record| record.full_name == "grid._3_._1_.flag")
+            .unwrap();
+        assert_eq!(record.dim_indices, [3, 1]);
+
+        // a scalar symbol is its own only record
+        let corner = crate::symbol::find_symbol("grid._1_._0_.corners._1_.x", &debugdata).unwrap();
+        let records: Vec<FlatRecord> = debugdata.flatten_symbol(&corner).collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].full_name, "grid._1_._0_.corners._1_.x");
+        assert_eq!(records[0].address, corner.address);
+        assert!(records[0].dim_indices.is_empty());
+
+        // bitfields are returned with their storage type, bit offset and bit size
+        let debugdata =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/enum_test.elf"), false)
+                .unwrap();
+        let control = crate::symbol::find_symbol("control", &debugdata).unwrap();
+        let records: Vec<FlatRecord> = debugdata.flatten_symbol(&control).collect();
+        let names: Vec<&str> = records
+            .iter()
+            .map(|record| record.full_name.as_str())
+            .collect();
+        assert_eq!(names, ["control.mode", "control.gear", "control.level"]);
+        let gear = &records[1];
+        assert!(gear.is_bitfield);
+        assert_eq!((gear.bit_offset, gear.bit_size), (0, 5));
+        assert!(matches!(gear.base_type.datatype, DbgDataType::Enum { .. }));
+        assert_eq!(gear.address, control.address + 4);
+    }
 }
@@ -0,0 +1,125 @@
+use crate::debuginfo::{DebugData, TypeInfo, get_section_name};
+use serde::Serialize;
+
+// one entry of the JSON list of symbols. Sizes are numbers, but addresses are hex strings, which are easier to read
+#[derive(Serialize)]
+struct JsonSymbol<'dbg> {
+    name: String,
+    address: String,
+    size: u64,
+    datatype: String,
+    unit: Option<&'dbg str>,
+    section: Option<&'dbg str>,
+}
+
+// write all variables as a JSON array. If expand_leaves is set, then each variable is replaced by its scalar
+// elements, e.g. "params.limits[1]". Compile-time constants have no address, so they are not included
+pub(crate) fn to_json(debug_data: &DebugData, expand_leaves: bool) -> String {
+    let make_entry = |name: String, address: u64, typeinfo: &TypeInfo, unit_idx: usize| {
+        let typeinfo = typeinfo.get_reference(&debug_data.types);
+        JsonSymbol {
+            name,
+            address: format!("{address:#x}"),
+            size: typeinfo.get_size(),
+            datatype: typeinfo.to_string(),
+            unit: debug_data.unit_name(unit_idx),
+            section: get_section_name(&debug_data.sections, address),
+        }
+    };
+
+    let symbols: Vec<JsonSymbol> = if expand_leaves {
+        debug_data
+            .iter(true)
+            .filter(|symbol| symbol.typeinfo.get_reference(&debug_data.types).is_scalar())
+            .map(|symbol| {
+                make_entry(
+                    symbol.name,
+                    symbol.address,
+                    symbol.typeinfo,
+                    symbol.unit_idx,
+                )
+            })
+            .collect()
+    } else {
+        debug_data
+            .variables
+            .iter()
+            .flat_map(|(name, varinfo_list)| {
+                varinfo_list.iter().filter_map(move |varinfo| {
+                    let address = varinfo.address?;
+                    let typeinfo = debug_data.types.get(&varinfo.typeref)?;
+                    Some((name, address, typeinfo, varinfo.unit_idx))
+                })
+            })
+            .map(|(name, address, typeinfo, unit_idx)| {
+                make_entry(name.clone(), address, typeinfo, unit_idx)
+            })
+            .collect()
+    };
+    serde_json::to_string_pretty(&symbols).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_to_json() {
+        let debug_data =
+            DebugData::load_dwarf(OsStr::new("fixtures/bin/namespace_test.elf"), false).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&debug_data.to_json(false)).unwrap();
+        let symbols = json.as_array().unwrap();
+        assert_eq!(
+            symbols.len(),
+            debug_data.variables.values().flatten().count()
+        );
+        for symbol in symbols {
+            let object = symbol.as_object().unwrap();
+            let mut keys: Vec<&str> = object.keys().map(String::as_str).collect();
+            keys.sort_unstable();
+            assert_eq!(
+                keys,
+                ["address", "datatype", "name", "section", "size", "unit"]
+            );
+            assert!(object["name"].is_string());
+            assert!(object["address"].as_str().unwrap().starts_with("0x"));
+            assert!(object["size"].is_u64());
+            assert!(object["datatype"].is_string());
+            assert_eq!(object["unit"], "namespace_test.cpp");
+            assert!(object["section"].is_string());
+        }
+        let params = symbols
+            .iter()
+            .find(|symbol| symbol["name"] == "params")
+            .unwrap();
+        assert_eq!(params["address"], "0x403000");
+        assert_eq!(params["size"], 8);
+        assert_eq!(params["datatype"], "Struct Params(2 members)");
+        assert_eq!(params["section"], ".bss");
+
+        // the leaves of params are its scalar members and array elements
+        let json: serde_json::Value = serde_json::from_str(&debug_data.to_json(true)).unwrap();
+        let leaves: Vec<(&str, &str)> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|symbol| symbol["name"].as_str().unwrap().starts_with("params"))
+            .map(|symbol| {
+                (
+                    symbol["name"].as_str().unwrap(),
+                    symbol["address"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            leaves,
+            [
+                ("params.gain", "0x403000"),
+                ("params.limits[0]", "0x403004"),
+                ("params.limits[1]", "0x403006")
+            ]
+        );
+    }
+}
@@ -1,19 +1,91 @@
 use indexmap::IndexMap;
+use object::{Architecture, Endianness};
 use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 mod dwarf;
+#[cfg(test)]
+pub(crate) use dwarf::UnitCache;
 pub(crate) mod iter;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "pdb")]
 mod pdb;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct VarInfo {
-    pub(crate) address: u64,
+    // None for compile-time constants, which only have a const_value
+    pub(crate) address: Option<u64>,
     pub(crate) typeref: usize,
+    // offset of the DIE of the variable in .debug_info, like the offsets shown by readelf --debug-dump=info.
+    // For PDB files this is the index of the symbol record. Merged files are shifted like the type offsets
+    pub(crate) dbginfo_offset: usize,
     pub(crate) unit_idx: usize,
     pub(crate) function: Option<String>,
     pub(crate) namespaces: Vec<String>,
+    // how the address of the variable is given, see LocationKind
+    pub(crate) location_kind: LocationKind,
+    // kind of storage, based on the section that contains the address. None if no section matches
+    pub(crate) storage: Option<Storage>,
+    // source file and line of the declaration, from DW_AT_decl_file and DW_AT_decl_line
+    pub(crate) decl_location: Option<(String, u64)>,
+    // compiler-generated variable (DW_AT_artificial). These are only loaded if LoadOptions::include_artificial is set,
+    // which the command line never does, so the flag is only read by the tests
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) artificial: bool,
+    // the value of a constant from DW_AT_const_value, e.g. of a const global whose storage was optimized away
+    pub(crate) const_value: Option<ConstValue>,
+    // the type of the variable is const-qualified, possibly through a typedef. The constness of
+    // the target of a pointer doesn't count: "const char *text" is not const, but "char *const text" is
+    pub(crate) is_const: bool,
+    // the variable is visible outside of its unit (DW_AT_external), unlike a static variable in C
+    pub(crate) is_external: bool,
+}
+
+impl VarInfo {
+    // thread-local variables: the address is an offset into the TLS block
+    pub(crate) fn is_tls(&self) -> bool {
+        self.location_kind == LocationKind::Tls
+    }
+}
+
+/// the value of a constant that only exists at compile time
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ConstValue {
+    /// the value of a signed integer type
+    Int(i64),
+    /// the value of any other integer type, e.g. an unsigned integer, a bool or an enum
+    Uint(u64),
+    /// the value of a float or double
+    Float(f64),
+    /// the bytes of a value of any other type, in the byte order of the target, e.g. of a struct or a string
+    Bytes(Vec<u8>),
+}
+
+/// the way the location of a variable is described by the debug info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LocationKind {
+    /// the variable has a fixed address
+    Static,
+    /// the variable is thread-local: the address is an offset into the TLS block
+    Tls,
+    /// the variable is located at an offset from the value of a register, e.g. "DW_OP_bregx 13 0x100".
+    /// Some embedded linker overlay schemes place global data like this. The address is only known at
+    /// runtime, so the variable has no address
+    RegisterRelative { register: u16, offset: i64 },
+}
+
+/// the kind of storage of a variable, derived from the section it resides in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Storage {
+    /// the variable has an initial value: .data, .sdata
+    Initialized,
+    /// the variable is zero-initialized at startup: .bss, .sbss
+    ZeroInit,
+    /// the variable is constant: .rodata, .rdata
+    ReadOnly,
 }
 
 #[derive(Debug, Clone)]
@@ -22,38 +94,63 @@ pub(crate) struct TypeInfo {
     pub(crate) unit_idx: usize,
     pub(crate) datatype: DbgDataType,
     pub(crate) dbginfo_offset: usize,
+    // the size given by DW_AT_byte_size, if any. It takes precedence over the size derived from the datatype
+    pub(crate) byte_size: Option<u64>,
 }
 
+// the discriminant of a union with a tag: the name of the discriminant member, and the name of the active
+// member for each discriminant value. The value of the default member is None
+pub(crate) type UnionDiscriminant = (String, Vec<(Option<u64>, String)>);
+
 #[derive(Debug, Clone)]
 pub(crate) enum DbgDataType {
+    // 1-byte types whose encoding says they are not plain integers
+    Bool,
+    SChar,
+    UChar,
     Uint8,
     Uint16,
     Uint32,
     Uint64,
+    Uint128,
     Sint8,
     Sint16,
     Sint32,
     Sint64,
+    Sint128,
     Float,
     Double,
+    // extended precision floating point type; the parameter is the storage size,
+    // e.g. 16 for the 80-bit x87 long double on x86_64
+    LongDouble(u64),
+    // IEEE 754 binary128, e.g. _Float128
+    Float128,
     Bitfield {
         basetype: Box<TypeInfo>,
         bit_offset: u16,
         bit_size: u16,
     },
     Pointer(u64, usize),
+    // a C++ reference (T& or T&&). It is stored like a pointer, but unlike a pointer it can't be reseated.
+    // The target is the offset of the referenced type, like the offset in Pointer
+    Reference {
+        size: u64,
+        target: usize,
+    },
     Struct {
         size: u64,
-        members: IndexMap<String, (TypeInfo, u64)>,
+        members: IndexMap<String, (TypeInfo, Option<u64>)>,
     },
     Class {
         size: u64,
-        inheritance: IndexMap<String, (TypeInfo, u64)>,
-        members: IndexMap<String, (TypeInfo, u64)>,
+        inheritance: IndexMap<String, BaseClass>,
+        members: IndexMap<String, (TypeInfo, Option<u64>)>,
     },
     Union {
         size: u64,
-        members: IndexMap<String, (TypeInfo, u64)>,
+        members: IndexMap<String, (TypeInfo, Option<u64>)>,
+        // only set for unions with a tag, see UnionDiscriminant
+        discriminant: Option<UnionDiscriminant>,
     },
     Enum {
         size: u64,
@@ -65,82 +162,862 @@ pub(crate) enum DbgDataType {
         dim: Vec<u64>,
         stride: u64,
         arraytype: Box<TypeInfo>,
+        // the index of the first element of each dimension (DW_AT_lower_bound), e.g. in Ada or Fortran.
+        // Empty if all dimensions start at 0
+        lower_bounds: Vec<u64>,
+        // the indices of the dimensions whose number of elements is only known at runtime, because DW_AT_count or
+        // DW_AT_upper_bound refers to another DIE, e.g. in a variable length array. Their entry in dim is 0
+        dynamic_dims: Vec<usize>,
+    },
+    // a Rust enum with data: the variants overlap like the members of a union, and the discriminant selects the active one
+    VariantEnum {
+        size: u64,
+        // the type of the discriminant and its offset inside the enum
+        discriminant: Option<(Box<TypeInfo>, u64)>,
+        // the variants by the discriminant value that selects them. The key of the default variant is None;
+        // it is active for all values that are not listed, e.g. in enums that store the discriminant in a niche
+        variants: IndexMap<Option<u64>, EnumVariant>,
     },
     TypeRef(usize, u64),
-    FuncPtr(u64),
+    // a pointer to a function; a void return type is represented as Other(0)
+    FunctionPointer {
+        return_type: Box<TypeInfo>,
+        params: Vec<TypeInfo>,
+        size: u64,
+    },
+    // a type that is only declared, e.g. "struct foo;", and whose definition is not available. Its size is unknown
+    Incomplete {
+        name: String,
+    },
     Other(u64),
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct BaseClass {
+    pub(crate) typeinfo: TypeInfo,
+    // the offset of a virtual base class depends on the most derived class; it is found at runtime through the vtable
+    pub(crate) offset: Option<u64>,
+    pub(crate) is_virtual: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EnumVariant {
+    pub(crate) name: String,
+    pub(crate) typeinfo: TypeInfo,
+    pub(crate) offset: u64,
+}
+
+/// the source language of a compile unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SourceLanguage {
+    C,
+    Cpp,
+    Rust,
+    /// any other language, identified by its DW_LANG_* value
+    Other(u16),
+}
+
+/// information about the compiler and language of a compile unit
+/// Only the language is used by the command line, the other fields are for callers that embed the loader
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) struct UnitInfo {
+    pub(crate) producer: Option<String>,
+    pub(crate) language: Option<SourceLanguage>,
+    // DWARF version of the unit header (2 to 5); 0 if the debug info is not DWARF
+    pub(crate) dwarf_version: u16,
+}
+
+/// a variable in the debug info that was not loaded, because it is not a usable global variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DroppedVariable {
+    /// the name of the variable; empty if the name could not be read
+    pub(crate) name: String,
+    /// offset of the DIE of the variable in .debug_info
+    pub(crate) offset: usize,
+    pub(crate) reason: DropReason,
+}
+
+/// a global variable whose address in the debug info differs from the address of its symbol in the symbol table,
+/// e.g. because the debug info is stale. The variable is loaded with the address from the debug info
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AddressDiscrepancy {
+    pub(crate) name: String,
+    pub(crate) unit_idx: usize,
+    pub(crate) dwarf_address: u64,
+    pub(crate) symbol_address: u64,
+}
+
+/// the reason why a variable was not loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DropReason {
+    /// a global variable without a static address, e.g. because it was optimized out
+    NoLocation,
+    /// the variable has a location, but its name or its type could not be read
+    Invalid(String),
+}
+
+/// the phase of loading the debug info that a ProgressEvent reports on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LoadPhase {
+    /// the variables are read unit by unit
+    Variables,
+    /// the types of the variables are read, variable by variable
+    Types,
+}
+
+/// progress of loading the debug info: done out of total units (Variables phase) or variables (Types phase)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ProgressEvent {
+    pub(crate) phase: LoadPhase,
+    pub(crate) done: usize,
+    pub(crate) total: usize,
+}
+
+/// options that control which variables are loaded from the debug info, and how their addresses are interpreted
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LoadOptions {
+    /// also load the variables that are generated by the compiler (DW_AT_artificial), e.g. guard
+    /// variables or compound literals. By default they are skipped
+    pub(crate) include_artificial: bool,
+    /// size of the addressing unit of the DWARF addresses in bits. The default is 8, i.e. byte addresses.
+    /// Word-addressed targets like the TI C2000 (16 bits) use larger units; their addresses are scaled
+    /// to byte addresses. Must be a non-zero multiple of 8
+    pub(crate) address_unit_bits: u32,
+    /// the names of the debug sections, for toolchains that don't use the standard names .debug_<xyz>.
+    /// By default the standard names are used, or __debug_<xyz> in Mach-O files
+    pub(crate) section_names: Option<fn(gimli::SectionId) -> &'static str>,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            include_artificial: false,
+            address_unit_bits: 8,
+            section_names: None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct DebugData {
     pub(crate) variables: IndexMap<String, Vec<VarInfo>>,
     pub(crate) types: HashMap<usize, TypeInfo>,
     pub(crate) typenames: HashMap<String, Vec<usize>>,
-    pub(crate) demangled_names: HashMap<String, String>,
+    // the mangled names of C++ variables by their demangled name. Several mangled names can demangle to
+    // the same name; they are sorted, so that the lexicographically smallest mangled name comes first
+    pub(crate) demangled_names: HashMap<String, Vec<String>>,
     pub(crate) unit_names: Vec<Option<String>>,
     pub(crate) sections: HashMap<String, (u64, u64)>,
     // pub(crate) linkage_names: HashMap<usize, ClassInfo>,
+    // C++ scope (namespaces and enclosing classes) of struct, class and union types, by dbginfo_offset
+    pub(crate) type_scopes: HashMap<usize, String>,
+    // producer and language of each unit, with the same index as unit_names. Only available for DWARF
+    pub(crate) unit_info: Vec<UnitInfo>,
+    // global variables that were found in the debug info, but not loaded. Only available for DWARF
+    pub(crate) dropped_variables: Vec<DroppedVariable>,
+    // global variables whose address in the debug info differs from the address in the symbol table. Only available for DWARF
+    pub(crate) address_discrepancies: Vec<AddressDiscrepancy>,
+    // byte order and architecture of the target. PDB files are always little endian, and their architecture
+    // is unknown if they are loaded without their PE file. Only the tests read these, because the command
+    // line doesn't write the BYTE_ORDER or ADDRESS_TYPE
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) endian: Endianness,
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) architecture: Architecture,
+}
+
+// a loaded DebugData can be shared in an Arc and queried from several threads, because it has no interior
+// mutability; this fails to compile if a field breaks that
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<DebugData>();
+    assert_send_sync::<DebugDataError>();
+};
+
+/// Errors that can occur while loading debug data or looking up symbols in it
+#[derive(Debug)]
+pub(crate) enum DebugDataError {
+    /// the file could not be opened or mapped into memory
+    Io {
+        context: String,
+        error: std::io::Error,
+    },
+    /// the file or the debug info inside it could not be parsed
+    Parse(String),
+    /// the file does not contain a .debug_info section
+    NoDebugInfo(String),
+    /// none of the compile units in the file contain debug info
+    NoCompileUnits(String),
+    /// the directory does not have the layout of a dSYM bundle
+    InvalidDsymBundle { path: String, reason: String },
+    /// the static archive does not contain a member with the given name
+    ArchiveMemberNotFound { archive: String, member: String },
+    /// the size of the addressing unit is not a non-zero multiple of 8 bits
+    InvalidAddressUnit(u32),
+    /// there is no variable with the given name
+    SymbolNotFound(String),
+    /// the variable is a compile-time constant, so neither the constant nor its members have an address
+    ConstantWithoutAddress(String),
+    /// the variable is thread-local, so its address is an offset into the TLS block of each thread
+    ThreadLocalLocation(String),
+    /// the variable is located relative to a register, so its address is only known at runtime
+    RegisterRelativeLocation {
+        symbol: String,
+        register: u16,
+        offset: i64,
+    },
+    /// the demangled name matches several mangled variables at different addresses.
+    /// The candidates are given by their mangled name and the name of their unit
+    AmbiguousDemangledName {
+        name: String,
+        candidates: Vec<(String, Option<String>)>,
+    },
+    /// the struct, union or class does not have a member with the given name
+    MemberNotFound { member: String, parent: String },
+    /// the member is not a member of the class itself, and several of its base classes have a member of that name.
+    /// The bases are the paths of base classes that lead to the candidates, e.g. "Left.Root"
+    AmbiguousMember {
+        member: String,
+        parent: String,
+        bases: Vec<String>,
+    },
+    /// some components of the symbol name remain after reaching a type that has no members or elements
+    UnmatchedComponents { remaining: String, symbol: String },
+    /// a component of the symbol name was expected to be an array index
+    InvalidIndex(String),
+    /// the symbol name descends through a virtual base class, whose offset can't be determined statically
+    VirtualBaseOffset { base: String, symbol: String },
+    /// the symbol name descends through a member whose location is a DWARF expression that can't be evaluated statically
+    DynamicMemberOffset { member: String, symbol: String },
+    /// the symbol name descends through a reference, whose target is only known at runtime
+    ReferenceDescent { reference: String, symbol: String },
+    /// the symbol name indexes an array dimension whose number of elements is only known at runtime
+    DynamicArrayDimension { dimension: usize, symbol: String },
+    /// an array index is larger than the array dimension
+    IndexOutOfBounds {
+        index: usize,
+        expression: String,
+        dim: u64,
+    },
+    /// the offset is outside of the symbol
+    OffsetOutOfBounds { offset: i32, symbol: String },
+    /// no component of the symbol starts at the offset
+    NoComponentAtOffset { offset: u64, symbol: String },
+    /// a negative offset was given, but there is no symbol at a lower address than the base symbol
+    NoPrecedingSymbol { offset: i32, symbol: String },
 }
 
 impl DebugData {
     // load the debug info from an elf file
-    pub(crate) fn load_dwarf(filename: &OsStr, verbose: bool) -> Result<Self, String> {
-        dwarf::load_dwarf(filename, verbose)
+    pub(crate) fn load_dwarf(filename: &OsStr, verbose: bool) -> Result<Self, DebugDataError> {
+        Self::load_dwarf_with_progress(filename, verbose, |_| {})
+    }
+
+    // load the debug info from an elf file, and report the progress after each unit and each variable
+    pub(crate) fn load_dwarf_with_progress(
+        filename: &OsStr,
+        verbose: bool,
+        mut progress: impl FnMut(ProgressEvent),
+    ) -> Result<Self, DebugDataError> {
+        dwarf::load_dwarf(filename, verbose, LoadOptions::default(), &mut progress)
+    }
+
+    // load the debug info from an elf file, with options that select which variables are loaded
+    #[cfg(test)]
+    pub(crate) fn load_dwarf_with_options(
+        filename: &OsStr,
+        verbose: bool,
+        options: LoadOptions,
+    ) -> Result<Self, DebugDataError> {
+        dwarf::load_dwarf(filename, verbose, options, &mut |_| {})
+    }
+
+    // load the debug info from the content of an elf file that is already in memory
+    #[cfg(test)]
+    pub(crate) fn load_dwarf_from_bytes(
+        data: &[u8],
+        verbose: bool,
+    ) -> Result<Self, DebugDataError> {
+        dwarf::load_dwarf_from_bytes(data, verbose)
+    }
+
+    // load the debug info from an elf file that is read from a stream, e.g. stdin in a CI pipeline.
+    // This doesn't use mmap: the whole file is buffered in memory while it is parsed
+    #[cfg(test)]
+    pub(crate) fn load_dwarf_from_reader(
+        reader: impl std::io::Read,
+        verbose: bool,
+    ) -> Result<Self, DebugDataError> {
+        dwarf::load_dwarf_from_reader(reader, verbose)
+    }
+
+    // load the debug info of the compile units with the given names from an elf file, see dwarf::load_dwarf_filtered()
+    #[cfg(test)]
+    pub(crate) fn load_dwarf_filtered(
+        filename: &OsStr,
+        units: &[&str],
+        verbose: bool,
+    ) -> Result<Self, DebugDataError> {
+        dwarf::load_dwarf_filtered(filename, units, verbose)
+    }
+
+    // load the debug info from a static archive, either from one member or merged across all members
+    #[cfg(test)]
+    pub(crate) fn load_dwarf_archive(
+        filename: &OsStr,
+        member: Option<&str>,
+        verbose: bool,
+    ) -> Result<Self, DebugDataError> {
+        dwarf::load_dwarf_archive(filename, member, verbose)
     }
 
+    // load the debug info from an elf file, reusing the results of a previous load for all unchanged compile units
+    #[cfg(test)]
+    pub(crate) fn load_dwarf_incremental(
+        filename: &OsStr,
+        cache: Option<&UnitCache>,
+        verbose: bool,
+    ) -> Result<(Self, UnitCache), DebugDataError> {
+        dwarf::load_dwarf_incremental(filename, cache, verbose)
+    }
+
+    #[cfg(feature = "pdb")]
     pub(crate) fn load_pdb(filename: &OsStr, verbose: bool) -> Result<Self, String> {
         pdb::load_pdb(filename, verbose)
     }
 
+    // load the debug info of an exe or dll file from its PDB file, with the absolute addresses of the variables.
+    // Without a PDB file name, the PDB file is located using the CodeView record of the exe file
+    #[cfg(feature = "pdb")]
+    pub(crate) fn load_pe_with_pdb(
+        pe_filename: &OsStr,
+        pdb_filename: Option<&OsStr>,
+        verbose: bool,
+    ) -> Result<Self, String> {
+        pdb::load_pe_with_pdb(pe_filename, pdb_filename, verbose)
+    }
+
+    // shift all addresses by the load bias, e.g. for a position-independent executable or a relocated image
+    // whose runtime base address is known. The address ranges of the sections are shifted too, so that
+    // get_storage() still finds the section of each variable. Offsets of thread-local variables are unchanged
+    pub(crate) fn with_load_bias(mut self, load_bias: u64) -> Self {
+        for varinfo in self.variables.values_mut().flatten() {
+            if !varinfo.is_tls()
+                && let Some(address) = &mut varinfo.address
+            {
+                *address = address.wrapping_add(load_bias);
+            }
+        }
+        for (start, end) in self.sections.values_mut() {
+            *start = start.wrapping_add(load_bias);
+            *end = end.wrapping_add(load_bias);
+        }
+        for discrepancy in &mut self.address_discrepancies {
+            discrepancy.dwarf_address = discrepancy.dwarf_address.wrapping_add(load_bias);
+            discrepancy.symbol_address = discrepancy.symbol_address.wrapping_add(load_bias);
+        }
+        self
+    }
+
+    // convert all addresses from addressing units of address_unit_bits to bytes, by multiplying them with
+    // address_unit_bits / 8. This converts the addresses of a word-addressed target to the byte addresses
+    // used in A2L files. Addresses of thread-local variables and the address ranges of the sections are scaled too.
+    // Sizes and member offsets of the types are not changed
+    pub(crate) fn with_address_unit_bits(mut self, address_unit_bits: u32) -> Self {
+        let scale = u64::from(address_unit_bits / 8);
+        if scale > 1 {
+            for address in self
+                .variables
+                .values_mut()
+                .flatten()
+                .filter_map(|varinfo| varinfo.address.as_mut())
+            {
+                *address = address.wrapping_mul(scale);
+            }
+            for (start, end) in self.sections.values_mut() {
+                *start = start.wrapping_mul(scale);
+                *end = end.wrapping_mul(scale);
+            }
+            for discrepancy in &mut self.address_discrepancies {
+                discrepancy.dwarf_address = discrepancy.dwarf_address.wrapping_mul(scale);
+                discrepancy.symbol_address = discrepancy.symbol_address.wrapping_mul(scale);
+            }
+        }
+        self
+    }
+
+    // get all scalar elements of a symbol, i.e. the symbol itself if it is a scalar, or the members and array elements
+    // that are not structs, unions or arrays, in the order of their declaration
+    #[cfg(test)]
+    pub(crate) fn flatten_symbol<'dbg>(
+        &'dbg self,
+        symbol: &crate::symbol::SymbolInfo<'dbg>,
+    ) -> iter::FlattenedSymbol<'dbg> {
+        iter::FlattenedSymbol::new(&self.types, symbol)
+    }
+
     pub(crate) fn iter(&self, use_new_arrays: bool) -> iter::VariablesIterator {
         iter::VariablesIterator::new(self, use_new_arrays)
     }
+
+    // write the variables as a JSON array of { name, address, size, datatype, unit, section } for other tools.
+    // If expand_leaves is set, then the scalar elements of the variables are listed instead
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_json(&self, expand_leaves: bool) -> String {
+        json::to_json(self, expand_leaves)
+    }
+
+    // get the producer of a unit, i.e. the compiler name and version, often followed by the command line options
+    #[cfg(test)]
+    pub(crate) fn unit_producer(&self, unit_idx: usize) -> Option<&str> {
+        self.unit_info.get(unit_idx)?.producer.as_deref()
+    }
+
+    // get the global variables whose address in the debug info differs from the address in the symbol table
+    #[cfg(test)]
+    pub(crate) fn address_discrepancies(&self) -> &[AddressDiscrepancy] {
+        &self.address_discrepancies
+    }
+
+    // get the source language of a unit
+    #[cfg(test)]
+    pub(crate) fn unit_language(&self, unit_idx: usize) -> Option<SourceLanguage> {
+        self.unit_info.get(unit_idx)?.language
+    }
+
+    // get the DWARF version of a unit. Returns 0 if the index is invalid or the debug info is not DWARF
+    #[cfg(test)]
+    pub(crate) fn unit_dwarf_version(&self, unit_idx: usize) -> u16 {
+        self.unit_info
+            .get(unit_idx)
+            .map_or(0, |unit_info| unit_info.dwarf_version)
+    }
+
+    // get the names of all variables that are defined more than once, e.g. static variables in
+    // different units, together with the number of definitions. Looking up one of these names without
+    // an additional specification of the function, unit or namespace may not select the intended variable
+    #[cfg(test)]
+    pub(crate) fn ambiguous_symbols(&self) -> Vec<(&str, usize)> {
+        self.variables
+            .iter()
+            .filter(|(_, varinfo_list)| varinfo_list.len() > 1)
+            .map(|(name, varinfo_list)| (name.as_str(), varinfo_list.len()))
+            .collect()
+    }
+
+    // get all static variables that are defined inside the function with the given name, in the order
+    // in which they were loaded. Statics of functions with the same name in different units are all included
+    #[cfg(test)]
+    pub(crate) fn static_locals(&self, function_name: &str) -> Vec<(&str, &VarInfo)> {
+        self.variables
+            .iter()
+            .flat_map(|(name, varinfo_list)| {
+                varinfo_list
+                    .iter()
+                    .filter(|varinfo| varinfo.function.as_deref() == Some(function_name))
+                    .map(move |varinfo| (name.as_str(), varinfo))
+            })
+            .collect()
+    }
+
+    // get all types with the given source name, e.g. "MotorConfig", ordered by their offset in the debug info.
+    // After deduplicate_types(), several types only share a name if they differ, e.g. because they are
+    // defined in different namespaces or the definitions in some units are different
+    #[cfg(test)]
+    pub(crate) fn types_by_name<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a TypeInfo> {
+        self.typenames
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|offset| self.types.get(offset))
+    }
+
+    // get the full name of a compile unit, as given by its DW_AT_name. Type units have no name
+    pub(crate) fn unit_name(&self, unit_idx: usize) -> Option<&str> {
+        self.unit_names.get(unit_idx)?.as_deref()
+    }
+
+    // get all compile units that have a name, together with their unit_idx
+    #[cfg(test)]
+    pub(crate) fn unit_names_iter(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.unit_names
+            .iter()
+            .enumerate()
+            .filter_map(|(unit_idx, name)| Some((unit_idx, name.as_deref()?)))
+    }
+
+    // get the byte order of the target, e.g. for the BYTE_ORDER of the A2L file
+    #[cfg(test)]
+    pub(crate) fn endianness(&self) -> Endianness {
+        self.endian
+    }
+
+    // get the architecture of the target
+    #[cfg(test)]
+    pub(crate) fn architecture(&self) -> Architecture {
+        self.architecture
+    }
+
+    // get the size of a pointer on the target in bytes, e.g. for the ADDRESS_TYPE of pointers in the A2L file.
+    // The result is 0 if the architecture is unknown
+    #[cfg(test)]
+    pub(crate) fn pointer_size(&self) -> u8 {
+        self.architecture
+            .address_size()
+            .map_or(0, |address_size| address_size.bytes())
+    }
+
+    // get the first type with the given source name, see types_by_name()
+    #[cfg(test)]
+    pub(crate) fn type_by_name(&self, name: &str) -> Option<&TypeInfo> {
+        self.types_by_name(name).next()
+    }
+
+    // A type that is defined in a header appears in the debug info of every unit that includes the header.
+    // Identical named types in the same scope are merged, so that all references use one canonical entry.
+    // Anonymous types are merged in the same way if they have the same structure.
+    // Returns the number of type entries that were removed
+    pub(crate) fn deduplicate_types(&mut self) -> usize {
+        // map the offset of each duplicate to the offset and unit of the canonical type
+        let mut replacements = HashMap::<usize, (usize, usize)>::new();
+        for offsets in self.typenames.values_mut() {
+            let mut canonical_offsets: Vec<usize> = Vec::new();
+            for &offset in offsets.iter() {
+                if canonical_offsets.contains(&offset) {
+                    continue;
+                }
+                let scope = self.type_scopes.get(&offset);
+                let canonical = self.types.get(&offset).and_then(|typeinfo| {
+                    canonical_offsets.iter().find_map(|canonical_offset| {
+                        let canonical_type = self.types.get(canonical_offset)?;
+                        (self.type_scopes.get(canonical_offset) == scope
+                            && canonical_type.compare(typeinfo, &self.types))
+                        .then_some((*canonical_offset, canonical_type.unit_idx))
+                    })
+                });
+                if let Some(canonical) = canonical {
+                    replacements.insert(offset, canonical);
+                } else {
+                    canonical_offsets.push(offset);
+                }
+            }
+            *offsets = canonical_offsets;
+        }
+
+        // anonymous types can't be grouped by name; instead they are grouped by a hash of their structure.
+        // The offsets are sorted so that the canonical type is always the first one in the debug info
+        let mut anonymous_offsets: Vec<usize> = self
+            .types
+            .iter()
+            .filter(|(_, typeinfo)| {
+                typeinfo.name.is_none()
+                    && matches!(
+                        typeinfo.datatype,
+                        DbgDataType::Struct { .. }
+                            | DbgDataType::Class { .. }
+                            | DbgDataType::Union { .. }
+                            | DbgDataType::Enum { .. }
+                    )
+            })
+            .map(|(offset, _)| *offset)
+            .collect();
+        anonymous_offsets.sort_unstable();
+        let mut anonymous_groups = HashMap::<u64, Vec<usize>>::new();
+        for offset in anonymous_offsets {
+            let typeinfo = &self.types[&offset];
+            let scope = self.type_scopes.get(&offset);
+            let group = anonymous_groups
+                .entry(typeinfo.structural_hash(&self.types))
+                .or_default();
+            let canonical = group.iter().find_map(|canonical_offset| {
+                let canonical_type = &self.types[canonical_offset];
+                (self.type_scopes.get(canonical_offset) == scope
+                    && canonical_type.compare(typeinfo, &self.types))
+                .then_some((*canonical_offset, canonical_type.unit_idx))
+            });
+            if let Some(canonical) = canonical {
+                replacements.insert(offset, canonical);
+            } else {
+                group.push(offset);
+            }
+        }
+
+        let mut removed = 0;
+        for offset in replacements.keys() {
+            if self.types.remove(offset).is_some() {
+                removed += 1;
+            }
+            self.type_scopes.remove(offset);
+        }
+        for typeinfo in self.types.values_mut() {
+            typeinfo.replace_type_offsets(&replacements);
+        }
+        for varinfo in self.variables.values_mut().flatten() {
+            if let Some((canonical_offset, _)) = replacements.get(&varinfo.typeref) {
+                varinfo.typeref = *canonical_offset;
+            }
+        }
+
+        removed
+    }
+
+    // add the debug info of another elf file, e.g. of an application to the debug info of its bootloader.
+    // Variables that are present in both files are appended to the list of variables with that name, so
+    // that they can be told apart by their unit. A warning is logged for each pair of sections whose
+    // address ranges overlap, because then the two files can't be loaded into the same memory
+    #[cfg(test)]
+    pub(crate) fn merge(&mut self, other: DebugData) {
+        for (name, other_name) in self.overlapping_sections(&other) {
+            log::warn!("Section {other_name} of the merged file overlaps with section {name}");
+        }
+        self.merge_rebased(other, self.next_type_offset());
+    }
+
+    // get the pairs of sections in self and other whose address ranges overlap. Empty sections never overlap
+    #[cfg(test)]
+    pub(crate) fn overlapping_sections(&self, other: &DebugData) -> Vec<(String, String)> {
+        let mut overlaps = Vec::new();
+        for (name, (start, end)) in &self.sections {
+            for (other_name, (other_start, other_end)) in &other.sections {
+                if start < other_end && other_start < end {
+                    overlaps.push((name.clone(), other_name.clone()));
+                }
+            }
+        }
+        overlaps.sort();
+        overlaps
+    }
+
+    // get the pairs of distinct variables whose address ranges overlap, e.g. variables in overlays that a linker
+    // script places at the same address. The names in each pair are sorted.
+    // Thread-local variables and variables without a size are not considered
+    #[cfg(test)]
+    pub(crate) fn overlapping_variables(&self) -> Vec<(String, String)> {
+        let mut ranges: Vec<(u64, u64, &str)> = self
+            .variables
+            .iter()
+            .flat_map(|(name, varinfos)| {
+                varinfos
+                    .iter()
+                    .filter(|varinfo| !varinfo.is_tls())
+                    .filter_map(|varinfo| {
+                        let address = varinfo.address?;
+                        let size = self.types.get(&varinfo.typeref)?.get_size();
+                        (size > 0).then(|| (address, address.saturating_add(size), name.as_str()))
+                    })
+            })
+            .collect();
+        ranges.sort_unstable();
+
+        let mut overlaps = Vec::new();
+        for (idx, (start, end, name)) in ranges.iter().enumerate() {
+            // the ranges are sorted by their start, so only the following ranges that start before the end overlap
+            for (other_start, _, other_name) in &ranges[idx + 1..] {
+                if other_start >= end {
+                    break;
+                }
+                if other_start != start || other_name != name {
+                    let (first, second) = if name <= other_name {
+                        (name, other_name)
+                    } else {
+                        (other_name, name)
+                    };
+                    overlaps.push((first.to_string(), second.to_string()));
+                }
+            }
+        }
+        overlaps.sort();
+        overlaps
+    }
+
+    // an offset that is larger than all offsets of types and variables in self
+    #[cfg(test)]
+    fn next_type_offset(&self) -> usize {
+        let type_offsets = self
+            .types
+            .iter()
+            .map(|(offset, typeinfo)| (*offset).max(typeinfo.dbginfo_offset));
+        let dropped_offsets = self.dropped_variables.iter().map(|dropped| dropped.offset);
+        let variable_offsets = self
+            .variables
+            .values()
+            .flatten()
+            .map(|varinfo| varinfo.dbginfo_offset);
+        type_offsets
+            .chain(self.type_scopes.keys().copied())
+            .chain(dropped_offsets)
+            .chain(variable_offsets)
+            .max()
+            .map_or(0, |offset| offset + 1)
+    }
+
+    // add the debug info of another file, e.g. of another member of a static archive.
+    // The type offsets of the other file are shifted by offset_base, which must be larger than all
+    // offsets in self. Its units are appended after the units that are already present
+    fn merge_rebased(&mut self, other: DebugData, offset_base: usize) {
+        let unit_base = self.unit_names.len();
+        for (name, varinfo_list) in other.variables {
+            let entry = self.variables.entry(name).or_default();
+            for mut varinfo in varinfo_list {
+                varinfo.typeref += offset_base;
+                varinfo.dbginfo_offset += offset_base;
+                varinfo.unit_idx += unit_base;
+                entry.push(varinfo);
+            }
+        }
+        for (offset, mut typeinfo) in other.types {
+            typeinfo.rebase_type_offsets(offset_base, unit_base);
+            self.types.insert(offset + offset_base, typeinfo);
+        }
+        for (name, offsets) in other.typenames {
+            self.typenames
+                .entry(name)
+                .or_default()
+                .extend(offsets.iter().map(|offset| offset + offset_base));
+        }
+        for (demangled, mangled_list) in other.demangled_names {
+            for mangled in mangled_list {
+                dwarf::insert_demangled_name(&mut self.demangled_names, demangled.clone(), mangled);
+            }
+        }
+        self.unit_names.extend(other.unit_names);
+        self.unit_info.extend(other.unit_info);
+        self.dropped_variables
+            .extend(
+                other
+                    .dropped_variables
+                    .into_iter()
+                    .map(|dropped| DroppedVariable {
+                        offset: dropped.offset + offset_base,
+                        ..dropped
+                    }),
+            );
+        self.address_discrepancies
+            .extend(other.address_discrepancies.into_iter().map(|discrepancy| {
+                AddressDiscrepancy {
+                    unit_idx: discrepancy.unit_idx + unit_base,
+                    ..discrepancy
+                }
+            }));
+        // if both files have a section of the same name, the address range of the first one is kept
+        for (name, range) in other.sections {
+            self.sections.entry(name).or_insert(range);
+        }
+        self.type_scopes.extend(
+            other
+                .type_scopes
+                .into_iter()
+                .map(|(offset, scope)| (offset + offset_base, scope)),
+        );
+    }
 }
 
 /// convert a full unit name, which might include a path, into a simple unit name
 pub(crate) fn make_simple_unit_name(debug_data: &DebugData, unit_idx: usize) -> Option<String> {
-    let full_name = debug_data.unit_names.get(unit_idx)?.as_deref()?;
+    let full_name = debug_data.unit_name(unit_idx)?;
+    Some(simplify_unit_name(full_name))
+}
 
-    let file_name = if let Some(pos) = full_name.rfind('\\') {
-        &full_name[(pos + 1)..]
-    } else if let Some(pos) = full_name.rfind('/') {
-        &full_name[(pos + 1)..]
-    } else {
-        full_name
-    };
+/// convert a unit name into the form that Vector tools use in {CompileUnit:...}: the file name without its path,
+/// with '.' replaced by '_'. Both '\\' and '/' separate the path, regardless of the OS on which the file was compiled
+pub(crate) fn simplify_unit_name(unit_name: &str) -> String {
+    let file_name = unit_name.rsplit(['\\', '/']).next().unwrap_or(unit_name);
+    file_name.replace('.', "_")
+}
+
+/// check if a {CompileUnit:...} spec refers to the given unit.
+/// The names are compared case-insensitively, because the unit names of files that were compiled on Windows
+/// may differ in case from the spec
+pub(crate) fn unit_matches_simple_name(
+    debug_data: &DebugData,
+    unit_idx: usize,
+    simple_unit_name: &str,
+) -> bool {
+    make_simple_unit_name(debug_data, unit_idx).is_some_and(|unit_name| {
+        unit_name.eq_ignore_ascii_case(&simplify_unit_name(simple_unit_name))
+    })
+}
+
+/// get the name of the section that contains an address. Thread-local sections are not considered
+pub(crate) fn get_section_name(
+    sections: &HashMap<String, (u64, u64)>,
+    address: u64,
+) -> Option<&str> {
+    let (name, _) = sections.iter().find(|(name, (start, end))| {
+        // .tbss does not occupy any memory, its address range overlaps the following sections
+        !name.starts_with(".tbss")
+            && !name.starts_with(".tdata")
+            && *start <= address
+            && address < *end
+    })?;
+    Some(name)
+}
+
+/// the verbose flag of the load functions raises the log level filter to Debug, so that the
+/// messages of the loader reach the installed logger. A higher filter set by the caller is kept
+pub(crate) fn raise_log_level(verbose: bool) {
+    if verbose {
+        log::set_max_level(log::LevelFilter::Debug.max(log::max_level()));
+    }
+}
 
-    Some(file_name.replace('.', "_"))
+/// get the kind of storage at an address from the name of the section that contains it
+pub(crate) fn get_storage(sections: &HashMap<String, (u64, u64)>, address: u64) -> Option<Storage> {
+    let name = get_section_name(sections, address)?;
+    if name.starts_with(".bss") || name.starts_with(".sbss") {
+        Some(Storage::ZeroInit)
+    } else if name.starts_with(".rodata")
+        || name.starts_with(".srodata")
+        || name.starts_with(".rdata")
+    {
+        Some(Storage::ReadOnly)
+    } else {
+        // .data, .sdata and any custom sections
+        Some(Storage::Initialized)
+    }
 }
 
 impl TypeInfo {
     const MAX_RECURSION_DEPTH: usize = 5;
 
     pub(crate) fn get_size(&self) -> u64 {
+        if let Some(byte_size) = self.byte_size {
+            return byte_size;
+        }
         match &self.datatype {
+            DbgDataType::Bool | DbgDataType::SChar | DbgDataType::UChar => 1,
             DbgDataType::Uint8 => 1,
             DbgDataType::Uint16 => 2,
             DbgDataType::Uint32 => 4,
             DbgDataType::Uint64 => 8,
+            DbgDataType::Uint128 => 16,
             DbgDataType::Sint8 => 1,
             DbgDataType::Sint16 => 2,
             DbgDataType::Sint32 => 4,
             DbgDataType::Sint64 => 8,
+            DbgDataType::Sint128 => 16,
             DbgDataType::Float => 4,
             DbgDataType::Double => 8,
+            DbgDataType::LongDouble(size) => *size,
+            DbgDataType::Float128 => 16,
             DbgDataType::Bitfield { basetype, .. } => basetype.get_size(),
+            DbgDataType::Incomplete { .. } => 0,
             DbgDataType::Pointer(size, _)
+            | DbgDataType::Reference { size, .. }
             | DbgDataType::Other(size)
             | DbgDataType::Struct { size, .. }
             | DbgDataType::Class { size, .. }
             | DbgDataType::Union { size, .. }
+            | DbgDataType::VariantEnum { size, .. }
             | DbgDataType::Enum { size, .. }
             | DbgDataType::Array { size, .. }
-            | DbgDataType::FuncPtr(size)
+            | DbgDataType::FunctionPointer { size, .. }
             | DbgDataType::TypeRef(_, size) => *size,
         }
     }
 
-    pub(crate) fn get_members(&self) -> Option<&IndexMap<String, (TypeInfo, u64)>> {
+    pub(crate) fn get_members(&self) -> Option<&IndexMap<String, (TypeInfo, Option<u64>)>> {
         match &self.datatype {
             DbgDataType::Struct { members, .. }
             | DbgDataType::Class { members, .. }
@@ -154,7 +1031,13 @@ impl TypeInfo {
         &self,
         types: &'a HashMap<usize, TypeInfo>,
     ) -> Option<(u64, &'a TypeInfo)> {
-        if let DbgDataType::Pointer(pt_size, pt_ref) = &self.datatype {
+        // a reference is stored like a pointer
+        if let DbgDataType::Pointer(pt_size, pt_ref)
+        | DbgDataType::Reference {
+            size: pt_size,
+            target: pt_ref,
+        } = &self.datatype
+        {
             let typeinfo = types.get(pt_ref)?;
             Some((*pt_size, typeinfo))
         } else {
@@ -170,6 +1053,378 @@ impl TypeInfo {
         }
     }
 
+    /// for an array type, get the size of a single element and the distance between the start addresses of consecutive elements
+    /// The stride can be larger than the element size, e.g. if DW_AT_byte_stride adds padding between the elements
+    #[cfg(test)]
+    pub(crate) fn element_stride(&self) -> Option<(u64, u64)> {
+        if let DbgDataType::Array {
+            stride, arraytype, ..
+        } = &self.datatype
+        {
+            Some((arraytype.get_size(), *stride))
+        } else {
+            None
+        }
+    }
+
+    /// check if the type is a C string, i.e. a one-dimensional array of a character type: char, signed char or unsigned char
+    /// Typedefs are resolved when the types are loaded, so an array of uint8_t is also a C string
+    pub(crate) fn is_c_string(&self, types: &HashMap<usize, TypeInfo>) -> bool {
+        if let DbgDataType::Array { dim, arraytype, .. } = &self.get_reference(types).datatype {
+            dim.len() == 1
+                && matches!(
+                    arraytype.get_reference(types).datatype,
+                    DbgDataType::SChar | DbgDataType::UChar
+                )
+        } else {
+            false
+        }
+    }
+
+    /// render a C-like declaration of a variable of this type, e.g. "uint8_t var_array[33]" or
+    /// "struct Point { int16_t x; int16_t y; } pos". Nested aggregates are expanded, while the
+    /// targets of pointers are only given by name, so that self-referential types terminate.
+    /// Scalar types are shown with the fixed-width name that a2ltool uses for them, not with their typedef name
+    #[cfg(test)]
+    pub(crate) fn to_c_declaration(
+        &self,
+        var_name: &str,
+        types: &HashMap<usize, TypeInfo>,
+    ) -> String {
+        self.c_declaration(var_name.to_string(), types, true)
+    }
+
+    #[cfg(test)]
+    fn c_declaration(
+        &self,
+        declarator: String,
+        types: &HashMap<usize, TypeInfo>,
+        expand: bool,
+    ) -> String {
+        let typeinfo = self.get_reference(types);
+        let base = match &typeinfo.datatype {
+            DbgDataType::Array {
+                dim,
+                arraytype,
+                dynamic_dims,
+                ..
+            } => {
+                // a pointer or reference to an array needs parentheses: "int32_t (*ptr)[4]"
+                let mut declarator = if declarator.starts_with(['*', '&']) {
+                    format!("({declarator})")
+                } else {
+                    declarator
+                };
+                for (idx, dimension) in dim.iter().enumerate() {
+                    // the length of a dynamic dimension is unknown, like in "int32_t values[]"
+                    if dynamic_dims.contains(&idx) {
+                        declarator.push_str("[]");
+                    } else {
+                        declarator.push_str(&format!("[{dimension}]"));
+                    }
+                }
+                return arraytype.c_declaration(declarator, types, expand);
+            }
+            DbgDataType::Pointer(_, dest_offset) => {
+                let declarator = format!("*{declarator}");
+                return match types.get(dest_offset) {
+                    Some(dest_type) => dest_type.c_declaration(declarator, types, false),
+                    None => format!("void {declarator}"),
+                };
+            }
+            DbgDataType::Reference { target, .. } => {
+                let declarator = format!("&{declarator}");
+                return match types.get(target) {
+                    Some(target_type) => target_type.c_declaration(declarator, types, false),
+                    None => format!("void {declarator}"),
+                };
+            }
+            DbgDataType::Bitfield {
+                basetype, bit_size, ..
+            } => {
+                let declaration = basetype.c_declaration(declarator, types, expand);
+                return format!("{declaration} : {bit_size}");
+            }
+            DbgDataType::FunctionPointer {
+                return_type,
+                params,
+                ..
+            } => {
+                let params = if params.is_empty() {
+                    "void".to_string()
+                } else {
+                    params
+                        .iter()
+                        .map(|param| param.c_declaration(String::new(), types, false))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let declarator = format!("(*{declarator})({params})");
+                return return_type.c_declaration(declarator, types, false);
+            }
+            DbgDataType::Struct { members, .. } => {
+                typeinfo.c_aggregate("struct", "", members, types, expand)
+            }
+            DbgDataType::Union { members, .. } => {
+                typeinfo.c_aggregate("union", "", members, types, expand)
+            }
+            DbgDataType::Class {
+                inheritance,
+                members,
+                ..
+            } => {
+                let bases = inheritance
+                    .iter()
+                    .map(|(name, baseclass)| {
+                        if baseclass.is_virtual {
+                            format!("virtual {name}")
+                        } else {
+                            name.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let bases = if bases.is_empty() {
+                    String::new()
+                } else {
+                    format!(" : {}", bases.join(", "))
+                };
+                typeinfo.c_aggregate("class", &bases, members, types, expand)
+            }
+            DbgDataType::VariantEnum { variants, .. } => {
+                // the variants overlap, so they are shown like the members of a union
+                let members = variants
+                    .values()
+                    .map(|variant| {
+                        (
+                            variant.name.clone(),
+                            (variant.typeinfo.clone(), Some(variant.offset)),
+                        )
+                    })
+                    .collect();
+                typeinfo.c_aggregate("enum", "", &members, types, expand)
+            }
+            DbgDataType::Enum { enumerators, .. } => {
+                let name = typeinfo.name.as_deref().map(|name| format!(" {name}"));
+                if expand || name.is_none() {
+                    let enumerators = enumerators
+                        .iter()
+                        .map(|(name, value)| format!("{name} = {value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("enum{} {{ {enumerators} }}", name.unwrap_or_default())
+                } else {
+                    format!("enum{}", name.unwrap_or_default())
+                }
+            }
+            DbgDataType::Incomplete { name } => format!("struct {name}"),
+            DbgDataType::Other(_) => typeinfo.name.clone().unwrap_or("void".to_string()),
+            DbgDataType::TypeRef(..) => "void".to_string(),
+            DbgDataType::Bool => "bool".to_string(),
+            DbgDataType::SChar => "char".to_string(),
+            DbgDataType::UChar => "unsigned char".to_string(),
+            DbgDataType::Uint8 => "uint8_t".to_string(),
+            DbgDataType::Uint16 => "uint16_t".to_string(),
+            DbgDataType::Uint32 => "uint32_t".to_string(),
+            DbgDataType::Uint64 => "uint64_t".to_string(),
+            DbgDataType::Uint128 => "unsigned __int128".to_string(),
+            DbgDataType::Sint8 => "int8_t".to_string(),
+            DbgDataType::Sint16 => "int16_t".to_string(),
+            DbgDataType::Sint32 => "int32_t".to_string(),
+            DbgDataType::Sint64 => "int64_t".to_string(),
+            DbgDataType::Sint128 => "__int128".to_string(),
+            DbgDataType::Float => "float".to_string(),
+            DbgDataType::Double => "double".to_string(),
+            DbgDataType::LongDouble(_) => "long double".to_string(),
+            DbgDataType::Float128 => "_Float128".to_string(),
+        };
+        if declarator.is_empty() {
+            base
+        } else {
+            format!("{base} {declarator}")
+        }
+    }
+
+    // render the body of a struct, union or class, unless only the name of a named type is needed
+    #[cfg(test)]
+    fn c_aggregate(
+        &self,
+        keyword: &str,
+        bases: &str,
+        members: &IndexMap<String, (TypeInfo, Option<u64>)>,
+        types: &HashMap<usize, TypeInfo>,
+        expand: bool,
+    ) -> String {
+        let name = self
+            .name
+            .as_deref()
+            .map(|name| format!(" {name}"))
+            .unwrap_or_default();
+        if !expand && !name.is_empty() {
+            return format!("{keyword}{name}");
+        }
+        let mut declaration = format!("{keyword}{name}{bases} {{");
+        for (member_name, (membertype, _)) in members {
+            declaration.push(' ');
+            declaration.push_str(&membertype.c_declaration(member_name.clone(), types, true));
+            declaration.push(';');
+        }
+        declaration.push_str(" }");
+        declaration
+    }
+
+    /// count the scalar elements inside the type, i.e. the number of symbols that leaves() would return for a variable of this type.
+    /// The count is calculated from the array dimensions and the struct members, without creating the elements
+    #[cfg(test)]
+    pub(crate) fn leaf_count(&self, debug_data: &DebugData) -> usize {
+        let typeinfo = self.get_reference(&debug_data.types);
+        if typeinfo.is_scalar() {
+            return 1;
+        }
+        match &typeinfo.datatype {
+            DbgDataType::Struct { members, .. }
+            | DbgDataType::Class { members, .. }
+            | DbgDataType::Union { members, .. } => members
+                .values()
+                .map(|(member_type, _)| member_type.leaf_count(debug_data))
+                .fold(0, usize::saturating_add),
+            DbgDataType::Array { dim, arraytype, .. } => dim
+                .iter()
+                .map(|count| usize::try_from(*count).unwrap_or(usize::MAX))
+                .fold(arraytype.leaf_count(debug_data), usize::saturating_mul),
+            _ => 0,
+        }
+    }
+
+    /// get the holes between the members of a struct or class, as (offset, size) in ascending order of the offset.
+    /// Padding after the last member is not a hole here; it is returned by trailing_padding().
+    /// Returns None if the type is not a struct or class, or if the offset of a member is only known at runtime
+    #[cfg(test)]
+    pub(crate) fn holes(&self) -> Option<Vec<(u64, u64)>> {
+        let occupied = self.occupied_ranges()?;
+        let holes = occupied
+            .windows(2)
+            .map(|pair| (pair[0].1, pair[1].0 - pair[0].1))
+            .collect();
+        Some(holes)
+    }
+
+    /// get the padding between the end of the last member of a struct or class and the end of the type, as (offset, size).
+    /// Returns None if there is no trailing padding, or if holes() would return None
+    #[cfg(test)]
+    pub(crate) fn trailing_padding(&self) -> Option<(u64, u64)> {
+        let occupied = self.occupied_ranges()?;
+        let end = occupied.last().map_or(0, |(_, range_end)| *range_end);
+        let size = self.get_size();
+        (end < size).then(|| (end, size - end))
+    }
+
+    // get the byte ranges (start, end) inside a struct or class that are used by members and base classes.
+    // Overlapping and adjacent ranges are merged, and the result is sorted by offset.
+    // The position of the bits of a bitfield in memory depends on the byte order of the target, which is not known here.
+    // A bitfield occupies the bytes that contain its bits in either byte order, so that a hole never contains data
+    #[cfg(test)]
+    fn occupied_ranges(&self) -> Option<Vec<(u64, u64)>> {
+        let (size, members, inheritance) = match &self.datatype {
+            DbgDataType::Struct { size, members } => (*size, members, None),
+            DbgDataType::Class {
+                size,
+                inheritance,
+                members,
+            } => (*size, members, Some(inheritance)),
+            _ => return None,
+        };
+        let mut ranges: Vec<(u64, u64)> = Vec::new();
+        for (membertype, offset) in members.values() {
+            // without the offset of every member, there is no way to tell which bytes are unused
+            let offset = (*offset)?;
+            if let DbgDataType::Bitfield {
+                basetype,
+                bit_offset,
+                bit_size,
+            } = &membertype.datatype
+            {
+                if *bit_size == 0 {
+                    continue;
+                }
+                let storage_size = basetype.get_size();
+                let first_byte = u64::from(*bit_offset) / 8;
+                let last_byte = (u64::from(*bit_offset) + u64::from(*bit_size) - 1) / 8;
+                ranges.push((offset + first_byte, offset + last_byte + 1));
+                // the same bits counted from the other end of the storage
+                if let Some(mirrored_last) = storage_size.checked_sub(first_byte + 1) {
+                    let mirrored_first = storage_size.saturating_sub(last_byte + 1);
+                    ranges.push((offset + mirrored_first, offset + mirrored_last + 1));
+                }
+            } else {
+                ranges.push((offset, offset + membertype.get_size()));
+            }
+        }
+        // the offset of a virtual base class is only known at runtime
+        for baseclass in inheritance.into_iter().flat_map(IndexMap::values) {
+            if let Some(offset) = baseclass.offset {
+                ranges.push((offset, offset + baseclass.typeinfo.get_size()));
+            }
+        }
+
+        // members with broken debug info could extend beyond the end of the type
+        for (_, end) in &mut ranges {
+            *end = (*end).min(size);
+        }
+        ranges.retain(|(start, end)| start < end);
+        ranges.sort_unstable();
+        let mut occupied: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match occupied.last_mut() {
+                Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+                _ => occupied.push((start, end)),
+            }
+        }
+        Some(occupied)
+    }
+
+    /// check if the type is a single value: an integer, floating point, enum, bitfield or pointer type
+    /// Structs, classes, unions and arrays are not scalar. Neither are TypeRef, which should be resolved
+    /// with get_reference() first, and Other, whose content is unknown.
+    #[cfg(any(test, feature = "serde"))]
+    pub(crate) fn is_scalar(&self) -> bool {
+        matches!(
+            &self.datatype,
+            DbgDataType::Bool
+                | DbgDataType::SChar
+                | DbgDataType::UChar
+                | DbgDataType::Uint8
+                | DbgDataType::Uint16
+                | DbgDataType::Uint32
+                | DbgDataType::Uint64
+                | DbgDataType::Uint128
+                | DbgDataType::Sint8
+                | DbgDataType::Sint16
+                | DbgDataType::Sint32
+                | DbgDataType::Sint64
+                | DbgDataType::Sint128
+                | DbgDataType::Float
+                | DbgDataType::Double
+                | DbgDataType::LongDouble(_)
+                | DbgDataType::Float128
+                | DbgDataType::Enum { .. }
+                | DbgDataType::Bitfield { .. }
+                | DbgDataType::Pointer(..)
+                | DbgDataType::Reference { .. }
+                | DbgDataType::FunctionPointer { .. }
+        )
+    }
+
+    /// get the innermost element type of an array, which may be nested in other arrays, or the underlying type of a bitfield.
+    /// All other types are their own base type.
+    #[cfg(test)]
+    pub(crate) fn base_type(&self) -> &TypeInfo {
+        match &self.datatype {
+            DbgDataType::Array { arraytype, .. } => arraytype.base_type(),
+            DbgDataType::Bitfield { basetype, .. } => basetype.base_type(),
+            _ => self,
+        }
+    }
+
     pub(crate) fn get_reference<'a>(&'a self, types: &'a HashMap<usize, TypeInfo>) -> &'a Self {
         if let DbgDataType::TypeRef(dbginfo_offset, _) = &self.datatype {
             types.get(dbginfo_offset).unwrap_or(self)
@@ -178,6 +1433,47 @@ impl TypeInfo {
         }
     }
 
+    // hash the parts of the type that are checked by compare(), so that equal types always have the same hash.
+    // The types of the members only contribute their name, size and kind, which avoids following references
+    pub(crate) fn structural_hash(&self, types: &HashMap<usize, TypeInfo>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let typeinfo = self.get_reference(types);
+        typeinfo.hash_outline(&mut hasher);
+        if let Some(members) = typeinfo.get_members() {
+            // compare() ignores the order of the members
+            let mut member_hashes: Vec<u64> = members
+                .iter()
+                .map(|(name, (membertype, offset))| {
+                    let mut member_hasher = DefaultHasher::new();
+                    name.hash(&mut member_hasher);
+                    offset.hash(&mut member_hasher);
+                    membertype
+                        .get_reference(types)
+                        .hash_outline(&mut member_hasher);
+                    member_hasher.finish()
+                })
+                .collect();
+            member_hashes.sort_unstable();
+            member_hashes.hash(&mut hasher);
+        }
+        if let DbgDataType::Enum {
+            signed,
+            enumerators,
+            ..
+        } = &typeinfo.datatype
+        {
+            signed.hash(&mut hasher);
+            enumerators.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn hash_outline(&self, hasher: &mut DefaultHasher) {
+        self.name.hash(hasher);
+        self.get_size().hash(hasher);
+        std::mem::discriminant(&self.datatype).hash(hasher);
+    }
+
     // not using PartialEq, because not all fields are considered for this comparison
     pub(crate) fn compare(&self, other: &TypeInfo, types: &HashMap<usize, TypeInfo>) -> bool {
         self.compare_internal(other, types, 0)
@@ -194,8 +1490,12 @@ impl TypeInfo {
 
         type_1.dbginfo_offset == type_2.dbginfo_offset
             || (type_1.name == type_2.name
+                && type_1.get_size() == type_2.get_size()
                 && match (&type_1.datatype, &type_2.datatype) {
-                    (DbgDataType::Uint8, DbgDataType::Uint8)
+                    (DbgDataType::Bool, DbgDataType::Bool)
+                    | (DbgDataType::SChar, DbgDataType::SChar)
+                    | (DbgDataType::UChar, DbgDataType::UChar)
+                    | (DbgDataType::Uint8, DbgDataType::Uint8)
                     | (DbgDataType::Uint16, DbgDataType::Uint16)
                     | (DbgDataType::Uint32, DbgDataType::Uint32)
                     | (DbgDataType::Uint64, DbgDataType::Uint64)
@@ -204,7 +1504,13 @@ impl TypeInfo {
                     | (DbgDataType::Sint32, DbgDataType::Sint32)
                     | (DbgDataType::Sint64, DbgDataType::Sint64)
                     | (DbgDataType::Float, DbgDataType::Float)
-                    | (DbgDataType::Double, DbgDataType::Double) => true,
+                    | (DbgDataType::Double, DbgDataType::Double)
+                    | (DbgDataType::Uint128, DbgDataType::Uint128)
+                    | (DbgDataType::Sint128, DbgDataType::Sint128)
+                    | (DbgDataType::Float128, DbgDataType::Float128) => true,
+                    (DbgDataType::LongDouble(size1), DbgDataType::LongDouble(size2)) => {
+                        size1 == size2
+                    }
                     (
                         DbgDataType::Enum {
                             size,
@@ -223,22 +1529,38 @@ impl TypeInfo {
                             dim,
                             stride,
                             arraytype,
+                            lower_bounds,
+                            dynamic_dims,
                         },
                         DbgDataType::Array {
                             size: size2,
                             dim: dim2,
                             stride: stride2,
                             arraytype: arraytype2,
+                            lower_bounds: lower_bounds2,
+                            dynamic_dims: dynamic_dims2,
                         },
                     ) => {
                         size == size2
                             && dim == dim2
                             && stride == stride2
+                            && lower_bounds == lower_bounds2
+                            && dynamic_dims == dynamic_dims2
                             && arraytype.compare_internal(arraytype2, types, depth + 1)
                     }
                     (
                         DbgDataType::Pointer(size1, dest_offset1),
                         DbgDataType::Pointer(size2, dest_offset2),
+                    )
+                    | (
+                        DbgDataType::Reference {
+                            size: size1,
+                            target: dest_offset1,
+                        },
+                        DbgDataType::Reference {
+                            size: size2,
+                            target: dest_offset2,
+                        },
                     ) => {
                         size1 == size2
                             && if dest_offset1 == dest_offset2 {
@@ -263,6 +1585,10 @@ impl TypeInfo {
                             }
                     }
                     (DbgDataType::Other(size1), DbgDataType::Other(size2)) => size1 == size2,
+                    (
+                        DbgDataType::Incomplete { name: name1 },
+                        DbgDataType::Incomplete { name: name2 },
+                    ) => name1 == name2,
                     (
                         DbgDataType::Bitfield {
                             basetype,
@@ -287,12 +1613,21 @@ impl TypeInfo {
                         },
                     ) => size == size2 && Self::compare_members(members, members2, types, depth),
                     (
-                        DbgDataType::Union { size, members },
+                        DbgDataType::Union {
+                            size,
+                            members,
+                            discriminant,
+                        },
                         DbgDataType::Union {
                             size: size2,
                             members: members2,
+                            discriminant: discriminant2,
                         },
-                    ) => size == size2 && Self::compare_members(members, members2, types, depth),
+                    ) => {
+                        size == size2
+                            && discriminant == discriminant2
+                            && Self::compare_members(members, members2, types, depth)
+                    }
                     (
                         DbgDataType::Class {
                             size,
@@ -307,16 +1642,105 @@ impl TypeInfo {
                     ) => {
                         size == size2
                             && Self::compare_members(members, members2, types, depth)
-                            && Self::compare_members(inheritance, inheritance2, types, depth)
+                            && Self::compare_inheritance(inheritance, inheritance2, types, depth)
+                    }
+                    (
+                        DbgDataType::VariantEnum {
+                            size,
+                            discriminant,
+                            variants,
+                        },
+                        DbgDataType::VariantEnum {
+                            size: size2,
+                            discriminant: discriminant2,
+                            variants: variants2,
+                        },
+                    ) => {
+                        size == size2
+                            && match (discriminant, discriminant2) {
+                                (
+                                    Some((discr_type, discr_offset)),
+                                    Some((discr_type2, discr_offset2)),
+                                ) => {
+                                    discr_offset == discr_offset2
+                                        && discr_type.compare_internal(
+                                            discr_type2,
+                                            types,
+                                            depth + 1,
+                                        )
+                                }
+                                (None, None) => true,
+                                _ => false,
+                            }
+                            && Self::compare_variants(variants, variants2, types, depth)
+                    }
+                    (
+                        DbgDataType::FunctionPointer {
+                            return_type,
+                            params,
+                            size,
+                        },
+                        DbgDataType::FunctionPointer {
+                            return_type: return_type2,
+                            params: params2,
+                            size: size2,
+                        },
+                    ) => {
+                        size == size2
+                            && params.len() == params2.len()
+                            && (depth >= Self::MAX_RECURSION_DEPTH
+                                || (return_type.compare_internal(return_type2, types, depth + 1)
+                                    && params.iter().zip(params2).all(|(param1, param2)| {
+                                        param1.compare_internal(param2, types, depth + 1)
+                                    })))
                     }
-                    (DbgDataType::FuncPtr(size1), DbgDataType::FuncPtr(size2)) => size1 == size2,
                     _ => false,
                 })
     }
 
+    fn compare_inheritance(
+        inheritance1: &IndexMap<String, BaseClass>,
+        inheritance2: &IndexMap<String, BaseClass>,
+        types: &HashMap<usize, TypeInfo>,
+        depth: usize,
+    ) -> bool {
+        inheritance1.len() == inheritance2.len()
+            && inheritance1.iter().all(|(name, base1)| {
+                inheritance2.get(name).is_some_and(|base2| {
+                    base1.offset == base2.offset
+                        && base1.is_virtual == base2.is_virtual
+                        && (depth >= Self::MAX_RECURSION_DEPTH
+                            || base1
+                                .typeinfo
+                                .compare_internal(&base2.typeinfo, types, depth + 1))
+                })
+            })
+    }
+
+    fn compare_variants(
+        variants1: &IndexMap<Option<u64>, EnumVariant>,
+        variants2: &IndexMap<Option<u64>, EnumVariant>,
+        types: &HashMap<usize, TypeInfo>,
+        depth: usize,
+    ) -> bool {
+        variants1.len() == variants2.len()
+            && variants1.iter().all(|(discr_value, variant1)| {
+                variants2.get(discr_value).is_some_and(|variant2| {
+                    variant1.name == variant2.name
+                        && variant1.offset == variant2.offset
+                        && (depth >= Self::MAX_RECURSION_DEPTH
+                            || variant1.typeinfo.compare_internal(
+                                &variant2.typeinfo,
+                                types,
+                                depth + 1,
+                            ))
+                })
+            })
+    }
+
     fn compare_members(
-        members1: &IndexMap<String, (TypeInfo, u64)>,
-        members2: &IndexMap<String, (TypeInfo, u64)>,
+        members1: &IndexMap<String, (TypeInfo, Option<u64>)>,
+        members2: &IndexMap<String, (TypeInfo, Option<u64>)>,
         types: &HashMap<usize, TypeInfo>,
         depth: usize,
     ) -> bool {
@@ -347,25 +1771,312 @@ impl TypeInfo {
         }
         true
     }
+
+    // redirect all references to merged duplicate types to the canonical type
+    fn replace_type_offsets(&mut self, replacements: &HashMap<usize, (usize, usize)>) {
+        if let Some((canonical_offset, canonical_unit)) = replacements.get(&self.dbginfo_offset) {
+            self.dbginfo_offset = *canonical_offset;
+            self.unit_idx = *canonical_unit;
+        }
+        match &mut self.datatype {
+            DbgDataType::Pointer(_, dest_offset)
+            | DbgDataType::Reference {
+                target: dest_offset,
+                ..
+            }
+            | DbgDataType::TypeRef(dest_offset, _) => {
+                if let Some((canonical_offset, _)) = replacements.get(dest_offset) {
+                    *dest_offset = *canonical_offset;
+                }
+            }
+            DbgDataType::Array { arraytype, .. } => arraytype.replace_type_offsets(replacements),
+            DbgDataType::Bitfield { basetype, .. } => basetype.replace_type_offsets(replacements),
+            DbgDataType::VariantEnum {
+                discriminant,
+                variants,
+                ..
+            } => {
+                if let Some((discr_type, _)) = discriminant {
+                    discr_type.replace_type_offsets(replacements);
+                }
+                for variant in variants.values_mut() {
+                    variant.typeinfo.replace_type_offsets(replacements);
+                }
+            }
+            DbgDataType::FunctionPointer {
+                return_type,
+                params,
+                ..
+            } => {
+                return_type.replace_type_offsets(replacements);
+                for param in params {
+                    param.replace_type_offsets(replacements);
+                }
+            }
+            DbgDataType::Struct { members, .. } | DbgDataType::Union { members, .. } => {
+                for (membertype, _) in members.values_mut() {
+                    membertype.replace_type_offsets(replacements);
+                }
+            }
+            DbgDataType::Class {
+                inheritance,
+                members,
+                ..
+            } => {
+                for baseclass in inheritance.values_mut() {
+                    baseclass.typeinfo.replace_type_offsets(replacements);
+                }
+                for (membertype, _) in members.values_mut() {
+                    membertype.replace_type_offsets(replacements);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // shift all type offsets and unit indices, when the debug info of several files is merged or a unit has moved.
+    // The shift uses wrapping arithmetic, so that offset_base.wrapping_neg() moves the offsets down again
+    fn rebase_type_offsets(&mut self, offset_base: usize, unit_base: usize) {
+        self.dbginfo_offset = self.dbginfo_offset.wrapping_add(offset_base);
+        self.unit_idx = self.unit_idx.wrapping_add(unit_base);
+        match &mut self.datatype {
+            DbgDataType::Pointer(_, dest_offset)
+            | DbgDataType::Reference {
+                target: dest_offset,
+                ..
+            }
+            | DbgDataType::TypeRef(dest_offset, _) => {
+                *dest_offset = dest_offset.wrapping_add(offset_base);
+            }
+            DbgDataType::Array { arraytype, .. } => {
+                arraytype.rebase_type_offsets(offset_base, unit_base);
+            }
+            DbgDataType::Bitfield { basetype, .. } => {
+                basetype.rebase_type_offsets(offset_base, unit_base);
+            }
+            DbgDataType::VariantEnum {
+                discriminant,
+                variants,
+                ..
+            } => {
+                if let Some((discr_type, _)) = discriminant {
+                    discr_type.rebase_type_offsets(offset_base, unit_base);
+                }
+                for variant in variants.values_mut() {
+                    variant.typeinfo.rebase_type_offsets(offset_base, unit_base);
+                }
+            }
+            DbgDataType::FunctionPointer {
+                return_type,
+                params,
+                ..
+            } => {
+                return_type.rebase_type_offsets(offset_base, unit_base);
+                for param in params {
+                    param.rebase_type_offsets(offset_base, unit_base);
+                }
+            }
+            DbgDataType::Struct { members, .. } | DbgDataType::Union { members, .. } => {
+                for (membertype, _) in members.values_mut() {
+                    membertype.rebase_type_offsets(offset_base, unit_base);
+                }
+            }
+            DbgDataType::Class {
+                inheritance,
+                members,
+                ..
+            } => {
+                for baseclass in inheritance.values_mut() {
+                    baseclass
+                        .typeinfo
+                        .rebase_type_offsets(offset_base, unit_base);
+                }
+                for (membertype, _) in members.values_mut() {
+                    membertype.rebase_type_offsets(offset_base, unit_base);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl DbgDataType {
+    /// get the number of elements at the top level of the type:
+    /// the product of all dimensions for arrays, the number of members for structs and unions,
+    /// and the number of members and base classes for classes. All other types count as one element.
+    #[cfg(test)]
+    pub(crate) fn element_count(&self) -> u64 {
+        match self {
+            DbgDataType::Array { dim, .. } => dim.iter().product(),
+            DbgDataType::Struct { members, .. } | DbgDataType::Union { members, .. } => {
+                members.len() as u64
+            }
+            DbgDataType::Class {
+                inheritance,
+                members,
+                ..
+            } => (inheritance.len() + members.len()) as u64,
+            DbgDataType::VariantEnum { variants, .. } => variants.len() as u64,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for DebugDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DebugDataError::Io { context, error } => write!(f, "{context}: {error}"),
+            DebugDataError::Parse(message) => f.write_str(message),
+            DebugDataError::NoDebugInfo(filename) => write!(
+                f,
+                "Error: {filename} does not contain DWARF2+ debug info. The section .debug_info is missing."
+            ),
+            DebugDataError::NoCompileUnits(filename) => write!(
+                f,
+                "Error: {filename} does not contain DWARF2+ debug info - zero compile units contain debug info."
+            ),
+            DebugDataError::InvalidDsymBundle { path, reason } => {
+                write!(f, "Error: {path} is not a valid dSYM bundle: {reason}")
+            }
+            DebugDataError::ArchiveMemberNotFound { archive, member } => {
+                write!(
+                    f,
+                    "Error: the archive {archive} does not contain a member {member}"
+                )
+            }
+            DebugDataError::InvalidAddressUnit(bits) => write!(
+                f,
+                "Error: an addressing unit of {bits} bits is not supported, it must be a non-zero multiple of 8 bits"
+            ),
+            DebugDataError::SymbolNotFound(name) => write!(f, "Symbol \"{name}\" does not exist"),
+            DebugDataError::ConstantWithoutAddress(symbol) => write!(
+                f,
+                "Symbol \"{symbol}\" belongs to a compile-time constant, which has no address"
+            ),
+            DebugDataError::ThreadLocalLocation(symbol) => write!(
+                f,
+                "Symbol \"{symbol}\" is thread-local, so its address is only known at runtime"
+            ),
+            DebugDataError::RegisterRelativeLocation {
+                symbol,
+                register,
+                offset,
+            } => write!(
+                f,
+                "Symbol \"{symbol}\" is located at offset {offset} from register {register}, so its address is only known at runtime"
+            ),
+            DebugDataError::AmbiguousDemangledName { name, candidates } => {
+                let candidates = candidates
+                    .iter()
+                    .map(|(mangled, unit_name)| match unit_name {
+                        Some(unit_name) => format!("{mangled} in {unit_name}"),
+                        None => mangled.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "The demangled name \"{name}\" is ambiguous; it could refer to {candidates}"
+                )
+            }
+            DebugDataError::MemberNotFound { member, parent } => {
+                write!(f, "There is no member \"{member}\" in \"{parent}\"")
+            }
+            DebugDataError::AmbiguousMember {
+                member,
+                parent,
+                bases,
+            } => write!(
+                f,
+                "The member \"{member}\" of \"{parent}\" is ambiguous; it must be qualified with one of the base classes {}",
+                bases.join(", ")
+            ),
+            DebugDataError::UnmatchedComponents { remaining, symbol } => write!(
+                f,
+                "Remaining portion \"{remaining}\" of \"{symbol}\" could not be matched"
+            ),
+            DebugDataError::InvalidIndex(component) => {
+                write!(f, "could not interpret \"{component}\" as an array index")
+            }
+            DebugDataError::VirtualBaseOffset { base, symbol } => write!(
+                f,
+                "The offset of the virtual base class \"{base}\" in \"{symbol}\" is only known at runtime"
+            ),
+            DebugDataError::DynamicMemberOffset { member, symbol } => write!(
+                f,
+                "The offset of the member \"{member}\" in \"{symbol}\" is only known at runtime"
+            ),
+            DebugDataError::ReferenceDescent { reference, symbol } => write!(
+                f,
+                "Can't resolve \"{symbol}\", because {reference} is a reference whose target is only known at runtime"
+            ),
+            DebugDataError::DynamicArrayDimension { dimension, symbol } => write!(
+                f,
+                "Can't index dimension {dimension} of the array in \"{symbol}\", because its number of elements is only known at runtime"
+            ),
+            DebugDataError::IndexOutOfBounds {
+                index,
+                expression,
+                dim,
+            } => write!(
+                f,
+                "requested array index {index} in expression \"{expression}\", but the array only has {dim} elements"
+            ),
+            DebugDataError::OffsetOutOfBounds { offset, symbol } => {
+                write!(
+                    f,
+                    "Offset {offset} is out of bounds for symbol \"{symbol}\""
+                )
+            }
+            DebugDataError::NoComponentAtOffset { offset, symbol } => write!(
+                f,
+                "Could not find a symbol component at offset {offset} from \"{symbol}\""
+            ),
+            DebugDataError::NoPrecedingSymbol { offset, symbol } => write!(
+                f,
+                "Offset {offset} from \"{symbol}\" is negative, but there is no symbol before \"{symbol}\""
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DebugDataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DebugDataError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
 }
 
 impl Display for TypeInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.datatype {
+            DbgDataType::Bool => f.write_str("Bool"),
+            DbgDataType::SChar => f.write_str("SChar"),
+            DbgDataType::UChar => f.write_str("UChar"),
             DbgDataType::Uint8 => f.write_str("Uint8"),
             DbgDataType::Uint16 => f.write_str("Uint16"),
             DbgDataType::Uint32 => f.write_str("Uint32"),
             DbgDataType::Uint64 => f.write_str("Uint64"),
+            DbgDataType::Uint128 => f.write_str("Uint128"),
             DbgDataType::Sint8 => f.write_str("Sint8"),
             DbgDataType::Sint16 => f.write_str("Sint16"),
             DbgDataType::Sint32 => f.write_str("Sint32"),
             DbgDataType::Sint64 => f.write_str("Sint64"),
+            DbgDataType::Sint128 => f.write_str("Sint128"),
             DbgDataType::Float => f.write_str("Float"),
             DbgDataType::Double => f.write_str("Double"),
+            DbgDataType::LongDouble(size) => write!(f, "LongDouble({size})"),
+            DbgDataType::Float128 => f.write_str("Float128"),
             DbgDataType::Bitfield { .. } => f.write_str("Bitfield"),
             DbgDataType::Pointer(_, _) => write!(f, "Pointer(...)"),
+            DbgDataType::Reference { .. } => write!(f, "Reference(...)"),
+            DbgDataType::Incomplete { name } => write!(f, "Incomplete({name})"),
             DbgDataType::Other(osize) => write!(f, "Other({osize})"),
-            DbgDataType::FuncPtr(osize) => write!(f, "function pointer({osize})"),
+            DbgDataType::FunctionPointer { params, size, .. } => {
+                write!(f, "function pointer({size}, {} params)", params.len())
+            }
             DbgDataType::Struct { members, .. } => {
                 if let Some(name) = &self.name {
                     write!(f, "Struct {name}({} members)", members.len())
@@ -387,6 +2098,13 @@ impl Display for TypeInfo {
                     write!(f, "Union <anonymous>({} members)", members.len())
                 }
             }
+            DbgDataType::VariantEnum { variants, .. } => {
+                if let Some(name) = &self.name {
+                    write!(f, "VariantEnum {name}({} variants)", variants.len())
+                } else {
+                    write!(f, "VariantEnum <anonymous>({} variants)", variants.len())
+                }
+            }
             DbgDataType::Enum { enumerators, .. } => {
                 if let Some(name) = &self.name {
                     write!(f, "Enum {name}({} enumerators)", enumerators.len())
@@ -403,4 +2121,323 @@ impl Display for TypeInfo {
 }
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_element_stride() {
+        let mut members = IndexMap::new();
+        members.insert(
+            "a".to_string(),
+            (
+                TypeInfo {
+                    name: None,
+                    unit_idx: 0,
+                    datatype: DbgDataType::Uint32,
+                    dbginfo_offset: 0,
+                    byte_size: None,
+                },
+                Some(0),
+            ),
+        );
+        members.insert(
+            "b".to_string(),
+            (
+                TypeInfo {
+                    name: None,
+                    unit_idx: 0,
+                    datatype: DbgDataType::Uint16,
+                    dbginfo_offset: 0,
+                    byte_size: None,
+                },
+                Some(4),
+            ),
+        );
+        let element = TypeInfo {
+            name: Some("elem".to_string()),
+            unit_idx: 0,
+            datatype: DbgDataType::Struct { size: 6, members },
+            dbginfo_offset: 0,
+            byte_size: None,
+        };
+        // the array elements are padded to 8 bytes
+        let array = TypeInfo {
+            name: None,
+            unit_idx: 0,
+            datatype: DbgDataType::Array {
+                size: 24,
+                dim: vec![3],
+                stride: 8,
+                arraytype: Box::new(element.clone()),
+                lower_bounds: Vec::new(),
+                dynamic_dims: Vec::new(),
+            },
+            dbginfo_offset: 0,
+            byte_size: None,
+        };
+        assert_eq!(array.element_stride(), Some((6, 8)));
+        assert_eq!(element.element_stride(), None);
+    }
+
+    #[test]
+    fn test_leaf_count() {
+        let mut debug_data = DebugData {
+            variables: IndexMap::new(),
+            types: HashMap::new(),
+            typenames: HashMap::new(),
+            demangled_names: HashMap::new(),
+            unit_names: Vec::new(),
+            sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: Endianness::Little,
+            architecture: Architecture::Unknown,
+        };
+        // struct Inner { uint8_t a; uint16_t b[3]; };
+        let mut inner_members = IndexMap::new();
+        inner_members.insert("a".to_string(), (make_type(DbgDataType::Uint8), Some(0)));
+        let b_array = DbgDataType::Array {
+            size: 6,
+            dim: vec![3],
+            stride: 2,
+            arraytype: Box::new(make_type(DbgDataType::Uint16)),
+            lower_bounds: Vec::new(),
+            dynamic_dims: Vec::new(),
+        };
+        inner_members.insert("b".to_string(), (make_type(b_array), Some(2)));
+        let inner = make_type(DbgDataType::Struct {
+            size: 8,
+            members: inner_members,
+        });
+        assert_eq!(inner.leaf_count(&debug_data), 4);
+        debug_data.types.insert(100, inner);
+
+        // struct Outer { struct Inner grid[2][5]; float value; struct {} empty; };
+        // the elements of grid refer to Inner through a TypeRef
+        let grid = DbgDataType::Array {
+            size: 80,
+            dim: vec![2, 5],
+            stride: 8,
+            arraytype: Box::new(make_type(DbgDataType::TypeRef(100, 8))),
+            lower_bounds: Vec::new(),
+            dynamic_dims: Vec::new(),
+        };
+        let mut outer_members = IndexMap::new();
+        outer_members.insert("grid".to_string(), (make_type(grid), Some(0)));
+        outer_members.insert(
+            "value".to_string(),
+            (make_type(DbgDataType::Float), Some(80)),
+        );
+        let empty = DbgDataType::Struct {
+            size: 0,
+            members: IndexMap::new(),
+        };
+        outer_members.insert("empty".to_string(), (make_type(empty), Some(84)));
+        let outer = make_type(DbgDataType::Struct {
+            size: 84,
+            members: outer_members,
+        });
+        assert_eq!(outer.leaf_count(&debug_data), 2 * 5 * 4 + 1);
+        assert_eq!(make_type(DbgDataType::Sint32).leaf_count(&debug_data), 1);
+        assert_eq!(make_type(DbgDataType::Other(4)).leaf_count(&debug_data), 0);
+
+        // a huge array does not overflow the count
+        let huge = make_type(DbgDataType::Array {
+            size: u64::MAX,
+            dim: vec![u64::MAX, 2],
+            stride: 1,
+            arraytype: Box::new(make_type(DbgDataType::Uint8)),
+            lower_bounds: Vec::new(),
+            dynamic_dims: Vec::new(),
+        });
+        assert_eq!(huge.leaf_count(&debug_data), usize::MAX);
+    }
+
+    #[test]
+    fn test_type_by_name() {
+        let mut debug_data = DebugData {
+            variables: IndexMap::new(),
+            types: HashMap::new(),
+            typenames: HashMap::new(),
+            demangled_names: HashMap::new(),
+            unit_names: Vec::new(),
+            sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: Endianness::Little,
+            architecture: Architecture::Unknown,
+        };
+        // two different types named Config, e.g. in different namespaces, and one named Mode
+        for (offset, datatype) in [
+            (100, DbgDataType::Uint32),
+            (200, DbgDataType::Float),
+            (300, DbgDataType::Uint8),
+        ] {
+            let mut typeinfo = make_type(datatype);
+            typeinfo.dbginfo_offset = offset;
+            debug_data.types.insert(offset, typeinfo);
+        }
+        debug_data
+            .typenames
+            .insert("Config".to_string(), vec![100, 200]);
+        debug_data.typenames.insert("Mode".to_string(), vec![300]);
+        // the name of a type that was not loaded
+        debug_data
+            .typenames
+            .insert("Missing".to_string(), vec![400]);
+
+        assert_eq!(
+            debug_data.type_by_name("Config").unwrap().dbginfo_offset,
+            100
+        );
+        let configs: Vec<usize> = debug_data
+            .types_by_name("Config")
+            .map(|typeinfo| typeinfo.dbginfo_offset)
+            .collect();
+        assert_eq!(configs, vec![100, 200]);
+        assert!(matches!(
+            debug_data.type_by_name("Mode").unwrap().datatype,
+            DbgDataType::Uint8
+        ));
+        assert!(debug_data.type_by_name("Missing").is_none());
+        assert!(debug_data.type_by_name("Unknown").is_none());
+        assert_eq!(debug_data.types_by_name("Unknown").count(), 0);
+    }
+
+    #[test]
+    fn test_unit_names() {
+        let debug_data = DebugData {
+            variables: IndexMap::new(),
+            types: HashMap::new(),
+            typenames: HashMap::new(),
+            demangled_names: HashMap::new(),
+            unit_names: vec![
+                Some("src/main.c".to_string()),
+                None,
+                Some("C:\\project\\Calib.c".to_string()),
+            ],
+            sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: Endianness::Little,
+            architecture: Architecture::Unknown,
+        };
+        assert_eq!(debug_data.unit_name(0), Some("src/main.c"));
+        // a type unit without a name, and an index past the end
+        assert_eq!(debug_data.unit_name(1), None);
+        assert_eq!(debug_data.unit_name(3), None);
+
+        let units: Vec<(usize, &str)> = debug_data.unit_names_iter().collect();
+        assert_eq!(units, vec![(0, "src/main.c"), (2, "C:\\project\\Calib.c")]);
+        let simple_names: Vec<String> = debug_data
+            .unit_names_iter()
+            .map(|(_, name)| simplify_unit_name(name))
+            .collect();
+        assert_eq!(simple_names, vec!["main_c", "Calib_c"]);
+    }
+
+    #[test]
+    fn test_byte_size_override() {
+        let mut padded = make_type(DbgDataType::Other(10));
+        assert_eq!(padded.get_size(), 10);
+        padded.byte_size = Some(12);
+        assert_eq!(padded.get_size(), 12);
+    }
+
+    fn make_type(datatype: DbgDataType) -> TypeInfo {
+        TypeInfo {
+            name: None,
+            unit_idx: 0,
+            datatype,
+            dbginfo_offset: 0,
+            byte_size: None,
+        }
+    }
+
+    #[test]
+    fn test_scalar_and_base_type() {
+        let uint16 = make_type(DbgDataType::Uint16);
+        let bitfield = make_type(DbgDataType::Bitfield {
+            basetype: Box::new(uint16.clone()),
+            bit_offset: 3,
+            bit_size: 2,
+        });
+        let enum_type = make_type(DbgDataType::Enum {
+            size: 4,
+            signed: false,
+            enumerators: vec![("A".to_string(), 0), ("B".to_string(), 1)],
+        });
+        // uint16_t[2][3]: the dimensions are folded into one array type
+        let array_2d = make_type(DbgDataType::Array {
+            size: 12,
+            dim: vec![2, 3],
+            stride: 2,
+            arraytype: Box::new(uint16.clone()),
+            lower_bounds: Vec::new(),
+            dynamic_dims: Vec::new(),
+        });
+        // an array of arrays, e.g. created by declaring an array of a typedef'd array type
+        let array_nested = make_type(DbgDataType::Array {
+            size: 48,
+            dim: vec![4],
+            stride: 12,
+            arraytype: Box::new(array_2d.clone()),
+            lower_bounds: Vec::new(),
+            dynamic_dims: Vec::new(),
+        });
+        let mut members = IndexMap::new();
+        members.insert("bits".to_string(), (bitfield.clone(), Some(0)));
+        members.insert("values".to_string(), (array_2d.clone(), Some(2)));
+        let struct_type = make_type(DbgDataType::Struct { size: 14, members });
+        let mut inheritance = IndexMap::new();
+        inheritance.insert(
+            "base".to_string(),
+            BaseClass {
+                typeinfo: struct_type.clone(),
+                offset: Some(0),
+                is_virtual: false,
+            },
+        );
+        let mut class_members = IndexMap::new();
+        class_members.insert("e".to_string(), (enum_type.clone(), Some(16)));
+        let class_type = make_type(DbgDataType::Class {
+            size: 20,
+            inheritance,
+            members: class_members,
+        });
+
+        assert!(uint16.is_scalar());
+        assert!(bitfield.is_scalar());
+        assert!(enum_type.is_scalar());
+        assert!(make_type(DbgDataType::Pointer(8, 0)).is_scalar());
+        assert!(!array_2d.is_scalar());
+        assert!(!struct_type.is_scalar());
+        assert!(!class_type.is_scalar());
+        assert!(!make_type(DbgDataType::TypeRef(0, 4)).is_scalar());
+
+        assert!(matches!(bitfield.base_type().datatype, DbgDataType::Uint16));
+        assert!(matches!(array_2d.base_type().datatype, DbgDataType::Uint16));
+        assert!(matches!(
+            array_nested.base_type().datatype,
+            DbgDataType::Uint16
+        ));
+        assert!(matches!(
+            struct_type.base_type().datatype,
+            DbgDataType::Struct { .. }
+        ));
+
+        assert_eq!(uint16.datatype.element_count(), 1);
+        assert_eq!(bitfield.datatype.element_count(), 1);
+        assert_eq!(enum_type.datatype.element_count(), 1);
+        assert_eq!(array_2d.datatype.element_count(), 6);
+        assert_eq!(array_nested.datatype.element_count(), 4);
+        assert_eq!(struct_type.datatype.element_count(), 2);
+        assert_eq!(class_type.datatype.element_count(), 2);
+    }
+}
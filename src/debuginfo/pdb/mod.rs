@@ -1,6 +1,8 @@
-use crate::debuginfo::{DbgDataType, DebugData, VarInfo};
+use crate::debuginfo::{DbgDataType, DebugData, LocationKind, VarInfo, get_storage};
 use indexmap::IndexMap;
+use object::Object;
 use pdb2::{AddressMap, FallibleIterator, PDB, RawString, SymbolData};
+use std::path::{Path, PathBuf};
 use std::{collections::HashMap, ffi::OsStr, fs::File, vec};
 use typereader::TypeReaderData;
 
@@ -13,37 +15,102 @@ struct ModuleVars {
     unit_list: Vec<Option<String>>,
 }
 
-pub(crate) fn load_pdb(filename: &OsStr, _verbose: bool) -> Result<DebugData, String> {
-    let file = File::open(filename).map_err(|ioerr| ioerr.to_string())?;
-    let pdb = match PDB::open(file) {
-        Ok(pdb) => pdb,
-        Err(pdb2::Error::UnimplementedFeature(feat)) => {
-            return Err(format!("PDB feature not implemented: {feat}"));
-        }
-        Err(pdb2::Error::IoError(ioerr)) => {
-            return Err(ioerr.to_string());
-        }
-        Err(pdb2::Error::UnrecognizedFileFormat) => {
-            return Err(format!(
-                "Input file {} is not in PDB format",
-                filename.to_string_lossy()
-            ));
-        }
-        Err(pdb2::Error::PageReferenceOutOfRange(_) | pdb2::Error::InvalidPageSize(_)) => {
+pub(crate) fn load_pdb(filename: &OsStr, verbose: bool) -> Result<DebugData, String> {
+    super::raise_log_level(verbose);
+    let pdb = open_pdb(filename)?;
+    read_pdb(pdb).map_err(|pdberr| format!("PDB error: {pdberr:?}"))
+}
+
+// load the debug info of a PE file (exe or dll) from its PDB file.
+// The PDB contains relative virtual addresses, so the image base of the PE file is added to all addresses.
+// If no PDB file is given, then the file named in the CodeView record of the PE file is used, or a file
+// with the name of the PE file and the extension .pdb in the same directory
+pub(crate) fn load_pe_with_pdb(
+    pe_filename: &OsStr,
+    pdb_filename: Option<&OsStr>,
+    verbose: bool,
+) -> Result<DebugData, String> {
+    super::raise_log_level(verbose);
+    let pe_data = std::fs::read(pe_filename).map_err(|ioerr| ioerr.to_string())?;
+    let pe_file = object::File::parse(&*pe_data).map_err(|err| err.to_string())?;
+    if pe_file.format() != object::BinaryFormat::Pe {
+        return Err(format!(
+            "Input file {} is not a PE file",
+            pe_filename.to_string_lossy()
+        ));
+    }
+    let codeview = pe_file.pdb_info().ok().flatten();
+
+    let pdb_path = match pdb_filename {
+        Some(pdb_filename) => PathBuf::from(pdb_filename),
+        None => find_pdb_file(Path::new(pe_filename), codeview.as_ref()),
+    };
+    log::debug!("Loading the PDB file {}", pdb_path.display());
+    let mut pdb = open_pdb(pdb_path.as_os_str())?;
+
+    // a PDB file matches the PE file if the GUIDs match and the PDB is at least as new as the PE file
+    if let Some(codeview) = &codeview {
+        let pdb_guid = pdb
+            .pdb_information()
+            .map_err(|pdberr| format!("PDB error: {pdberr:?}"))?
+            .guid;
+        let pdb_age = pdb
+            .debug_information()
+            .map_err(|pdberr| format!("PDB error: {pdberr:?}"))?
+            .age()
+            .unwrap_or_default();
+        if pdb_guid.to_bytes_le() != codeview.guid() || pdb_age < codeview.age() {
             return Err(format!(
-                "Input file {} is corrupted",
-                filename.to_string_lossy()
+                "The PDB file {} does not belong to {}",
+                pdb_path.display(),
+                pe_filename.to_string_lossy()
             ));
         }
-        Err(err) => {
-            return Err(format!(
-                "Unknown error reading PDB file {}: {err}",
-                filename.to_string_lossy()
-            ));
+    }
+
+    let mut debug_data = read_pdb(pdb).map_err(|pdberr| format!("PDB error: {pdberr:?}"))?;
+    debug_data.architecture = pe_file.architecture();
+    Ok(debug_data.with_load_bias(pe_file.relative_address_base()))
+}
+
+// the CodeView record usually contains the full path of the PDB file on the build machine.
+// If that file does not exist, then the PDB file is expected next to the PE file
+fn find_pdb_file(pe_path: &Path, codeview: Option<&object::CodeView>) -> PathBuf {
+    if let Some(codeview) = codeview {
+        let codeview_path = String::from_utf8_lossy(codeview.path()).into_owned();
+        if !codeview_path.is_empty() {
+            if Path::new(&codeview_path).is_file() {
+                return PathBuf::from(codeview_path);
+            }
+            // the path may use Windows separators, even if a2ltool runs on another OS
+            if let Some(file_name) = codeview_path.rsplit(['\\', '/']).next() {
+                return pe_path.with_file_name(file_name);
+            }
         }
-    };
+    }
+    pe_path.with_extension("pdb")
+}
 
-    read_pdb(pdb).map_err(|pdberr| format!("PDB error: {pdberr:?}"))
+fn open_pdb(filename: &OsStr) -> Result<PDB<'static, File>, String> {
+    let file = File::open(filename).map_err(|ioerr| ioerr.to_string())?;
+    match PDB::open(file) {
+        Ok(pdb) => Ok(pdb),
+        Err(pdb2::Error::UnimplementedFeature(feat)) => {
+            Err(format!("PDB feature not implemented: {feat}"))
+        }
+        Err(pdb2::Error::IoError(ioerr)) => Err(ioerr.to_string()),
+        Err(pdb2::Error::UnrecognizedFileFormat) => Err(format!(
+            "Input file {} is not in PDB format",
+            filename.to_string_lossy()
+        )),
+        Err(pdb2::Error::PageReferenceOutOfRange(_) | pdb2::Error::InvalidPageSize(_)) => Err(
+            format!("Input file {} is corrupted", filename.to_string_lossy()),
+        ),
+        Err(err) => Err(format!(
+            "Unknown error reading PDB file {}: {err}",
+            filename.to_string_lossy()
+        )),
+    }
 }
 
 fn read_pdb(mut pdb: PDB<'_, File>) -> Result<DebugData, pdb2::Error> {
@@ -76,6 +143,11 @@ fn read_pdb(mut pdb: PDB<'_, File>) -> Result<DebugData, pdb2::Error> {
             sections.insert(name, (virt_addr, virt_addr + length));
         }
     }
+    for varinfo in variables.values_mut().flatten() {
+        varinfo.storage = varinfo
+            .address
+            .and_then(|address| get_storage(&sections, address));
+    }
 
     Ok(DebugData {
         variables,
@@ -84,6 +156,12 @@ fn read_pdb(mut pdb: PDB<'_, File>) -> Result<DebugData, pdb2::Error> {
         demangled_names,
         unit_names: unit_list,
         sections,
+        type_scopes: HashMap::new(),
+        unit_info: Vec::new(),
+        dropped_variables: Vec::new(),
+        address_discrepancies: Vec::new(),
+        endian: object::Endianness::Little,
+        architecture: object::Architecture::Unknown,
     })
 }
 
@@ -109,11 +187,19 @@ fn read_global_variables(
                     .entry(symbol_name)
                     .or_default()
                     .push(VarInfo {
-                        address: virt_addr.0 as u64,
+                        address: Some(virt_addr.0 as u64),
                         typeref: data_symbol.type_index.0 as usize,
+                        dbginfo_offset: symbol.index().0 as usize,
                         unit_idx: 0,
                         function: None,
                         namespaces: ns_components,
+                        location_kind: LocationKind::Static,
+                        storage: None,
+                        decl_location: None,
+                        artificial: false,
+                        const_value: None,
+                        is_const: false,
+                        is_external: data_symbol.global,
                     });
             }
         }
@@ -182,11 +268,19 @@ fn read_static_variables(
                             .entry(sym_name)
                             .or_default()
                             .push(VarInfo {
-                                address: virt_addr.0 as u64,
+                                address: Some(virt_addr.0 as u64),
                                 typeref: data_symbol.type_index.0 as usize,
+                                dbginfo_offset: symbol.index().0 as usize,
                                 unit_idx: modvars.unit_list.len() - 1,
                                 function: function_name,
                                 namespaces: vec![],
+                                location_kind: LocationKind::Static,
+                                storage: None,
+                                decl_location: None,
+                                artificial: false,
+                                const_value: None,
+                                is_const: false,
+                                is_external: data_symbol.global,
                             });
                     }
                 }
@@ -367,7 +461,7 @@ mod test {
                             },
                             ..
                         },
-                        0
+                        Some(0)
                     ))
                 ));
                 assert!(matches!(
@@ -381,7 +475,7 @@ mod test {
                             },
                             ..
                         },
-                        0
+                        Some(0)
                     ))
                 ));
                 assert!(matches!(
@@ -395,7 +489,7 @@ mod test {
                             },
                             ..
                         },
-                        4
+                        Some(4)
                     ))
                 ));
                 assert!(matches!(
@@ -409,7 +503,7 @@ mod test {
                             },
                             ..
                         },
-                        4
+                        Some(4)
                     ))
                 ));
             }
@@ -467,4 +561,56 @@ mod test {
             assert!(matches!(arraytype.datatype, DbgDataType::Float));
         }
     }
+
+    #[test]
+    fn test_load_pe_with_pdb() {
+        let exefile = OsStr::new("fixtures/bin/pe_pdb_test.exe");
+        let debugdata = DebugData::load_pe_with_pdb(
+            exefile,
+            Some(OsStr::new("fixtures/bin/pe_pdb_test.pdb")),
+            false,
+        )
+        .unwrap();
+        // the addresses include the image base 0x140000000 of the exe
+        let calib = crate::symbol::find_symbol("calib", &debugdata).unwrap();
+        assert_eq!(calib.address, 0x140002000);
+        let DbgDataType::Struct { size, members } = &calib.typeinfo.datatype else {
+            panic!("Expected struct type, got {:?}", calib.typeinfo.datatype);
+        };
+        assert_eq!(*size, 8);
+        assert_eq!(members.len(), 2);
+        let factor = crate::symbol::find_symbol("calib.factor", &debugdata).unwrap();
+        assert_eq!(factor.address, 0x140002004);
+        assert!(matches!(factor.typeinfo.datatype, DbgDataType::Float));
+        let counter = crate::symbol::find_symbol("counter", &debugdata).unwrap();
+        assert_eq!(counter.address, 0x140002008);
+        assert!(matches!(counter.typeinfo.datatype, DbgDataType::Sint16));
+        assert_eq!(
+            debugdata.sections.get(".data"),
+            Some(&(0x140002000, 0x14000200a))
+        );
+
+        // without a PDB file name, the PDB file next to the exe file is used
+        let debugdata = DebugData::load_pe_with_pdb(exefile, None, false).unwrap();
+        assert_eq!(
+            debugdata.variables.get("calib").unwrap()[0].address,
+            Some(0x140002000)
+        );
+
+        // the PDB file only contains relative addresses
+        let debugdata =
+            DebugData::load_pdb(OsStr::new("fixtures/bin/pe_pdb_test.pdb"), false).unwrap();
+        assert_eq!(
+            debugdata.variables.get("calib").unwrap()[0].address,
+            Some(0x2000)
+        );
+
+        // an elf file is not a PE file
+        let result = DebugData::load_pe_with_pdb(
+            OsStr::new("fixtures/bin/namespace_test.elf"),
+            Some(OsStr::new("fixtures/bin/pe_pdb_test.pdb")),
+            false,
+        );
+        assert!(result.is_err());
+    }
 }
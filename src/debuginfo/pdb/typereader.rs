@@ -1,4 +1,4 @@
-use crate::debuginfo::{DbgDataType, TypeInfo, VarInfo};
+use crate::debuginfo::{BaseClass, DbgDataType, TypeInfo, VarInfo};
 use indexmap::IndexMap;
 use pdb2::{FallibleIterator, ItemIter, PDB, TypeData, TypeIndex};
 use std::{collections::HashMap, fs::File};
@@ -188,6 +188,7 @@ fn read_type(
             name: display_name,
             unit_idx: 0, // in the PDB, all types are global
             dbginfo_offset: type_index as usize,
+            byte_size: None,
         };
 
         typereader_data.types.insert(type_index as usize, typeinfo);
@@ -235,6 +236,7 @@ fn read_type_from_typedata(
                     DbgDataType::Union {
                         size: union_type.size,
                         members: IndexMap::new(),
+                        discriminant: None,
                     },
                     None,
                 ))
@@ -311,15 +313,15 @@ fn read_primitive_type(primitive_type: &pdb2::PrimitiveType) -> (DbgDataType, Op
         pdb2::PrimitiveKind::Bool32 => (DbgDataType::Uint32, "bool32"),
         pdb2::PrimitiveKind::Bool64 => (DbgDataType::Uint64, "bool64"),
         // types below are not supported by a2l
-        pdb2::PrimitiveKind::Octa => (DbgDataType::Other(16), "octa"),
-        pdb2::PrimitiveKind::UOcta => (DbgDataType::Other(16), "uocta"),
-        pdb2::PrimitiveKind::I128 => (DbgDataType::Other(16), "i128"),
-        pdb2::PrimitiveKind::U128 => (DbgDataType::Other(16), "u128"),
+        pdb2::PrimitiveKind::Octa => (DbgDataType::Sint128, "octa"),
+        pdb2::PrimitiveKind::UOcta => (DbgDataType::Uint128, "uocta"),
+        pdb2::PrimitiveKind::I128 => (DbgDataType::Sint128, "i128"),
+        pdb2::PrimitiveKind::U128 => (DbgDataType::Uint128, "u128"),
         pdb2::PrimitiveKind::F16 => (DbgDataType::Other(2), "f16"),
         pdb2::PrimitiveKind::F32PP => (DbgDataType::Other(4), "f32pp"),
         pdb2::PrimitiveKind::F48 => (DbgDataType::Other(6), "f48"),
-        pdb2::PrimitiveKind::F80 => (DbgDataType::Other(10), "f80"),
-        pdb2::PrimitiveKind::F128 => (DbgDataType::Other(16), "f128"),
+        pdb2::PrimitiveKind::F80 => (DbgDataType::LongDouble(10), "f80"),
+        pdb2::PrimitiveKind::F128 => (DbgDataType::Float128, "f128"),
         pdb2::PrimitiveKind::Complex32 => (DbgDataType::Other(4), "complex32"),
         pdb2::PrimitiveKind::Complex64 => (DbgDataType::Other(8), "complex64"),
         pdb2::PrimitiveKind::Complex80 => (DbgDataType::Other(10), "complex80"),
@@ -378,7 +380,14 @@ fn read_class(
         } else {
             // copy all inherited members from the base classes
             // this allows the inherited members ot be accessed without naming the base class
-            for (baseclass_type, baseclass_offset) in inheritance.values() {
+            for BaseClass {
+                typeinfo: baseclass_type,
+                offset: baseclass_offset,
+                ..
+            } in inheritance.values()
+            {
+                // virtual base classes are not read from the PDB, so the offset is always known
+                let baseclass_offset = baseclass_offset.unwrap_or_default();
                 match &baseclass_type.datatype {
                     DbgDataType::Struct {
                         members: baseclass_members,
@@ -391,7 +400,10 @@ fn read_class(
                         for (name, (m_type, m_offset)) in baseclass_members {
                             members.insert(
                                 name.clone(),
-                                (m_type.clone(), m_offset + baseclass_offset),
+                                (
+                                    m_type.clone(),
+                                    m_offset.map(|m_offset| m_offset + baseclass_offset),
+                                ),
                             );
                         }
                     }
@@ -429,7 +441,11 @@ fn read_union(
     let size = union_type.size;
     let fields_index = union_type.fields.0;
     let members = read_fields(fields_index, typereader_data, pdb_data)?;
-    let datatype = DbgDataType::Union { members, size };
+    let datatype = DbgDataType::Union {
+        members,
+        size,
+        discriminant: None,
+    };
     Ok((datatype, None))
 }
 
@@ -437,7 +453,7 @@ fn read_fields(
     type_index: u32,
     typereader_data: &mut TypeReaderData,
     pdb_data: &PdbData<'_>,
-) -> Result<IndexMap<String, (TypeInfo, u64)>, String> {
+) -> Result<IndexMap<String, (TypeInfo, Option<u64>)>, String> {
     let mut opt_fields_index = Some(type_index);
 
     let mut members = IndexMap::new();
@@ -486,13 +502,14 @@ fn read_fields(
                         name,
                         unit_idx: 0,
                         dbginfo_offset: 0,
+                        byte_size: None,
                     }
                 } else {
                     // use simple types directly
                     member_typeinfo.clone()
                 };
 
-                members.insert(member_name, (typeinfo, offset));
+                members.insert(member_name, (typeinfo, Some(offset)));
             }
         }
 
@@ -507,7 +524,7 @@ fn read_class_inheritance(
     type_index: u32,
     typereader_data: &mut TypeReaderData,
     pdb_data: &PdbData<'_>,
-) -> Result<IndexMap<String, (TypeInfo, u64)>, String> {
+) -> Result<IndexMap<String, BaseClass>, String> {
     let mut inheritance = IndexMap::new();
     let mut opt_fields_index = Some(type_index);
 
@@ -541,7 +558,14 @@ fn read_class_inheritance(
                     .clone()
                     .expect("class lacks a name");
 
-                inheritance.insert(name, (referenced_typeinfo.clone(), base_offset));
+                inheritance.insert(
+                    name,
+                    BaseClass {
+                        typeinfo: referenced_typeinfo.clone(),
+                        offset: Some(base_offset),
+                        is_virtual: false,
+                    },
+                );
             }
         }
 
@@ -702,6 +726,8 @@ fn read_array(
         dim: array_dim,
         stride,
         arraytype: Box::new(element_type.clone()),
+        lower_bounds: Vec::new(),
+        dynamic_dims: Vec::new(),
     };
 
     Ok((datatype, element_type.name.clone()))
@@ -173,17 +173,16 @@ pub(crate) fn read_builtin_type(
         BUILTIN_TYPE_REAL32 => DbgDataType::Float,
         BUILTIN_TYPE_REAL64 => DbgDataType::Double,
 
-        BUILTIN_TYPE_REAL80 | BUILTIN_TYPE_COMPLEX80 => {
-            // a2l does not support 80 bit floating point numbers or complex numbers
+        BUILTIN_TYPE_REAL80 => DbgDataType::LongDouble(10),
+        BUILTIN_TYPE_COMPLEX80 => {
+            // a2l does not support complex numbers
             DbgDataType::Other(10)
         }
-        BUILTIN_TYPE_OCT
-        | BUILTIN_TYPE_INT128
-        | BUILTIN_TYPE_UOCT
-        | BUILTIN_TYPE_UINT128
-        | BUILTIN_TYPE_REAL128
-        | BUILTIN_TYPE_COMPLEX128 => {
-            // a2l does not support any 128 bit types
+        BUILTIN_TYPE_OCT | BUILTIN_TYPE_INT128 => DbgDataType::Sint128,
+        BUILTIN_TYPE_UOCT | BUILTIN_TYPE_UINT128 => DbgDataType::Uint128,
+        BUILTIN_TYPE_REAL128 => DbgDataType::Float128,
+        BUILTIN_TYPE_COMPLEX128 => {
+            // a2l does not support complex numbers
             DbgDataType::Other(16)
         }
         BUILTIN_TYPE_REAL48 => {
@@ -216,6 +215,7 @@ pub(crate) fn read_builtin_type(
             name: None,
             unit_idx: 0,
             dbginfo_offset: 0,
+            byte_size: None,
         },
     );
 
@@ -241,6 +241,7 @@ pub(crate) fn read_builtin_type(
                 name: None,
                 unit_idx: 0,
                 dbginfo_offset: 0,
+                byte_size: None,
             },
         );
     }
@@ -59,7 +59,7 @@ pub(crate) fn insert_items(
     let mut insert_list: Vec<(&str, SymbolInfo, bool)> = Vec::new();
 
     for measure_sym in measurement_symbols {
-        match crate::symbol::find_symbol(measure_sym, debug_data) {
+        match crate::symbol::find_symbol_with_address(measure_sym, debug_data) {
             Ok(sym_info) => insert_list.push((measure_sym, sym_info, false)),
             Err(errmsg) => log_msgs.push(format!(
                 "Insert skipped: Symbol {measure_sym} could not be added: {errmsg}"
@@ -67,7 +67,7 @@ pub(crate) fn insert_items(
         }
     }
     for characteristic_sym in characteristic_symbols {
-        match crate::symbol::find_symbol(characteristic_sym, debug_data) {
+        match crate::symbol::find_symbol_with_address(characteristic_sym, debug_data) {
             Ok(sym_info) => insert_list.push((characteristic_sym, sym_info, true)),
             Err(errmsg) => log_msgs.push(format!(
                 "Insert skipped: Symbol {characteristic_sym} could not be added: {errmsg}"
@@ -117,7 +117,10 @@ pub(crate) fn insert_items(
                 }
             }
         } else if enable_structures
-            && !matches!(sym_info.typeinfo.datatype, DbgDataType::FuncPtr(_))
+            && !matches!(
+                sym_info.typeinfo.datatype,
+                DbgDataType::FunctionPointer { .. }
+            )
         {
             match insert_instance_sym(
                 module, debug_data, sym_name, &sym_info, &name_map, &sym_map, is_calib,
@@ -539,9 +542,19 @@ pub(crate) fn insert_many<'param>(
     while let Some(sym_info) = current_item {
         let mut skip_children = false;
         match &sym_info.typeinfo.datatype {
-            DbgDataType::TypeRef(_, _) | DbgDataType::FuncPtr(_) => {}
+            // a2l has no data types for 128 bit integers and floats with extended precision,
+            // and Rust enums with data can't be represented as an INSTANCE. The size of incomplete types is unknown
+            DbgDataType::TypeRef(_, _)
+            | DbgDataType::Incomplete { .. }
+            | DbgDataType::FunctionPointer { .. }
+            | DbgDataType::VariantEnum { .. }
+            | DbgDataType::Uint128
+            | DbgDataType::Sint128
+            | DbgDataType::LongDouble(_)
+            | DbgDataType::Float128 => {}
             DbgDataType::Other(_)
             | DbgDataType::Pointer(_, _)
+            | DbgDataType::Reference { .. }
             | DbgDataType::Struct { .. }
             | DbgDataType::Class { .. }
             | DbgDataType::Union { .. } => {
@@ -563,6 +576,9 @@ pub(crate) fn insert_many<'param>(
             DbgDataType::Enum { .. }
             | DbgDataType::Float
             | DbgDataType::Double
+            | DbgDataType::Bool
+            | DbgDataType::SChar
+            | DbgDataType::UChar
             | DbgDataType::Sint8
             | DbgDataType::Sint16
             | DbgDataType::Sint32
@@ -616,6 +632,9 @@ fn is_simple_type(typeinfo: &TypeInfo) -> bool {
         DbgDataType::Enum { .. }
             | DbgDataType::Float
             | DbgDataType::Double
+            | DbgDataType::Bool
+            | DbgDataType::SChar
+            | DbgDataType::UChar
             | DbgDataType::Sint8
             | DbgDataType::Sint16
             | DbgDataType::Sint32
@@ -887,7 +906,10 @@ fn insert_instance_sym<'dbg>(
     sym_map: &HashMap<String, Vec<ItemType>>,
     is_calib: bool,
 ) -> Result<(String, &'dbg TypeInfo), String> {
-    if !matches!(&sym_info.typeinfo.datatype, DbgDataType::FuncPtr(_)) {
+    if !matches!(
+        &sym_info.typeinfo.datatype,
+        DbgDataType::FunctionPointer { .. }
+    ) {
         // Abort if a INSTANCE for this symbol already exists. Warn if any other reference to the symbol exists
         let item_name = make_unique_instance_name(module, sym_map, &sym_info.name, name_map)?;
 
@@ -1323,4 +1345,48 @@ mod test {
         assert_eq!(a2l.project.module[0].measurement.len(), 0);
         assert_eq!(a2l.project.module[0].characteristic.len(), 0);
     }
+
+    #[test]
+    fn reject_tls_variables() {
+        // thread-local variables only have an offset into the TLS block, not an address
+        let mut a2l = a2lfile::new();
+        let debug_data = crate::debuginfo::DebugData::load_dwarf(
+            &OsString::from("fixtures/bin/tls_test.elf"),
+            false,
+        )
+        .unwrap();
+
+        let measurement_symbols = vec!["tls_counter", "plain_var"];
+        let characteristic_symbols = vec!["tls_flags"];
+        let mut log_msgs = Vec::new();
+        insert_items(
+            &mut a2l,
+            &debug_data,
+            measurement_symbols,
+            characteristic_symbols,
+            None,
+            &mut log_msgs,
+            false,
+        );
+        assert_eq!(a2l.project.module[0].measurement.len(), 1);
+        assert!(a2l.project.module[0].measurement.contains_key("plain_var"));
+        assert_eq!(a2l.project.module[0].characteristic.len(), 0);
+
+        // the TLS variables are not matched by a regex either
+        let mut a2l = a2lfile::new();
+        let mut log_msgs = Vec::new();
+        insert_many(
+            &mut a2l,
+            &debug_data,
+            &[],
+            &[],
+            vec!["tls_.*"],
+            vec!["tls_.*"],
+            None,
+            &mut log_msgs,
+            false,
+        );
+        assert_eq!(a2l.project.module[0].measurement.len(), 0);
+        assert_eq!(a2l.project.module[0].characteristic.len(), 0);
+    }
 }
@@ -61,7 +61,28 @@ macro_rules! ext_println {
     };
 }
 
+// diagnostic messages of the debug info loader are sent through the log crate.
+// The log level filter stays at Off unless verbose output is requested.
+struct ConsoleLogger;
+
+impl log::Log for ConsoleLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            println!("{}", record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: ConsoleLogger = ConsoleLogger;
+
 fn main() {
+    let _ = log::set_logger(&LOGGER);
     let args = std::env::args_os();
     match core(args) {
         Ok(()) => {}
@@ -115,6 +136,9 @@ fn core(args: impl Iterator<Item = OsString>) -> Result<(), String> {
         .get_one::<bool>("MERGEINCLUDES")
         .expect("option merge-includes must always exist");
     let verbose = arg_matches.get_count("VERBOSE");
+    if verbose > 0 {
+        log::set_max_level(log::LevelFilter::Debug);
+    }
     let opt_update_type = arg_matches.get_one::<UpdateType>("UPDATE_TYPE");
 
     if let Some(true) = arg_matches.get_one::<bool>("SAFE_UPDATE") {
@@ -193,12 +217,25 @@ fn core(args: impl Iterator<Item = OsString>) -> Result<(), String> {
     // load debuginfo from an elf or pdb file
     let opt_elffile = arg_matches.get_one::<OsString>("ELFFILE");
     let opt_pdbfile = arg_matches.get_one::<OsString>("PDBFILE");
-    let debuginfo = if let Some(elffile) = opt_elffile {
-        Some(DebugData::load_dwarf(elffile, verbose > 0)?)
-    } else if let Some(pdbfile) = opt_pdbfile {
-        Some(DebugData::load_pdb(pdbfile, verbose > 0)?)
-    } else {
-        None
+    let debuginfo = match (opt_elffile, opt_pdbfile) {
+        #[cfg(feature = "pdb")]
+        (Some(exefile), Some(pdbfile)) => Some(DebugData::load_pe_with_pdb(
+            exefile,
+            Some(pdbfile),
+            verbose > 0,
+        )?),
+        (Some(elffile), None) => {
+            Some(DebugData::load_dwarf(elffile, verbose > 0).map_err(|err| err.to_string())?)
+        }
+        #[cfg(feature = "pdb")]
+        (None, Some(pdbfile)) => Some(DebugData::load_pdb(pdbfile, verbose > 0)?),
+        #[cfg(not(feature = "pdb"))]
+        (_, Some(_)) => {
+            return Err(
+                "PDB files can't be loaded: a2ltool was built without the pdb feature".to_string(),
+            );
+        }
+        (None, None) => None,
     };
     // display statistics and debug data if requested
     if let Some(debuginfo) = &debuginfo {
@@ -623,7 +660,7 @@ fn parse_args(args: impl Iterator<Item = OsString>) -> ArgMatches {
         .action(clap::ArgAction::SetTrue)
     )
     .arg(Arg::new("ELFFILE")
-        .help("Elf file containing symbols and address information in DWARF2+ format.\nAn exe file produced by MinGW with DWARF2 debug info can also be used.")
+        .help("Elf file containing symbols and address information in DWARF2+ format.\nAn exe file produced by MinGW with DWARF2 debug info can also be used.\nOn macOS, a dSYM bundle or an executable with a dSYM bundle next to it can be used.\nA WebAssembly module with DWARF debug info can also be used; the addresses are offsets into its linear memory.")
         .short('e')
         .long("elffile")
         .number_of_values(1)
@@ -633,7 +670,7 @@ fn parse_args(args: impl Iterator<Item = OsString>) -> ArgMatches {
         .alias("elf")
     )
     .arg(Arg::new("PDBFILE")
-        .help("PDB file containig debugging information in Microsoft's Program Database format.")
+        .help("PDB file containig debugging information in Microsoft's Program Database format.\nIf an exe file is also given with --elffile, then the absolute addresses of the variables in the exe file are used.")
         .long("pdbfile")
         .number_of_values(1)
         .value_name("PDBFILE")
@@ -868,7 +905,7 @@ The arg --update must be present.")
     .group(
         ArgGroup::new("DEBUGINFO_ARGGROUP")
             .args(["ELFFILE", "PDBFILE"])
-            .multiple(false)
+            .multiple(true)
     )
     .group(
         ArgGroup::new("INPUT_ARGGROUP")
@@ -1,15 +1,19 @@
-use clap::builder::Str;
-use object::read::pe::PeRelocationIterator;
-
+#[cfg(test)]
+use crate::debuginfo::get_section_name;
 use crate::debuginfo::iter::TypeInfoIter;
-use crate::debuginfo::{DbgDataType, VarInfo};
-use crate::debuginfo::{DebugData, TypeInfo, make_simple_unit_name};
-use lazy_static::lazy_static;
-use std::fs::OpenOptions;
-use std::io::Write;
-use std::sync::Mutex;
+use crate::debuginfo::{
+    BaseClass, DebugData, DebugDataError, Storage, TypeInfo, make_simple_unit_name,
+    unit_matches_simple_name,
+};
+use crate::debuginfo::{ConstValue, DbgDataType, LocationKind, VarInfo};
+#[cfg(test)]
+use std::collections::{HashMap, HashSet};
+#[cfg(test)]
+use std::fmt::Write;
 
+// several fields only serve callers that embed the symbol lookup; the command line doesn't read them
 #[derive(Clone)]
+#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) struct SymbolInfo<'dbg> {
     pub(crate) name: String,
     pub(crate) address: u64,
@@ -18,19 +22,429 @@ pub(crate) struct SymbolInfo<'dbg> {
     pub(crate) function_name: &'dbg Option<String>,
     pub(crate) namespaces: &'dbg [String],
     pub(crate) is_unique: bool,
+    pub(crate) is_tls: bool,
+    pub(crate) storage: Option<Storage>,
+    // source file and line of the declaration of the variable
+    pub(crate) source_location: &'dbg Option<(String, u64)>,
+    // offset of the DIE of the variable that contains the symbol, see VarInfo::dbginfo_offset
+    pub(crate) dbginfo_offset: usize,
+    pub(crate) match_kind: MatchKind,
+    // the value of a compile-time constant, which has no address. The address of the symbol is 0 in this case
+    pub(crate) const_value: Option<&'dbg ConstValue>,
+    // the variable that contains the symbol is const, see VarInfo::is_const
+    pub(crate) is_const: bool,
+    // the variable that contains the symbol is visible outside of its unit, see VarInfo::is_external
+    pub(crate) is_external: bool,
+}
+
+/// how the name of a symbol was matched by find_symbol
+/// There is no case-insensitive match: C and C++ names are case sensitive, so "Speed" and "speed" can be
+/// different variables, and matching them would silently link an A2L object to the wrong one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MatchKind {
+    /// the name was found as it was given
+    Exact,
+    /// the name was not found, but it is the demangled form of a mangled C++ name that was found,
+    /// or a C++ qualified name whose namespaces select the variable
+    Demangled,
+}
+
+/// whether a symbol should be described by a CHARACTERISTIC or a MEASUREMENT in an A2L file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg(test)]
+pub(crate) enum A2lKind {
+    /// a tunable constant, which is calibrated
+    Characteristic,
+    /// a variable that changes at runtime, which is measured
+    Measurement,
+}
+
+/// the rules that SymbolInfo::a2l_kind_with_rules() uses to classify a symbol
+#[derive(Debug, Clone)]
+#[cfg(test)]
+pub(crate) struct A2lKindRules {
+    /// constants in .rodata and similar sections
+    pub(crate) read_only: A2lKind,
+    /// variables with an initial value in .data, and in custom sections
+    pub(crate) initialized: A2lKind,
+    /// zero-initialized variables in .bss
+    pub(crate) zero_init: A2lKind,
+    /// variables whose section is not known, e.g. in PDB files
+    pub(crate) unknown: A2lKind,
+    /// the kind of the variables in specific sections, e.g. (".calib", Characteristic), matched by the
+    /// prefix of the section name. These take precedence over the other rules
+    pub(crate) sections: Vec<(String, A2lKind)>,
+}
+
+#[cfg(test)]
+impl Default for A2lKindRules {
+    fn default() -> Self {
+        Self {
+            read_only: A2lKind::Characteristic,
+            initialized: A2lKind::Measurement,
+            zero_init: A2lKind::Measurement,
+            unknown: A2lKind::Measurement,
+            sections: Vec::new(),
+        }
+    }
+}
+
+/// one scalar element in the memory layout of a symbol
+#[derive(Debug, Clone)]
+#[cfg(test)]
+pub(crate) struct LayoutEntry<'dbg> {
+    /// the full name of the element, e.g. "var.member._2_"
+    pub(crate) path: String,
+    /// offset of the element relative to the address of the symbol
+    pub(crate) offset: u64,
+    pub(crate) size: u64,
+    pub(crate) datatype: &'dbg DbgDataType,
+}
+
+impl<'dbg> SymbolInfo<'dbg> {
+    /// get the C++ style qualified name of the symbol.
+    /// For a variable this is the variable name prefixed by its namespaces, e.g. "ns1::ns2::var".
+    /// For a member of a struct or class the name is built from the scope of the containing type, e.g. "ns1::ns2::Class::member".
+    /// If the containing type has no name, then the member path is appended to the qualified variable name, e.g. "ns1::var.member"
+    #[cfg(test)]
+    pub(crate) fn qualified_name(&self, debug_data: &DebugData) -> String {
+        let components = split_symbol_components(&self.name);
+
+        // the symbol may have been found using its mangled name: the demangled name is already qualified
+        let qualified_varname = if let Some((demangled, _)) = debug_data
+            .demangled_names
+            .iter()
+            .find(|(_, mangled_list)| mangled_list.iter().any(|mangled| mangled == components[0]))
+        {
+            demangled.clone()
+        } else {
+            // namespaces are stored innermost first
+            let mut scope: Vec<&str> = self.namespaces.iter().rev().map(String::as_str).collect();
+            scope.push(components[0]);
+            scope.join("::")
+        };
+        if components.len() == 1 {
+            return qualified_varname;
+        }
+
+        if let Some((parent_type, member)) = self.find_member_parent(&components, debug_data)
+            && let Some(parent_name) = &parent_type.name
+        {
+            return match debug_data.type_scopes.get(&parent_type.dbginfo_offset) {
+                Some(scope) => format!("{scope}::{parent_name}::{member}"),
+                None => format!("{parent_name}::{member}"),
+            };
+        }
+
+        let member_path = self.name.strip_prefix(components[0]).unwrap_or_default();
+        format!("{qualified_varname}{member_path}")
+    }
+
+    /// get the source file and line where the variable containing the symbol was declared
+    #[cfg(test)]
+    pub(crate) fn decl_location(&self) -> Option<(String, u64)> {
+        self.source_location.clone()
+    }
+
+    /// get the range of addresses that is occupied by the symbol: from its address up to, but not including, the end
+    #[cfg(test)]
+    pub(crate) fn address_range(&self) -> (u64, u64) {
+        (
+            self.address,
+            self.address.saturating_add(self.typeinfo.get_size()),
+        )
+    }
+
+    /// check if the symbol is a pointer, including function pointers. Typedefs of pointers are resolved when the types are loaded
+    #[cfg(test)]
+    pub(crate) fn is_pointer(&self, debug_data: &DebugData) -> bool {
+        matches!(
+            self.typeinfo.get_reference(&debug_data.types).datatype,
+            DbgDataType::Pointer(..) | DbgDataType::FunctionPointer { .. }
+        )
+    }
+
+    /// get a flat list of all scalar elements inside the symbol, with their offsets and sizes
+    /// The elements are listed in the order of their declaration. The members of a union all
+    /// have the same offset, so they overlap in the list
+    #[cfg(test)]
+    pub(crate) fn layout(&self, debug_data: &'dbg DebugData) -> Vec<LayoutEntry<'dbg>> {
+        let typeinfo = self.typeinfo.get_reference(&debug_data.types);
+        if typeinfo.is_scalar() {
+            return vec![LayoutEntry {
+                path: self.name.clone(),
+                offset: 0,
+                size: typeinfo.get_size(),
+                datatype: &typeinfo.datatype,
+            }];
+        }
+
+        TypeInfoIter::new(&debug_data.types, typeinfo, false)
+            .filter(|(_, item_type, _)| item_type.is_scalar())
+            .map(|(name, item_type, offset)| LayoutEntry {
+                path: format!("{}{name}", self.name),
+                offset,
+                size: item_type.get_size(),
+                datatype: &item_type.datatype,
+            })
+            .collect()
+    }
+
+    /// get all scalar elements inside the symbol as individual symbols, with their full names and absolute addresses.
+    /// Bitfield elements keep their bitfield type, so that the bit offset and bit size remain available.
+    /// A scalar symbol only yields itself
+    #[cfg(test)]
+    pub(crate) fn leaves(
+        &self,
+        debug_data: &'dbg DebugData,
+    ) -> impl Iterator<Item = SymbolInfo<'dbg>> + use<'dbg> {
+        self.leaves_core(debug_data, false)
+    }
+
+    /// like leaves(), but C strings (arrays of char) are returned as one element instead of one element per character.
+    /// Individual characters can still be accessed by find_symbol with an explicit index, e.g. "var.name[3]"
+    #[cfg(test)]
+    pub(crate) fn leaves_with_strings(
+        &self,
+        debug_data: &'dbg DebugData,
+    ) -> impl Iterator<Item = SymbolInfo<'dbg>> + use<'dbg> {
+        self.leaves_core(debug_data, true)
+    }
+
+    /// check if the symbol is a C string, i.e. an array of char
+    #[cfg(test)]
+    pub(crate) fn is_c_string(&self, debug_data: &DebugData) -> bool {
+        self.typeinfo.is_c_string(&debug_data.types)
+    }
+
+    /// guess whether the symbol is a CHARACTERISTIC or a MEASUREMENT, based on the section that contains it:
+    /// constants in .rodata are characteristics, variables in .data and .bss are measurements
+    #[cfg(test)]
+    pub(crate) fn a2l_kind(&self, debug_data: &DebugData) -> A2lKind {
+        self.a2l_kind_with_rules(debug_data, &A2lKindRules::default())
+    }
+
+    /// like a2l_kind(), but with custom rules for each kind of storage and for specific sections.
+    /// Thread-local variables only exist at runtime, so they are always measurements
+    #[cfg(test)]
+    pub(crate) fn a2l_kind_with_rules(
+        &self,
+        debug_data: &DebugData,
+        rules: &A2lKindRules,
+    ) -> A2lKind {
+        if self.is_tls {
+            return A2lKind::Measurement;
+        }
+        if let Some(section_name) = get_section_name(&debug_data.sections, self.address)
+            && let Some((_, kind)) = rules
+                .sections
+                .iter()
+                .find(|(prefix, _)| section_name.starts_with(prefix.as_str()))
+        {
+            return *kind;
+        }
+        match self.storage {
+            Some(Storage::ReadOnly) => rules.read_only,
+            Some(Storage::Initialized) => rules.initialized,
+            Some(Storage::ZeroInit) => rules.zero_init,
+            // without a section, e.g. in an object file, a const variable is still known to be read-only
+            None if self.is_const => rules.read_only,
+            None => rules.unknown,
+        }
+    }
+
+    /// get the enumerators (name, value) of the symbol in declaration order, if the symbol is an enum.
+    /// This includes bitfields whose base type is an enum
+    #[cfg(test)]
+    pub(crate) fn enum_entries(&self) -> Option<&'dbg [(String, i64)]> {
+        let typeinfo = match &self.typeinfo.datatype {
+            DbgDataType::Bitfield { basetype, .. } => &**basetype,
+            _ => self.typeinfo,
+        };
+        if let DbgDataType::Enum { enumerators, .. } = &typeinfo.datatype {
+            Some(enumerators)
+        } else {
+            None
+        }
+    }
+
+    /// get all distinct types that are used by the symbol: its own type and all types that are reachable from it
+    /// through members, base classes, array elements, pointers and function pointers.
+    /// Each type is returned once, even if the type graph contains cycles, e.g. in a linked list.
+    /// Typedefs and qualifiers are resolved, and bitfields are represented by their base type
+    #[cfg(test)]
+    pub(crate) fn referenced_types(&self, debug_data: &'dbg DebugData) -> Vec<&'dbg TypeInfo> {
+        let mut visited = HashSet::new();
+        let mut referenced = Vec::new();
+        let mut pending = vec![self.typeinfo];
+        while let Some(typeinfo) = pending.pop() {
+            // references are resolved, and bitfields are represented by their base type
+            let typeinfo = typeinfo.get_reference(&debug_data.types);
+            if let DbgDataType::Bitfield { basetype, .. } = &typeinfo.datatype {
+                pending.push(basetype);
+                continue;
+            }
+            if !visited.insert(typeinfo.dbginfo_offset) {
+                continue;
+            }
+            referenced.push(typeinfo);
+
+            // the children are pushed in reverse, so that they are visited in declaration order
+            let first_child = pending.len();
+            match &typeinfo.datatype {
+                DbgDataType::Pointer(_, dest_offset)
+                | DbgDataType::Reference {
+                    target: dest_offset,
+                    ..
+                } => {
+                    pending.extend(debug_data.types.get(dest_offset));
+                }
+                DbgDataType::Array { arraytype, .. } => pending.push(arraytype),
+                DbgDataType::Struct { members, .. } | DbgDataType::Union { members, .. } => {
+                    pending.extend(members.values().map(|(membertype, _)| membertype));
+                }
+                DbgDataType::Class {
+                    inheritance,
+                    members,
+                    ..
+                } => {
+                    pending.extend(inheritance.values().map(|baseclass| &baseclass.typeinfo));
+                    pending.extend(members.values().map(|(membertype, _)| membertype));
+                }
+                DbgDataType::VariantEnum {
+                    discriminant,
+                    variants,
+                    ..
+                } => {
+                    pending.extend(discriminant.iter().map(|(discr_type, _)| &**discr_type));
+                    pending.extend(variants.values().map(|variant| &variant.typeinfo));
+                }
+                DbgDataType::FunctionPointer {
+                    return_type,
+                    params,
+                    ..
+                } => {
+                    pending.push(return_type);
+                    pending.extend(params);
+                }
+                _ => {}
+            }
+            pending[first_child..].reverse();
+        }
+
+        referenced
+    }
+
+    #[cfg(test)]
+    fn leaves_core(
+        &self,
+        debug_data: &'dbg DebugData,
+        strings_as_values: bool,
+    ) -> impl Iterator<Item = SymbolInfo<'dbg>> + use<'dbg> {
+        let types = &debug_data.types;
+        let is_leaf = move |item_type: &TypeInfo| {
+            item_type.is_scalar() || (strings_as_values && item_type.is_c_string(types))
+        };
+        let typeinfo = self.typeinfo.get_reference(types);
+        let own_leaf = is_leaf(typeinfo).then(|| SymbolInfo {
+            typeinfo,
+            ..self.clone()
+        });
+
+        let parent = self.clone();
+        let mut iter = TypeInfoIter::new(types, typeinfo, false);
+        if strings_as_values {
+            iter = iter.strings_as_values();
+        }
+        let member_leaves = iter
+            .filter(move |(_, item_type, _)| is_leaf(item_type))
+            .map(move |(name, item_type, offset)| SymbolInfo {
+                name: format!("{}{name}", parent.name),
+                address: parent.address + offset,
+                typeinfo: item_type,
+                ..parent.clone()
+            });
+        own_leaf.into_iter().chain(member_leaves)
+    }
+
+    // find the type that contains the last member in the symbol name
+    #[cfg(test)]
+    fn find_member_parent<'a>(
+        &self,
+        components: &[&'a str],
+        debug_data: &'a DebugData,
+    ) -> Option<(&'a TypeInfo, &'a str)> {
+        let varinfo = debug_data
+            .variables
+            .get(components[0])?
+            .iter()
+            .find(|vi| vi.unit_idx == self.unit_idx && vi.namespaces == self.namespaces)?;
+        let mut current_type = debug_data
+            .types
+            .get(&varinfo.typeref)?
+            .get_reference(&debug_data.types);
+        let mut parent = None;
+        let mut idx = 1;
+        while idx < components.len() {
+            match &current_type.datatype {
+                DbgDataType::Array { dim, arraytype, .. } => {
+                    // all dimensions of the array are indexed at once
+                    idx += dim.len();
+                    current_type = arraytype.get_reference(&debug_data.types);
+                }
+                DbgDataType::Class {
+                    members,
+                    inheritance,
+                    ..
+                } => {
+                    if let Some((membertype, _)) = members.get(components[idx]) {
+                        parent = Some((current_type, components[idx]));
+                        current_type = membertype.get_reference(&debug_data.types);
+                    } else if let Some(baseclass) = inheritance.get(components[idx]) {
+                        current_type = baseclass.typeinfo.get_reference(&debug_data.types);
+                        idx += base_separator_len(components, idx + 1, current_type);
+                    } else {
+                        // an inherited member that is used without naming its base class
+                        let mut paths =
+                            find_base_paths_with_member(current_type, components[idx], debug_data);
+                        let path = paths.pop().filter(|_| paths.is_empty())?;
+                        let (_, baseclass) = path[path.len() - 1];
+                        current_type = baseclass.typeinfo.get_reference(&debug_data.types);
+                        continue;
+                    }
+                    idx += 1;
+                }
+                DbgDataType::Struct { members, .. } | DbgDataType::Union { members, .. } => {
+                    let (membertype, _) = members.get(components[idx])?;
+                    parent = Some((current_type, components[idx]));
+                    current_type = membertype.get_reference(&debug_data.types);
+                    idx += 1;
+                }
+                _ => return None,
+            }
+        }
+        parent
+    }
 }
 
 struct AdditionalSpec {
     function_name: Option<String>,
     simple_unit_name: Option<String>,
     namespaces: Vec<String>,
+    // exact unit index and address, given by {Unit:<index>} and {Address:0x...}
+    unit_idx: Option<usize>,
+    address: Option<u64>,
 }
 
 // find a symbol in the elf_info data structure that was derived from the DWARF debug info in the elf file
+// The variable may be given with its C++ namespaces, e.g. "ns1::ns2::var.member[1]", and a static data member
+// of a class with the enclosing classes, e.g. "ns1::Class::static_member". The name is split on '.'
+// first, so "::" separators are only recognized in the variable name before the first '.', and all
+// further components are struct members or array indices
 pub(crate) fn find_symbol<'a>(
     varname: &str,
     debug_data: &'a DebugData,
-) -> Result<SymbolInfo<'a>, String> {
+) -> Result<SymbolInfo<'a>, DebugDataError> {
     // Extension seen in files generated by Vector tools:
     // The varname in a symbol link might contain additional information
     // var{Function:FuncName}{CompileUnit:UnitName_c}{Namespace:Global}"
@@ -48,43 +462,344 @@ pub(crate) fn find_symbol<'a>(
         }),
         Err(find_err) => {
             // it was not found using the given varname; if this is name has a mangled form then try that instead
-            if let Some(mangled) = debug_data.demangled_names.get(components[0]) {
-                let mut components_mangled = components.clone();
-                components_mangled[0] = mangled;
-                if let Ok(sym_info) =
-                    find_symbol_from_components(&components_mangled, &additional_spec, debug_data)
-                {
-                    let mangled_varname =
-                        mangled.to_owned() + varname.strip_prefix(components[0]).unwrap();
-                    return Ok(SymbolInfo {
-                        name: mangled_varname,
-                        ..sym_info
-                    });
-                }
+            if let Some(result) =
+                find_demangled_symbol(plain_symbol, &components, &additional_spec, debug_data)
+            {
+                return result;
+            }
+
+            // a qualified name like "ns1::ns2::var": find the variable "var" whose namespaces are ns1::ns2
+            if let Some((scope, plain_varname)) = components[0].rsplit_once("::")
+                && let Some(sym_info) = find_qualified_symbol(
+                    &components,
+                    scope,
+                    plain_varname,
+                    additional_spec,
+                    debug_data,
+                )
+            {
+                log::trace!("Symbol {varname} found as {plain_varname} in the namespace {scope}");
+                let plain_symbol = plain_varname.to_owned()
+                    + plain_symbol.strip_prefix(components[0]).unwrap_or_default();
+                return Ok(SymbolInfo {
+                    name: plain_symbol,
+                    match_kind: MatchKind::Demangled,
+                    ..sym_info
+                });
             }
 
+            log::trace!("Symbol {varname} not found: {find_err}");
+            Err(find_err)
+        }
+    }
+}
+
+/// like find_symbol(), but compile-time constants and thread-local variables are not accepted,
+/// because they have no fixed address
+pub(crate) fn find_symbol_with_address<'a>(
+    varname: &str,
+    debug_data: &'a DebugData,
+) -> Result<SymbolInfo<'a>, DebugDataError> {
+    let sym_info = find_symbol(varname, debug_data)?;
+    if sym_info.const_value.is_some() {
+        return Err(DebugDataError::ConstantWithoutAddress(varname.to_string()));
+    }
+    if sym_info.is_tls {
+        return Err(DebugDataError::ThreadLocalLocation(varname.to_string()));
+    }
+    Ok(sym_info)
+}
+
+/// a contiguous range of array elements, see find_symbol_slice()
+#[cfg(test)]
+pub(crate) struct SymbolSlice<'dbg> {
+    /// the first element of the slice. Its address is the start address of the slice
+    pub(crate) first: SymbolInfo<'dbg>,
+    /// an array with the length of the slice and the element type of the sliced array. If the sliced dimension
+    /// is not the last one, the inner dimensions are kept, e.g. matrix[0:2] of "int matrix[4][3]" is int[2][3]
+    pub(crate) typeinfo: TypeInfo,
+}
+
+/// find a range of the elements of an array, e.g. "buffer[4:8]" or "buffer[4..8]". The end of the range is
+/// exclusive in both notations, so "buffer[4:8]" contains the elements 4 to 7. Like the indices in find_symbol(),
+/// the bounds are source indices. The leading dimensions of a multi-dimensional array can be fixed by regular
+/// indices before the range, as in "matrix[1][0:2]". Both bounds are required, and inclusive ranges like
+/// "buffer[4..=8]" are not supported
+#[cfg(test)]
+pub(crate) fn find_symbol_slice<'a>(
+    varname: &str,
+    debug_data: &'a DebugData,
+) -> Result<SymbolSlice<'a>, DebugDataError> {
+    let (plain_symbol, _) = get_additional_spec(varname);
+    let spec_str = &varname[plain_symbol.len()..];
+    let Some(range_pos) = plain_symbol.rfind('[') else {
+        return Err(DebugDataError::InvalidIndex(varname.to_string()));
+    };
+    let Some((start, end)) = get_slice_range(&plain_symbol[range_pos..]) else {
+        return Err(DebugDataError::InvalidIndex(
+            plain_symbol[range_pos..].to_string(),
+        ));
+    };
+    // the regular indices that fix the leading dimensions directly precede the range
+    let mut array_name = &plain_symbol[..range_pos];
+    let mut indices = Vec::new();
+    while let Some(prefix) = array_name.strip_suffix(']')
+        && let Some(pos) = prefix.rfind('[')
+    {
+        let index = get_index(&array_name[pos..])
+            .ok_or_else(|| DebugDataError::InvalidIndex(array_name[pos..].to_string()))?;
+        indices.insert(0, index);
+        array_name = &array_name[..pos];
+    }
+
+    let array = find_symbol(&format!("{array_name}{spec_str}"), debug_data)?;
+    let DbgDataType::Array {
+        dim,
+        stride,
+        arraytype,
+        lower_bounds,
+        dynamic_dims,
+        ..
+    } = &array.typeinfo.get_reference(&debug_data.types).datatype
+    else {
+        return Err(DebugDataError::UnmatchedComponents {
+            remaining: plain_symbol[array_name.len()..].to_string(),
+            symbol: plain_symbol.to_string(),
+        });
+    };
+    let range_dim = indices.len();
+    if range_dim >= dim.len() {
+        return Err(DebugDataError::UnmatchedComponents {
+            remaining: plain_symbol[range_pos..].to_string(),
+            symbol: plain_symbol.to_string(),
+        });
+    }
+    // the strides of all dimensions up to the range, and the length of the inner dimensions are needed
+    if let Some(dimension) = dynamic_dims.iter().min() {
+        return Err(DebugDataError::DynamicArrayDimension {
+            dimension: *dimension,
+            symbol: plain_symbol.to_string(),
+        });
+    }
+
+    let lower_bound = |idx_pos: usize| lower_bounds.get(idx_pos).copied().unwrap_or(0) as usize;
+    // the source indices of the first element: the inner dimensions start at their lower bound
+    let mut first_indices = indices;
+    first_indices.push(start);
+    first_indices.extend((range_dim + 1..dim.len()).map(lower_bound));
+    let mut first_index = 0;
+    for (idx_pos, (indexval, current_dim)) in first_indices.iter().zip(dim).enumerate() {
+        if *indexval < lower_bound(idx_pos)
+            || indexval - lower_bound(idx_pos) >= *current_dim as usize
+        {
+            return Err(DebugDataError::IndexOutOfBounds {
+                index: *indexval,
+                expression: plain_symbol.to_string(),
+                dim: *current_dim,
+            });
+        }
+        first_index = first_index * (*current_dim) as usize + indexval - lower_bound(idx_pos);
+    }
+    // end is exclusive, so it may be one past the last element
+    if end <= start || end - lower_bound(range_dim) > dim[range_dim] as usize {
+        return Err(DebugDataError::IndexOutOfBounds {
+            index: end,
+            expression: plain_symbol.to_string(),
+            dim: dim[range_dim],
+        });
+    }
+
+    let mut slice_dim = vec![(end - start) as u64];
+    slice_dim.extend_from_slice(&dim[range_dim + 1..]);
+    let element_count: u64 = slice_dim.iter().product();
+    let mut slice_lower_bounds = Vec::new();
+    if lower_bounds
+        .iter()
+        .skip(range_dim + 1)
+        .any(|bound| *bound != 0)
+    {
+        // the slice starts at index 0, but the inner dimensions keep their bounds
+        slice_lower_bounds.push(0);
+        slice_lower_bounds.extend_from_slice(&lower_bounds[range_dim + 1..]);
+    }
+    let typeinfo = TypeInfo {
+        name: None,
+        unit_idx: array.typeinfo.unit_idx,
+        dbginfo_offset: array.typeinfo.dbginfo_offset,
+        byte_size: None,
+        datatype: DbgDataType::Array {
+            size: element_count * stride,
+            dim: slice_dim,
+            stride: *stride,
+            arraytype: arraytype.clone(),
+            lower_bounds: slice_lower_bounds,
+            dynamic_dims: Vec::new(),
+        },
+    };
+
+    // the name of the first element uses the same notation as the slice, e.g. "buffer[4]"
+    let mut first_name = array_name.to_string();
+    for indexval in &first_indices {
+        let _ = write!(first_name, "[{indexval}]");
+    }
+    let first = SymbolInfo {
+        name: first_name,
+        address: array.address + first_index as u64 * stride,
+        typeinfo: arraytype.get_reference(&debug_data.types),
+        ..array
+    };
+    Ok(SymbolSlice { first, typeinfo })
+}
+
+// find a symbol by the raw name of its variable, e.g. the mangled linker name "_ZN2ns3varE" from a map file.
+// Unlike find_symbol, the variable name is looked up exactly as it is given: it is not demangled, and it is not
+// split into namespaces. A variable whose name contains '.', like the "counter.0" of a function-local static,
+// is found with its full name; otherwise the name may continue with struct members or array indices
+#[cfg(test)]
+pub(crate) fn find_symbol_mangled<'a>(
+    mangled: &str,
+    debug_data: &'a DebugData,
+) -> Result<SymbolInfo<'a>, DebugDataError> {
+    let (plain_symbol, additional_spec) = get_additional_spec(mangled);
+    let components = if debug_data.variables.contains_key(plain_symbol) {
+        vec![plain_symbol]
+    } else {
+        split_symbol_components(plain_symbol)
+    };
+
+    match find_symbol_from_components(&components, &additional_spec, debug_data) {
+        Ok(sym_info) => Ok(SymbolInfo {
+            name: plain_symbol.to_owned(),
+            ..sym_info
+        }),
+        Err(find_err) => {
+            log::trace!("Symbol {mangled} not found: {find_err}");
             Err(find_err)
         }
     }
 }
 
+// look up a symbol whose first component is the demangled name of one or more mangled variables.
+// Each mangled candidate is tried; if the candidates that are found are located at different addresses,
+// then the demangled name is ambiguous, and none of them is selected
+fn find_demangled_symbol<'a>(
+    plain_symbol: &str,
+    components: &[&str],
+    additional_spec: &Option<AdditionalSpec>,
+    debug_data: &'a DebugData,
+) -> Option<Result<SymbolInfo<'a>, DebugDataError>> {
+    let mangled_list = debug_data.demangled_names.get(components[0])?;
+    let mut found: Vec<(&str, SymbolInfo)> = Vec::new();
+    for mangled in mangled_list {
+        let mut components_mangled = components.to_vec();
+        components_mangled[0] = mangled;
+        log::trace!("Symbol {plain_symbol} not found, trying the mangled name {mangled}");
+        if let Ok(sym_info) =
+            find_symbol_from_components(&components_mangled, additional_spec, debug_data)
+        {
+            found.push((mangled, sym_info));
+        }
+    }
+
+    let (first_mangled, first_sym_info) = found.first()?;
+    if found
+        .iter()
+        .any(|(_, sym_info)| sym_info.address != first_sym_info.address)
+    {
+        let candidates = found
+            .iter()
+            .map(|(mangled, sym_info)| {
+                (
+                    mangled.to_string(),
+                    make_simple_unit_name(debug_data, sym_info.unit_idx),
+                )
+            })
+            .collect();
+        return Some(Err(DebugDataError::AmbiguousDemangledName {
+            name: components[0].to_string(),
+            candidates,
+        }));
+    }
+
+    let mangled_varname =
+        first_mangled.to_string() + plain_symbol.strip_prefix(components[0]).unwrap();
+    Some(Ok(SymbolInfo {
+        name: mangled_varname,
+        match_kind: MatchKind::Demangled,
+        ..first_sym_info.clone()
+    }))
+}
+
+// look up the components of a symbol, whose first component was split into the scope and the plain variable name
+// Only a variable whose namespaces are exactly the given scope is accepted
+fn find_qualified_symbol<'a>(
+    components: &[&str],
+    scope: &str,
+    plain_varname: &str,
+    additional_spec: Option<AdditionalSpec>,
+    debug_data: &'a DebugData,
+) -> Option<SymbolInfo<'a>> {
+    // namespaces are stored innermost first. An empty scope, as in "::var", is the global namespace
+    let namespaces: Vec<String> = if scope.is_empty() {
+        vec![]
+    } else {
+        scope.rsplit("::").map(str::to_string).collect()
+    };
+    let qualified_spec = AdditionalSpec {
+        namespaces,
+        ..additional_spec.unwrap_or(AdditionalSpec {
+            function_name: None,
+            simple_unit_name: None,
+            namespaces: vec![],
+            unit_idx: None,
+            address: None,
+        })
+    };
+    let varinfo_list = debug_data.variables.get(plain_varname)?;
+    // select_varinfo falls back to the first variable if the spec doesn't match, but here the namespaces must match
+    if !varinfo_list
+        .iter()
+        .any(|vi| varinfo_matches_spec(vi, &qualified_spec, debug_data))
+    {
+        return None;
+    }
+
+    let mut components_plain = components.to_vec();
+    components_plain[0] = plain_varname;
+    find_symbol_from_components(&components_plain, &Some(qualified_spec), debug_data).ok()
+}
+
 fn find_symbol_from_components<'a>(
     components: &[&str],
     additional_spec: &Option<AdditionalSpec>,
     debug_data: &'a DebugData,
-) -> Result<SymbolInfo<'a>, String> {
+) -> Result<SymbolInfo<'a>, DebugDataError> {
     // the first component of the symbol name is the name of the global variable.
     if let Some(varinfo_list) = debug_data.variables.get(components[0]) {
         // somtimes there are several variables with the same name in different files or functions
         // select the best one of them based on the additional_data
         let varinfo = select_varinfo(varinfo_list, additional_spec, debug_data);
         let is_unique = varinfo_list.len() == 1;
+        if let LocationKind::RegisterRelative { register, offset } = varinfo.location_kind {
+            return Err(DebugDataError::RegisterRelativeLocation {
+                symbol: components.join("."),
+                register,
+                offset,
+            });
+        }
+        // the members of constants can't be located, since the constant is not stored anywhere
+        let Some(address) = varinfo
+            .address
+            .or_else(|| (components.len() == 1).then_some(0))
+        else {
+            return Err(DebugDataError::ConstantWithoutAddress(components.join(".")));
+        };
 
         // we also need the type in order to resolve struct members, etc.
         if let Some(vartype) = debug_data.types.get(&varinfo.typeref) {
             // all further components of the symbol name are struct/union members or array indices
-            find_membertype(vartype, debug_data, components, 1, varinfo.address).map(
-                |(addr, typeinfo)| SymbolInfo {
+            find_membertype(vartype, debug_data, components, 1, address).map(|(addr, typeinfo)| {
+                SymbolInfo {
                     name: "".to_string(),
                     address: addr,
                     typeinfo,
@@ -92,36 +807,52 @@ fn find_symbol_from_components<'a>(
                     function_name: &varinfo.function,
                     namespaces: &varinfo.namespaces,
                     is_unique,
-                },
-            )
+                    is_tls: varinfo.is_tls(),
+                    storage: varinfo.storage,
+                    source_location: &varinfo.decl_location,
+                    dbginfo_offset: varinfo.dbginfo_offset,
+                    match_kind: MatchKind::Exact,
+                    const_value: varinfo.const_value.as_ref(),
+                    is_const: varinfo.is_const,
+                    is_external: varinfo.is_external,
+                }
+            })
         } else {
             // this exists for completeness, but shouldn't happen with a correctly generated elffile
             // if the variable is present in the elffile, then the type should also be present
             if components.len() == 1 {
                 Ok(SymbolInfo {
                     name: "".to_string(),
-                    address: varinfo.address,
+                    address,
                     typeinfo: &TypeInfo {
                         datatype: DbgDataType::Uint8,
                         name: None,
                         unit_idx: usize::MAX,
                         dbginfo_offset: 0,
+                        byte_size: None,
                     },
                     unit_idx: varinfo.unit_idx,
                     namespaces: &varinfo.namespaces,
                     function_name: &None,
                     is_unique,
+                    is_tls: varinfo.is_tls(),
+                    storage: varinfo.storage,
+                    source_location: &varinfo.decl_location,
+                    dbginfo_offset: varinfo.dbginfo_offset,
+                    match_kind: MatchKind::Exact,
+                    const_value: varinfo.const_value.as_ref(),
+                    is_const: varinfo.is_const,
+                    is_external: varinfo.is_external,
                 })
             } else {
-                Err(format!(
-                    "Remaining portion \"{}\" of \"{}\" could not be matched",
-                    components[1..].join("."),
-                    components.join(".")
-                ))
+                Err(DebugDataError::UnmatchedComponents {
+                    remaining: components[1..].join("."),
+                    symbol: components.join("."),
+                })
             }
         }
     } else {
-        Err(format!("Symbol \"{}\" does not exist", components[0]))
+        Err(DebugDataError::SymbolNotFound(components[0].to_string()))
     }
 }
 
@@ -131,24 +862,46 @@ fn select_varinfo<'a>(
     debug_data: &DebugData,
 ) -> &'a VarInfo {
     if let Some(additional_spec) = additional_spec {
-        let unit = &additional_spec.simple_unit_name;
-        let func = &additional_spec.function_name;
-        let ns = &additional_spec.namespaces;
-        for vi in varinfo_list {
-            if (unit.is_none() || *unit == make_simple_unit_name(debug_data, vi.unit_idx))
-                && (func.is_none() || *func == vi.function)
-                && *ns == vi.namespaces
-            {
+        // the unit index and the address identify the variable exactly, so they are checked first
+        if additional_spec.unit_idx.is_some() || additional_spec.address.is_some() {
+            let unit_idx = additional_spec.unit_idx;
+            let address = additional_spec.address;
+            if let Some(vi) = varinfo_list.iter().find(|vi| {
+                (unit_idx.is_none() || unit_idx == Some(vi.unit_idx))
+                    && (address.is_none() || address == vi.address)
+            }) {
                 return vi;
             }
         }
+
+        if let Some(vi) = varinfo_list
+            .iter()
+            .find(|vi| varinfo_matches_spec(vi, additional_spec, debug_data))
+        {
+            return vi;
+        }
         // spec was NOT matched. In this case we simply continue as if the spec didin't exist
     }
     &varinfo_list[0]
 }
 
+// check if the variable matches the compile unit, function and namespaces of the spec
+fn varinfo_matches_spec(
+    vi: &VarInfo,
+    additional_spec: &AdditionalSpec,
+    debug_data: &DebugData,
+) -> bool {
+    let unit = &additional_spec.simple_unit_name;
+    let func = &additional_spec.function_name;
+    unit.as_ref()
+        .is_none_or(|unit| unit_matches_simple_name(debug_data, vi.unit_idx, unit))
+        && (func.is_none() || *func == vi.function)
+        && additional_spec.namespaces == vi.namespaces
+}
+
 // split up a string of the form
 // var{Function:FuncName}{CompileUnit:UnitName_c}{Namespace:Global}"
+// The specs {Unit:<index>} and {Address:0x...} may be added anywhere to select a variable by its unit index or address
 fn get_additional_spec(varname_ext: &str) -> (&str, Option<AdditionalSpec>) {
     if let Some(pos) = varname_ext.find('{') {
         let (base, spec_str) = varname_ext.split_at(pos);
@@ -159,17 +912,29 @@ fn get_additional_spec(varname_ext: &str) -> (&str, Option<AdditionalSpec>) {
                 function_name: None,
                 simple_unit_name: None,
                 namespaces: vec![],
+                unit_idx: None,
+                address: None,
             };
+            let mut compile_unit_seen = false;
             for component in spec_str.split("}{") {
                 // component = "Function:FuncName" / "CompileUnit:UnitName_c" / "Namespace:Global"
-                if let Some(func_name) = component.strip_prefix("Function:") {
+                if let Some(index) = component.strip_prefix("Unit:") {
+                    add_spec.unit_idx = index.parse().ok();
+                } else if let Some(address) = component.strip_prefix("Address:") {
+                    add_spec.address = address.strip_prefix("0x").map_or_else(
+                        || address.parse().ok(),
+                        |hex| u64::from_str_radix(hex, 16).ok(),
+                    );
+                } else if compile_unit_seen {
+                    // CompileUnit:... is the last interesting entry - skip the final {Namespace:Global}
+                    continue;
+                } else if let Some(func_name) = component.strip_prefix("Function:") {
                     add_spec.function_name = Some(func_name.to_string());
                 } else if let Some(nsname) = component.strip_prefix("Namespace:") {
                     add_spec.namespaces.push(nsname.to_string());
                 } else if let Some(name) = component.strip_prefix("CompileUnit:") {
                     add_spec.simple_unit_name = Some(name.to_string());
-                    // CompileUnit:... is the last interesting entry - skip the final {Namespace:Global}
-                    break;
+                    compile_unit_seen = true;
                 }
             }
 
@@ -208,11 +973,10 @@ fn find_membertype<'a>(
     components: &[&str],
     component_index: usize,
     address: u64,
-) -> Result<(u64, &'a TypeInfo), String> {
+) -> Result<(u64, &'a TypeInfo), DebugDataError> {
     if component_index >= components.len() {
         Ok((address, typeinfo))
     } else {
-        // println!("typeinfo.datatype: {:?}", &typeinfo.datatype);
         match &typeinfo.datatype {
             DbgDataType::Class {
                 members,
@@ -220,6 +984,7 @@ fn find_membertype<'a>(
                 ..
             } => {
                 if let Some((membertype, offset)) = members.get(components[component_index]) {
+                    let offset = member_offset(*offset, components, component_index)?;
                     let membertype = membertype.get_reference(&debug_data.types);
                     find_membertype(
                         membertype,
@@ -228,30 +993,76 @@ fn find_membertype<'a>(
                         component_index + 1,
                         address + offset,
                     )
-                } else if let Some((baseclass_type, offset)) =
-                    inheritance.get(components[component_index])
-                {
-                    let skip = usize::from(
-                        components.len() > component_index + 1
-                            && components[component_index + 1] == "_",
-                    );
+                } else if let Some(baseclass) = inheritance.get(components[component_index]) {
+                    let Some(offset) = baseclass.offset else {
+                        return Err(DebugDataError::VirtualBaseOffset {
+                            base: components[component_index].to_string(),
+                            symbol: components.join("."),
+                        });
+                    };
+                    let basetype = baseclass.typeinfo.get_reference(&debug_data.types);
+                    let skip = base_separator_len(components, component_index + 1, basetype);
                     find_membertype(
-                        baseclass_type,
+                        basetype,
                         debug_data,
                         components,
                         component_index + 1 + skip,
                         address + offset,
                     )
                 } else {
-                    Err(format!(
-                        "There is no member \"{}\" in \"{}\"",
-                        components[component_index],
-                        components[..component_index].join(".")
-                    ))
+                    // like in C++, a member of a base class can be used without naming the base class,
+                    // unless several base classes have a member of that name
+                    let member = components[component_index];
+                    let mut paths = find_base_paths_with_member(typeinfo, member, debug_data);
+                    if paths.len() > 1 {
+                        return Err(DebugDataError::AmbiguousMember {
+                            member: member.to_string(),
+                            parent: components[..component_index].join("."),
+                            bases: paths
+                                .iter()
+                                .map(|path| {
+                                    let names: Vec<&str> =
+                                        path.iter().map(|(name, _)| *name).collect();
+                                    names.join(".")
+                                })
+                                .collect(),
+                        });
+                    }
+                    let Some(path) = paths.pop() else {
+                        return Err(DebugDataError::MemberNotFound {
+                            member: member.to_string(),
+                            parent: components[..component_index].join("."),
+                        });
+                    };
+                    let mut basetype = typeinfo;
+                    let mut base_address = address;
+                    for (name, baseclass) in path {
+                        let Some(offset) = baseclass.offset else {
+                            return Err(DebugDataError::VirtualBaseOffset {
+                                base: name.to_string(),
+                                symbol: components.join("."),
+                            });
+                        };
+                        basetype = baseclass.typeinfo.get_reference(&debug_data.types);
+                        base_address += offset;
+                    }
+                    find_membertype(
+                        basetype,
+                        debug_data,
+                        components,
+                        component_index,
+                        base_address,
+                    )
                 }
             }
             DbgDataType::Struct { members, .. } | DbgDataType::Union { members, .. } => {
-                if let Some((membertype, offset)) = members.get(components[component_index]) {
+                // bit-packed arrays are loaded as structs whose members are named like array elements, e.g. "_3_"
+                let member = members.get(components[component_index]).or_else(|| {
+                    get_index(components[component_index])
+                        .and_then(|idx| members.get(&format!("_{idx}_")))
+                });
+                if let Some((membertype, offset)) = member {
+                    let offset = member_offset(*offset, components, component_index)?;
                     let membertype = membertype.get_reference(&debug_data.types);
                     find_membertype(
                         membertype,
@@ -261,35 +1072,67 @@ fn find_membertype<'a>(
                         address + offset,
                     )
                 } else {
-                    Err(format!(
-                        "There is no member \"{}\" in \"{}\"",
-                        components[component_index],
-                        components[..component_index].join(".")
-                    ))
+                    Err(DebugDataError::MemberNotFound {
+                        member: components[component_index].to_string(),
+                        parent: components[..component_index].join("."),
+                    })
+                }
+            }
+            DbgDataType::VariantEnum { variants, .. } => {
+                // the variant is selected by name, regardless of the current value of the discriminant
+                if let Some(variant) = variants
+                    .values()
+                    .find(|variant| variant.name == components[component_index])
+                {
+                    let varianttype = variant.typeinfo.get_reference(&debug_data.types);
+                    find_membertype(
+                        varianttype,
+                        debug_data,
+                        components,
+                        component_index + 1,
+                        address + variant.offset,
+                    )
+                } else {
+                    Err(DebugDataError::MemberNotFound {
+                        member: components[component_index].to_string(),
+                        parent: components[..component_index].join("."),
+                    })
                 }
             }
             DbgDataType::Array {
                 dim,
                 stride,
                 arraytype,
+                lower_bounds,
+                dynamic_dims,
                 ..
             } => {
                 let mut multi_index = 0;
                 for (idx_pos, current_dim) in dim.iter().enumerate() {
-                    let arraycomponent =
-                        components.get(component_index + idx_pos).unwrap_or(&"_0_"); // default to first element if no more components are specified
-                    let indexval = get_index(arraycomponent).ok_or_else(|| {
-                        format!("could not interpret \"{arraycomponent}\" as an array index")
-                    })?;
-                    if indexval >= *current_dim as usize {
-                        return Err(format!(
-                            "requested array index {} in expression \"{}\", but the array only has {} elements",
-                            indexval,
-                            components.join("."),
-                            current_dim
-                        ));
+                    // neither the bounds of a dynamic dimension nor the strides of the outer dimensions are known
+                    if dynamic_dims.contains(&idx_pos) {
+                        return Err(DebugDataError::DynamicArrayDimension {
+                            dimension: idx_pos,
+                            symbol: components.join("."),
+                        });
+                    }
+                    // the indices in the symbol name are source indices, which start at the lower bound of the dimension
+                    let lower_bound = lower_bounds.get(idx_pos).copied().unwrap_or(0) as usize;
+                    // default to first element if no more components are specified
+                    let indexval = match components.get(component_index + idx_pos) {
+                        Some(arraycomponent) => get_index(arraycomponent).ok_or_else(|| {
+                            DebugDataError::InvalidIndex(arraycomponent.to_string())
+                        })?,
+                        None => lower_bound,
+                    };
+                    if indexval < lower_bound || indexval - lower_bound >= *current_dim as usize {
+                        return Err(DebugDataError::IndexOutOfBounds {
+                            index: indexval,
+                            expression: components.join("."),
+                            dim: *current_dim,
+                        });
                     }
-                    multi_index = multi_index * (*current_dim) as usize + indexval;
+                    multi_index = multi_index * (*current_dim) as usize + indexval - lower_bound;
                 }
 
                 let elementaddr = address + (multi_index as u64 * stride);
@@ -301,23 +1144,92 @@ fn find_membertype<'a>(
                     elementaddr,
                 )
             }
+            DbgDataType::Reference { .. } => {
+                // like a pointer, a reference has to be read at runtime to find the object it refers to
+                Err(DebugDataError::ReferenceDescent {
+                    reference: components[..component_index].join("."),
+                    symbol: components.join("."),
+                })
+            }
             _ => {
                 if component_index >= components.len() {
                     Ok((address, typeinfo))
                 } else {
                     // could not descend further to match additional symbol name components
 
-                    Err(format!(
-                        "Remaining portion \"{}\" of \"{}\" could not be matched",
-                        components[component_index..].join("."),
-                        components.join(".")
-                    ))
+                    Err(DebugDataError::UnmatchedComponents {
+                        remaining: components[component_index..].join("."),
+                        symbol: components.join("."),
+                    })
                 }
             }
         }
     }
 }
 
+// find the base classes of a class that contain a member with the given name. Each result is the path of base classes
+// from the class to the base that contains the member. The search stops at the first base on each path that has
+// the member, since it hides any members of the same name further up. A virtual base class that is reached on
+// several paths is only a single subobject, so it is only returned once
+fn find_base_paths_with_member<'a>(
+    typeinfo: &'a TypeInfo,
+    member: &str,
+    debug_data: &'a DebugData,
+) -> Vec<Vec<(&'a str, &'a BaseClass)>> {
+    let mut paths = Vec::new();
+    let DbgDataType::Class { inheritance, .. } = &typeinfo.datatype else {
+        return paths;
+    };
+    for (name, baseclass) in inheritance {
+        let basetype = baseclass.typeinfo.get_reference(&debug_data.types);
+        let DbgDataType::Class { members, .. } = &basetype.datatype else {
+            continue;
+        };
+        if members.contains_key(member) {
+            paths.push(vec![(name.as_str(), baseclass)]);
+        } else {
+            for mut path in find_base_paths_with_member(basetype, member, debug_data) {
+                path.insert(0, (name.as_str(), baseclass));
+                paths.push(path);
+            }
+        }
+    }
+
+    let mut virtual_bases = Vec::new();
+    paths.retain(|path| {
+        let (name, baseclass) = path[path.len() - 1];
+        !baseclass.is_virtual || {
+            let is_new = !virtual_bases.contains(&name);
+            virtual_bases.push(name);
+            is_new
+        }
+    });
+    paths
+}
+
+// A base class may be addressed in a symbol name with or without a separator before the next component:
+//   var.Base.member / var.Base._.member / var.Base.__.member
+// Several levels of inheritance are written as var.Base.BaseBase.member, each base optionally followed by a separator.
+// The separator is not skipped if the base class has a member or base class of the same name.
+fn base_separator_len(components: &[&str], idx: usize, basetype: &TypeInfo) -> usize {
+    let Some(&component) = components.get(idx) else {
+        return 0;
+    };
+    if component != "_" && component != "__" {
+        return 0;
+    }
+    if let DbgDataType::Class {
+        members,
+        inheritance,
+        ..
+    } = &basetype.datatype
+        && (members.contains_key(component) || inheritance.contains_key(component))
+    {
+        return 0;
+    }
+    1
+}
+
 // before ASAP2 1.7 array indices in symbol names could not written as [x], but only as _x_
 // this function will get the numerical index for either representation
 fn get_index(idxstr: &str) -> Option<usize> {
@@ -331,18 +1243,32 @@ fn get_index(idxstr: &str) -> Option<usize> {
     }
 }
 
+// get the range of a slice of array elements, given as "[start:end]" or "[start..end]"
+#[cfg(test)]
+fn get_slice_range(slicestr: &str) -> Option<(usize, usize)> {
+    let range = slicestr.strip_prefix('[')?.strip_suffix(']')?;
+    let (start, end) = range.split_once("..").or_else(|| range.split_once(':'))?;
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+}
+
 /// find a component of a symbol based on an offset from the base address
 /// For example this could be a particular array element or struct member
+/// A negative offset refers to the variable that immediately precedes the base symbol in memory.
+/// The result is then the component of that variable which starts at base address + offset,
+/// or the variable itself if the offset leads to its start address
 pub(crate) fn find_symbol_by_offset<'a>(
     base_symbol: &SymbolInfo<'a>,
     offset: i32,
     debug_data: &'a DebugData,
-) -> Result<SymbolInfo<'a>, String> {
-    if offset < 0 || offset > base_symbol.typeinfo.get_size() as i32 {
-        return Err(format!(
-            "Offset {} is out of bounds for symbol \"{}\"",
-            offset, base_symbol.name
-        ));
+) -> Result<SymbolInfo<'a>, DebugDataError> {
+    if offset < 0 {
+        return find_symbol_before(base_symbol, offset, debug_data);
+    }
+    if offset > base_symbol.typeinfo.get_size() as i32 {
+        return Err(DebugDataError::OffsetOutOfBounds {
+            offset,
+            symbol: base_symbol.name.clone(),
+        });
     }
 
     let offset = offset as u64;
@@ -350,66 +1276,300 @@ pub(crate) fn find_symbol_by_offset<'a>(
     let iter = TypeInfoIter::new(&debug_data.types, base_symbol.typeinfo, false);
     for (name, typeinfo, item_offset) in iter {
         if item_offset == offset {
-            return Ok(SymbolInfo {
-                name: format!("{}{}", base_symbol.name, name),
-                address: item_offset + base_symbol.address,
+            return Ok(component_at_offset(
+                base_symbol,
+                &name,
                 typeinfo,
-                unit_idx: base_symbol.unit_idx,
-                function_name: base_symbol.function_name,
-                namespaces: base_symbol.namespaces,
-                is_unique: base_symbol.is_unique,
-            });
+                item_offset,
+            ));
         }
     }
 
-    Err(format!(
-        "Could not find a symbol component at offset {offset} from \"{}\"",
-        base_symbol.name
-    ))
+    // offset 0 of a symbol without components, e.g. a scalar or an empty struct, is the symbol itself
+    if offset == 0 {
+        return Ok(base_symbol.clone());
+    }
+
+    Err(DebugDataError::NoComponentAtOffset {
+        offset,
+        symbol: base_symbol.name.clone(),
+    })
+}
+
+// get the offset of the member components[component_index], or an error if its location is only known at runtime
+fn member_offset(
+    offset: Option<u64>,
+    components: &[&str],
+    component_index: usize,
+) -> Result<u64, DebugDataError> {
+    offset.ok_or_else(|| DebugDataError::DynamicMemberOffset {
+        member: components[component_index].to_string(),
+        symbol: components.join("."),
+    })
 }
 
+/// answers many find_symbol_by_offset queries against the same types, e.g. while importing an A2L file with
+/// thousands of offset-based references to one base symbol.
+/// The components of a type are listed once, by their offset, and the result is cached by the dbginfo_offset of the type
 #[cfg(test)]
-mod test {
-    use super::*;
-    use indexmap::IndexMap;
-    use std::collections::HashMap;
+pub(crate) struct OffsetLookup<'dbg> {
+    debug_data: &'dbg DebugData,
+    // key: dbginfo_offset and size of the type. The nested arrays inside a bit-packed array share the
+    // dbginfo_offset of the outer array, but they are smaller
+    components: HashMap<(usize, u64), ComponentsByOffset<'dbg>>,
+}
 
-    #[test]
-    fn test_split_symbol_components() {
-        let result = split_symbol_components("my_struct.array_field[5][1]");
-        assert_eq!(result.len(), 4);
-        assert_eq!(result[0], "my_struct");
-        assert_eq!(result[1], "array_field");
-        assert_eq!(result[2], "[5]");
-        assert_eq!(result[3], "[1]");
+// the name and type of the outermost component at each offset inside a type
+#[cfg(test)]
+type ComponentsByOffset<'dbg> = HashMap<u64, (String, &'dbg TypeInfo)>;
 
-        let result2 = split_symbol_components("my_struct.array_field._5_._1_");
-        assert_eq!(result2.len(), 4);
-        assert_eq!(result2[0], "my_struct");
-        assert_eq!(result2[1], "array_field");
-        assert_eq!(result2[2], "_5_");
-        assert_eq!(result2[3], "_1_");
+#[cfg(test)]
+impl<'dbg> OffsetLookup<'dbg> {
+    pub(crate) fn new(debug_data: &'dbg DebugData) -> Self {
+        Self {
+            debug_data,
+            components: HashMap::new(),
+        }
     }
 
-    #[test]
-    fn test_find_symbol_of_array() {
-        let mut dbgdata = DebugData {
-            types: HashMap::new(),
-            typenames: HashMap::new(),
-            variables: IndexMap::new(),
-            demangled_names: HashMap::new(),
-            unit_names: Vec::new(),
-            sections: HashMap::new(),
-        };
+    /// same as find_symbol_by_offset(), but the components of the type of base_symbol are only listed on the first call.
+    /// Negative offsets are resolved in the preceding variable, which is not cached
+    pub(crate) fn find_symbol_by_offset(
+        &mut self,
+        base_symbol: &SymbolInfo<'dbg>,
+        offset: i32,
+    ) -> Result<SymbolInfo<'dbg>, DebugDataError> {
+        if offset < 0 {
+            return find_symbol_before(base_symbol, offset, self.debug_data);
+        }
+        if offset > base_symbol.typeinfo.get_size() as i32 {
+            return Err(DebugDataError::OffsetOutOfBounds {
+                offset,
+                symbol: base_symbol.name.clone(),
+            });
+        }
+
+        let offset = offset as u64;
+        let typeinfo = base_symbol.typeinfo;
+        let types = &self.debug_data.types;
+        let components = self
+            .components
+            .entry((typeinfo.dbginfo_offset, typeinfo.get_size()))
+            .or_insert_with(|| {
+                let mut components = HashMap::new();
+                // the first component at each offset is the outermost one, which is also found by the linear search
+                for (name, item_type, item_offset) in TypeInfoIter::new(types, typeinfo, false) {
+                    components.entry(item_offset).or_insert((name, item_type));
+                }
+                components
+            });
+
+        if let Some((name, item_type)) = components.get(&offset) {
+            return Ok(component_at_offset(base_symbol, name, item_type, offset));
+        }
+        if offset == 0 {
+            return Ok(base_symbol.clone());
+        }
+        Err(DebugDataError::NoComponentAtOffset {
+            offset,
+            symbol: base_symbol.name.clone(),
+        })
+    }
+}
+
+fn component_at_offset<'a>(
+    base_symbol: &SymbolInfo<'a>,
+    name: &str,
+    typeinfo: &'a TypeInfo,
+    offset: u64,
+) -> SymbolInfo<'a> {
+    SymbolInfo {
+        name: format!("{}{}", base_symbol.name, name),
+        address: offset + base_symbol.address,
+        typeinfo,
+        ..base_symbol.clone()
+    }
+}
+
+// resolve a negative offset from base_symbol in the variable with the next lower address
+fn find_symbol_before<'a>(
+    base_symbol: &SymbolInfo<'a>,
+    offset: i32,
+    debug_data: &'a DebugData,
+) -> Result<SymbolInfo<'a>, DebugDataError> {
+    // thread-local variables are only compared with other thread-local variables, since their addresses are TLS offsets
+    let preceding = debug_data
+        .variables
+        .iter()
+        .flat_map(|(name, varinfo_list)| {
+            varinfo_list.iter().filter_map(move |varinfo| {
+                Some((name, varinfo, varinfo.address?, varinfo_list.len() == 1))
+            })
+        })
+        .filter(|(_, varinfo, address, _)| {
+            *address < base_symbol.address && varinfo.is_tls() == base_symbol.is_tls
+        })
+        .max_by_key(|(_, _, address, _)| *address);
+    let Some((name, varinfo, address, is_unique)) = preceding else {
+        return Err(DebugDataError::NoPrecedingSymbol {
+            offset,
+            symbol: base_symbol.name.clone(),
+        });
+    };
+    let Some(typeinfo) = debug_data.types.get(&varinfo.typeref) else {
+        return Err(DebugDataError::NoComponentAtOffset {
+            offset: 0,
+            symbol: name.clone(),
+        });
+    };
+
+    let target_address = base_symbol
+        .address
+        .checked_sub(u64::from(offset.unsigned_abs()));
+    let Some(prev_offset) = target_address
+        .and_then(|addr| addr.checked_sub(address))
+        .filter(|prev_offset| *prev_offset < typeinfo.get_size() || *prev_offset == 0)
+    else {
+        // the target address is below the preceding variable, or in the gap between it and the base symbol.
+        // The start address of a preceding variable with size 0, e.g. an empty struct, is accepted
+        return Err(DebugDataError::OffsetOutOfBounds {
+            offset,
+            symbol: base_symbol.name.clone(),
+        });
+    };
+
+    let prev_symbol = SymbolInfo {
+        name: name.clone(),
+        address,
+        typeinfo,
+        unit_idx: varinfo.unit_idx,
+        function_name: &varinfo.function,
+        namespaces: &varinfo.namespaces,
+        is_unique,
+        is_tls: varinfo.is_tls(),
+        storage: varinfo.storage,
+        source_location: &varinfo.decl_location,
+        dbginfo_offset: varinfo.dbginfo_offset,
+        match_kind: MatchKind::Exact,
+        const_value: None,
+        is_const: varinfo.is_const,
+        is_external: varinfo.is_external,
+    };
+    if prev_offset == 0 {
+        Ok(prev_symbol)
+    } else {
+        find_symbol_by_offset(&prev_symbol, prev_offset as i32, debug_data)
+    }
+}
+
+/// find the innermost component of a symbol that contains the byte at the given offset from the base address
+/// Unlike find_symbol_by_offset, the offset does not need to be the start of a component, e.g. it
+/// could refer to the second byte of an integer. Returns the component and the remaining offset inside it
+#[cfg(test)]
+pub(crate) fn find_symbol_containing_offset<'a>(
+    base_symbol: &SymbolInfo<'a>,
+    offset: i32,
+    debug_data: &'a DebugData,
+) -> Result<(SymbolInfo<'a>, u64), DebugDataError> {
+    if offset < 0 || offset as u64 >= base_symbol.typeinfo.get_size() {
+        return Err(DebugDataError::OffsetOutOfBounds {
+            offset,
+            symbol: base_symbol.name.clone(),
+        });
+    }
+
+    let offset = offset as u64;
+
+    // each component is returned by the iterator before its children, so a later match is nested
+    // inside the previous one, or it is another member of the same union
+    let mut innermost = None;
+    let iter = TypeInfoIter::new(&debug_data.types, base_symbol.typeinfo, false);
+    for (name, typeinfo, item_offset) in iter {
+        if item_offset <= offset && offset < item_offset + typeinfo.get_size() {
+            innermost = Some((name, typeinfo, item_offset));
+        }
+    }
+
+    let Some((name, typeinfo, item_offset)) = innermost else {
+        // the symbol has no components, e.g. because it is a scalar
+        return Ok((base_symbol.clone(), offset));
+    };
+    let sym_info = SymbolInfo {
+        name: format!("{}{}", base_symbol.name, name),
+        address: item_offset + base_symbol.address,
+        typeinfo,
+        unit_idx: base_symbol.unit_idx,
+        function_name: base_symbol.function_name,
+        namespaces: base_symbol.namespaces,
+        is_unique: base_symbol.is_unique,
+        is_tls: base_symbol.is_tls,
+        storage: base_symbol.storage,
+        source_location: base_symbol.source_location,
+        dbginfo_offset: base_symbol.dbginfo_offset,
+        match_kind: base_symbol.match_kind,
+        const_value: None,
+        is_const: base_symbol.is_const,
+        is_external: base_symbol.is_external,
+    };
+    Ok((sym_info, offset - item_offset))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indexmap::IndexMap;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_split_symbol_components() {
+        let result = split_symbol_components("my_struct.array_field[5][1]");
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0], "my_struct");
+        assert_eq!(result[1], "array_field");
+        assert_eq!(result[2], "[5]");
+        assert_eq!(result[3], "[1]");
+
+        let result2 = split_symbol_components("my_struct.array_field._5_._1_");
+        assert_eq!(result2.len(), 4);
+        assert_eq!(result2[0], "my_struct");
+        assert_eq!(result2[1], "array_field");
+        assert_eq!(result2[2], "_5_");
+        assert_eq!(result2[3], "_1_");
+    }
+
+    #[test]
+    fn test_find_symbol_of_array() {
+        let mut dbgdata = DebugData {
+            types: HashMap::new(),
+            typenames: HashMap::new(),
+            variables: IndexMap::new(),
+            demangled_names: HashMap::new(),
+            unit_names: Vec::new(),
+            sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: object::Endianness::Little,
+            architecture: object::Architecture::Unknown,
+        };
         // global variable: uint32_t my_array[2]
         dbgdata.variables.insert(
             "my_array".to_string(),
             vec![crate::debuginfo::VarInfo {
-                address: 0x1234,
+                address: Some(0x1234),
                 typeref: 1,
+                dbginfo_offset: 0,
                 unit_idx: 0,
                 function: None,
                 namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
             }],
         );
         dbgdata.types.insert(
@@ -421,14 +1581,18 @@ mod test {
                         name: None,
                         unit_idx: usize::MAX,
                         dbginfo_offset: 0,
+                        byte_size: None,
                     }),
                     dim: vec![2],
                     size: 8, // total size of the array
                     stride: 4,
+                    lower_bounds: Vec::new(),
+                    dynamic_dims: Vec::new(),
                 },
                 name: None,
                 unit_idx: usize::MAX,
                 dbginfo_offset: 0,
+                byte_size: None,
             },
         );
 
@@ -460,12 +1624,18 @@ mod test {
             demangled_names: HashMap::new(),
             unit_names: Vec::new(),
             sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: object::Endianness::Little,
+            architecture: object::Architecture::Unknown,
         };
         // global variable defined in C like this:
         // struct {
         //        uint32_t array_item[2];
         // } my_struct;
-        let mut structmembers: IndexMap<String, (TypeInfo, u64)> = IndexMap::new();
+        let mut structmembers: IndexMap<String, (TypeInfo, Option<u64>)> = IndexMap::new();
         structmembers.insert(
             "array_item".to_string(),
             (
@@ -476,26 +1646,38 @@ mod test {
                             name: None,
                             unit_idx: usize::MAX,
                             dbginfo_offset: 0,
+                            byte_size: None,
                         }),
                         dim: vec![2],
                         size: 8,
                         stride: 4,
+                        lower_bounds: Vec::new(),
+                        dynamic_dims: Vec::new(),
                     },
                     name: None,
                     unit_idx: usize::MAX,
                     dbginfo_offset: 0,
+                    byte_size: None,
                 },
-                0,
+                Some(0),
             ),
         );
         dbgdata.variables.insert(
             "my_struct".to_string(),
             vec![crate::debuginfo::VarInfo {
-                address: 0x00ca_fe00,
+                address: Some(0x00ca_fe00),
                 typeref: 2,
+                dbginfo_offset: 0,
                 unit_idx: 0,
                 function: None,
                 namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
             }],
         );
         dbgdata.types.insert(
@@ -508,6 +1690,7 @@ mod test {
                 unit_idx: 0,
                 name: None,
                 dbginfo_offset: 0,
+                byte_size: None,
             },
         );
 
@@ -520,7 +1703,1563 @@ mod test {
 
         // theres should not be a result if the symbol name contains extra unmatched components
         let result3 = find_symbol("my_struct.array_item._0_.extra.unused", &dbgdata);
-        assert!(result3.is_err());
+        assert!(matches!(
+            result3,
+            Err(DebugDataError::UnmatchedComponents { .. })
+        ));
+
+        // the different kinds of errors can be distinguished without looking at the message
+        let result4 = find_symbol("other_struct", &dbgdata);
+        assert!(matches!(result4, Err(DebugDataError::SymbolNotFound(_))));
+        let result5 = find_symbol("my_struct.no_such_item", &dbgdata);
+        let Err(err5) = result5 else { panic!() };
+        assert!(matches!(err5, DebugDataError::MemberNotFound { .. }));
+        assert_eq!(
+            err5.to_string(),
+            "There is no member \"no_such_item\" in \"my_struct\""
+        );
+        let result6 = find_symbol("my_struct.array_item[x]", &dbgdata);
+        assert!(matches!(result6, Err(DebugDataError::InvalidIndex(_))));
+        let result7 = find_symbol("my_struct.array_item[2]", &dbgdata);
+        assert!(matches!(
+            result7,
+            Err(DebugDataError::IndexOutOfBounds {
+                index: 2,
+                dim: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_find_symbol_of_padded_array() {
+        let mut dbgdata = DebugData {
+            types: HashMap::new(),
+            typenames: HashMap::new(),
+            variables: IndexMap::new(),
+            demangled_names: HashMap::new(),
+            unit_names: Vec::new(),
+            sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: object::Endianness::Little,
+            architecture: object::Architecture::Unknown,
+        };
+        // global variable: an array of 6-byte structs, where each element is padded to 8 bytes
+        let mut structmembers: IndexMap<String, (TypeInfo, Option<u64>)> = IndexMap::new();
+        structmembers.insert(
+            "value".to_string(),
+            (
+                TypeInfo {
+                    datatype: DbgDataType::Uint32,
+                    name: None,
+                    unit_idx: usize::MAX,
+                    dbginfo_offset: 0,
+                    byte_size: None,
+                },
+                Some(0),
+            ),
+        );
+        structmembers.insert(
+            "flags".to_string(),
+            (
+                TypeInfo {
+                    datatype: DbgDataType::Uint16,
+                    name: None,
+                    unit_idx: usize::MAX,
+                    dbginfo_offset: 0,
+                    byte_size: None,
+                },
+                Some(4),
+            ),
+        );
+        dbgdata.variables.insert(
+            "padded_array".to_string(),
+            vec![crate::debuginfo::VarInfo {
+                address: Some(0x1000),
+                typeref: 1,
+                dbginfo_offset: 0,
+                unit_idx: 0,
+                function: None,
+                namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
+            }],
+        );
+        dbgdata.types.insert(
+            1,
+            TypeInfo {
+                datatype: DbgDataType::Array {
+                    arraytype: Box::new(TypeInfo {
+                        datatype: DbgDataType::Struct {
+                            size: 6,
+                            members: structmembers,
+                        },
+                        name: None,
+                        unit_idx: usize::MAX,
+                        dbginfo_offset: 0,
+                        byte_size: None,
+                    }),
+                    dim: vec![4],
+                    size: 32,
+                    stride: 8,
+                    lower_bounds: Vec::new(),
+                    dynamic_dims: Vec::new(),
+                },
+                name: None,
+                unit_idx: usize::MAX,
+                dbginfo_offset: 0,
+                byte_size: None,
+            },
+        );
+
+        let array_sym = find_symbol("padded_array", &dbgdata).unwrap();
+        assert_eq!(array_sym.typeinfo.element_stride(), Some((6, 8)));
+
+        // the address of each element is based on the stride, not on the element size
+        let result = find_symbol("padded_array[1]", &dbgdata).unwrap();
+        assert_eq!(result.address, 0x1008);
+        let result = find_symbol("padded_array[3].flags", &dbgdata).unwrap();
+        assert_eq!(result.address, 0x101c);
+    }
+
+    #[test]
+    fn test_array_stride() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/stride_test.elf"), false)
+                .unwrap();
+        // an explicit byte stride on the array type or on the subrange overrides the element size
+        let base = find_symbol("padded_ints", &debug_data).unwrap().address;
+        let symbol = find_symbol("padded_ints[1]", &debug_data).unwrap();
+        assert_eq!(symbol.address, base + 8);
+        let symbol = find_symbol("padded_ints._3_", &debug_data).unwrap();
+        assert_eq!(symbol.address, base + 24);
+        assert_eq!(symbol.typeinfo.get_size(), 4);
+        let base = find_symbol("subrange_ints", &debug_data).unwrap().address;
+        let symbol = find_symbol("subrange_ints[1]", &debug_data).unwrap();
+        assert_eq!(symbol.address, base + 12);
+
+        // the elements of a bit-packed array are bitfields
+        let bitfield = |name| {
+            let symbol = find_symbol(name, &debug_data).unwrap();
+            let DbgDataType::Bitfield {
+                bit_offset,
+                bit_size,
+                ..
+            } = symbol.typeinfo.datatype
+            else {
+                panic!("{name} is not a bitfield");
+            };
+            (symbol.address, bit_offset, bit_size)
+        };
+        let base = find_symbol("packed", &debug_data).unwrap().address;
+        assert_eq!(bitfield("packed[1]"), (base, 2, 2));
+        assert_eq!(bitfield("packed[5]"), (base + 1, 2, 2));
+        assert_eq!(bitfield("packed._7_"), (base + 1, 6, 2));
+        assert!(find_symbol("packed[8]", &debug_data).is_err());
+        let base = find_symbol("packed_2d", &debug_data).unwrap().address;
+        assert_eq!(bitfield("packed_2d[0][2]"), (base + 1, 0, 4));
+        assert_eq!(bitfield("packed_2d[1][0]"), (base + 1, 4, 4));
+        assert_eq!(bitfield("packed_2d[1][2]"), (base + 2, 4, 4));
+
+        let symbol = find_symbol("packed_2d", &debug_data).unwrap();
+        let leaves: Vec<_> = symbol.leaves(&debug_data).collect();
+        assert_eq!(leaves.len(), 6);
+        assert_eq!(leaves[4].name, "packed_2d._1_._1_");
+        assert_eq!(symbol.typeinfo.get_size(), 3);
+    }
+
+    #[test]
+    fn test_qualified_name() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/qualified_name_test.elf"),
+            false,
+        )
+        .unwrap();
+        let qualified_name = |name: &str| {
+            find_symbol(name, &debug_data)
+                .unwrap()
+                .qualified_name(&debug_data)
+        };
+
+        assert_eq!(qualified_name("outer_var"), "ns1::ns2::outer_var");
+        assert_eq!(qualified_name("outer_var.count"), "ns1::ns2::Outer::count");
+        assert_eq!(
+            qualified_name("outer_var.inner.value"),
+            "ns1::ns2::Outer::Inner::value"
+        );
+        assert_eq!(
+            qualified_name("outer_var.inner.data[2]"),
+            "ns1::ns2::Outer::Inner::data"
+        );
+        assert_eq!(
+            qualified_name("config_var.mode"),
+            "(anonymous namespace)::Config::mode"
+        );
+        // the struct type has no name
+        assert_eq!(qualified_name("anon_struct_var.x"), "anon_struct_var.x");
+    }
+
+    #[test]
+    fn test_match_kind() {
+        let mut debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/qualified_name_test.elf"),
+            false,
+        )
+        .unwrap();
+        // some compilers name C++ variables by their mangled name instead of the plain name
+        let mangled = "_ZN3ns13ns29outer_varE".to_string();
+        let outer_var = debug_data.variables.shift_remove("outer_var").unwrap();
+        debug_data.variables.insert(mangled.clone(), outer_var);
+        debug_data
+            .demangled_names
+            .insert("ns1::ns2::outer_var".to_string(), vec![mangled]);
+
+        let symbol = find_symbol("config_var.mode", &debug_data).unwrap();
+        assert_eq!(symbol.match_kind, MatchKind::Exact);
+        let symbol = find_symbol("_ZN3ns13ns29outer_varE.count", &debug_data).unwrap();
+        assert_eq!(symbol.match_kind, MatchKind::Exact);
+
+        // the demangled name is only found through its mangled form
+        let symbol = find_symbol("ns1::ns2::outer_var.count", &debug_data).unwrap();
+        assert_eq!(symbol.match_kind, MatchKind::Demangled);
+        assert_eq!(symbol.name, "_ZN3ns13ns29outer_varE.count");
+        // symbols derived from a demangled match keep the match kind
+        let (member, _) = find_symbol_containing_offset(&symbol, 0, &debug_data).unwrap();
+        assert_eq!(member.match_kind, MatchKind::Demangled);
+
+        // names are case sensitive, so there is no fuzzy match
+        assert!(matches!(
+            find_symbol("CONFIG_VAR.mode", &debug_data),
+            Err(DebugDataError::SymbolNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_size_types() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/empty_struct_test.elf"),
+            false,
+        )
+        .unwrap();
+
+        // an empty struct is found at its address, with size 0
+        let empty_var = find_symbol("empty_var", &debug_data).unwrap();
+        assert_eq!(empty_var.address, 0x40300c);
+        assert_eq!(empty_var.typeinfo.get_size(), 0);
+        assert_eq!(empty_var.leaves(&debug_data).count(), 0);
+        let symbol = find_symbol_by_offset(&empty_var, 0, &debug_data).unwrap();
+        assert_eq!(symbol.name, "empty_var");
+        assert_eq!(symbol.address, empty_var.address);
+        assert!(find_symbol_by_offset(&empty_var, 1, &debug_data).is_err());
+
+        // the elements of an array of empty structs all have the address of the array
+        let empty_array = find_symbol("empty_array", &debug_data).unwrap();
+        assert_eq!(empty_array.typeinfo.get_size(), 0);
+        let element = find_symbol("empty_array[3]", &debug_data).unwrap();
+        assert_eq!(element.address, empty_array.address);
+        assert_eq!(empty_array.leaves(&debug_data).count(), 0);
+        let element = find_symbol_by_offset(&empty_array, 0, &debug_data).unwrap();
+        assert_eq!(element.address, empty_array.address);
+        // empty_var immediately precedes empty_array
+        let symbol = find_symbol_by_offset(&empty_array, -1, &debug_data).unwrap();
+        assert_eq!(symbol.name, "empty_var");
+
+        // zero-size members don't hide the members that follow them
+        let holder_var = find_symbol("holder_var", &debug_data).unwrap();
+        let marker = find_symbol("holder_var.marker[1]", &debug_data).unwrap();
+        assert_eq!(marker.address, holder_var.address + 4);
+        let tail = find_symbol("holder_var.tail", &debug_data).unwrap();
+        assert_eq!(tail.address, holder_var.address + 4);
+        assert_eq!(tail.typeinfo.get_size(), 0);
+        let leaves: Vec<String> = holder_var
+            .leaves(&debug_data)
+            .map(|leaf| leaf.name)
+            .collect();
+        assert_eq!(leaves, vec!["holder_var.id"]);
+
+        // a flexible array member has no elements
+        let payload = find_symbol("packet_var.payload", &debug_data).unwrap();
+        assert_eq!(payload.typeinfo.get_size(), 0);
+        assert!(find_symbol("packet_var.payload[0]", &debug_data).is_err());
+        let symbol = find_symbol("after_var", &debug_data).unwrap();
+        assert_eq!(symbol.typeinfo.get_size(), 4);
+    }
+
+    #[test]
+    fn test_ambiguous_demangled_name() {
+        let mut debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/qualified_name_test.elf"),
+            false,
+        )
+        .unwrap();
+        // the mangled names of the overloads ns::func() and ns::func(int) both demangle to "ns::func",
+        // because the parameters are omitted
+        let counter_v = "_ZN2ns4funcEv".to_string();
+        let counter_i = "_ZN2ns4funcEi".to_string();
+        let outer_var = debug_data.variables.shift_remove("outer_var").unwrap();
+        debug_data.variables.insert(counter_v.clone(), outer_var);
+        let config_var = debug_data.variables.shift_remove("config_var").unwrap();
+        debug_data.variables.insert(counter_i.clone(), config_var);
+        debug_data.demangled_names.insert(
+            "ns::func".to_string(),
+            vec![counter_i.clone(), counter_v.clone()],
+        );
+
+        let result = find_symbol("ns::func", &debug_data);
+        let Err(DebugDataError::AmbiguousDemangledName { name, candidates }) = result else {
+            panic!("the demangled name should be ambiguous");
+        };
+        assert_eq!(name, "ns::func");
+        let candidate_names: Vec<&str> = candidates
+            .iter()
+            .map(|(mangled, _)| mangled.as_str())
+            .collect();
+        assert_eq!(
+            candidate_names,
+            vec![counter_i.as_str(), counter_v.as_str()]
+        );
+        assert!(candidates.iter().all(|(_, unit_name)| unit_name.is_some()));
+        // each candidate can still be selected by its mangled name
+        let symbol = find_symbol(&counter_v, &debug_data).unwrap();
+        assert_eq!(symbol.match_kind, MatchKind::Exact);
+
+        // candidates that resolve to the same address are not ambiguous
+        let address = debug_data.variables.get(&counter_v).unwrap()[0].address;
+        debug_data.variables.get_mut(&counter_i).unwrap()[0].address = address;
+        let symbol = find_symbol("ns::func", &debug_data).unwrap();
+        assert_eq!(symbol.name, counter_i);
+        assert_eq!(Some(symbol.address), address);
+        assert_eq!(symbol.match_kind, MatchKind::Demangled);
+
+        // a candidate that does not exist is skipped
+        debug_data.variables.shift_remove(&counter_i);
+        let symbol = find_symbol("ns::func", &debug_data).unwrap();
+        assert_eq!(symbol.name, counter_v);
+    }
+
+    #[test]
+    fn test_find_qualified_symbol() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/namespace_test.elf"),
+            false,
+        )
+        .unwrap();
+
+        let symbol = find_symbol("motor::control::params.limits[1]", &debug_data).unwrap();
+        assert_eq!(symbol.address, 0x403006);
+        assert_eq!(symbol.name, "params.limits[1]");
+        assert_eq!(symbol.match_kind, MatchKind::Demangled);
+
+        // the namespaces select one of the variables with the same name
+        let address = |name| find_symbol(name, &debug_data).unwrap().address;
+        assert_eq!(address("motor::control::counter"), 0x403008);
+        assert_eq!(address("motor::counter"), 0x40300c);
+        // a leading "::" selects the variable in the global namespace
+        assert_eq!(address("::counter"), 0x403010);
+        assert!(find_symbol("control::counter", &debug_data).is_err());
+        assert!(find_symbol("motor::params", &debug_data).is_err());
+        // "::" is not a separator after the first '.'
+        assert!(find_symbol("motor::control::params.motor::gain", &debug_data).is_err());
+
+        // the qualified name of a namespaced symbol finds the same symbol
+        let counters = debug_data.variables.get("counter").unwrap();
+        for varinfo in counters.iter().filter(|vi| !vi.namespaces.is_empty()) {
+            let symbol = find_symbol(
+                &format!("counter{{Address:{:#x}}}", varinfo.address.unwrap()),
+                &debug_data,
+            )
+            .unwrap();
+            let qualified_name = symbol.qualified_name(&debug_data);
+            assert_eq!(
+                Some(find_symbol(&qualified_name, &debug_data).unwrap().address),
+                varinfo.address
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_static_member() {
+        for filename in [
+            "fixtures/bin/class_static_test.elf",
+            "fixtures/bin/class_static_dwarf4_test.elf",
+        ] {
+            let debug_data = DebugData::load_dwarf(std::ffi::OsStr::new(filename), false).unwrap();
+            let address = |name| find_symbol(name, &debug_data).unwrap().address;
+            let limit = debug_data.variables.get("limit").unwrap();
+            let member_address = limit
+                .iter()
+                .find(|vi| vi.namespaces == ["Config", "app"])
+                .and_then(|vi| vi.address)
+                .unwrap();
+            let global_address = limit
+                .iter()
+                .find(|vi| vi.namespaces.is_empty())
+                .and_then(|vi| vi.address)
+                .unwrap();
+            assert_eq!(address("app::Config::limit"), member_address);
+            assert_eq!(address("::limit"), global_address);
+            // the class is part of the scope, so the namespace alone doesn't select the static member
+            assert!(find_symbol("app::limit", &debug_data).is_err());
+
+            let scale = find_symbol("app::Config::scale", &debug_data).unwrap();
+            assert_eq!(scale.name, "scale");
+            assert!(scale.is_const);
+            assert!(find_symbol("app::Outer::Inner::counter", &debug_data).is_ok());
+            assert!(find_symbol("Global::total", &debug_data).is_ok());
+            assert_eq!(
+                find_symbol("app::Config::limit", &debug_data)
+                    .unwrap()
+                    .qualified_name(&debug_data),
+                "app::Config::limit"
+            );
+        }
+    }
+
+    #[test]
+    fn test_c_strings() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/string_test.elf"), false)
+                .unwrap();
+
+        let name = find_symbol("name", &debug_data).unwrap();
+        assert!(name.is_c_string(&debug_data));
+        assert_eq!(name.leaves(&debug_data).count(), 16);
+        // the whole array is one leaf if strings are kept together
+        let string_leaves: Vec<_> = name.leaves_with_strings(&debug_data).collect();
+        assert_eq!(string_leaves.len(), 1);
+        assert_eq!(string_leaves[0].name, "name");
+        assert_eq!(string_leaves[0].address, name.address);
+        // individual characters are still available with an explicit index
+        let character = find_symbol("name[3]", &debug_data).unwrap();
+        assert_eq!(character.address, name.address + 3);
+        assert!(!character.is_c_string(&debug_data));
+
+        let device = find_symbol("device", &debug_data).unwrap();
+        assert!(!device.is_c_string(&debug_data));
+        let leaf_names: Vec<String> = device
+            .leaves_with_strings(&debug_data)
+            .map(|leaf| leaf.name)
+            .collect();
+        // uint8_t is a typedef of unsigned char, so raw is a string too. Two-dimensional arrays are not strings
+        let mut expected = vec![
+            "device.id".to_string(),
+            "device.label".to_string(),
+            "device.serial".to_string(),
+            "device.raw".to_string(),
+        ];
+        expected.extend((0..6).map(|idx| format!("device.matrix._{}_._{}_", idx / 3, idx % 3)));
+        assert_eq!(leaf_names, expected);
+        let label = find_symbol("device.label", &debug_data).unwrap();
+        assert!(label.is_c_string(&debug_data));
+        assert_eq!(label.typeinfo.get_size(), 8);
+        assert_eq!(
+            find_symbol("device.label[2]", &debug_data).unwrap().address,
+            label.address + 2
+        );
+        assert_eq!(device.leaves(&debug_data).count(), 1 + 8 + 4 + 4 + 6);
+    }
+
+    #[test]
+    fn test_big_endian_bitfields() {
+        // Dwarf 4 style DW_AT_data_bit_offset and Dwarf 2 style DW_AT_bit_offset
+        for filename in [
+            "fixtures/bin/bitfield_be_test.o",
+            "fixtures/bin/bitfield_be_dwarf3_test.o",
+        ] {
+            let debug_data = DebugData::load_dwarf(std::ffi::OsStr::new(filename), false).unwrap();
+            let filedata = std::fs::read(filename).unwrap();
+            let objfile = object::File::parse(&*filedata).unwrap();
+            let section = object::Object::section_by_name(&objfile, ".data").unwrap();
+            let data = object::ObjectSection::data(&section).unwrap();
+
+            let base = find_symbol("flags", &debug_data).unwrap().address;
+            for (name, offset, expected_bit_offset, expected_value) in [
+                ("flags.a", 0, 29, 5),
+                ("flags.b", 0, 22, 0x55),
+                ("flags.c", 0, 0, 0x2abcde),
+                ("flags.d", 4, 12, 0xa),
+                ("flags.e", 4, 0, 0x123),
+            ] {
+                let symbol = find_symbol(name, &debug_data).unwrap();
+                assert_eq!(symbol.address, base + offset, "{filename}: {name}");
+                let DbgDataType::Bitfield {
+                    bit_offset,
+                    bit_size,
+                    ..
+                } = symbol.typeinfo.datatype
+                else {
+                    panic!("{name} is not a bitfield");
+                };
+                assert_eq!(bit_offset, expected_bit_offset, "{filename}: {name}");
+
+                // read the storage unit as a big-endian value and apply the mask
+                let start = symbol.address as usize;
+                let storage_unit = &data[start..start + symbol.typeinfo.get_size() as usize];
+                let raw = storage_unit
+                    .iter()
+                    .fold(0u64, |value, byte| (value << 8) | u64::from(*byte));
+                let value = (raw >> bit_offset) & ((1 << bit_size) - 1);
+                assert_eq!(value, expected_value, "{filename}: {name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_referenced_types() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/leaves_test.elf"), false)
+                .unwrap();
+        let symbol = find_symbol("leaves_var", &debug_data).unwrap();
+        let types = symbol.referenced_types(&debug_data);
+        let names: Vec<(Option<&str>, bool)> = types
+            .iter()
+            .map(|typeinfo| {
+                let is_array = matches!(typeinfo.datatype, DbgDataType::Array { .. });
+                (typeinfo.name.as_deref(), is_array)
+            })
+            .collect();
+        // the three bitfields of Flags share their base type, and Inner is used through an array
+        assert_eq!(
+            names,
+            vec![
+                (Some("Outer"), false),
+                (Some("int"), false),
+                (Some("Flags"), false),
+                (Some("unsigned int"), false),
+                (Some("Inner"), true),
+                (Some("Inner"), false),
+                (Some("short int"), false),
+                (Some("unsigned char"), true),
+                (Some("unsigned char"), false),
+                (Some("float"), false),
+            ]
+        );
+
+        // pointers in linked lists lead back to types that were already visited
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/linked_list_test.elf"),
+            false,
+        )
+        .unwrap();
+        let symbol = find_symbol("node", &debug_data).unwrap();
+        let types = symbol.referenced_types(&debug_data);
+        assert_eq!(types.len(), 3);
+        assert!(matches!(types[0].datatype, DbgDataType::Struct { .. }));
+        assert!(matches!(types[1].datatype, DbgDataType::Sint32));
+        assert!(matches!(types[2].datatype, DbgDataType::Pointer(..)));
+
+        // struct A and struct B point to each other
+        let symbol = find_symbol("a_var", &debug_data).unwrap();
+        let types = symbol.referenced_types(&debug_data);
+        let struct_names: Vec<&str> = types
+            .iter()
+            .filter(|typeinfo| matches!(typeinfo.datatype, DbgDataType::Struct { .. }))
+            .filter_map(|typeinfo| typeinfo.name.as_deref())
+            .collect();
+        assert_eq!(struct_names, vec!["A", "B"]);
+        let mut offsets: Vec<usize> = types
+            .iter()
+            .map(|typeinfo| typeinfo.dbginfo_offset)
+            .collect();
+        offsets.sort_unstable();
+        offsets.dedup();
+        assert_eq!(offsets.len(), types.len());
+    }
+
+    #[test]
+    fn test_ambiguous_inherited_member() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/multiple_inheritance_test.elf"),
+            false,
+        )
+        .unwrap();
+        let address = |name| find_symbol(name, &debug_data).unwrap().address;
+        let fusion = address("fusion");
+
+        // x is a member of both SensorA and SensorB
+        let Err(DebugDataError::AmbiguousMember {
+            member,
+            parent,
+            bases,
+        }) = find_symbol("fusion.x", &debug_data)
+        else {
+            panic!("fusion.x should be ambiguous");
+        };
+        assert_eq!(member, "x");
+        assert_eq!(parent, "fusion");
+        assert_eq!(bases, vec!["SensorA", "SensorB"]);
+        assert_eq!(address("fusion.SensorA.x"), fusion);
+        assert_eq!(address("fusion.SensorB.x"), fusion + 8);
+
+        // members that exist in only one base class can be used without naming the base
+        assert_eq!(address("fusion.a_only"), fusion + 4);
+        assert_eq!(address("fusion.b_only"), fusion + 12);
+        assert_eq!(address("fusion.y"), fusion + 16);
+        let symbol = find_symbol("fusion.b_only", &debug_data).unwrap();
+        assert_eq!(symbol.qualified_name(&debug_data), "SensorB::b_only");
+
+        // a member of the derived class hides the members of the same name in the bases
+        let override_var = address("override_var");
+        assert_eq!(address("override_var.x"), override_var + 16);
+
+        // in a non-virtual diamond, each path leads to a separate Root subobject
+        let bottom = address("bottom");
+        let Err(DebugDataError::AmbiguousMember { bases, .. }) =
+            find_symbol("bottom.id", &debug_data)
+        else {
+            panic!("bottom.id should be ambiguous");
+        };
+        assert_eq!(bases, vec!["Left.Root", "Right.Root"]);
+        assert_eq!(address("bottom.Left.id"), bottom);
+        assert_eq!(address("bottom.Right.Root.id"), bottom + 8);
+        assert_eq!(address("bottom.right_value"), bottom + 12);
+
+        // a virtual base is a single subobject, so its members are not ambiguous
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/virtual_base_test.elf"),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            find_symbol("diamond.base_value", &debug_data),
+            Err(DebugDataError::VirtualBaseOffset { .. })
+        ));
+        let diamond = find_symbol("diamond", &debug_data).unwrap().address;
+        let symbol = find_symbol("diamond.plain_value", &debug_data).unwrap();
+        assert!(symbol.address > diamond);
+    }
+
+    #[test]
+    fn test_enum_entries() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/enum_test.elf"), false)
+                .unwrap();
+        let entries = |name: &str| {
+            find_symbol(name, &debug_data)
+                .unwrap()
+                .enum_entries()
+                .map(<[_]>::to_vec)
+        };
+        let gear_entries = vec![
+            ("GEAR_REVERSE".to_string(), -1),
+            ("GEAR_NEUTRAL".to_string(), 0),
+            ("GEAR_FIRST".to_string(), 1),
+            ("GEAR_SECOND".to_string(), 2),
+            ("GEAR_PARK".to_string(), 10),
+        ];
+        assert_eq!(entries("gear_state"), Some(gear_entries.clone()));
+        assert_eq!(entries("gear_history._1_"), Some(gear_entries.clone()));
+        assert_eq!(entries("control.gear"), Some(gear_entries));
+        // the enumerators stay in declaration order, even if their values are not sorted
+        let mode_entries = vec![
+            ("MODE_OFF".to_string(), 3),
+            ("MODE_ON".to_string(), 1),
+            ("MODE_AUTO".to_string(), 2),
+        ];
+        assert_eq!(entries("operating_mode"), Some(mode_entries.clone()));
+        assert_eq!(entries("control.mode"), Some(mode_entries));
+
+        assert_eq!(entries("gear_history"), None);
+        assert_eq!(entries("control"), None);
+        assert_eq!(entries("control.level"), None);
+    }
+
+    #[test]
+    fn test_array_lower_bound() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/lower_bound_test.elf"),
+            false,
+        )
+        .unwrap();
+        let arr = find_symbol("arr", &debug_data).unwrap();
+        let arr_address = arr.address;
+        // the index in the name is a source index, so arr._1_ is the first element
+        let first = find_symbol("arr._1_", &debug_data).unwrap();
+        assert_eq!(first.address, arr_address);
+        let last = find_symbol("arr[4]", &debug_data).unwrap();
+        assert_eq!(last.address, arr_address + 12);
+        assert!(matches!(
+            find_symbol("arr._0_", &debug_data),
+            Err(DebugDataError::IndexOutOfBounds {
+                index: 0,
+                dim: 4,
+                ..
+            })
+        ));
+        assert!(matches!(
+            find_symbol("arr._5_", &debug_data),
+            Err(DebugDataError::IndexOutOfBounds {
+                index: 5,
+                dim: 4,
+                ..
+            })
+        ));
+
+        // only the second dimension of matrix has a lower bound
+        let matrix = find_symbol("matrix", &debug_data).unwrap();
+        let element = find_symbol("matrix._1_._6_", &debug_data).unwrap();
+        assert_eq!(element.address, matrix.address + (3 + 1) * 2);
+        assert!(matches!(
+            find_symbol("matrix._1_._2_", &debug_data),
+            Err(DebugDataError::IndexOutOfBounds {
+                index: 2,
+                dim: 3,
+                ..
+            })
+        ));
+        let names: Vec<String> = debug_data
+            .flatten_symbol(&matrix)
+            .take(4)
+            .map(|record| record.full_name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "matrix._0_._5_",
+                "matrix._0_._6_",
+                "matrix._0_._7_",
+                "matrix._1_._5_"
+            ]
+        );
+
+        // a Fortran array without DW_AT_lower_bound starts at 1
+        let fortran_array = find_symbol("fortran_array", &debug_data).unwrap();
+        let DbgDataType::Array { dim, .. } = &fortran_array.typeinfo.datatype else {
+            panic!("fortran_array should be an array");
+        };
+        assert_eq!(dim, &[3]);
+        let element = find_symbol("fortran_array._3_", &debug_data).unwrap();
+        assert_eq!(element.address, fortran_array.address + 8);
+        assert!(find_symbol("fortran_array._0_", &debug_data).is_err());
+    }
+
+    #[test]
+    fn test_find_symbol_slice() {
+        let mut dbgdata = DebugData {
+            types: HashMap::new(),
+            typenames: HashMap::new(),
+            variables: IndexMap::new(),
+            demangled_names: HashMap::new(),
+            unit_names: Vec::new(),
+            sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: object::Endianness::Little,
+            architecture: object::Architecture::Unknown,
+        };
+        // global variable: uint16_t buffer[10]
+        dbgdata.variables.insert(
+            "buffer".to_string(),
+            vec![crate::debuginfo::VarInfo {
+                address: Some(0x2000),
+                typeref: 1,
+                dbginfo_offset: 0,
+                unit_idx: 0,
+                function: None,
+                namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
+            }],
+        );
+        dbgdata.types.insert(
+            1,
+            TypeInfo {
+                datatype: DbgDataType::Array {
+                    arraytype: Box::new(TypeInfo {
+                        datatype: DbgDataType::Uint16,
+                        name: None,
+                        unit_idx: usize::MAX,
+                        dbginfo_offset: 0,
+                        byte_size: None,
+                    }),
+                    dim: vec![10],
+                    size: 20,
+                    stride: 2,
+                    lower_bounds: Vec::new(),
+                    dynamic_dims: Vec::new(),
+                },
+                name: None,
+                unit_idx: usize::MAX,
+                dbginfo_offset: 0,
+                byte_size: None,
+            },
+        );
+
+        // the end of the range is exclusive: buffer[2:5] contains the elements 2, 3 and 4
+        let slice = find_symbol_slice("buffer[2:5]", &dbgdata).unwrap();
+        assert_eq!(slice.first.name, "buffer[2]");
+        assert_eq!(slice.first.address, 0x2004);
+        assert!(matches!(slice.first.typeinfo.datatype, DbgDataType::Uint16));
+        let DbgDataType::Array { dim, size, .. } = &slice.typeinfo.datatype else {
+            panic!("the slice should be an array");
+        };
+        assert_eq!(dim, &[3]);
+        assert_eq!(*size, 6);
+        assert_eq!(slice.typeinfo.get_size(), 6);
+        // both notations are equivalent
+        let slice = find_symbol_slice("buffer[2..5]", &dbgdata).unwrap();
+        assert_eq!(slice.first.address, 0x2004);
+        assert_eq!(slice.typeinfo.get_size(), 6);
+        // the whole array
+        let slice = find_symbol_slice("buffer[0:10]", &dbgdata).unwrap();
+        assert_eq!(slice.first.address, 0x2000);
+        assert_eq!(slice.typeinfo.get_size(), 20);
+
+        assert!(matches!(
+            find_symbol_slice("buffer[8:11]", &dbgdata),
+            Err(DebugDataError::IndexOutOfBounds {
+                index: 11,
+                dim: 10,
+                ..
+            })
+        ));
+        assert!(matches!(
+            find_symbol_slice("buffer[10:11]", &dbgdata),
+            Err(DebugDataError::IndexOutOfBounds { index: 10, .. })
+        ));
+        // empty and reversed ranges are rejected
+        assert!(find_symbol_slice("buffer[4:4]", &dbgdata).is_err());
+        assert!(find_symbol_slice("buffer[5:2]", &dbgdata).is_err());
+        // open and inclusive ranges are rejected
+        for varname in ["buffer[4:]", "buffer[:8]", "buffer[4..]", "buffer[4..=8]"] {
+            assert!(matches!(
+                find_symbol_slice(varname, &dbgdata),
+                Err(DebugDataError::InvalidIndex(_))
+            ));
+        }
+        // the name must end with a range
+        assert!(matches!(
+            find_symbol_slice("buffer[2]", &dbgdata),
+            Err(DebugDataError::InvalidIndex(_))
+        ));
+        assert!(matches!(
+            find_symbol_slice("buffer[1][0:2]", &dbgdata),
+            Err(DebugDataError::UnmatchedComponents { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_symbol_slice_lower_bound() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/lower_bound_test.elf"),
+            false,
+        )
+        .unwrap();
+        let arr = find_symbol("arr", &debug_data).unwrap();
+        // arr[1..4] in the source; the range uses source indices
+        let slice = find_symbol_slice("arr[2:4]", &debug_data).unwrap();
+        assert_eq!(slice.first.address, arr.address + 4);
+        assert_eq!(slice.typeinfo.get_size(), 8);
+        assert!(find_symbol_slice("arr[1:5]", &debug_data).is_ok());
+        assert!(matches!(
+            find_symbol_slice("arr[0:2]", &debug_data),
+            Err(DebugDataError::IndexOutOfBounds { index: 0, .. })
+        ));
+        assert!(matches!(
+            find_symbol_slice("arr[3:6]", &debug_data),
+            Err(DebugDataError::IndexOutOfBounds { index: 6, .. })
+        ));
+
+        // short matrix[0..1][5..7]: the leading dimension is fixed by an index
+        let matrix = find_symbol("matrix", &debug_data).unwrap();
+        let slice = find_symbol_slice("matrix[1][6:8]", &debug_data).unwrap();
+        assert_eq!(slice.first.name, "matrix[1][6]");
+        assert_eq!(slice.first.address, matrix.address + (3 + 1) * 2);
+        assert_eq!(slice.typeinfo.get_size(), 4);
+        // a range of the outer dimension keeps the inner dimension and its lower bound
+        let slice = find_symbol_slice("matrix[0:1]", &debug_data).unwrap();
+        assert_eq!(slice.first.name, "matrix[0][5]");
+        let DbgDataType::Array {
+            dim, lower_bounds, ..
+        } = &slice.typeinfo.datatype
+        else {
+            panic!("the slice should be an array");
+        };
+        assert_eq!(dim, &[1, 3]);
+        assert_eq!(lower_bounds, &[0, 5]);
+    }
+
+    #[test]
+    fn test_find_symbol_concurrently() {
+        let debug_data = std::sync::Arc::new(
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/enum_test.elf"), false)
+                .unwrap(),
+        );
+        let expected = find_symbol("control.level", &debug_data).unwrap().address;
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let debug_data = std::sync::Arc::clone(&debug_data);
+                std::thread::spawn(move || {
+                    let mut addresses = Vec::new();
+                    for _ in 0..100 {
+                        let sym_info = find_symbol("control.level", &debug_data).unwrap();
+                        addresses.push(sym_info.address);
+                        assert!(find_symbol("gear_history._2_", &debug_data).is_ok());
+                        assert!(find_symbol("no_such_symbol", &debug_data).is_err());
+                    }
+                    addresses
+                })
+            })
+            .collect();
+        for thread in threads {
+            let addresses = thread.join().unwrap();
+            assert!(addresses.iter().all(|address| *address == expected));
+        }
+    }
+
+    #[test]
+    fn test_find_symbol_mangled() {
+        let mut debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/qualified_name_test.elf"),
+            false,
+        )
+        .unwrap();
+        let mangled = "_ZN3ns13ns29outer_varE".to_string();
+        let outer_var = debug_data.variables.shift_remove("outer_var").unwrap();
+        debug_data.variables.insert(mangled.clone(), outer_var);
+        debug_data
+            .demangled_names
+            .insert("ns1::ns2::outer_var".to_string(), vec![mangled]);
+
+        let symbol = find_symbol_mangled("_ZN3ns13ns29outer_varE", &debug_data).unwrap();
+        assert_eq!(symbol.name, "_ZN3ns13ns29outer_varE");
+        assert_eq!(symbol.match_kind, MatchKind::Exact);
+        let member = find_symbol_mangled("_ZN3ns13ns29outer_varE.count", &debug_data).unwrap();
+        assert_eq!(
+            member.address,
+            find_symbol("ns1::ns2::outer_var.count", &debug_data)
+                .unwrap()
+                .address
+        );
+        // the name is not demangled or split into namespaces
+        assert!(matches!(
+            find_symbol_mangled("ns1::ns2::outer_var", &debug_data),
+            Err(DebugDataError::SymbolNotFound(_))
+        ));
+
+        // a variable name that contains a '.' is not split into members
+        let counter = debug_data.variables.get("config_var").unwrap().clone();
+        debug_data
+            .variables
+            .insert("_ZZ4funcvE7counter.0".to_string(), counter);
+        let symbol = find_symbol_mangled("_ZZ4funcvE7counter.0", &debug_data).unwrap();
+        assert_eq!(symbol.name, "_ZZ4funcvE7counter.0");
+        assert_eq!(
+            symbol.address,
+            find_symbol("config_var", &debug_data).unwrap().address
+        );
+    }
+
+    #[test]
+    fn test_offset_lookup() {
+        for (filename, symbols) in [
+            (
+                "fixtures/bin/layout_test.elf",
+                &["layout_var", "scalar_var"][..],
+            ),
+            ("fixtures/bin/nested_array_test.elf", &["grid"][..]),
+            ("fixtures/bin/leaves_test.elf", &["leaves_var"][..]),
+        ] {
+            let debug_data = DebugData::load_dwarf(std::ffi::OsStr::new(filename), false).unwrap();
+            let mut lookup = OffsetLookup::new(&debug_data);
+            for name in symbols {
+                let symbol = find_symbol(name, &debug_data).unwrap();
+                let size = symbol.typeinfo.get_size() as i32;
+                // query every offset twice, so that the second round is answered from the cache
+                for offset in (0..=size).chain(0..=size) {
+                    let linear = find_symbol_by_offset(&symbol, offset, &debug_data);
+                    let cached = lookup.find_symbol_by_offset(&symbol, offset);
+                    match (linear, cached) {
+                        (Ok(linear), Ok(cached)) => {
+                            assert_eq!(linear.name, cached.name);
+                            assert_eq!(linear.address, cached.address);
+                            assert_eq!(
+                                linear.typeinfo.dbginfo_offset,
+                                cached.typeinfo.dbginfo_offset
+                            );
+                        }
+                        (Err(linear), Err(cached)) => {
+                            assert_eq!(linear.to_string(), cached.to_string())
+                        }
+                        (linear, cached) => panic!(
+                            "{name} + {offset}: {:?} != {:?}",
+                            linear.map(|sym_info| sym_info.name),
+                            cached.map(|sym_info| sym_info.name)
+                        ),
+                    }
+                }
+            }
+            assert_eq!(lookup.components.len(), symbols.len());
+        }
+    }
+
+    #[test]
+    fn test_dynamic_member_offset() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/member_expr_test.elf"),
+            false,
+        )
+        .unwrap();
+        let dyn_var = find_symbol("dyn_var", &debug_data).unwrap();
+        let members = dyn_var.typeinfo.get_members().unwrap();
+        assert_eq!(members["len"].1, Some(0));
+        // DW_OP_push_object_address followed by a constant offset can be evaluated statically
+        assert_eq!(members["tail"].1, Some(8));
+        // the offset of data is read from the object at runtime
+        assert_eq!(members["data"].1, None);
+
+        let tail = find_symbol("dyn_var.tail", &debug_data).unwrap();
+        assert_eq!(tail.address, dyn_var.address + 8);
+        let result = find_symbol("dyn_var.data", &debug_data);
+        assert!(matches!(
+            result,
+            Err(DebugDataError::DynamicMemberOffset { ref member, .. }) if member == "data"
+        ));
+
+        // the member without a fixed offset is not an element of the layout
+        let names: Vec<String> = dyn_var
+            .leaves(&debug_data)
+            .map(|sym_info| sym_info.name)
+            .collect();
+        assert_eq!(names, ["dyn_var.len", "dyn_var.tail"]);
+        assert_eq!(dyn_var.typeinfo.holes(), None);
+    }
+
+    #[test]
+    fn test_unit_name_separators() {
+        let mut debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/storage_test.elf"), false)
+                .unwrap();
+        let storage_units = debug_data.unit_names.len();
+        debug_data.merge(
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/storage_test.elf"), false)
+                .unwrap(),
+        );
+        let first_unit = find_symbol("zero_init_var", &debug_data).unwrap().unit_idx;
+        let second_unit = first_unit + storage_units;
+        debug_data.unit_names[first_unit] = Some(r"C:\work\src\Storage.c".to_string());
+        debug_data.unit_names[second_unit] = Some("/home/user/lib/other.c".to_string());
+        assert_eq!(
+            make_simple_unit_name(&debug_data, first_unit).as_deref(),
+            Some("Storage_c")
+        );
+        assert_eq!(
+            make_simple_unit_name(&debug_data, second_unit).as_deref(),
+            Some("other_c")
+        );
+
+        // Windows, Unix and mixed paths map to the same simple name
+        for unit_name in [r"src\foo.c", "src/foo.c", r"C:\work/src\foo.c", "foo.c"] {
+            debug_data.unit_names[second_unit] = Some(unit_name.to_string());
+            assert_eq!(
+                make_simple_unit_name(&debug_data, second_unit).as_deref(),
+                Some("foo_c")
+            );
+            let symbol = find_symbol("zero_init_var{CompileUnit:foo_c}", &debug_data).unwrap();
+            assert_eq!(symbol.unit_idx, second_unit);
+        }
+
+        // the case of the file name doesn't matter
+        let symbol = find_symbol("zero_init_var{CompileUnit:storage_c}", &debug_data).unwrap();
+        assert_eq!(symbol.unit_idx, first_unit);
+        let symbol = find_symbol("zero_init_var{CompileUnit:FOO_C}", &debug_data).unwrap();
+        assert_eq!(symbol.unit_idx, second_unit);
+    }
+
+    #[test]
+    fn test_a2l_kind() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/storage_test.elf"), false)
+                .unwrap();
+        let kind = |name: &str, rules: &A2lKindRules| {
+            find_symbol(name, &debug_data)
+                .unwrap()
+                .a2l_kind_with_rules(&debug_data, rules)
+        };
+        let symbol = find_symbol("read_only_var", &debug_data).unwrap();
+        assert_eq!(symbol.a2l_kind(&debug_data), A2lKind::Characteristic);
+        let default_rules = A2lKindRules::default();
+        assert_eq!(
+            kind("initialized_var", &default_rules),
+            A2lKind::Measurement
+        );
+        assert_eq!(kind("zero_init_var", &default_rules), A2lKind::Measurement);
+
+        // initialized variables are calibrated in some projects
+        let rules = A2lKindRules {
+            initialized: A2lKind::Characteristic,
+            ..A2lKindRules::default()
+        };
+        assert_eq!(kind("initialized_var", &rules), A2lKind::Characteristic);
+        assert_eq!(kind("zero_init_var", &rules), A2lKind::Measurement);
+
+        // section rules take precedence over the storage
+        let rules = A2lKindRules {
+            sections: vec![(".bss".to_string(), A2lKind::Characteristic)],
+            ..A2lKindRules::default()
+        };
+        assert_eq!(kind("zero_init_var", &rules), A2lKind::Characteristic);
+        assert_eq!(kind("initialized_var", &rules), A2lKind::Measurement);
+
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/tls_test.elf"), false)
+                .unwrap();
+        let tls_var = find_symbol("tls_counter", &debug_data).unwrap();
+        assert_eq!(tls_var.a2l_kind(&debug_data), A2lKind::Measurement);
+    }
+
+    #[test]
+    fn test_virtual_base_class() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/virtual_base_test.elf"),
+            false,
+        )
+        .unwrap();
+
+        let left = &debug_data.variables.get("left").unwrap()[0];
+        let left_type = debug_data.types.get(&left.typeref).unwrap();
+        let DbgDataType::Class { inheritance, .. } = &left_type.datatype else {
+            panic!("left is not a class");
+        };
+        let base = inheritance.get("Base").unwrap();
+        assert!(base.is_virtual);
+        assert_eq!(base.offset, None);
+
+        let diamond = &debug_data.variables.get("diamond").unwrap()[0];
+        let diamond_type = debug_data.types.get(&diamond.typeref).unwrap();
+        let DbgDataType::Class { inheritance, .. } = &diamond_type.datatype else {
+            panic!("diamond is not a class");
+        };
+        let offsets: Vec<_> = inheritance
+            .iter()
+            .map(|(name, base)| (name.as_str(), base.offset, base.is_virtual))
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![
+                ("Left", Some(0), false),
+                ("Right", Some(0x10), false),
+                ("Plain", Some(0x1c), false)
+            ]
+        );
+
+        let result = find_symbol("left.Base.base_value", &debug_data);
+        assert!(matches!(
+            result,
+            Err(DebugDataError::VirtualBaseOffset { .. })
+        ));
+        let plain_value = find_symbol("diamond.Plain.plain_value", &debug_data).unwrap();
+        assert_eq!(plain_value.address, diamond.address.unwrap() + 0x1c);
+    }
+
+    #[test]
+    fn test_base_class_separator() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/inheritance_test.elf"),
+            false,
+        )
+        .unwrap();
+        let base = find_symbol("derived", &debug_data).unwrap().address;
+        let cases = [
+            ("derived.Base.base_value", 4),
+            ("derived.Base._.base_value", 4),
+            ("derived.Base.__.base_value", 4),
+            ("derived.Base.BaseBase.deep_value", 0),
+            ("derived.Base._.BaseBase._.deep_value", 0),
+            ("derived.Base.__.BaseBase.deep_value", 0),
+            ("derived.Underscore.other", 12),
+            // "_" is a genuine member of Underscore and must not be skipped
+            ("derived.Underscore._", 8),
+            ("derived.derived_value", 16),
+        ];
+        for (name, offset) in cases {
+            let symbol = find_symbol(name, &debug_data).unwrap();
+            assert_eq!(symbol.address, base + offset, "{name}");
+        }
+        let symbol = find_symbol("derived.Underscore._", &debug_data).unwrap();
+        assert!(matches!(symbol.typeinfo.datatype, DbgDataType::Sint32));
+        assert_eq!(symbol.qualified_name(&debug_data), "Underscore::_");
+        let symbol = find_symbol("derived.Base._.BaseBase._.deep_value", &debug_data).unwrap();
+        assert_eq!(symbol.qualified_name(&debug_data), "BaseBase::deep_value");
+        // "_" is not a member of Base, and only one separator is allowed
+        assert!(find_symbol("derived.Base._._.base_value", &debug_data).is_err());
+    }
+
+    #[test]
+    fn test_symbol_layout() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/layout_test.elf"), false)
+                .unwrap();
+
+        let symbol = find_symbol("layout_var", &debug_data).unwrap();
+        let layout: Vec<_> = symbol
+            .layout(&debug_data)
+            .into_iter()
+            .map(|entry| (entry.path, entry.offset, entry.size))
+            .collect();
+        let expected = [
+            ("layout_var.tag", 0, 1),
+            ("layout_var.count", 4, 4),
+            ("layout_var.points._0_.x", 8, 2),
+            ("layout_var.points._0_.y", 10, 2),
+            ("layout_var.points._1_.x", 12, 2),
+            ("layout_var.points._1_.y", 14, 2),
+            // the union members overlap
+            ("layout_var.raw.word", 16, 4),
+            ("layout_var.raw.bytes._0_", 16, 1),
+            ("layout_var.raw.bytes._1_", 17, 1),
+            ("layout_var.raw.bytes._2_", 18, 1),
+            ("layout_var.raw.bytes._3_", 19, 1),
+            ("layout_var.value", 24, 8),
+        ];
+        assert_eq!(layout.len(), expected.len());
+        for ((path, offset, size), (exp_path, exp_offset, exp_size)) in layout.iter().zip(expected)
+        {
+            assert_eq!(path, exp_path);
+            assert_eq!(*offset, exp_offset);
+            assert_eq!(*size, exp_size);
+        }
+
+        let layout = symbol.layout(&debug_data);
+        assert!(matches!(layout[1].datatype, DbgDataType::Sint32));
+        assert!(matches!(layout[11].datatype, DbgDataType::Double));
+
+        // the layout of a scalar symbol consists of the symbol itself
+        let symbol = find_symbol("scalar_var", &debug_data).unwrap();
+        let layout = symbol.layout(&debug_data);
+        assert_eq!(layout.len(), 1);
+        assert_eq!(layout[0].path, "scalar_var");
+        assert_eq!(layout[0].offset, 0);
+        assert_eq!(layout[0].size, 4);
+    }
+
+    #[test]
+    fn test_symbol_leaves() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/leaves_test.elf"), false)
+                .unwrap();
+        let symbol = find_symbol("leaves_var", &debug_data).unwrap();
+        let leaves: Vec<_> = symbol.leaves(&debug_data).collect();
+        let expected = [
+            ("leaves_var.id", 0),
+            ("leaves_var.flags.ready", 4),
+            ("leaves_var.flags.mode", 4),
+            ("leaves_var.flags.count", 4),
+            ("leaves_var.inner._0_.a", 8),
+            ("leaves_var.inner._0_.b._0_", 10),
+            ("leaves_var.inner._0_.b._1_", 11),
+            ("leaves_var.inner._1_.a", 12),
+            ("leaves_var.inner._1_.b._0_", 14),
+            ("leaves_var.inner._1_.b._1_", 15),
+            ("leaves_var.value", 16),
+        ];
+        assert_eq!(leaves.len(), expected.len());
+        assert_eq!(symbol.typeinfo.leaf_count(&debug_data), leaves.len());
+        for (leaf, (exp_name, exp_offset)) in leaves.iter().zip(expected) {
+            assert_eq!(leaf.name, exp_name);
+            assert_eq!(leaf.address, symbol.address + exp_offset, "{exp_name}");
+            assert_eq!(leaf.unit_idx, symbol.unit_idx);
+        }
+
+        // bitfield leaves carry their bit position
+        let bits: Vec<_> = leaves[1..4]
+            .iter()
+            .map(|leaf| match &leaf.typeinfo.datatype {
+                DbgDataType::Bitfield {
+                    bit_offset,
+                    bit_size,
+                    ..
+                } => (*bit_offset, *bit_size),
+                _ => panic!("{} is not a bitfield", leaf.name),
+            })
+            .collect();
+        assert_eq!(bits, [(0, 1), (1, 3), (4, 12)]);
+
+        // a partial path expands to the leaves below it
+        let symbol = find_symbol("leaves_var.inner[1]", &debug_data).unwrap();
+        let leaves: Vec<_> = symbol.leaves(&debug_data).collect();
+        assert_eq!(leaves.len(), 3);
+        assert_eq!(leaves[0].address, symbol.address);
+        assert_eq!(leaves[2].address, symbol.address + 3);
+        assert!(leaves[2].name.ends_with(".b._1_"));
+
+        // a scalar symbol is its own leaf
+        let symbol = find_symbol("leaves_var.value", &debug_data).unwrap();
+        let leaves: Vec<_> = symbol.leaves(&debug_data).collect();
+        assert_eq!(leaves.len(), 1);
+        assert_eq!(leaves[0].name, symbol.name);
+        assert_eq!(leaves[0].address, symbol.address);
+    }
+
+    #[test]
+    fn test_nested_array_descent() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/nested_array_test.elf"),
+            false,
+        )
+        .unwrap();
+        // struct Cell grid[4][2] with sizeof(struct Cell) == 20, corners at offset 4, flag at offset 16
+        let base = find_symbol("grid", &debug_data).unwrap().address;
+        let cases = [
+            ("grid[0][0].id", 0),
+            ("grid[1][0].corners[2].y", 2 * 20 + 4 + 2 * 4 + 2),
+            ("grid[2][1].corners[1].x", 5 * 20 + 4 + 4),
+            ("grid[3][1].flag", 7 * 20 + 16),
+            ("grid._2_._1_.corners._1_.x", 5 * 20 + 4 + 4),
+            ("grid[3][1].corners[2]", 7 * 20 + 4 + 2 * 4),
+        ];
+        for (name, offset) in cases {
+            let symbol = find_symbol(name, &debug_data).unwrap();
+            assert_eq!(symbol.address, base + offset, "{name}");
+        }
+        let symbol = find_symbol("grid[1][0].corners[2].y", &debug_data).unwrap();
+        assert!(matches!(symbol.typeinfo.datatype, DbgDataType::Sint16));
+        let symbol = find_symbol("grid[3][1].corners[2]", &debug_data).unwrap();
+        assert!(matches!(
+            symbol.typeinfo.datatype,
+            DbgDataType::Struct { .. }
+        ));
+
+        // the reverse lookup by offset finds the same element
+        let grid = find_symbol("grid", &debug_data).unwrap();
+        let symbol = find_symbol_by_offset(&grid, 2 * 20 + 4 + 2 * 4 + 2, &debug_data).unwrap();
+        assert_eq!(symbol.name, "grid._1_._0_.corners._2_.y");
+
+        // indices are checked against the dimension they belong to
+        assert!(find_symbol("grid[1][2].id", &debug_data).is_err());
+        assert!(find_symbol("grid[1][0].corners[3].x", &debug_data).is_err());
+        // a member can't be used in place of an array index
+        assert!(find_symbol("grid[1].id", &debug_data).is_err());
+    }
+
+    #[test]
+    fn test_find_symbol_containing_offset() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/layout_test.elf"), false)
+                .unwrap();
+        let symbol = find_symbol("layout_var", &debug_data).unwrap();
+
+        // the exact match is still required by find_symbol_by_offset
+        assert!(matches!(
+            find_symbol_by_offset(&symbol, 6, &debug_data),
+            Err(DebugDataError::NoComponentAtOffset { .. })
+        ));
+
+        // inside a scalar
+        let (component, residual) = find_symbol_containing_offset(&symbol, 6, &debug_data).unwrap();
+        assert_eq!(component.name, "layout_var.count");
+        assert_eq!(component.address, symbol.address + 4);
+        assert_eq!(residual, 2);
+
+        // inside an array element
+        let (component, residual) =
+            find_symbol_containing_offset(&symbol, 15, &debug_data).unwrap();
+        assert_eq!(component.name, "layout_var.points._1_.y");
+        assert_eq!(residual, 1);
+
+        // the start of a struct in an array resolves to its first member
+        let (component, residual) = find_symbol_containing_offset(&symbol, 8, &debug_data).unwrap();
+        assert_eq!(component.name, "layout_var.points._0_.x");
+        assert_eq!(residual, 0);
+
+        // padding is only contained in the symbol itself
+        let (component, residual) = find_symbol_containing_offset(&symbol, 2, &debug_data).unwrap();
+        assert_eq!(component.name, "layout_var");
+        assert_eq!(residual, 2);
+
+        assert!(matches!(
+            find_symbol_containing_offset(&symbol, 32, &debug_data),
+            Err(DebugDataError::OffsetOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_symbol_by_negative_offset() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/adjacent_test.elf"),
+            false,
+        )
+        .unwrap();
+        let header = find_symbol("header", &debug_data).unwrap();
+        let payload = find_symbol("payload", &debug_data).unwrap();
+        // the array is aligned to 16 bytes, so there are 12 bytes of padding after the header
+        assert_eq!(payload.address, header.address + 16);
+
+        let result = find_symbol_by_offset(&payload, -16, &debug_data).unwrap();
+        assert_eq!(result.name, "header");
+        assert_eq!(result.address, header.address);
+
+        let result = find_symbol_by_offset(&payload, -14, &debug_data).unwrap();
+        assert_eq!(result.name, "header.length");
+        assert_eq!(result.address, header.address + 2);
+
+        // the padding does not belong to any symbol
+        assert!(matches!(
+            find_symbol_by_offset(&payload, -8, &debug_data),
+            Err(DebugDataError::OffsetOutOfBounds { .. })
+        ));
+
+        assert!(matches!(
+            find_symbol_by_offset(&header, -2, &debug_data),
+            Err(DebugDataError::NoPrecedingSymbol { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rust_enum_variants() {
+        let debug_data = DebugData::load_dwarf(
+            std::ffi::OsStr::new("fixtures/bin/rust_enum_test.elf"),
+            false,
+        )
+        .unwrap();
+
+        let message = find_symbol("MESSAGE", &debug_data).unwrap();
+        let DbgDataType::VariantEnum {
+            size,
+            discriminant,
+            variants,
+        } = &message.typeinfo.datatype
+        else {
+            panic!("MESSAGE is not a VariantEnum: {}", message.typeinfo);
+        };
+        assert_eq!(*size, 12);
+        let (discr_type, discr_offset) = discriminant.as_ref().unwrap();
+        assert!(matches!(discr_type.datatype, DbgDataType::Uint8));
+        assert_eq!(*discr_offset, 0);
+        let variant_names: Vec<_> = variants
+            .iter()
+            .map(|(discr_value, variant)| (*discr_value, variant.name.as_str()))
+            .collect();
+        assert_eq!(
+            variant_names,
+            vec![(Some(0), "Quit"), (Some(1), "Move"), (Some(2), "Write")]
+        );
+
+        let move_y = find_symbol("MESSAGE.Move.y", &debug_data).unwrap();
+        assert_eq!(move_y.address, message.address + 8);
+        assert!(matches!(move_y.typeinfo.datatype, DbgDataType::Sint32));
+        let write_1 = find_symbol("MESSAGE.Write.__1", &debug_data).unwrap();
+        assert_eq!(write_1.address, message.address + 2);
+        assert!(matches!(write_1.typeinfo.datatype, DbgDataType::Uint16));
+        assert!(matches!(
+            find_symbol("MESSAGE.Read", &debug_data),
+            Err(DebugDataError::MemberNotFound { .. })
+        ));
+
+        // Some is the default variant, which is active for any value other than the null pointer
+        let optional = find_symbol("OPTIONAL", &debug_data).unwrap();
+        let DbgDataType::VariantEnum { variants, .. } = &optional.typeinfo.datatype else {
+            panic!("OPTIONAL is not a VariantEnum: {}", optional.typeinfo);
+        };
+        assert_eq!(variants.get(&Some(0)).unwrap().name, "None");
+        assert_eq!(variants.get(&None).unwrap().name, "Some");
+        let some_value = find_symbol("OPTIONAL.Some.__0", &debug_data).unwrap();
+        assert_eq!(some_value.address, optional.address);
+    }
+
+    #[test]
+    fn test_function_pointers() {
+        let debug_data =
+            DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/funcptr_test.elf"), false)
+                .unwrap();
+
+        let isr_table = &debug_data.variables.get("isr_table").unwrap()[0];
+        let isr_entry = find_symbol("isr_table[2]", &debug_data).unwrap();
+        assert_eq!(isr_entry.address, isr_table.address.unwrap() + 16);
+        let DbgDataType::FunctionPointer {
+            return_type,
+            params,
+            size,
+        } = &isr_entry.typeinfo.datatype
+        else {
+            panic!("isr_table[2] is not a function pointer");
+        };
+        assert_eq!(*size, 8);
+        assert!(params.is_empty());
+        assert_eq!(return_type.name.as_deref(), Some("void"));
+
+        // the handlers point to a typedef of a function type
+        let machine = &debug_data.variables.get("machine").unwrap()[0];
+        let handler = find_symbol("machine.handlers[1]", &debug_data).unwrap();
+        assert_eq!(handler.address, machine.address.unwrap() + 16);
+        let DbgDataType::FunctionPointer {
+            return_type,
+            params,
+            ..
+        } = &handler.typeinfo.datatype
+        else {
+            panic!("machine.handlers[1] is not a function pointer");
+        };
+        assert!(matches!(return_type.datatype, DbgDataType::Sint32));
+        assert_eq!(params.len(), 2);
+        // the pointer to the declared struct refers to its definition
+        let DbgDataType::Pointer(8, target) = params[0].datatype else {
+            panic!("the first parameter is not a pointer");
+        };
+        assert_eq!(params[0].name.as_deref(), Some("state_machine"));
+        assert!(matches!(
+            debug_data.types.get(&target).unwrap().datatype,
+            DbgDataType::Struct { .. }
+        ));
+        assert!(matches!(params[1].datatype, DbgDataType::UChar));
+    }
+
+    #[test]
+    fn test_lookup_is_quiet() {
+        // stdout of the test itself is captured by the test harness, so the loading and
+        // lookup run in a child process whose output can be inspected
+        if std::env::var_os("A2LTOOL_QUIET_TEST_CHILD").is_some() {
+            let debug_data =
+                DebugData::load_dwarf(std::ffi::OsStr::new("fixtures/bin/quiet_test.elf"), false)
+                    .unwrap();
+            for name in [
+                "g_lksStateStruct.flags[2]",
+                "g_FsmRunnable.state",
+                "g_fsmRunnable",
+            ] {
+                assert!(find_symbol(name, &debug_data).is_ok());
+            }
+            return;
+        }
+
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "symbol::test::test_lookup_is_quiet",
+                "--nocapture",
+                "-q",
+            ])
+            .env("A2LTOOL_QUIET_TEST_CHILD", "1")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // only the output of the test harness is expected
+        let unexpected: Vec<&str> = stdout
+            .lines()
+            .filter(|line| {
+                !line.is_empty()
+                    && *line != "."
+                    && !line.starts_with("running ")
+                    && !line.starts_with("test result: ")
+            })
+            .collect();
+        assert!(unexpected.is_empty(), "unexpected output: {unexpected:?}");
     }
 
     #[test]
@@ -532,6 +3271,12 @@ mod test {
             demangled_names: HashMap::new(),
             unit_names: Vec::new(),
             sections: HashMap::new(),
+            type_scopes: HashMap::new(),
+            unit_info: Vec::new(),
+            dropped_variables: Vec::new(),
+            address_discrepancies: Vec::new(),
+            endian: object::Endianness::Little,
+            architecture: object::Architecture::Unknown,
         };
         debug_data.types.insert(
             0,
@@ -540,52 +3285,120 @@ mod test {
                 name: None,
                 unit_idx: 0,
                 dbginfo_offset: 0,
+                byte_size: None,
             },
         );
         debug_data.variables.insert(
             "var".to_string(),
             vec![
                 VarInfo {
-                    address: 0,
+                    address: Some(0),
                     typeref: 0,
+                    dbginfo_offset: 0,
                     unit_idx: 0,
                     function: Some("func_a".to_string()),
                     namespaces: vec![],
+                    location_kind: LocationKind::Static,
+                    storage: None,
+                    decl_location: None,
+                    artificial: false,
+                    const_value: None,
+                    is_const: false,
+                    is_external: false,
                 },
                 VarInfo {
-                    address: 1000,
+                    address: Some(1000),
                     typeref: 0,
+                    dbginfo_offset: 0,
                     unit_idx: 1,
                     function: Some("func_b".to_string()),
                     namespaces: vec![],
+                    location_kind: LocationKind::Static,
+                    storage: None,
+                    decl_location: None,
+                    artificial: false,
+                    const_value: None,
+                    is_const: false,
+                    is_external: false,
                 },
                 VarInfo {
-                    address: 2000,
+                    address: Some(2000),
                     typeref: 0,
+                    dbginfo_offset: 0,
                     unit_idx: 1,
                     function: Some("func_c".to_string()),
                     namespaces: vec![],
+                    location_kind: LocationKind::Static,
+                    storage: None,
+                    decl_location: None,
+                    artificial: false,
+                    const_value: None,
+                    is_const: false,
+                    is_external: false,
                 },
             ],
         );
+        debug_data.variables.insert(
+            "unique_var".to_string(),
+            vec![VarInfo {
+                address: Some(3000),
+                typeref: 0,
+                dbginfo_offset: 0,
+                unit_idx: 0,
+                function: None,
+                namespaces: vec![],
+                location_kind: LocationKind::Static,
+                storage: None,
+                decl_location: None,
+                artificial: false,
+                const_value: None,
+                is_const: false,
+                is_external: false,
+            }],
+        );
         debug_data.unit_names.push(Some("file1.c".to_string()));
         debug_data.unit_names.push(Some("file2.c".to_string()));
+        assert_eq!(debug_data.ambiguous_symbols(), vec![("var", 3)]);
         let varinfo_list = debug_data.variables.get("var").unwrap();
+        let static_locals = debug_data.static_locals("func_b");
+        assert_eq!(static_locals.len(), 1);
+        assert_eq!(static_locals[0].0, "var");
+        assert!(std::ptr::eq(static_locals[0].1, &varinfo_list[1]));
+        assert_eq!(debug_data.static_locals("func_c")[0].1.address, Some(2000));
+        assert!(debug_data.static_locals("func_d").is_empty());
         let (base, additional_spec) =
             get_additional_spec("var{Function:func_a}{CompileUnit:file1_c}{Namespace:Global}");
         assert_eq!(base, "var");
         let varinfo = select_varinfo(varinfo_list, &additional_spec, &debug_data);
-        assert_eq!(varinfo.address, 0);
+        assert_eq!(varinfo.address, Some(0));
         let (base, additional_spec) =
             get_additional_spec("var{Function:func_b}{CompileUnit:file2_c}{Namespace:Global}");
         assert_eq!(base, "var");
         let varinfo = select_varinfo(varinfo_list, &additional_spec, &debug_data);
-        assert_eq!(varinfo.address, 1000);
+        assert_eq!(varinfo.address, Some(1000));
         let (base, additional_spec) =
             get_additional_spec("var{Function:func_c}{CompileUnit:file2_c}{Namespace:Global}");
         assert_eq!(base, "var");
         let varinfo = select_varinfo(varinfo_list, &additional_spec, &debug_data);
-        assert_eq!(varinfo.address, 2000);
+        assert_eq!(varinfo.address, Some(2000));
+
+        // index-based spec: selects the first variable of the unit
+        let (base, additional_spec) = get_additional_spec("var{Unit:1}");
+        assert_eq!(base, "var");
+        let varinfo = select_varinfo(varinfo_list, &additional_spec, &debug_data);
+        assert_eq!(varinfo.address, Some(1000));
+        // address-based spec: takes priority over the name-based specs
+        let (base, additional_spec) = get_additional_spec(
+            "var{Function:func_b}{CompileUnit:file2_c}{Namespace:Global}{Address:0x7d0}",
+        );
+        assert_eq!(base, "var");
+        let varinfo = select_varinfo(varinfo_list, &additional_spec, &debug_data);
+        assert_eq!(varinfo.address, Some(2000));
+        // a unit index that does not match the address falls back to name-based matching
+        let (_, additional_spec) =
+            get_additional_spec("var{Function:func_b}{Unit:0}{Address:1000}");
+        let varinfo = select_varinfo(varinfo_list, &additional_spec, &debug_data);
+        assert_eq!(varinfo.address, Some(1000));
     }
 
     #[test]
@@ -601,5 +3414,13 @@ mod test {
         assert_eq!(add_spec.function_name, Some("func".to_string()));
         assert_eq!(add_spec.namespaces, vec!["Foo", "Bar"]);
         assert_eq!(add_spec.simple_unit_name, Some("file_c".to_string()));
+
+        let (base, add_spec) =
+            get_additional_spec("varname{CompileUnit:file_c}{Unit:3}{Address:0x1234}");
+        assert_eq!(base, "varname");
+        let add_spec = add_spec.unwrap();
+        assert_eq!(add_spec.unit_idx, Some(3));
+        assert_eq!(add_spec.address, Some(0x1234));
+        assert_eq!(add_spec.simple_unit_name, Some("file_c".to_string()));
     }
 }
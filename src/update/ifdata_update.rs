@@ -62,7 +62,7 @@ pub(crate) fn update_ifdata_type(ifdata_vec: &mut Vec<IfData>, datatype: &TypeIn
 fn update_ifdata_type_canape_ext(canape_ext: &mut ifdata::CanapeExt, typeinfo: &TypeInfo) {
     if let Some(link_map) = &mut canape_ext.link_map {
         match &typeinfo.datatype {
-            DbgDataType::Uint8 => {
+            DbgDataType::Bool | DbgDataType::UChar | DbgDataType::Uint8 => {
                 link_map.datatype = 0x87;
                 link_map.bit_offset = 0;
                 link_map.datatype_valid = 1;
@@ -82,7 +82,7 @@ fn update_ifdata_type_canape_ext(canape_ext: &mut ifdata::CanapeExt, typeinfo: &
                 link_map.bit_offset = 0;
                 link_map.datatype_valid = 1;
             }
-            DbgDataType::Sint8 => {
+            DbgDataType::SChar | DbgDataType::Sint8 => {
                 link_map.datatype = 0xc7;
                 link_map.bit_offset = 0;
                 link_map.datatype_valid = 1;
@@ -133,7 +133,8 @@ fn update_ifdata_type_canape_ext(canape_ext: &mut ifdata::CanapeExt, typeinfo: &
                 bit_size,
             } => {
                 let signed: u16 = match &basetype.datatype {
-                    DbgDataType::Sint8
+                    DbgDataType::SChar
+                    | DbgDataType::Sint8
                     | DbgDataType::Sint16
                     | DbgDataType::Sint32
                     | DbgDataType::Sint64 => 0x40,
@@ -158,7 +159,11 @@ fn update_ifdata_type_canape_ext(canape_ext: &mut ifdata::CanapeExt, typeinfo: &
 fn update_ifdata_type_asap1b_ccp(asap1b_ccp: &mut ifdata::Asap1bCcp, typeinfo: &TypeInfo) {
     if let Some(dp_blob) = &mut asap1b_ccp.dp_blob {
         match &typeinfo.datatype {
-            DbgDataType::Uint8 | DbgDataType::Sint8 => dp_blob.size = 1,
+            DbgDataType::Bool
+            | DbgDataType::SChar
+            | DbgDataType::UChar
+            | DbgDataType::Uint8
+            | DbgDataType::Sint8 => dp_blob.size = 1,
             DbgDataType::Uint16 | DbgDataType::Sint16 => dp_blob.size = 2,
             DbgDataType::Float | DbgDataType::Uint32 | DbgDataType::Sint32 => {
                 dp_blob.size = 4;
@@ -233,6 +238,7 @@ mod test {
         unit_idx: 0,
         datatype: DbgDataType::Uint32,
         dbginfo_offset: 0,
+        byte_size: None,
     };
 
     fn test_setup(input: &str) -> a2lfile::A2lFile {
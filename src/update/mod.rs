@@ -20,7 +20,7 @@ pub(crate) mod typedef;
 
 use crate::datatype::{get_a2l_datatype, get_type_limits};
 use crate::debuginfo::DbgDataType;
-use crate::symbol::{SymbolInfo, find_symbol, find_symbol_by_offset};
+use crate::symbol::{SymbolInfo, find_symbol_by_offset, find_symbol_with_address};
 use axis_pts::*;
 use blob::{cleanup_removed_blobs, update_all_module_blobs};
 use characteristic::*;
@@ -236,18 +236,18 @@ fn get_symbol_info<'a>(
     let mut object_name_errmsg = None;
     // preferred: get symbol information from a SYMBOL_LINK attribute
     if let Some(symbol_link) = opt_symbol_link {
-        match find_symbol(&symbol_link.symbol_name, debug_data) {
+        match find_symbol_with_address(&symbol_link.symbol_name, debug_data) {
             Ok(sym_info) => {
                 if symbol_link.offset == 0 {
                     return Ok(sym_info);
                 } else {
                     match find_symbol_by_offset(&sym_info, symbol_link.offset, debug_data) {
                         Ok(sym_info) => return Ok(sym_info),
-                        Err(errmsg) => return Err(vec![errmsg]),
+                        Err(err) => return Err(vec![err.to_string()]),
                     }
                 }
             }
-            Err(errmsg) => symbol_link_errmsg = Some(errmsg),
+            Err(err) => symbol_link_errmsg = Some(err.to_string()),
         };
     }
 
@@ -255,17 +255,17 @@ fn get_symbol_info<'a>(
     // The content of IF_DATA can be different for each tool vendor, but the blocks used
     // by the Vector tools are understood by some other software.
     if let Some(ifdata_symbol_name) = get_symbol_name_from_ifdata(ifdata_vec) {
-        match find_symbol(&ifdata_symbol_name, debug_data) {
+        match find_symbol_with_address(&ifdata_symbol_name, debug_data) {
             Ok(sym_info) => return Ok(sym_info),
-            Err(errmsg) => ifdata_errmsg = Some(errmsg),
+            Err(err) => ifdata_errmsg = Some(err.to_string()),
         };
     }
 
     // If there is no SYMBOL_LINK and no (usable) IF_DATA, then maybe the object name is also the symbol name
     if opt_symbol_link.is_none() {
-        match find_symbol(name, debug_data) {
+        match find_symbol_with_address(name, debug_data) {
             Ok(sym_info) => return Ok(sym_info),
-            Err(errmsg) => object_name_errmsg = Some(errmsg),
+            Err(err) => object_name_errmsg = Some(err.to_string()),
         };
     }
 
@@ -463,7 +463,9 @@ pub(crate) fn set_bitmask(opt_bitmask: &mut Option<BitMask>, typeinfo: &TypeInfo
 
 /// set or delete the `ADDRESS_TYPE`
 pub(crate) fn set_address_type(address_type_opt: &mut Option<AddressType>, newtype: &TypeInfo) {
-    if let DbgDataType::Pointer(ptsize, _) = &newtype.datatype {
+    if let DbgDataType::Pointer(ptsize, _) | DbgDataType::Reference { size: ptsize, .. } =
+        &newtype.datatype
+    {
         let address_type = address_type_opt.get_or_insert(AddressType::new(AddrType::Direct));
         address_type.address_type = match ptsize {
             1 => AddrType::Pbyte,
@@ -667,6 +669,7 @@ mod test {
             unit_idx: 0,
             datatype: DbgDataType::Uint8,
             dbginfo_offset: 0,
+            byte_size: None,
         };
         let mut compu_method = CompuMethod::new(
             "name".to_string(),
@@ -687,6 +690,7 @@ mod test {
             unit_idx: 0,
             datatype: DbgDataType::Uint8,
             dbginfo_offset: 0,
+            byte_size: None,
         };
         let mut compu_method = CompuMethod::new(
             "name".to_string(),
@@ -709,6 +713,7 @@ mod test {
             unit_idx: 0,
             datatype: DbgDataType::Double,
             dbginfo_offset: 0,
+            byte_size: None,
         };
         let mut compu_method = CompuMethod::new(
             "name".to_string(),
@@ -1179,6 +1184,26 @@ mod test {
         assert!(sym_info_result.is_err());
     }
 
+    #[test]
+    fn test_symbol_tls() {
+        // thread-local variables have no fixed address, so they can't be used to update an item
+        let debug_data = crate::debuginfo::DebugData::load_dwarf(
+            &OsString::from("fixtures/bin/tls_test.elf"),
+            false,
+        )
+        .unwrap();
+
+        let symbol_link = a2lfile::SymbolLink::new("tls_flags".to_string(), 0);
+        let sym_info_result = get_symbol_info("", &Some(symbol_link), &[], &debug_data);
+        assert!(sym_info_result.is_err());
+
+        let sym_info_result = get_symbol_info("tls_counter", &None, &[], &debug_data);
+        assert!(sym_info_result.is_err());
+
+        let sym_info = get_symbol_info("plain_var", &None, &[], &debug_data).unwrap();
+        assert!(sym_info.address != 0);
+    }
+
     fn make_bitfield_type(offset: u16, mask: u16) -> TypeInfo {
         TypeInfo {
             name: None,
@@ -1189,11 +1214,13 @@ mod test {
                     unit_idx: 0,
                     datatype: DbgDataType::Uint64,
                     dbginfo_offset: 0,
+                    byte_size: None,
                 }),
                 bit_offset: offset,
                 bit_size: mask,
             },
             dbginfo_offset: 0,
+            byte_size: None,
         }
     }
 
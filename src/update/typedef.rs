@@ -709,7 +709,7 @@ impl<'dbg, 'a2l, 'rl, 'log> TypedefUpdater<'dbg, 'a2l, 'rl, 'log> {
             } else if is_structure_typeinfo(typeinfo, &self.debug_data.types) {
                 self.create_typedef_structure(name.clone(), typeinfo, enum_convlist, is_calib);
             } else {
-                // FuncPtr and Other don't work for measurement
+                // FunctionPointer and Other don't work for measurement
                 return None;
             }
         } else {
@@ -1277,7 +1277,7 @@ impl<'dbg, 'a2l, 'rl, 'log> TypedefUpdater<'dbg, 'a2l, 'rl, 'log> {
                     td_struct.structure_component.truncate(0);
                 }
             }
-            DbgDataType::Pointer(_, _) => {
+            DbgDataType::Pointer(_, _) | DbgDataType::Reference { .. } => {
                 // insanity! - the original declaration would have to be something like "sometype*** var".
                 // In that situation, the INSTANCE would consume the first layer of indirection and set ADDRESS_TYPE,
                 // then this TYPEDEF_STRUCTURE gets the second layer and also sets ADDRESS_TYPE, and finally we get here.
@@ -1323,7 +1323,7 @@ impl<'dbg, 'a2l, 'rl, 'log> TypedefUpdater<'dbg, 'a2l, 'rl, 'log> {
     fn update_typedef_struct_content(
         &mut self,
         td_struct: &mut TypedefStructure,
-        members: &'dbg IndexMap<String, (TypeInfo, u64)>,
+        members: &'dbg IndexMap<String, (TypeInfo, Option<u64>)>,
         enum_convlist: &mut HashMap<String, &'dbg TypeInfo>,
         is_calib: bool,
     ) {
@@ -1333,6 +1333,10 @@ impl<'dbg, 'a2l, 'rl, 'log> TypedefUpdater<'dbg, 'a2l, 'rl, 'log> {
             &mut td_struct.structure_component,
         );
         for (cur_member_name, (typeinfo_ref, cur_member_offset)) in members {
+            // a STRUCTURE_COMPONENT needs a fixed offset, which is not known for members of variable-layout objects
+            let Some(cur_member_offset) = cur_member_offset else {
+                continue;
+            };
             let cur_type = typeinfo_ref.get_reference(&self.debug_data.types);
             let mut sc = if let Some(sc) = structure_components.get(cur_member_name) {
                 sc.clone()
@@ -1354,10 +1358,14 @@ impl<'dbg, 'a2l, 'rl, 'log> TypedefUpdater<'dbg, 'a2l, 'rl, 'log> {
 
             if let Some(final_typeinfo) = fully_unwrap_typeinfo(self.debug_data, cur_type_unwrapped)
             {
-                // only create a STRUCTURE_COMPONENT for items whose inner datatype is not FuncPtr
+                // only create a STRUCTURE_COMPONENT for items whose inner datatype is not FunctionPointer, VariantEnum or Incomplete
                 // Other is used for void pointers, which is only allowed for calibration as a TYPEDEF_BLOB
-                if !matches!(&final_typeinfo.datatype, DbgDataType::FuncPtr(_))
-                    && (is_calib || !matches!(&final_typeinfo.datatype, DbgDataType::Other(_)))
+                if !matches!(
+                    &final_typeinfo.datatype,
+                    DbgDataType::FunctionPointer { .. }
+                        | DbgDataType::VariantEnum { .. }
+                        | DbgDataType::Incomplete { .. }
+                ) && (is_calib || !matches!(&final_typeinfo.datatype, DbgDataType::Other(_)))
                 {
                     sc.set_name(cur_member_name.clone());
                     // set ADDRESS_TYPE if cur_member_typeinfo is a pointer, or delete it
@@ -1559,7 +1567,7 @@ fn get_typeinfo_from_symbol_link<'dbg>(
 fn get_structure_component_typeinfo<'dbg>(
     debug_data: &'dbg DebugData,
     structure_component: &StructureComponent,
-    members: &'dbg IndexMap<String, (TypeInfo, u64)>,
+    members: &'dbg IndexMap<String, (TypeInfo, Option<u64>)>,
 ) -> Option<&'dbg TypeInfo> {
     let symtypelink = structure_component.symbol_type_link.as_ref()?;
     // get the member type info - this is a TypeRef when referring to another struct / etc.
@@ -1579,11 +1587,13 @@ fn get_structure_component_typeinfo<'dbg>(
 fn is_structure_typeinfo(typeinfo: &TypeInfo, types: &HashMap<usize, TypeInfo>) -> bool {
     let typeinfo = typeinfo.get_pointer(types).map_or(typeinfo, |(_, t)| t);
     match &typeinfo.datatype {
-        DbgDataType::Pointer(_, offset) => {
+        DbgDataType::Pointer(_, offset) | DbgDataType::Reference { target: offset, .. } => {
             if let Some(pt_type) = types.get(offset) {
                 // inner type can be a pointer to anything, or a valid structure datatype
-                matches!(&pt_type.datatype, DbgDataType::Pointer(_, _))
-                    || is_structure_typeinfo(pt_type, types)
+                matches!(
+                    &pt_type.datatype,
+                    DbgDataType::Pointer(_, _) | DbgDataType::Reference { .. }
+                ) || is_structure_typeinfo(pt_type, types)
             } else {
                 false
             }
@@ -1610,7 +1620,10 @@ fn is_calibration_typeinfo(typeinfo: &TypeInfo) -> bool {
     !matches!(
         &typeinfo.datatype,
         DbgDataType::Pointer(_, _)
-            | DbgDataType::FuncPtr(_)
+            | DbgDataType::Reference { .. }
+            | DbgDataType::FunctionPointer { .. }
+            | DbgDataType::VariantEnum { .. }
+            | DbgDataType::Incomplete { .. }
             | DbgDataType::Other(_)
             | DbgDataType::Union { .. }
             | DbgDataType::TypeRef(_, _)
@@ -1622,16 +1635,19 @@ fn is_measurement_typeinfo(typeinfo: &TypeInfo, types: &HashMap<usize, TypeInfo>
     let typeinfo = typeinfo.get_pointer(types).map_or(typeinfo, |(_, t)| t);
     let typeinfo = typeinfo.get_arraytype().unwrap_or(typeinfo);
     match &typeinfo.datatype {
-        DbgDataType::Pointer(_, offset) => {
+        DbgDataType::Pointer(_, offset) | DbgDataType::Reference { target: offset, .. } => {
             if let Some(pt_type) = types.get(offset) {
                 // inner type must be a measurement type, except it can't be a pointer itself
-                !matches!(&pt_type.datatype, DbgDataType::Pointer(_, _))
-                    && is_measurement_typeinfo(pt_type, types)
+                !matches!(
+                    &pt_type.datatype,
+                    DbgDataType::Pointer(_, _) | DbgDataType::Reference { .. }
+                ) && is_measurement_typeinfo(pt_type, types)
             } else {
                 false
             }
         }
         DbgDataType::Other(_)
+        | DbgDataType::Incomplete { .. }
         | DbgDataType::Struct { .. }
         | DbgDataType::Class { .. }
         | DbgDataType::Union { .. }
@@ -1698,7 +1714,11 @@ fn calc_distinct_types<'a>(
 /// create a suitable name for a TYPEDEF_* based on the given typeinfo
 fn make_typedef_name(debug_data: &DebugData, typeinfo: &TypeInfo, is_calib: bool) -> String {
     match &typeinfo.datatype {
-        DbgDataType::Pointer(pt_size, pt_dbg_offset) => {
+        DbgDataType::Pointer(pt_size, pt_dbg_offset)
+        | DbgDataType::Reference {
+            size: pt_size,
+            target: pt_dbg_offset,
+        } => {
             let prefix = match pt_size {
                 1 => "BytePointer",
                 2 => "ShortPointer",
@@ -1751,7 +1771,14 @@ fn make_typedef_name(debug_data: &DebugData, typeinfo: &TypeInfo, is_calib: bool
             .map_or("_invalid_reference_".to_string(), |t| {
                 make_typedef_name(debug_data, t, is_calib)
             }),
-        DbgDataType::FuncPtr(_) | DbgDataType::Other(_) => {
+        DbgDataType::Incomplete { name } => name.clone(),
+        DbgDataType::FunctionPointer { .. }
+        | DbgDataType::VariantEnum { .. }
+        | DbgDataType::Other(_)
+        | DbgDataType::Uint128
+        | DbgDataType::Sint128
+        | DbgDataType::LongDouble(_)
+        | DbgDataType::Float128 => {
             // BLOBs might refer to void pointers, which can be represented as Other()
             typeinfo
                 .name
@@ -1759,11 +1786,13 @@ fn make_typedef_name(debug_data: &DebugData, typeinfo: &TypeInfo, is_calib: bool
                 .unwrap_or("_unnamed_item_")
                 .to_string()
         }
-        DbgDataType::Uint8 => make_basic_name(is_calib, "UByte"),
+        DbgDataType::Bool | DbgDataType::UChar | DbgDataType::Uint8 => {
+            make_basic_name(is_calib, "UByte")
+        }
         DbgDataType::Uint16 => make_basic_name(is_calib, "UWord"),
         DbgDataType::Uint32 => make_basic_name(is_calib, "ULong"),
         DbgDataType::Uint64 => make_basic_name(is_calib, "UInt64"),
-        DbgDataType::Sint8 => make_basic_name(is_calib, "SByte"),
+        DbgDataType::SChar | DbgDataType::Sint8 => make_basic_name(is_calib, "SByte"),
         DbgDataType::Sint16 => make_basic_name(is_calib, "SWord"),
         DbgDataType::Sint32 => make_basic_name(is_calib, "SLong"),
         DbgDataType::Sint64 => make_basic_name(is_calib, "SInt64"),
@@ -1798,7 +1827,7 @@ fn fully_unwrap_typeinfo<'dbg>(
     // fully unwrap all indirections, until the type is not one of Pointer / Array / TypeRef
     loop {
         match &cur_typeinfo.datatype {
-            DbgDataType::Pointer(_, off) => {
+            DbgDataType::Pointer(_, off) | DbgDataType::Reference { target: off, .. } => {
                 // for void* the off may be 0, then debug_data.types.get() fails
                 if let Some(ptype) = debug_data.types.get(off) {
                     cur_typeinfo = ptype;
@@ -1823,10 +1852,13 @@ fn fully_unwrap_typeinfo<'dbg>(
 
 #[cfg(test)]
 mod test {
-    use super::{TypedefUpdater, update_module_typedefs};
+    use super::{
+        TypedefUpdater, fully_unwrap_typeinfo, is_calibration_typeinfo, is_measurement_typeinfo,
+        is_structure_typeinfo, update_module_typedefs,
+    };
     use crate::{
         A2lVersion,
-        debuginfo::{DebugData, TypeInfo},
+        debuginfo::{DbgDataType, DebugData, TypeInfo},
         update::{A2lUpdateInfo, RecordLayoutInfo, TypedefNames, TypedefReferrer, get_symbol_info},
     };
     use a2lfile::{A2lFile, A2lObjectName};
@@ -2130,6 +2162,75 @@ mod test {
         assert_eq!(tdu.module.typedef_blob.len(), 1);
     }
 
+    #[test]
+    fn test_create_typedef_reference() {
+        let mut a2l = a2lfile::new();
+        let elf_name = OsString::from("fixtures/bin/typedef_reference_test.elf");
+        let debug_data = crate::debuginfo::DebugData::load_dwarf(&elf_name, false).unwrap();
+        let typedef_names = TypedefNames::new(&a2l.project.module[0]);
+        let mut recordlayout_info = RecordLayoutInfo::build(&a2l.project.module[0]);
+        let mut msgs = Vec::new();
+        let mut tdu = TypedefUpdater::new(
+            &mut a2l.project.module[0],
+            &debug_data,
+            &mut msgs,
+            typedef_names,
+            &mut recordlayout_info,
+            HashMap::new(),
+        );
+        let mut enum_convlist = HashMap::<String, &TypeInfo>::new();
+
+        // struct Settings has the reference members "int &gain" and "Limits &limits"
+        let typeinfo = debug_data
+            .types
+            .get(&debug_data.variables.get("settings").unwrap()[0].typeref)
+            .unwrap();
+        let name = tdu
+            .create_typedef(typeinfo, true, &mut enum_convlist)
+            .unwrap();
+        assert_eq!(name, "Settings");
+
+        // the reference members are handled like pointers: the components get an ADDRESS_TYPE,
+        // and their COMPONENT_TYPE is the typedef of the referenced type
+        let settings = tdu.typedef_structs.get("Settings").unwrap();
+        let gain = settings.structure_component.get("gain").unwrap();
+        assert!(gain.address_type.is_some());
+        assert_eq!(gain.component_type, "Parameter_SLong");
+        let limits = settings.structure_component.get("limits").unwrap();
+        assert!(limits.address_type.is_some());
+        assert_eq!(limits.component_type, "Limits");
+        assert!(tdu.typedef_structs.contains_key("Limits"));
+        assert_eq!(tdu.module.typedef_characteristic.len(), 2);
+
+        let members = typeinfo.get_members().unwrap();
+        let gain_type = members
+            .get("gain")
+            .unwrap()
+            .0
+            .get_reference(&debug_data.types);
+        assert!(!is_calibration_typeinfo(gain_type));
+        assert!(is_measurement_typeinfo(gain_type, &debug_data.types));
+        assert!(matches!(
+            fully_unwrap_typeinfo(&debug_data, gain_type)
+                .unwrap()
+                .datatype,
+            DbgDataType::Sint32
+        ));
+        let limits_type = members
+            .get("limits")
+            .unwrap()
+            .0
+            .get_reference(&debug_data.types);
+        assert!(!is_calibration_typeinfo(limits_type));
+        assert!(is_structure_typeinfo(limits_type, &debug_data.types));
+        assert!(matches!(
+            fully_unwrap_typeinfo(&debug_data, limits_type)
+                .unwrap()
+                .datatype,
+            DbgDataType::Struct { .. }
+        ));
+    }
+
     #[test]
     fn test_update() {
         let (mut a2l, debug_data, names, mut reclayout) = test_setup(